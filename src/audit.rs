@@ -0,0 +1,17 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Audit mode makes deletion impossible for the rest of the process: the cleaner,
+// uninstaller, Trash and Space Lens deletes all check it right where they would
+// touch the disk, so no UI path can get around it. Once on, it stays on.
+static AUDIT: AtomicBool = AtomicBool::new(false);
+
+pub const ENV_VAR: &str = "CLEANMAC_AUDIT";
+pub const REFUSED: &str = "Audit mode: deletion is disabled";
+
+pub fn enable() {
+    AUDIT.store(true, Ordering::SeqCst);
+}
+
+pub fn is_active() -> bool {
+    AUDIT.load(Ordering::SeqCst) || std::env::var(ENV_VAR).is_ok_and(|v| v == "1")
+}
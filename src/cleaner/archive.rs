@@ -0,0 +1,192 @@
+use crate::plugin::ScanResult;
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[derive(Debug, Clone)]
+pub struct ArchiveResult {
+    pub archive_path: PathBuf,
+    pub original_size: u64,
+    pub compressed_size: u64,
+    pub entries: usize,
+}
+
+// Members are stored relative to `/`, so extracting with `tar -xf ... -C /`
+// puts every file back where it came from.
+fn member_name(path: &Path) -> PathBuf {
+    path.strip_prefix("/").unwrap_or(path).to_path_buf()
+}
+
+fn inside_cleaned_set(dest: &Path, items: &[ScanResult]) -> Option<PathBuf> {
+    items
+        .iter()
+        .find(|item| dest.starts_with(&item.path))
+        .map(|item| item.path.clone())
+}
+
+// Packs `items` into a new .tar.zst under `dest_dir` and checks that every item
+// made it into the archive. Nothing is deleted here; the caller removes the
+// originals only once this returns Ok.
+pub fn archive_items(items: &[ScanResult], dest_dir: &Path) -> Result<ArchiveResult> {
+    if items.is_empty() {
+        bail!("Nothing to archive");
+    }
+
+    let dest = std::path::absolute(dest_dir)?;
+    if let Some(item) = inside_cleaned_set(&dest, items) {
+        bail!(
+            "Archive directory {} is inside {}, which is about to be removed",
+            dest.display(),
+            item.display()
+        );
+    }
+    fs::create_dir_all(&dest)?;
+    // Check again through symlinks now that the directory exists.
+    let dest = dest.canonicalize()?;
+    if let Some(item) = inside_cleaned_set(&dest, items) {
+        bail!(
+            "Archive directory {} resolves inside {}, which is about to be removed",
+            dest.display(),
+            item.display()
+        );
+    }
+
+    let archive_path = dest.join(format!(
+        "cleanmac-archive-{}.tar.zst",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let members: Vec<PathBuf> = items.iter().map(|item| member_name(&item.path)).collect();
+
+    if let Err(e) = write_archive(&archive_path, &members) {
+        let _ = fs::remove_file(&archive_path);
+        return Err(e);
+    }
+    if let Err(e) = verify_archive(&archive_path, &members) {
+        let _ = fs::remove_file(&archive_path);
+        return Err(e);
+    }
+
+    Ok(ArchiveResult {
+        compressed_size: fs::metadata(&archive_path)?.len(),
+        archive_path,
+        original_size: items.iter().map(|i| i.size).sum(),
+        entries: items.len(),
+    })
+}
+
+fn write_archive(archive_path: &Path, members: &[PathBuf]) -> Result<()> {
+    let mut child = Command::new("tar")
+        .arg("--zstd")
+        .arg("-cf")
+        .arg(archive_path)
+        .args(["-C", "/", "--null", "-T", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Could not run tar")?;
+
+    // NUL-separated on stdin, so odd file names and long lists both survive.
+    if let Some(mut stdin) = child.stdin.take() {
+        for member in members {
+            stdin.write_all(member.as_os_str().as_encoded_bytes())?;
+            stdin.write_all(b"\0")?;
+        }
+    }
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!(
+            "tar failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn verify_archive(archive_path: &Path, members: &[PathBuf]) -> Result<()> {
+    let output = Command::new("tar")
+        .arg("--zstd")
+        .arg("-tf")
+        .arg(archive_path)
+        .output()
+        .context("Could not run tar")?;
+    if !output.status.success() {
+        bail!(
+            "Archive {} failed verification: {}",
+            archive_path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let listed: HashSet<PathBuf> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| PathBuf::from(line.trim_end_matches('/')))
+        .collect();
+    if let Some(missing) = members.iter().find(|m| !listed.contains(*m)) {
+        bail!(
+            "Archive {} is missing {}",
+            archive_path.display(),
+            missing.display()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cleanmac-archive-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn archives_files_with_their_paths_and_contents() {
+        let root = temp_dir("roundtrip");
+        let file = root.join("videos/old.mov");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, vec![b'a'; 64 * 1024]).unwrap();
+
+        let item = ScanResult::new("old", "old.mov", file.clone()).with_size(64 * 1024);
+        let result = archive_items(&[item], &root.join("archives")).unwrap();
+        assert!(result.archive_path.exists());
+        assert_eq!(result.original_size, 64 * 1024);
+        assert!(result.compressed_size < result.original_size);
+        assert!(file.exists());
+
+        let restore = root.join("restore");
+        fs::create_dir_all(&restore).unwrap();
+        let status = Command::new("tar")
+            .arg("--zstd")
+            .arg("-xf")
+            .arg(&result.archive_path)
+            .arg("-C")
+            .arg(&restore)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let restored = restore.join(member_name(&file));
+        assert_eq!(fs::read(restored).unwrap(), vec![b'a'; 64 * 1024]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn refuses_an_archive_directory_inside_the_cleaned_set() {
+        let root = temp_dir("guard");
+        let item = ScanResult::new("dir", "dir", root.clone());
+
+        let err = archive_items(&[item], &root.join("archives")).unwrap_err();
+        assert!(err.to_string().contains("about to be removed"));
+        assert!(!root.join("archives").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
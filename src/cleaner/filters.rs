@@ -0,0 +1,430 @@
+use super::PROTECTED_REFUSAL;
+use crate::output::{ExecutionResult, PlanResult};
+use crate::plugin::registry::CategoryScanResult;
+use crate::plugin::{SafetyLevel, ScanResult, ScannerCategory};
+use crate::safety::SafetyChecker;
+use crate::utils::days_before;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+// Splits items into (to_clean, kept), keeping the `keep` most recently modified.
+// Items without a modification time count as oldest.
+pub fn keep_recent(items: &[ScanResult], keep: usize) -> (Vec<ScanResult>, Vec<ScanResult>) {
+    let mut sorted: Vec<ScanResult> = items.to_vec();
+    sorted.sort_by_key(|item| std::cmp::Reverse(item.last_modified));
+
+    let to_clean = sorted.split_off(keep.min(sorted.len()));
+    (to_clean, sorted)
+}
+
+// Splits items into (to_clean, kept) by age. Items without a modification time
+// are kept, since their age can't be proven to exceed the policy.
+pub fn older_than(
+    items: &[ScanResult],
+    max_age_days: u64,
+    now: DateTime<Utc>,
+) -> Result<(Vec<ScanResult>, Vec<ScanResult>)> {
+    let cutoff = days_before(now, max_age_days)
+        .ok_or_else(|| anyhow!("{} days is too far back to compare dates", max_age_days))?;
+    Ok(items
+        .iter()
+        .cloned()
+        .partition(|item| item.last_modified.is_some_and(|modified| modified < cutoff)))
+}
+
+// Keeps only items that are Safe both as reported by their scanner and by path,
+// so items from a plan file (which carry no scanner verdict) are re-checked too.
+pub fn only_safe(items: Vec<ScanResult>) -> (Vec<ScanResult>, usize) {
+    let checker = SafetyChecker::new();
+    let before = items.len();
+    let safe: Vec<ScanResult> = items
+        .into_iter()
+        .filter(|item| {
+            item.safety_level == SafetyLevel::Safe
+                && checker.check_path(&item.path) == SafetyLevel::Safe
+        })
+        .collect();
+    let dropped = before - safe.len();
+    (safe, dropped)
+}
+
+// What `safe-clean` draws from: caches, logs and the Trash, which apps and
+// macOS recreate or nobody expects back. Everything else wants a look first.
+pub const SAFE_CLEAN_SCANNERS: [&str; 4] =
+    ["system_caches", "browser_cache", "system_logs", "trash"];
+
+// Items `safe-clean` removes from a scan: only the scanners above, only Safe
+// items, and logs only once they are older than `log_age_days`.
+pub fn safe_clean_items(
+    categories: &[CategoryScanResult],
+    log_age_days: u64,
+    now: DateTime<Utc>,
+) -> Result<Vec<ScanResult>> {
+    let mut items = Vec::new();
+    for category in categories {
+        if !SAFE_CLEAN_SCANNERS.contains(&category.scanner_id.as_str()) {
+            continue;
+        }
+        if category.scanner_id == "system_logs" {
+            items.extend(older_than(&category.items, log_age_days, now)?.0);
+        } else {
+            items.extend(category.items.iter().cloned());
+        }
+    }
+    Ok(only_safe(items).0)
+}
+
+// Splits items into (confirmed, needs_review) for runs nobody is watching, by
+// the scanner that found them. An empty allowlist confirms everything; items
+// with no known scanner (from --resume) always need review.
+pub fn auto_confirmed(
+    items: Vec<ScanResult>,
+    allowlist: &[String],
+) -> (Vec<ScanResult>, Vec<ScanResult>) {
+    if allowlist.is_empty() {
+        return (items, Vec::new());
+    }
+    items.into_iter().partition(|item| {
+        item.metadata
+            .get("scanner_id")
+            .is_some_and(|id| allowlist.contains(id))
+    })
+}
+
+// Rebuilds the items a previous run failed on, as (retry, gone). Paths that no
+// longer exist are returned separately, and items refused as Protected are not
+// retried at all. The execution file carries no scanner verdict, so the rest are
+// rated Caution at best: a path that looks harmless may still be one a scanner
+// wanted reviewed.
+pub fn failed_from_execution(exec: &ExecutionResult) -> (Vec<ScanResult>, Vec<PathBuf>) {
+    let checker = SafetyChecker::new();
+    let mut seen = HashSet::new();
+    let mut retry = Vec::new();
+    let mut gone = Vec::new();
+
+    for failed in exec.categories.iter().flat_map(|c| c.failed_items.iter()) {
+        if failed.error == PROTECTED_REFUSAL {
+            continue;
+        }
+        if !seen.insert(failed.path.clone()) {
+            continue;
+        }
+        if failed.path.symlink_metadata().is_err() {
+            gone.push(failed.path.clone());
+            continue;
+        }
+
+        let size = walkdir::WalkDir::new(&failed.path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter_map(|e| e.metadata().ok())
+            .filter(|m| m.is_file())
+            .map(|m| m.len())
+            .sum();
+        let name = failed
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let safety = match checker.check_path(&failed.path) {
+            SafetyLevel::Protected => SafetyLevel::Protected,
+            _ => SafetyLevel::Caution,
+        };
+        retry.push(
+            ScanResult::new(
+                failed.path.to_string_lossy().to_string(),
+                name,
+                failed.path.clone(),
+            )
+            .with_size(size)
+            .with_safety(safety),
+        );
+    }
+
+    (retry, gone)
+}
+
+// Turns a plan file back into cleanable items with the verdict the scan gave
+// them. Items the plan doesn't rate are treated as Caution, so --only-safe and
+// clean_caution = false still hold them back.
+pub fn items_from_plan(plan: &PlanResult) -> Vec<ScanResult> {
+    plan.categories
+        .iter()
+        .flat_map(|cat| cat.items.iter().map(move |item| (cat, item)))
+        .map(|(cat, item)| {
+            let name = item
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let mut scan_item = ScanResult::new(
+                item.path.to_string_lossy().to_string(),
+                name,
+                item.path.clone(),
+            )
+            .with_size(item.size_bytes)
+            .with_file_count(1)
+            .with_safety(item.safety_level.unwrap_or(SafetyLevel::Caution))
+            .with_category(item.category.unwrap_or(ScannerCategory::System));
+            scan_item
+                .metadata
+                .insert("scanner_id".to_string(), cat.id.clone());
+            scan_item
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use std::path::PathBuf;
+
+    fn items(ages_days: &[Option<i64>]) -> Vec<ScanResult> {
+        let now = Utc::now();
+        ages_days
+            .iter()
+            .enumerate()
+            .map(|(i, age)| {
+                ScanResult::new(format!("log_{}", i), format!("log_{}", i), PathBuf::new())
+                    .with_last_modified(age.map(|d| now - Duration::days(d)))
+            })
+            .collect()
+    }
+
+    fn ids(items: &[ScanResult]) -> Vec<&str> {
+        items.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn keeps_newest_and_cleans_the_rest() {
+        let set = items(&[Some(30), Some(1), None, Some(7), Some(90)]);
+        let (to_clean, kept) = keep_recent(&set, 2);
+
+        assert_eq!(ids(&kept), vec!["log_1", "log_3"]);
+        assert_eq!(ids(&to_clean), vec!["log_0", "log_4", "log_2"]);
+    }
+
+    #[test]
+    fn keeping_more_than_available_cleans_nothing() {
+        let set = items(&[Some(3), Some(2)]);
+        let (to_clean, kept) = keep_recent(&set, 10);
+
+        assert!(to_clean.is_empty());
+        assert_eq!(kept.len(), 2);
+    }
+
+    #[test]
+    fn only_allowlisted_categories_are_auto_confirmed() {
+        let item = |id: &str, scanner: Option<&str>| {
+            let mut item = ScanResult::new(id, id, PathBuf::from(format!("/tmp/{}", id)));
+            if let Some(scanner) = scanner {
+                item.metadata
+                    .insert("scanner_id".to_string(), scanner.to_string());
+            }
+            item
+        };
+        let set = vec![
+            item("cache", Some("system_caches")),
+            item("dup", Some("duplicates")),
+            item("trashed", Some("trash")),
+            item("big", Some("large_old_files")),
+            item("resumed", None),
+        ];
+        let allowlist = vec!["trash".to_string(), "system_caches".to_string()];
+
+        let (confirmed, review) = auto_confirmed(set.clone(), &allowlist);
+        assert_eq!(ids(&confirmed), vec!["cache", "trashed"]);
+        assert_eq!(ids(&review), vec!["dup", "big", "resumed"]);
+
+        let (confirmed, review) = auto_confirmed(set, &[]);
+        assert_eq!(confirmed.len(), 5);
+        assert!(review.is_empty());
+    }
+
+    #[test]
+    fn keep_zero_cleans_everything() {
+        let set = items(&[Some(3), None]);
+        let (to_clean, kept) = keep_recent(&set, 0);
+
+        assert_eq!(to_clean.len(), 2);
+        assert!(kept.is_empty());
+    }
+
+    #[test]
+    fn older_than_keeps_recent_and_undated_items() {
+        let set = items(&[Some(45), Some(10), None, Some(31)]);
+        let (to_clean, kept) = older_than(&set, 30, Utc::now()).unwrap();
+
+        assert_eq!(ids(&to_clean), vec!["log_0", "log_3"]);
+        assert_eq!(ids(&kept), vec!["log_1", "log_2"]);
+    }
+
+    #[test]
+    fn older_than_rejects_ages_past_what_dates_can_hold() {
+        let set = items(&[Some(45)]);
+        assert!(older_than(&set, u64::MAX, Utc::now()).is_err());
+        assert!(older_than(&set, 1 << 40, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn only_safe_drops_caution_and_protected_paths() {
+        let set = vec![
+            ScanResult::new("a", "a", PathBuf::from("/tmp/a")),
+            ScanResult::new("b", "b", PathBuf::from("/tmp/b")).with_safety(SafetyLevel::Caution),
+            ScanResult::new("c", "c", PathBuf::from("/tmp/.hidden")),
+            ScanResult::new("d", "d", PathBuf::from("/System/Library/x")),
+        ];
+        let (safe, dropped) = only_safe(set);
+
+        assert_eq!(ids(&safe), vec!["a"]);
+        assert_eq!(dropped, 3);
+    }
+
+    #[test]
+    fn safe_clean_leaves_out_reviewed_categories_and_recent_logs() {
+        let now = Utc::now();
+        let category = |id: &str, items: Vec<ScanResult>| CategoryScanResult {
+            scanner_id: id.to_string(),
+            name: id.to_string(),
+            category: crate::plugin::ScannerCategory::System,
+            items,
+            scan_duration: std::time::Duration::ZERO,
+            unreadable: 0,
+            omitted: 0,
+        };
+        let item = |id: &str, age_days: i64| {
+            ScanResult::new(id, id, PathBuf::from(format!("/tmp/{}", id)))
+                .with_last_modified(Some(now - Duration::days(age_days)))
+        };
+        let categories = vec![
+            category(
+                "system_caches",
+                vec![
+                    item("cache", 1),
+                    item("risky_cache", 1).with_safety(SafetyLevel::Caution),
+                ],
+            ),
+            category("browser_cache", vec![item("chrome_cache", 4)]),
+            category("system_logs", vec![item("old_log", 30), item("new_log", 2)]),
+            category("trash", vec![item("trashed", 3)]),
+            category("duplicates", vec![item("dupe", 100)]),
+            category("large_old_files", vec![item("video", 400)]),
+            category("privacy", vec![item("history", 10)]),
+        ];
+
+        let picked = safe_clean_items(&categories, 7, now).unwrap();
+        assert_eq!(
+            ids(&picked),
+            vec!["cache", "chrome_cache", "old_log", "trashed"]
+        );
+    }
+
+    #[test]
+    fn resume_retries_only_failed_paths_that_still_exist() {
+        let dir = std::env::temp_dir().join("cleanmac-resume-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("locked")).unwrap();
+        std::fs::write(dir.join("locked/data.bin"), vec![0u8; 64]).unwrap();
+        std::fs::write(dir.join("login.plist"), b"plist").unwrap();
+
+        let exec_file = dir.join("execution.json");
+        let json = serde_json::json!({
+            "version": "1.0",
+            "timestamp": Utc::now(),
+            "plan_file": null,
+            "status": "partial",
+            "categories": [{
+                "id": "all",
+                "status": "partial",
+                "deleted_count": 3,
+                "deleted_size_bytes": 4096,
+                "failed_count": 4,
+                "failed_items": [
+                    { "path": dir.join("login.plist"), "error": PROTECTED_REFUSAL },
+                    { "path": dir.join("locked"), "error": "Permission denied" },
+                    { "path": dir.join("already-gone"), "error": "Resource busy" },
+                    { "path": dir.join("locked"), "error": "Permission denied" }
+                ]
+            }],
+            "total_deleted_size": 4096,
+            "duration_ms": 10
+        });
+        std::fs::write(&exec_file, json.to_string()).unwrap();
+
+        let content = std::fs::read_to_string(&exec_file).unwrap();
+        let exec: ExecutionResult = serde_json::from_str(&content).unwrap();
+        let (retry, gone) = failed_from_execution(&exec);
+
+        assert_eq!(retry.len(), 1);
+        assert_eq!(retry[0].path, dir.join("locked"));
+        assert_eq!(retry[0].size, 64);
+        assert_eq!(retry[0].safety_level, SafetyLevel::Caution);
+        assert_eq!(gone, vec![dir.join("already-gone")]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn plan_keeps_its_verdicts_and_protected_items_are_refused() {
+        use crate::cleaner::DefaultCleaner;
+        use crate::output::{CategoryPlanResult, PlanItem};
+        use crate::plugin::{CleanConfig, Cleaner, TrashMode};
+
+        let dir = std::env::temp_dir().join("cleanmac-plan-verdicts");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let safe = dir.join("app.cache");
+        let protected = dir.join("login.keychain-db");
+        std::fs::write(&safe, vec![0u8; 10]).unwrap();
+        std::fs::write(&protected, vec![0u8; 20]).unwrap();
+
+        let plan_item = |path: &PathBuf, size, level| PlanItem {
+            path: path.clone(),
+            size_bytes: size,
+            safety_level: Some(level),
+            category: Some(ScannerCategory::System),
+        };
+        let plan = PlanResult::new(
+            vec![CategoryPlanResult {
+                id: "system_caches".to_string(),
+                action: "delete".to_string(),
+                items: vec![
+                    plan_item(&safe, 10, SafetyLevel::Safe),
+                    plan_item(&protected, 20, SafetyLevel::Protected),
+                ],
+            }],
+            None,
+        );
+        let json = serde_json::to_string(&plan).unwrap();
+        let items = items_from_plan(&serde_json::from_str(&json).unwrap());
+        assert_eq!(items[1].safety_level, SafetyLevel::Protected);
+
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: false,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: true,
+            trash_mode: TrashMode::Permanent,
+        };
+        let result = DefaultCleaner::new()
+            .with_quiet(true)
+            .clean(&items, &config)
+            .unwrap();
+
+        assert_eq!(result.success_count, 1);
+        assert_eq!(result.total_freed, 10);
+        assert_eq!(
+            result.failed_items,
+            vec![(protected.clone(), PROTECTED_REFUSAL.to_string())]
+        );
+        assert!(!safe.exists());
+        assert!(protected.exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::config::Config;
+use anyhow::{bail, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::os::unix::io::AsRawFd;
+use std::path::Path;
+
+// Held for the whole of a deleting command so a scheduled run and a manual one
+// can't clean at the same time. The kernel drops the flock when the process
+// exits, so a crash never leaves it stuck.
+pub struct CleanLock {
+    _file: File,
+}
+
+impl CleanLock {
+    pub fn acquire() -> Result<Self> {
+        Self::acquire_at(&Config::clean_lock_path())
+    }
+
+    fn acquire_at(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(path)?;
+
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::WouldBlock {
+                bail!("another cleanmac operation is in progress");
+            }
+            return Err(err.into());
+        }
+        Ok(Self { _file: file })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn second_acquisition_fails_until_the_first_is_dropped() {
+        let dir = std::env::temp_dir().join("cleanmac-clean-lock");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("clean.lock");
+
+        let first = CleanLock::acquire_at(&path).unwrap();
+        let err = CleanLock::acquire_at(&path).err().unwrap();
+        assert_eq!(err.to_string(), "another cleanmac operation is in progress");
+
+        drop(first);
+        assert!(CleanLock::acquire_at(&path).is_ok());
+    }
+}
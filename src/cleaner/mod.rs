@@ -1,15 +1,46 @@
-use crate::history::HistoryLogger;
-use crate::plugin::{CleanConfig, CleanResult, Cleaner, SafetyLevel, ScanResult};
+mod archive;
+mod filters;
+mod lock;
+mod open_files;
+mod trash;
+mod undo;
+
+pub use archive::archive_items;
+pub use filters::{
+    auto_confirmed, failed_from_execution, items_from_plan, keep_recent, older_than, only_safe,
+    safe_clean_items, SAFE_CLEAN_SCANNERS,
+};
+pub use lock::CleanLock;
+pub use open_files::OpenFileIndex;
+pub use trash::{
+    empty_trash, home_trash, missed_trash, remove_path, trash_locations, EmptyTrashResult,
+    TrashLocation,
+};
+pub use undo::{last_run_id, pending_removals, undo_entries};
+
+use crate::history::{new_run_id, HistoryLogger};
+use crate::plugin::ignore::IgnoreRules;
+use crate::plugin::{
+    CleanConfig, CleanResult, Cleaner, CommandOutcome, SafetyLevel, ScanResult, TrashMode,
+};
 use crate::safety::SafetyChecker;
+use crate::scanner::duplicates::removable_copies;
+use crate::suggest::disk_space;
 use anyhow::Result;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Command;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+// Why `clean` refused a Protected item; `--resume` relies on it to never retry one.
+pub const PROTECTED_REFUSAL: &str = "Protected; cleanmac never deletes it";
 
 pub struct DefaultCleaner {
     safety_checker: SafetyChecker,
     history_logger: HistoryLogger,
+    quiet: bool,
+    trash_dir: PathBuf,
+    ignore_rules: IgnoreRules,
 }
 
 impl DefaultCleaner {
@@ -17,21 +48,101 @@ impl DefaultCleaner {
         Self {
             safety_checker: SafetyChecker::new(),
             history_logger: HistoryLogger::new(),
+            quiet: false,
+            trash_dir: home_trash(),
+            ignore_rules: IgnoreRules::default(),
         }
     }
+
+    #[cfg(test)]
+    fn with_trash_dir(mut self, trash_dir: PathBuf) -> Self {
+        self.trash_dir = trash_dir;
+        self
+    }
+
+    #[cfg(test)]
+    fn with_history(mut self, history_logger: HistoryLogger) -> Self {
+        self.history_logger = history_logger;
+        self
+    }
+
+    // Suppresses per-item lines so stdout can carry machine-readable output.
+    pub fn with_quiet(mut self, quiet: bool) -> Self {
+        self.quiet = quiet;
+        self
+    }
 }
 
 impl Cleaner for DefaultCleaner {
     fn clean(&self, items: &[ScanResult], config: &CleanConfig) -> Result<CleanResult> {
         let start = Instant::now();
-        let mut result = CleanResult::new();
+        let dry_run = config.dry_run || crate::audit::is_active();
+        let mut result = CleanResult {
+            trash_mode: config.trash_mode,
+            dry_run,
+            ..CleanResult::new()
+        };
+
+        // A duplicate group stands for its other copies, never the one it keeps.
+        let copies: Vec<ScanResult>;
+        let items = if items.iter().any(|i| i.metadata.contains_key("keep_path")) {
+            copies = items
+                .iter()
+                .flat_map(|i| {
+                    if i.metadata.contains_key("keep_path") {
+                        removable_copies(i)
+                    } else {
+                        vec![Ok(i.clone())]
+                    }
+                })
+                .filter_map(|copy| {
+                    copy.map_err(|refused| {
+                        result.failed_items.push(refused);
+                        result.failed_count += 1;
+                    })
+                    .ok()
+                })
+                .collect();
+            &copies[..]
+        } else {
+            items
+        };
+
+        let open_files = if config.skip_open_files {
+            let paths: Vec<&Path> = items.iter().map(|i| i.path.as_path()).collect();
+            OpenFileIndex::query(&paths)
+        } else {
+            OpenFileIndex::default()
+        };
+        let mut attempted_delete = false;
+        let run_id = new_run_id();
 
         for item in items {
             if let Some(command) = item.metadata.get("command") {
                 if item.metadata.get("scanner_id").map(|s| s.as_str()) == Some("maintenance") {
-                    match self.execute_command(command, config.dry_run) {
-                        Ok(()) => {
-                            result.success_count += 1;
+                    if item.metadata.get("available").map(|s| s.as_str()) == Some("false") {
+                        result
+                            .failed_items
+                            .push((item.path.clone(), "Command not found".to_string()));
+                        result.failed_count += 1;
+                        continue;
+                    }
+                    let measure = item.metadata.get("measure_path").map(Path::new);
+                    match self.execute_command(&item.name, command, measure, dry_run) {
+                        Ok(None) => result.success_count += 1,
+                        Ok(Some(outcome)) => {
+                            if outcome.succeeded() {
+                                result.success_count += 1;
+                                result.total_freed += outcome.freed.unwrap_or(0);
+                            } else {
+                                let error = match outcome.exit_code {
+                                    Some(code) => format!("Command exited with {}", code),
+                                    None => "Command was killed by a signal".to_string(),
+                                };
+                                result.failed_items.push((item.path.clone(), error));
+                                result.failed_count += 1;
+                            }
+                            result.commands.push(outcome);
                         }
                         Err(e) => {
                             result.failed_items.push((item.path.clone(), e.to_string()));
@@ -42,23 +153,97 @@ impl Cleaner for DefaultCleaner {
                 }
             }
 
-            if !self.can_clean(item) {
-                result
-                    .failed_items
-                    .push((item.path.clone(), "Not safe to delete".to_string()));
+            // Plans and --resume files skip the scan, so check the rules again.
+            if self.ignore_rules.is_ignored(&item.path) {
+                self.log(&format!(
+                    "Skipped (.cleanmacignore): {}",
+                    item.path.display()
+                ));
+                result.ignored.push(item.path.clone());
+                continue;
+            }
+
+            if !self.can_clean(item, config) {
+                if self.effective_level(item) == SafetyLevel::Caution {
+                    self.log(&format!(
+                        "Skipped (caution level excluded): {}",
+                        item.path.display()
+                    ));
+                    result.excluded_caution.push(item.path.clone());
+                } else {
+                    result
+                        .failed_items
+                        .push((item.path.clone(), PROTECTED_REFUSAL.to_string()));
+                    result.failed_count += 1;
+                }
+                continue;
+            }
+
+            // Under `--user`, never touch files that belong to someone else.
+            if let Some(reason) = crate::user::ownership_refusal(&item.path) {
+                result.failed_items.push((item.path.clone(), reason));
                 result.failed_count += 1;
                 continue;
             }
 
-            match self.delete_path(&item.path, config.dry_run) {
-                Ok(()) => {
+            if let Some(holder) = open_files.holder_of(&item.path) {
+                self.log(&format!(
+                    "Skipped (open by {} [{}]): {}",
+                    holder.command,
+                    holder.pid,
+                    item.path.display()
+                ));
+                result
+                    .skipped_items
+                    .push((item.path.clone(), holder.command.clone()));
+                continue;
+            }
+
+            if item.path.symlink_metadata().is_err() {
+                self.log(&format!("Already gone: {}", item.path.display()));
+                result.already_gone.push(item.path.clone());
+                continue;
+            }
+
+            if let Some(ms) = config.throttle_ms.filter(|_| !dry_run) {
+                if attempted_delete {
+                    std::thread::sleep(Duration::from_millis(ms));
+                }
+            }
+            attempted_delete = true;
+
+            let contents_only =
+                item.metadata.get("clean_mode").map(|s| s.as_str()) == Some("contents");
+
+            match self.delete_path(
+                &item.path,
+                contents_only,
+                config.trash_mode,
+                &run_id,
+                dry_run,
+            ) {
+                Ok(used) => {
+                    if missed_trash(&item.path, config.trash_mode, used, &self.trash_dir) {
+                        result.deleted_permanently.push(item.path.clone());
+                    }
                     result.success_count += 1;
-                    result.total_freed += item.size;
+                    if used == TrashMode::MoveToTrash {
+                        result.moved_to_trash += item.size;
+                    } else {
+                        result.total_freed += item.size;
+                    }
 
-                    if config.log_history {
-                        let _ = self.history_logger.log_delete(&item.path, Some(item.size));
+                    // A dry run removed nothing, so there is nothing to undo.
+                    if config.log_history && !dry_run {
+                        let _ = self
+                            .history_logger
+                            .log_removal(&item.path, item.size, used, &run_id);
+                        result.run_id = Some(run_id.clone());
                     }
                 }
+                Err(e) if is_not_found(&e) => {
+                    result.already_gone.push(item.path.clone());
+                }
                 Err(e) => {
                     result.failed_items.push((item.path.clone(), e.to_string()));
                     result.failed_count += 1;
@@ -70,49 +255,462 @@ impl Cleaner for DefaultCleaner {
         Ok(result)
     }
 
-    fn can_clean(&self, item: &ScanResult) -> bool {
-        matches!(item.safety_level, SafetyLevel::Safe | SafetyLevel::Caution)
-            && self.safety_checker.is_safe_to_delete(&item.path)
+    fn can_clean(&self, item: &ScanResult, config: &CleanConfig) -> bool {
+        match self.effective_level(item) {
+            SafetyLevel::Safe => true,
+            SafetyLevel::Caution => config.clean_caution,
+            SafetyLevel::Protected => false,
+        }
     }
 }
 
 impl DefaultCleaner {
-    fn delete_path(&self, path: &Path, dry_run: bool) -> Result<()> {
+    // The stricter of the scanner's rating and what the path itself looks like.
+    fn effective_level(&self, item: &ScanResult) -> SafetyLevel {
+        match (
+            item.safety_level,
+            self.safety_checker.check_path(&item.path),
+        ) {
+            (SafetyLevel::Protected, _) | (_, SafetyLevel::Protected) => SafetyLevel::Protected,
+            (SafetyLevel::Caution, _) | (_, SafetyLevel::Caution) => SafetyLevel::Caution,
+            _ => SafetyLevel::Safe,
+        }
+    }
+
+    fn log(&self, line: &str) {
+        if !self.quiet {
+            println!("{}", line);
+        }
+    }
+
+    // Returns how the item went; emptying a folder reports Permanent if any
+    // of its children had to be deleted outright.
+    fn delete_path(
+        &self,
+        path: &Path,
+        contents_only: bool,
+        mode: TrashMode,
+        run_id: &str,
+        dry_run: bool,
+    ) -> Result<TrashMode> {
         if dry_run {
-            println!("[DRY-RUN] Would delete: {}", path.display());
-            return Ok(());
+            let action = match (contents_only, mode) {
+                (true, _) => "empty",
+                (false, TrashMode::MoveToTrash) => "move to Trash",
+                (false, TrashMode::Permanent) => "delete",
+            };
+            self.log(&format!("[DRY-RUN] Would {}: {}", action, path.display()));
+            return Ok(mode);
         }
 
-        if path.is_dir() {
-            fs::remove_dir_all(path)?;
-        } else if path.exists() {
-            fs::remove_file(path)?;
+        // Apps expect their cache root to exist, so only its children go.
+        if contents_only && path.is_dir() {
+            let mut used = mode;
+            for entry in fs::read_dir(path)? {
+                let child = entry?.path();
+                if self.ignore_rules.is_ignored(&child) {
+                    continue;
+                }
+                if remove_path(&child, mode, &self.trash_dir, Some(run_id))? == TrashMode::Permanent
+                {
+                    used = TrashMode::Permanent;
+                }
+            }
+            self.log(&format!("Emptied: {}", path.display()));
+            return Ok(used);
         }
 
-        println!("Deleted: {}", path.display());
-        Ok(())
+        let used = remove_path(path, mode, &self.trash_dir, Some(run_id))?;
+        if missed_trash(path, mode, used, &self.trash_dir) {
+            self.log(&format!(
+                "Deleted (could not move to Trash): {}",
+                path.display()
+            ));
+        } else if used == TrashMode::MoveToTrash {
+            self.log(&format!("Moved to Trash: {}", path.display()));
+        } else {
+            self.log(&format!("Deleted: {}", path.display()));
+        }
+        Ok(used)
     }
 
-    fn execute_command(&self, command: &str, dry_run: bool) -> Result<()> {
+    // Runs a maintenance command and keeps what it printed. A non-zero exit is
+    // still an outcome; only failing to start the shell is an error.
+    fn execute_command(
+        &self,
+        task: &str,
+        command: &str,
+        measure: Option<&Path>,
+        dry_run: bool,
+    ) -> Result<Option<CommandOutcome>> {
         if dry_run {
-            println!("[DRY-RUN] Would execute: {}", command);
-            return Ok(());
+            self.log(&format!("[DRY-RUN] Would execute: {}", command));
+            return Ok(None);
         }
 
+        let free_before = measure.and_then(|p| disk_space(p).ok());
         let output = Command::new("sh").arg("-c").arg(command).output()?;
+        let free_after = measure.and_then(|p| disk_space(p).ok());
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Command failed: {}", stderr);
-        }
+        let outcome = CommandOutcome {
+            task: task.to_string(),
+            command: command.to_string(),
+            exit_code: output.status.code(),
+            stdout: String::from_utf8_lossy(&output.stdout).trim().to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            freed: free_before
+                .zip(free_after)
+                .map(|(before, after)| after.free_bytes.saturating_sub(before.free_bytes)),
+        };
 
-        println!("Executed: {}", command);
-        Ok(())
+        match outcome.exit_code {
+            Some(code) => self.log(&format!("Executed: {} (exit {})", command, code)),
+            None => self.log(&format!("Executed: {} (killed by a signal)", command)),
+        }
+        for line in outcome.stdout.lines().chain(outcome.stderr.lines()) {
+            self.log(&format!("  {}", line));
+        }
+        if let Some(freed) = outcome.freed {
+            self.log(&format!("  Freed: {}", crate::utils::format_size(freed)));
+        }
+        Ok(Some(outcome))
     }
 }
 
+// Lost a race with whatever else was cleaning the same path.
+fn is_not_found(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<std::io::Error>()
+        .is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound)
+}
+
 impl Default for DefaultCleaner {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contents_mode_keeps_directory() {
+        let root = std::env::temp_dir().join("cleanmac-clean-mode");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("nested")).unwrap();
+        fs::write(root.join("a.cache"), vec![0u8; 100]).unwrap();
+        fs::write(root.join("nested/b.cache"), vec![0u8; 200]).unwrap();
+
+        let mut item = ScanResult::new("cache_test", "Cache", root.clone()).with_size(300);
+        item.metadata
+            .insert("clean_mode".to_string(), "contents".to_string());
+
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: false,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: true,
+            trash_mode: TrashMode::Permanent,
+        };
+        let result = DefaultCleaner::new().clean(&[item], &config).unwrap();
+
+        assert_eq!(result.success_count, 1);
+        assert_eq!(result.total_freed, 300);
+        assert!(root.is_dir());
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn duplicate_group_removes_only_the_other_copies() {
+        let root = std::env::temp_dir().join("cleanmac-clean-dups");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let paths: Vec<_> = ["a", "b|c", "c"].iter().map(|n| root.join(n)).collect();
+        for path in &paths {
+            fs::write(path, vec![1u8; 10]).unwrap();
+        }
+
+        let mut group = ScanResult::new("dup_test", "a", paths[0].clone());
+        group.members = paths.clone();
+        group
+            .metadata
+            .insert("member_size".to_string(), "10".to_string());
+        group.metadata.insert(
+            "member_hash".to_string(),
+            crate::scanner::duplicates::file_hash(&paths[0]).unwrap(),
+        );
+        assert!(crate::scanner::duplicates::set_keeper(
+            &mut group, &paths[1]
+        ));
+
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: false,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: true,
+            trash_mode: TrashMode::Permanent,
+        };
+        let result = DefaultCleaner::new().clean(&[group], &config).unwrap();
+
+        assert_eq!(result.success_count, 2);
+        assert_eq!(result.total_freed, 20);
+        assert!(!paths[0].exists());
+        assert!(paths[1].exists());
+        assert!(!paths[2].exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn maintenance_commands_report_their_output_and_exit_status() {
+        let task = |name: &str, command: &str| {
+            let mut item = ScanResult::new(name, name, PathBuf::from(command));
+            item.metadata
+                .insert("scanner_id".to_string(), "maintenance".to_string());
+            item.metadata
+                .insert("command".to_string(), command.to_string());
+            item.metadata.insert(
+                "measure_path".to_string(),
+                std::env::temp_dir().display().to_string(),
+            );
+            item
+        };
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: false,
+            ..Default::default()
+        };
+        let result = DefaultCleaner::new()
+            .with_quiet(true)
+            .clean(
+                &[
+                    task("Greet", "echo '  hello  '; echo warned >&2"),
+                    task("Fail", "echo broken >&2; exit 3"),
+                ],
+                &config,
+            )
+            .unwrap();
+
+        assert_eq!((result.success_count, result.failed_count), (1, 1));
+        let greet = &result.commands[0];
+        assert_eq!(greet.exit_code, Some(0));
+        assert_eq!(greet.stdout, "hello");
+        assert_eq!(greet.stderr, "warned");
+        assert!(greet.freed.is_some());
+        let fail = &result.commands[1];
+        assert_eq!(fail.exit_code, Some(3));
+        assert_eq!(fail.stderr, "broken");
+        assert_eq!(result.failed_items[0].1, "Command exited with 3");
+    }
+
+    #[test]
+    fn throttle_pauses_between_deletions() {
+        let root = std::env::temp_dir().join("cleanmac-throttle-test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let items: Vec<ScanResult> = (0..3)
+            .map(|i| {
+                let path = root.join(format!("f{}", i));
+                fs::write(&path, b"x").unwrap();
+                ScanResult::new(format!("f{}", i), format!("f{}", i), path)
+            })
+            .collect();
+
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: false,
+            skip_open_files: false,
+            throttle_ms: Some(40),
+            clean_caution: true,
+            trash_mode: TrashMode::Permanent,
+        };
+        let start = Instant::now();
+        let result = DefaultCleaner::new().clean(&items, &config).unwrap();
+
+        assert_eq!(result.success_count, 3);
+        // Two pauses: none before the first deletion.
+        assert!(start.elapsed() >= Duration::from_millis(80));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn vanished_path_is_already_gone_not_freed() {
+        let root = std::env::temp_dir().join("cleanmac-vanished-test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let kept = root.join("kept.cache");
+        let vanished = root.join("vanished.cache");
+        fs::write(&kept, vec![0u8; 10]).unwrap();
+        fs::write(&vanished, vec![0u8; 500]).unwrap();
+
+        let items = vec![
+            ScanResult::new("kept", "kept", kept.clone()).with_size(10),
+            ScanResult::new("vanished", "vanished", vanished.clone()).with_size(500),
+        ];
+        fs::remove_file(&vanished).unwrap();
+
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: false,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: true,
+            trash_mode: TrashMode::Permanent,
+        };
+        let result = DefaultCleaner::new().clean(&items, &config).unwrap();
+
+        assert_eq!(result.success_count, 1);
+        assert_eq!(result.failed_count, 0);
+        assert_eq!(result.total_freed, 10);
+        assert_eq!(result.already_gone, vec![vanished]);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn safe_only_skips_caution_items() {
+        let root = std::env::temp_dir().join("cleanmac-safe-only-test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let safe = root.join("safe.cache");
+        let caution = root.join("caution.log");
+        fs::write(&safe, vec![0u8; 10]).unwrap();
+        fs::write(&caution, vec![0u8; 20]).unwrap();
+
+        let items = vec![
+            ScanResult::new("safe", "safe", safe.clone()).with_size(10),
+            ScanResult::new("caution", "caution", caution.clone())
+                .with_size(20)
+                .with_safety(SafetyLevel::Caution),
+        ];
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: false,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: false,
+            trash_mode: TrashMode::Permanent,
+        };
+        let result = DefaultCleaner::new()
+            .with_quiet(true)
+            .clean(&items, &config)
+            .unwrap();
+
+        assert_eq!(result.success_count, 1);
+        assert_eq!(result.failed_count, 0);
+        assert_eq!(result.total_freed, 10);
+        assert_eq!(result.excluded_caution, vec![caution.clone()]);
+        assert!(!safe.exists());
+        assert!(caution.exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn ignored_paths_are_skipped_even_without_a_scan() {
+        let root = std::env::temp_dir().join("cleanmac-clean-ignore-test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("Caches")).unwrap();
+        fs::write(root.join(".cleanmacignore"), "keep.bin\n").unwrap();
+        let keep = root.join("keep.bin");
+        let kept_child = root.join("Caches/keep.bin");
+        let cache = root.join("Caches/blob.cache");
+        for path in [&keep, &kept_child, &cache] {
+            fs::write(path, vec![0u8; 10]).unwrap();
+        }
+
+        let mut emptied = ScanResult::new("caches", "caches", root.join("Caches")).with_size(20);
+        emptied
+            .metadata
+            .insert("clean_mode".to_string(), "contents".to_string());
+        let items = vec![
+            ScanResult::new("keep", "keep", keep.clone()).with_size(10),
+            emptied,
+        ];
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: false,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: true,
+            trash_mode: TrashMode::Permanent,
+        };
+        let result = DefaultCleaner::new()
+            .with_quiet(true)
+            .clean(&items, &config)
+            .unwrap();
+
+        assert_eq!(result.ignored, vec![keep.clone()]);
+        assert!(keep.exists());
+        assert!(kept_child.exists());
+        assert!(!cache.exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn cleaned_file_lands_in_the_trash() {
+        let root = std::env::temp_dir().join("cleanmac-trash-mode-test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("Caches")).unwrap();
+        let trash = root.join(".Trash");
+        let cache = root.join("Caches/blob.cache");
+        fs::write(&cache, vec![0u8; 40]).unwrap();
+
+        let items = vec![ScanResult::new("blob", "blob", cache.clone()).with_size(40)];
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: false,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: true,
+            trash_mode: TrashMode::MoveToTrash,
+        };
+        let result = DefaultCleaner::new()
+            .with_quiet(true)
+            .with_trash_dir(trash.clone())
+            .clean(&items, &config)
+            .unwrap();
+
+        assert_eq!(result.success_count, 1);
+        assert_eq!(result.total_freed, 0);
+        assert_eq!(result.moved_to_trash, 40);
+        assert_eq!(result.trash_mode, TrashMode::MoveToTrash);
+        assert!(result.deleted_permanently.is_empty());
+        assert!(!cache.exists());
+        assert_eq!(fs::read(trash.join("blob.cache")).unwrap().len(), 40);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+    #[test]
+    fn dry_run_result_says_nothing_moved() {
+        let root = std::env::temp_dir().join("cleanmac-dry-run-result");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let cache = root.join("blob.cache");
+        fs::write(&cache, vec![0u8; 40]).unwrap();
+
+        let items = vec![ScanResult::new("blob", "blob", cache.clone()).with_size(40)];
+        let config = CleanConfig {
+            dry_run: true,
+            log_history: false,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: true,
+            trash_mode: TrashMode::MoveToTrash,
+        };
+        let result = DefaultCleaner::new()
+            .with_quiet(true)
+            .clean(&items, &config)
+            .unwrap();
+
+        assert!(result.dry_run);
+        assert!(result.run_id.is_none());
+        assert!(cache.exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
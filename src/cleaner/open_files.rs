@@ -0,0 +1,122 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenFile {
+    pub pid: u32,
+    pub command: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Default)]
+pub struct OpenFileIndex {
+    files: Vec<OpenFile>,
+}
+
+impl OpenFileIndex {
+    // One lsof call for the whole batch; spawning per file is far too slow.
+    pub fn query(candidates: &[&Path]) -> Self {
+        let existing: Vec<&Path> = candidates.iter().copied().filter(|p| p.exists()).collect();
+        if existing.is_empty() {
+            return Self::default();
+        }
+
+        let mut files = Vec::new();
+        for chunk in existing.chunks(200) {
+            let mut cmd = Command::new("lsof");
+            cmd.arg("-n").arg("-P").arg("-F").arg("pcn");
+            let (dirs, plain): (Vec<&Path>, Vec<&Path>) = chunk.iter().partition(|p| p.is_dir());
+            for dir in dirs {
+                cmd.arg("+D").arg(dir);
+            }
+            if !plain.is_empty() {
+                cmd.arg("--").args(plain);
+            }
+
+            // lsof exits 1 when some of the paths are not open; output is still valid
+            if let Ok(output) = cmd.output() {
+                files.extend(parse_lsof_output(&String::from_utf8_lossy(&output.stdout)));
+            }
+        }
+
+        Self { files }
+    }
+
+    pub fn holder_of(&self, path: &Path) -> Option<&OpenFile> {
+        self.files.iter().find(|f| f.path.starts_with(path))
+    }
+}
+
+pub fn parse_lsof_output(output: &str) -> Vec<OpenFile> {
+    let mut files = Vec::new();
+    let mut pid = 0;
+    let mut command = String::new();
+
+    for line in output.lines() {
+        let Some(tag) = line.chars().next() else {
+            continue;
+        };
+        let value = &line[tag.len_utf8()..];
+
+        match tag {
+            'p' => {
+                pid = value.parse().unwrap_or(0);
+                command.clear();
+            }
+            'c' => command = value.to_string(),
+            'n' if value.starts_with('/') => files.push(OpenFile {
+                pid,
+                command: command.clone(),
+                path: PathBuf::from(value),
+            }),
+            _ => {}
+        }
+    }
+
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_field_output() {
+        let output = "p412\n\
+                      cGoogle Chrome\n\
+                      f12\n\
+                      n/Users/me/Library/Caches/Google/Chrome/Default/Cache/data_0\n\
+                      f13\n\
+                      n/Users/me/Library/Caches/Google/Chrome/Default/Cache/index\n\
+                      p977\n\
+                      cSlack Helper\n\
+                      f4\n\
+                      n/Users/me/Library/Application Support/Slack/Cache/blob\n\
+                      f5\n\
+                      n127.0.0.1:5000->127.0.0.1:61000\n";
+
+        let files = parse_lsof_output(output);
+        assert_eq!(files.len(), 3);
+        assert_eq!(files[0].pid, 412);
+        assert_eq!(files[0].command, "Google Chrome");
+        assert_eq!(files[2].command, "Slack Helper");
+        assert_eq!(
+            files[2].path,
+            PathBuf::from("/Users/me/Library/Application Support/Slack/Cache/blob")
+        );
+    }
+
+    #[test]
+    fn holder_matches_files_inside_directories() {
+        let index = OpenFileIndex {
+            files: parse_lsof_output("p1\ncChrome\nn/cache/chrome/data_0\n"),
+        };
+
+        assert_eq!(
+            index.holder_of(Path::new("/cache/chrome")).unwrap().command,
+            "Chrome"
+        );
+        assert!(index.holder_of(Path::new("/cache/firefox")).is_none());
+        assert!(index.holder_of(Path::new("/cache/chrome/data_1")).is_none());
+    }
+}
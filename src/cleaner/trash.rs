@@ -0,0 +1,413 @@
+use crate::plugin::TrashMode;
+use crate::scanner::calculate_dir_size;
+use chrono::{Local, Utc};
+use plist::{Dictionary, Value};
+use std::ffi::{OsStr, OsString};
+use std::fs;
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Clone)]
+pub struct TrashLocation {
+    pub path: PathBuf,
+    pub size: u64,
+    pub entries: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct EmptyTrashResult {
+    pub freed: u64,
+    pub removed: usize,
+    // (trash folder, bytes freed there), for the history log.
+    pub emptied: Vec<(PathBuf, u64)>,
+    pub errors: Vec<String>,
+    pub via_finder: bool,
+}
+
+// ~/.Trash plus the per-user `.Trashes/<uid>` folder of every mounted volume,
+// keeping only the ones with something in them.
+pub fn trash_locations() -> Vec<TrashLocation> {
+    let uid = match crate::user::target_user() {
+        Some(user) => user.uid,
+        None => unsafe { libc::getuid() },
+    };
+    locations_in(
+        &crate::user::home_dir().join(".Trash"),
+        Path::new("/Volumes"),
+        uid,
+    )
+}
+
+fn locations_in(home_trash: &Path, volumes: &Path, uid: u32) -> Vec<TrashLocation> {
+    let mut paths = vec![home_trash.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(volumes) {
+        let mut volume_trashes: Vec<PathBuf> = entries
+            .flatten()
+            .map(|e| e.path().join(".Trashes").join(uid.to_string()))
+            .collect();
+        volume_trashes.sort();
+        paths.extend(volume_trashes);
+    }
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let entries = fs::read_dir(&path).ok()?.count();
+            (entries > 0).then(|| TrashLocation {
+                size: calculate_dir_size(&path),
+                path,
+                entries,
+            })
+        })
+        .collect()
+}
+
+pub fn home_trash() -> PathBuf {
+    crate::user::home_dir().join(".Trash")
+}
+
+// `trash` itself or a volume's .Trashes folder; moving from there would go nowhere.
+fn is_in_trash(path: &Path, trash: &Path) -> bool {
+    path.starts_with(trash)
+        || path
+            .components()
+            .any(|c| c == Component::Normal(".Trashes".as_ref()))
+}
+
+// Removes `path` the way `mode` asks and returns how it actually went.
+// Anything already in a Trash, or that can't be moved there (another
+// volume, ...), is deleted outright with a warning on stderr.
+pub fn remove_path(
+    path: &Path,
+    mode: TrashMode,
+    trash: &Path,
+    run_id: Option<&str>,
+) -> io::Result<TrashMode> {
+    if mode == TrashMode::MoveToTrash && !is_in_trash(path, trash) {
+        match move_to_trash(path, trash, run_id) {
+            Ok(_) => return Ok(TrashMode::MoveToTrash),
+            Err(e) if e.kind() != io::ErrorKind::NotFound => eprintln!(
+                "Warning: could not move {} to Trash ({}); deleting it permanently",
+                path.display(),
+                e
+            ),
+            Err(_) => {}
+        }
+    }
+
+    if path.is_dir() && !path.is_symlink() {
+        fs::remove_dir_all(path)?;
+    } else if path.symlink_metadata().is_ok() {
+        fs::remove_file(path)?;
+    }
+    Ok(TrashMode::Permanent)
+}
+
+// Asked for the Trash but deleted outright, and not because it was already
+// in a Trash.
+pub fn missed_trash(path: &Path, asked: TrashMode, used: TrashMode, trash: &Path) -> bool {
+    asked == TrashMode::MoveToTrash && used == TrashMode::Permanent && !is_in_trash(path, trash)
+}
+
+// Moves `path` into `trash` and writes a hidden `.<name>.cleanmac.plist`
+// beside it with the original path and the clean run, so the item can be put
+// back. A name already in the Trash gets a timestamp. Fails with EXDEV across
+// volumes.
+pub fn move_to_trash(path: &Path, trash: &Path, run_id: Option<&str>) -> io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?;
+    fs::create_dir_all(trash)?;
+
+    let dest = free_name_in(trash, Path::new(name));
+    fs::rename(path, &dest)?;
+
+    // Raw bytes, so names that aren't valid UTF-8 can still be put back.
+    let mut record = Dictionary::new();
+    record.insert(
+        "OriginalPath".to_string(),
+        Value::Data(path.as_os_str().as_bytes().to_vec()),
+    );
+    record.insert(
+        "TrashedAt".to_string(),
+        Value::String(Utc::now().to_rfc3339()),
+    );
+    if let Some(run_id) = run_id {
+        record.insert("RunId".to_string(), Value::String(run_id.to_string()));
+    }
+    // The item is already safe in the Trash; a missing record only costs the
+    // put-back information.
+    if let Err(e) = Value::Dictionary(record).to_file_xml(record_path(&dest)) {
+        eprintln!(
+            "Warning: could not record where {} came from: {}",
+            dest.display(),
+            e
+        );
+    }
+    Ok(dest)
+}
+
+pub(crate) fn record_path(trashed: &Path) -> PathBuf {
+    let mut name = OsString::from(".");
+    name.push(trashed.file_name().unwrap_or_default());
+    name.push(".cleanmac.plist");
+    trashed.with_file_name(name)
+}
+
+// Records written before OriginalPath was stored as bytes hold a string.
+fn original_path(value: &Value) -> Option<PathBuf> {
+    match value {
+        Value::Data(bytes) => Some(PathBuf::from(OsStr::from_bytes(bytes))),
+        Value::String(text) => Some(PathBuf::from(text)),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrashRecord {
+    pub trashed: PathBuf,
+    pub original: PathBuf,
+    pub run_id: Option<String>,
+}
+
+// Everything in `trash` that cleanmac put there and that is still there.
+pub fn trash_records(trash: &Path) -> Vec<TrashRecord> {
+    let Ok(entries) = fs::read_dir(trash) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            let name = file_name
+                .as_bytes()
+                .strip_prefix(b".")?
+                .strip_suffix(b".cleanmac.plist")?;
+            let trashed = trash.join(OsStr::from_bytes(name));
+            trashed.symlink_metadata().ok()?;
+
+            let record = Value::from_file(entry.path()).ok()?;
+            let record = record.as_dictionary()?;
+            let text = |key: &str| record.get(key).and_then(|v| v.as_string());
+            Some(TrashRecord {
+                original: original_path(record.get("OriginalPath")?)?,
+                run_id: text("RunId").map(|s| s.to_string()),
+                trashed,
+            })
+        })
+        .collect()
+}
+
+// Moves a trashed item back where it came from. Something new at the
+// original path is never replaced; that fails with AlreadyExists.
+pub fn put_back(record: &TrashRecord) -> io::Result<()> {
+    if record.original.symlink_metadata().is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            "something else is at the original path",
+        ));
+    }
+    if let Some(parent) = record.original.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&record.trashed, &record.original)?;
+    let _ = fs::remove_file(record_path(&record.trashed));
+    Ok(())
+}
+
+// `notes.txt`, then `notes 2026-10-16 14.03.22.txt`, then with a counter.
+fn free_name_in(trash: &Path, name: &Path) -> PathBuf {
+    let taken = |p: &Path| p.symlink_metadata().is_ok() || record_path(p).exists();
+    let candidate = trash.join(name);
+    if !taken(&candidate) {
+        return candidate;
+    }
+
+    let stem = name.file_stem().unwrap_or_default();
+    let stamp = Local::now().format("%Y-%m-%d %H.%M.%S");
+    let mut n = 1;
+    loop {
+        let mut candidate = stem.to_os_string();
+        if n == 1 {
+            candidate.push(format!(" {}", stamp));
+        } else {
+            candidate.push(format!(" {} {}", stamp, n));
+        }
+        if let Some(ext) = name.extension() {
+            candidate.push(".");
+            candidate.push(ext);
+        }
+        let candidate = trash.join(candidate);
+        if !taken(&candidate) {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+// Finder handles locked files and permissions better than we can, but it only
+// knows the trash of the logged-in user, so `--user` always removes directly.
+pub fn empty_trash(locations: &[TrashLocation]) -> EmptyTrashResult {
+    empty_locations(locations, crate::user::target_user().is_none())
+}
+
+// Whatever Finder leaves behind is removed by hand afterwards.
+fn empty_locations(locations: &[TrashLocation], use_finder: bool) -> EmptyTrashResult {
+    if crate::audit::is_active() {
+        return EmptyTrashResult {
+            errors: vec![crate::audit::REFUSED.to_string()],
+            ..Default::default()
+        };
+    }
+
+    let mut result = EmptyTrashResult {
+        via_finder: use_finder && finder_empty_trash(),
+        ..Default::default()
+    };
+
+    for location in locations {
+        if let Ok(entries) = fs::read_dir(&location.path) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                let removed = if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                    fs::remove_dir_all(&path)
+                } else {
+                    fs::remove_file(&path)
+                };
+                if let Err(e) = removed {
+                    result.errors.push(format!("{}: {}", path.display(), e));
+                }
+            }
+        }
+
+        let remaining = fs::read_dir(&location.path)
+            .map(|entries| entries.count())
+            .unwrap_or(0);
+        let freed = location
+            .size
+            .saturating_sub(calculate_dir_size(&location.path));
+        result.removed += location.entries.saturating_sub(remaining);
+        result.freed += freed;
+        if freed > 0 {
+            result.emptied.push((location.path.clone(), freed));
+        }
+    }
+
+    result
+}
+
+fn finder_empty_trash() -> bool {
+    Command::new("osascript")
+        .arg("-e")
+        .arg("tell application \"Finder\" to empty trash")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_and_empties_home_and_volume_trashes() {
+        let root = std::env::temp_dir().join("cleanmac-empty-trash");
+        let _ = fs::remove_dir_all(&root);
+        let home_trash = root.join("home/.Trash");
+        let volume_trash = root.join("Volumes/Backup/.Trashes/501");
+        fs::create_dir_all(home_trash.join("old project")).unwrap();
+        fs::write(home_trash.join("old project/notes.txt"), vec![0u8; 300]).unwrap();
+        fs::write(home_trash.join("photo.jpg"), vec![0u8; 200]).unwrap();
+        fs::create_dir_all(&volume_trash).unwrap();
+        fs::write(volume_trash.join("movie.mov"), vec![0u8; 500]).unwrap();
+        // Another user's trash on the same volume is left alone.
+        fs::create_dir_all(root.join("Volumes/Backup/.Trashes/502")).unwrap();
+        fs::write(root.join("Volumes/Backup/.Trashes/502/keep"), b"x").unwrap();
+        fs::create_dir_all(root.join("Volumes/Empty")).unwrap();
+
+        let locations = locations_in(&home_trash, &root.join("Volumes"), 501);
+        let found: Vec<(PathBuf, u64, usize)> = locations
+            .iter()
+            .map(|l| (l.path.clone(), l.size, l.entries))
+            .collect();
+        assert_eq!(
+            found,
+            vec![(home_trash.clone(), 500, 2), (volume_trash.clone(), 500, 1)]
+        );
+
+        let result = empty_locations(&locations, false);
+        assert_eq!(result.freed, 1000);
+        assert_eq!(result.removed, 3);
+        assert!(result.errors.is_empty());
+        assert!(home_trash.exists() && fs::read_dir(&home_trash).unwrap().count() == 0);
+        assert!(root.join("Volumes/Backup/.Trashes/502/keep").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn trashed_items_keep_their_original_path_and_never_clobber() {
+        let root = std::env::temp_dir().join("cleanmac-move-to-trash");
+        let _ = fs::remove_dir_all(&root);
+        let trash = root.join(".Trash");
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::create_dir_all(root.join("b/report")).unwrap();
+        fs::write(root.join("a/report.pdf"), b"first").unwrap();
+        fs::write(root.join("b/report.pdf"), b"second").unwrap();
+        fs::write(root.join("b/report/page.txt"), b"dir").unwrap();
+
+        let first = move_to_trash(&root.join("a/report.pdf"), &trash, None).unwrap();
+        let second = move_to_trash(&root.join("b/report.pdf"), &trash, None).unwrap();
+        let dir = move_to_trash(&root.join("b/report"), &trash, None).unwrap();
+
+        assert_eq!(first, trash.join("report.pdf"));
+        assert_ne!(second, first);
+        let second_name = second.file_name().unwrap().to_string_lossy().to_string();
+        assert!(second_name.starts_with("report ") && second_name.ends_with(".pdf"));
+        assert_eq!(fs::read(&first).unwrap(), b"first");
+        assert_eq!(fs::read(&second).unwrap(), b"second");
+        assert!(dir.join("page.txt").exists());
+        assert!(!root.join("a/report.pdf").exists());
+
+        let record = Value::from_file(record_path(&second)).unwrap();
+        let original = record
+            .as_dictionary()
+            .and_then(|d| d.get("OriginalPath"))
+            .and_then(original_path)
+            .unwrap();
+        assert_eq!(original, root.join("b/report.pdf"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn names_that_are_not_utf8_can_be_put_back() {
+        let root = std::env::temp_dir().join("cleanmac-trash-bytes");
+        let _ = fs::remove_dir_all(&root);
+        let trash = root.join(".Trash");
+        fs::create_dir_all(root.join("a")).unwrap();
+        fs::create_dir_all(&trash).unwrap();
+        let original = root.join("a").join(OsStr::from_bytes(b"caf\xe9.txt"));
+        fs::write(&original, b"bytes").unwrap();
+        fs::write(trash.join(OsStr::from_bytes(b"caf\xe9.txt")), b"taken").unwrap();
+
+        let trashed = move_to_trash(&original, &trash, Some("run-1")).unwrap();
+        assert!(trashed
+            .file_name()
+            .unwrap()
+            .as_bytes()
+            .starts_with(b"caf\xe9 "));
+        let records = trash_records(&trash);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].original, original);
+        assert_eq!(records[0].trashed, trashed);
+
+        put_back(&records[0]).unwrap();
+        assert_eq!(fs::read(&original).unwrap(), b"bytes");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,188 @@
+use super::trash::{put_back, trash_records, TrashRecord};
+use crate::history::HistoryEntry;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default)]
+pub struct UndoResult {
+    pub restored: Vec<PathBuf>,
+    // Went to the Trash but is no longer there (emptied, moved away).
+    pub missing: Vec<PathBuf>,
+    // Something new is at the original path, so the item stays in the Trash.
+    pub occupied: Vec<PathBuf>,
+    pub permanent: Vec<PathBuf>,
+    pub failed: Vec<(PathBuf, String)>,
+    // History entries something was put back for; each gets a RESTORE line.
+    pub reversed: Vec<HistoryEntry>,
+}
+
+// Removals not undone yet, oldest first as history reads them. A RESTORE
+// line cancels the earlier removal of the same path in the same run.
+pub fn pending_removals(entries: &[HistoryEntry]) -> Vec<&HistoryEntry> {
+    let mut pending: Vec<&HistoryEntry> = Vec::new();
+    for entry in entries {
+        if entry.action == "RESTORE" {
+            pending.retain(|e| e.path != entry.path || e.run_id != entry.run_id);
+        } else if entry.removed_something() {
+            pending.push(entry);
+        }
+    }
+    pending
+}
+
+// The newest run among `entries`. Entries written before runs had ids
+// belong to none.
+pub fn last_run_id<'a>(entries: &[&'a HistoryEntry]) -> Option<&'a str> {
+    entries.iter().rev().find_map(|e| e.run_id.as_deref())
+}
+
+// Puts back what `entries` moved to `trash`. The Trash records say where each
+// item came from; history says what was removed, so anything that can't be
+// found there is reported rather than silently dropped.
+pub fn undo_entries(entries: &[&HistoryEntry], trash: &Path) -> UndoResult {
+    let records = trash_records(trash);
+    let mut chosen: Vec<&TrashRecord> = Vec::new();
+    // (entry, record) pairs, to tell which entries a restore reverses.
+    let mut claims: Vec<(&HistoryEntry, &Path)> = Vec::new();
+    let mut result = UndoResult::default();
+
+    for entry in entries.iter().filter(|e| e.removed_something()) {
+        // An emptied folder is one entry but many records below it. Entries
+        // from before run ids only match records without one, never another
+        // run's.
+        let matching: Vec<&TrashRecord> = records
+            .iter()
+            .filter(|r| r.original.starts_with(&entry.path) && r.run_id == entry.run_id)
+            .collect();
+        if matching.is_empty() {
+            // Older history logged every removal as DELETE; with no record
+            // in the Trash it really is gone.
+            if entry.action == "TRASH" {
+                result.missing.push(entry.path.clone());
+            } else {
+                result.permanent.push(entry.path.clone());
+            }
+            continue;
+        }
+        for record in matching {
+            claims.push((entry, &record.trashed));
+            if !chosen.iter().any(|c| c.trashed == record.trashed) {
+                chosen.push(record);
+            }
+        }
+    }
+
+    // Parents first, in case a folder and something inside it were removed.
+    chosen.sort_by_key(|r| r.original.components().count());
+    let mut put_back_from: Vec<&Path> = Vec::new();
+    for record in chosen {
+        match put_back(record) {
+            Ok(()) => {
+                put_back_from.push(&record.trashed);
+                result.restored.push(record.original.clone())
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                result.occupied.push(record.original.clone())
+            }
+            Err(e) => result.failed.push((record.original.clone(), e.to_string())),
+        }
+    }
+
+    let mut reversed: Vec<&HistoryEntry> = Vec::new();
+    for (entry, trashed) in claims {
+        if put_back_from.contains(&trashed) && !reversed.iter().any(|r| std::ptr::eq(*r, entry)) {
+            reversed.push(entry);
+        }
+    }
+    result.reversed = reversed.into_iter().cloned().collect();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cleaner::DefaultCleaner;
+    use crate::history::HistoryLogger;
+    use crate::plugin::{CleanConfig, Cleaner, ScanResult, TrashMode};
+    use std::fs;
+
+    #[test]
+    fn undo_restores_trashed_items_without_clobbering() {
+        let root = std::env::temp_dir().join("cleanmac-undo-test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("Caches/app")).unwrap();
+        let trash = root.join(".Trash");
+        let blob = root.join("Caches/app/blob.cache");
+        let index = root.join("Caches/app/index.db");
+        let emptied = root.join("Caches/app/emptied.cache");
+        for path in [&blob, &index, &emptied] {
+            fs::write(path, b"cached").unwrap();
+        }
+
+        let logger = HistoryLogger::with_path(root.join("history.log"));
+        let cleaner = DefaultCleaner::new()
+            .with_quiet(true)
+            .with_trash_dir(trash.clone())
+            .with_history(HistoryLogger::with_path(root.join("history.log")));
+        let config = CleanConfig {
+            dry_run: false,
+            log_history: true,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: true,
+            trash_mode: TrashMode::MoveToTrash,
+        };
+        let items: Vec<ScanResult> = [&blob, &index, &emptied]
+            .iter()
+            .map(|p| ScanResult::new("x", "x", p.to_path_buf()).with_size(6))
+            .collect();
+        let result = cleaner.clean(&items, &config).unwrap();
+        assert_eq!(result.success_count, 3);
+
+        // The user emptied one from the Trash and made a new index meanwhile.
+        fs::remove_file(trash.join("emptied.cache")).unwrap();
+        fs::write(&index, b"new").unwrap();
+
+        // A removal from before run ids, long gone.
+        logger
+            .log(&HistoryEntry::new("DELETE", root.join("old.log")).with_size(9))
+            .unwrap();
+
+        let entries = logger.read_history(None).unwrap();
+        let pending = pending_removals(&entries);
+        let run_id = last_run_id(&pending).unwrap();
+        assert_eq!(Some(run_id), result.run_id.as_deref());
+
+        // A legacy entry above the run's items doesn't claim what the run trashed.
+        let legacy_parent = HistoryEntry::new("DELETE", root.join("Caches"));
+        let undo = undo_entries(&[&legacy_parent], &trash);
+        assert!(undo.restored.is_empty());
+        assert_eq!(undo.permanent, vec![root.join("Caches")]);
+
+        let undo = undo_entries(&pending, &trash);
+        assert_eq!(undo.restored, vec![blob.clone()]);
+        assert_eq!(undo.occupied, vec![index.clone()]);
+        assert_eq!(undo.missing, vec![emptied.clone()]);
+        assert_eq!(undo.permanent, vec![root.join("old.log")]);
+        assert!(undo.failed.is_empty());
+        assert_eq!(fs::read(&blob).unwrap(), b"cached");
+        assert_eq!(fs::read(&index).unwrap(), b"new");
+        assert!(trash.join("index.db").exists());
+
+        // Once logged, a restore takes its entry out of what undo offers.
+        for entry in &undo.reversed {
+            logger.log_restore(entry).unwrap();
+        }
+        let entries = logger.read_history(None).unwrap();
+        let pending: Vec<&std::path::Path> = pending_removals(&entries)
+            .iter()
+            .map(|e| e.path.as_path())
+            .collect();
+        assert_eq!(
+            pending,
+            vec![index.as_path(), emptied.as_path(), &root.join("old.log")]
+        );
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
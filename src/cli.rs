@@ -7,20 +7,48 @@ use clap::{Parser, Subcommand, ValueEnum};
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+    #[arg(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Scan and clean NAME's home directory instead of your own (requires root)"
+    )]
+    pub user: Option<String>,
+    #[arg(
+        long,
+        global = true,
+        help = "Let the duplicate and large-file scanners look inside hidden files and folders"
+    )]
+    pub include_hidden: bool,
+    #[arg(
+        long,
+        global = true,
+        value_name = "POLICY",
+        help = "Which copy of a duplicate group to keep: oldest, newest or path:<text> (overrides [scan] duplicate_keep)"
+    )]
+    pub keep_duplicate: Option<crate::config::KeepPolicy>,
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        help = "Run at most N scanners at once (overrides [scan] scan_threads)"
+    )]
+    pub scan_threads: Option<usize>,
+    #[arg(
+        long,
+        global = true,
+        help = "Audit mode: nothing can be deleted; --execute and --yes only preview (also CLEANMAC_AUDIT=1)"
+    )]
+    pub audit: bool,
 }
 
-#[derive(Clone, Debug, ValueEnum)]
+#[derive(Clone, Debug, ValueEnum, Default)]
 pub enum OutputFormat {
+    #[default]
     Human,
     Json,
 }
 
-impl Default for OutputFormat {
-    fn default() -> Self {
-        Self::Human
-    }
-}
-
 #[derive(Subcommand)]
 pub enum Commands {
     #[command(about = "Scan for cleanable items")]
@@ -33,6 +61,61 @@ pub enum Commands {
         out: Option<String>,
         #[arg(short = 'M', long, help = "Collect Spotlight metadata (slower)")]
         metadata: bool,
+        #[arg(long, help = "Show how long each scanner took")]
+        timing: bool,
+        #[arg(
+            long,
+            default_value = "size",
+            help = "Order categories and items: size (largest first), name, age (oldest first), count (most files first)"
+        )]
+        sort: ScanSort,
+        #[arg(
+            long,
+            default_value = "category",
+            conflicts_with = "brief",
+            help = "Group items by category, top-level directory (path), or size (size-bucket: <10MB, 10-100MB, >100MB)"
+        )]
+        group_by: ScanGroupBy,
+        #[arg(
+            long,
+            help = "Print only a one-line total (one JSON object with -F json), skipping slow scanners"
+        )]
+        brief: bool,
+        #[arg(
+            long,
+            requires = "brief",
+            help = "With --brief, also run the slow scanners"
+        )]
+        all: bool,
+        #[arg(
+            long,
+            value_name = "ID",
+            conflicts_with = "brief",
+            help = "Explain why one item (by id from -F json, or by path) was flagged and what deleting it means"
+        )]
+        explain: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "brief",
+            help = "Also report the bytes deleting would really free, counting hard links and APFS clones once (slow)"
+        )]
+        unique_size: bool,
+        #[arg(
+            long,
+            help = "Walk every directory instead of reusing sizes of directories unchanged since the last scan"
+        )]
+        no_cache: bool,
+        #[arg(
+            long,
+            conflicts_with_all = ["brief", "explain", "format", "out", "unique_size", "timing"],
+            help = "Print only the paths that would be cleaned, one per line (for xargs and friends)"
+        )]
+        paths_only: bool,
+        #[arg(
+            long,
+            help = "Pick again which scanners run by default (saved to the config)"
+        )]
+        reconfigure: bool,
     },
     #[command(about = "Create a cleanup plan from scan results")]
     Plan {
@@ -44,11 +127,24 @@ pub enum Commands {
         format: OutputFormat,
         #[arg(short, long)]
         out: Option<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["format", "out"],
+            help = "Print only the planned paths, one per line (for xargs and friends)"
+        )]
+        paths_only: bool,
     },
     #[command(about = "Execute the cleanup plan")]
     Apply {
         #[arg(short, long)]
         plan: Option<String>,
+        #[arg(
+            long,
+            value_name = "EXECUTION_JSON",
+            conflicts_with = "plan",
+            help = "Retry only the items that failed in a previous execution result"
+        )]
+        resume: Option<String>,
         #[arg(short, long)]
         category: Option<String>,
         #[arg(long)]
@@ -57,11 +153,36 @@ pub enum Commands {
         format: OutputFormat,
         #[arg(short, long)]
         out: Option<String>,
+        #[arg(long, help = "Skip files currently held open by a running process")]
+        skip_open_files: bool,
+        #[arg(
+            long,
+            visible_alias = "safe-only",
+            help = "Only clean items rated Safe; Caution items are never touched"
+        )]
+        only_safe: bool,
+        #[arg(
+            long,
+            conflicts_with = "permanent",
+            help = "Move cleaned items to the Trash (the default)"
+        )]
+        trash: bool,
+        #[arg(
+            long,
+            help = "Delete cleaned items outright instead of moving them to the Trash"
+        )]
+        permanent: bool,
     },
     #[command(about = "Generate a report from scan or execution results")]
     Report {
-        #[arg(short, long)]
-        from: String,
+        #[arg(short, long, required_unless_present = "trend")]
+        from: Option<String>,
+        #[arg(
+            long,
+            conflicts_with = "from",
+            help = "Chart freed space per day from the history log, with reclaimable totals from past scans"
+        )]
+        trend: bool,
         #[arg(short = 'F', long, default_value = "md")]
         format: ReportFormat,
         #[arg(short, long)]
@@ -73,6 +194,98 @@ pub enum Commands {
         category: String,
         #[arg(long)]
         execute: bool,
+        #[arg(long, help = "Skip files currently held open by a running process")]
+        skip_open_files: bool,
+        #[arg(
+            long,
+            value_name = "N",
+            help = "Keep the N most recently modified items per category"
+        )]
+        keep_recent: Option<usize>,
+        #[arg(
+            long,
+            visible_alias = "safe-only",
+            help = "Only clean items rated Safe; Caution items are never touched"
+        )]
+        only_safe: bool,
+        #[arg(
+            long,
+            conflicts_with = "permanent",
+            help = "Move cleaned items to the Trash (the default)"
+        )]
+        trash: bool,
+        #[arg(
+            long,
+            help = "Delete cleaned items outright instead of moving them to the Trash"
+        )]
+        permanent: bool,
+        #[arg(
+            long,
+            value_name = "MS",
+            help = "Pause MS milliseconds between deletions to keep disk IO light (slower overall)"
+        )]
+        throttle: Option<u64>,
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Large & old files only: pack them into a .tar.zst in DIR, verify it, then remove the originals"
+        )]
+        archive: Option<String>,
+        #[arg(short = 'F', long, default_value = "human")]
+        format: OutputFormat,
+        #[arg(
+            long,
+            help = "Pick again which scanners run by default (saved to the config)"
+        )]
+        reconfigure: bool,
+    },
+    #[command(about = "Clean only caches, old logs and the Trash, and only items rated Safe")]
+    SafeClean {
+        #[arg(long, help = "Actually clean; without this only shows what would go")]
+        execute: bool,
+        #[arg(
+            long,
+            value_name = "DAYS",
+            default_value_t = 7,
+            help = "Only clean logs older than this many days"
+        )]
+        log_age: u64,
+    },
+    #[command(about = "Clean items older than the [retention] policies in the config")]
+    EnforceRetention {
+        #[arg(long)]
+        execute: bool,
+    },
+    #[command(about = "Run scans periodically through launchd (scan and report only)")]
+    Schedule {
+        #[command(subcommand)]
+        action: ScheduleActions,
+    },
+    #[command(about = "Put back what a clean run moved to the Trash")]
+    Undo {
+        #[arg(help = "Run to undo, as shown by `history`; the most recent run if omitted")]
+        run_id: Option<String>,
+        #[arg(
+            short = 'n',
+            long,
+            conflicts_with = "run_id",
+            help = "Undo the last N removals instead of a run"
+        )]
+        count: Option<usize>,
+        #[arg(
+            long,
+            conflicts_with = "run_id",
+            help = "Undo removals on or after this date (YYYY-MM-DD or RFC 3339)"
+        )]
+        since: Option<String>,
+    },
+    #[command(about = "Empty the Trash, including the trash of every mounted volume")]
+    EmptyTrash {
+        #[arg(
+            long,
+            help = "Actually empty it; without this only lists what would go"
+        )]
+        execute: bool,
     },
     #[command(about = "Uninstall an application completely")]
     Uninstall {
@@ -80,9 +293,32 @@ pub enum Commands {
         name: String,
         #[arg(long)]
         execute: bool,
+        #[arg(
+            long,
+            requires = "execute",
+            help = "Ask before deleting each category of related files (needs a terminal)"
+        )]
+        confirm_each: bool,
+        #[arg(
+            long,
+            conflicts_with = "execute",
+            help = "Only list the app and its related files; nothing is deleted"
+        )]
+        list_related: bool,
+        #[arg(short = 'F', long, default_value = "human")]
+        format: OutputFormat,
     },
     #[command(about = "Browse and uninstall apps (TUI)")]
-    Apps,
+    Apps {
+        #[arg(long, help = "Print installed apps instead of opening the TUI")]
+        list: bool,
+        #[arg(long, help = "List apps installed more than once (same bundle id)")]
+        duplicates: bool,
+        #[arg(short = 'F', long, default_value = "human")]
+        format: OutputFormat,
+        #[arg(long, default_value = "size")]
+        sort: AppSort,
+    },
     #[command(about = "Visualize disk usage (TUI)")]
     Space {
         #[arg(short, long)]
@@ -91,6 +327,19 @@ pub enum Commands {
         single: bool,
         #[arg(short = 't', long, default_value = "4")]
         threads: usize,
+        #[arg(
+            long,
+            default_value = "7",
+            help = "Dim entries modified within this many days (0 to disable)"
+        )]
+        recent_days: i64,
+        #[arg(
+            long,
+            value_name = "SECS",
+            default_value = "60",
+            help = "Re-scan cached folders older than this when revisited"
+        )]
+        cache_ttl: u64,
     },
     #[command(about = "Manage configuration")]
     Config {
@@ -101,9 +350,57 @@ pub enum Commands {
     History {
         #[arg(short, long, default_value = "20")]
         limit: usize,
+        #[arg(short = 'F', long, default_value = "human")]
+        format: OutputFormat,
+        #[arg(
+            long,
+            help = "Only show entries on or after this date (YYYY-MM-DD or RFC 3339)"
+        )]
+        since: Option<String>,
+        #[arg(
+            long,
+            help = "Only show entries on or before this date (YYYY-MM-DD or RFC 3339)"
+        )]
+        until: Option<String>,
     },
+    #[command(about = "Re-scan periodically and show how category sizes change")]
+    Watch {
+        #[arg(default_value = "all")]
+        category: String,
+        #[arg(short, long, default_value = "10s")]
+        interval: String,
+        #[arg(short = 'F', long, default_value = "human")]
+        format: OutputFormat,
+    },
+    #[command(about = "Suggest the fastest way to free up disk space")]
+    Suggest {
+        #[arg(long, help = "Free space to reach, in GB (default: 15% of the disk)")]
+        target_gb: Option<f64>,
+        #[arg(short = 'F', long, default_value = "human")]
+        format: OutputFormat,
+    },
+    #[command(about = "Show version, scanners and detected integrations")]
+    Info {
+        #[arg(short = 'F', long, default_value = "human")]
+        format: OutputFormat,
+    },
+    #[command(about = "Check a scan/plan/result JSON file against the known schemas")]
+    Validate { file: String },
     #[command(about = "Run as MCP server (for AI integration)")]
-    Mcp,
+    Mcp {
+        #[arg(long, default_value = "stdio")]
+        transport: McpTransport,
+        #[arg(long, default_value = "127.0.0.1", help = "Address to bind (tcp only)")]
+        bind: String,
+        #[arg(long, default_value = "9000", help = "Port to listen on (tcp only)")]
+        port: u16,
+    },
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum McpTransport {
+    Stdio,
+    Tcp,
 }
 
 #[derive(Clone, Debug, ValueEnum)]
@@ -113,6 +410,61 @@ pub enum ReportFormat {
     Txt,
 }
 
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ScanSort {
+    Size,
+    Name,
+    Age,
+    Count,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ScanGroupBy {
+    Category,
+    Path,
+    SizeBucket,
+}
+
+#[derive(Clone, Debug, ValueEnum)]
+pub enum AppSort {
+    Size,
+    Name,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ScheduleInterval {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Subcommand)]
+pub enum ScheduleActions {
+    #[command(about = "Install a LaunchAgent that scans on a schedule and saves JSON reports")]
+    Install {
+        #[arg(long, default_value = "weekly")]
+        interval: ScheduleInterval,
+        #[arg(
+            short,
+            long,
+            default_value = "all",
+            value_delimiter = ',',
+            help = "Categories to scan, comma-separated (one report each)"
+        )]
+        category: Vec<String>,
+        #[arg(
+            long,
+            value_name = "DIR",
+            help = "Where reports go (default: ~/cleanmac-reports)"
+        )]
+        report_dir: Option<String>,
+    },
+    #[command(about = "Remove the scheduled scan")]
+    Uninstall,
+    #[command(about = "Show whether a scheduled scan is installed and loaded")]
+    Status,
+}
+
 #[derive(Subcommand)]
 pub enum ConfigActions {
     #[command(about = "Show current configuration")]
@@ -129,6 +481,17 @@ pub enum ConfigActions {
         #[arg(short, long)]
         path: String,
     },
+    #[command(about = "Write the current configuration to a file (stdout if omitted)")]
+    Export { file: Option<String> },
+    #[command(about = "Validate a configuration file and make it the current one")]
+    Import {
+        file: String,
+        #[arg(
+            long,
+            help = "Union list settings like exclusions with the current config and override the values the file sets, instead of replacing it"
+        )]
+        merge: bool,
+    },
 }
 
 impl Cli {
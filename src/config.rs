@@ -1,16 +1,29 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
+    // Policy ceiling for managed machines: when set, scanners outside this list are
+    // never registered anywhere (CLI, TUI, MCP). Kept first so it serializes as a
+    // top-level key ahead of the tables.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowed_scanners: Option<Vec<String>>,
     #[serde(default)]
     pub scan: ScanConfig,
     #[serde(default)]
     pub clean: CleanConfig,
     #[serde(default)]
     pub ui: UiConfig,
+    #[serde(default)]
+    pub theme: ThemeConfig,
+    // scanner id -> max age in days, enforced by `enforce-retention`
+    #[serde(default)]
+    pub retention: BTreeMap<String, u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,6 +36,95 @@ pub struct ScanConfig {
     pub excluded_paths: Vec<String>,
     #[serde(default)]
     pub scan_paths: Vec<String>,
+    // Per scanner, from when it starts. A scan cancelled by the user ends at
+    // once without waiting for it; time spent paused in the TUI doesn't count.
+    #[serde(default = "default_scanner_timeout")]
+    pub scanner_timeout_secs: u64,
+    // How many scanners `scan_all` runs at once. 0 = all of them.
+    #[serde(default)]
+    pub scan_threads: usize,
+    // Descend into symlinked directories while scanning. Off by default so a link
+    // into another volume or a loop can't blow up a scan.
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    // Let the duplicate and large-file scanners look at dot-files and dot-dirs
+    // (VM images, ~/.ollama models, ~/.gradle). Excludes still apply.
+    #[serde(default)]
+    pub include_hidden: bool,
+    // Most items a scanner may report; the largest are kept. 0 = unlimited.
+    #[serde(default = "default_max_items")]
+    pub max_items: usize,
+    // scanner id -> its own max_items, overriding the one above
+    #[serde(default)]
+    pub max_items_per_scanner: BTreeMap<String, usize>,
+    // What `scan`/`clean` run when no category is given; empty means every
+    // scanner. Picked on first run, unlike the allowed_scanners policy ceiling.
+    #[serde(default)]
+    pub enabled_scanners: Vec<String>,
+    // Which copy of a duplicate group is kept: oldest, newest or path:<text>.
+    #[serde(default)]
+    pub duplicate_keep: KeepPolicy,
+    // What the large & old files scanner counts as large and old, and how many
+    // of the largest it keeps (0 = all). An entry for large_old_files in
+    // max_items_per_scanner still wins over the last one.
+    #[serde(default = "default_large_file_min_size")]
+    pub large_file_min_size: u64,
+    #[serde(default = "default_large_file_min_age_days")]
+    pub large_file_min_age_days: u64,
+    #[serde(default = "default_large_file_max_results")]
+    pub large_file_max_results: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum KeepPolicy {
+    #[default]
+    Oldest,
+    Newest,
+    // The oldest copy whose path contains this text; oldest if none does.
+    PreferPath(String),
+}
+
+impl std::str::FromStr for KeepPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "oldest" => Ok(KeepPolicy::Oldest),
+            "newest" => Ok(KeepPolicy::Newest),
+            _ => match s.strip_prefix("path:") {
+                Some(text) if !text.is_empty() => Ok(KeepPolicy::PreferPath(text.to_string())),
+                _ => Err(format!(
+                    "unknown keep policy '{}' (use oldest, newest or path:<text>)",
+                    s
+                )),
+            },
+        }
+    }
+}
+
+impl TryFrom<String> for KeepPolicy {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<KeepPolicy> for String {
+    fn from(policy: KeepPolicy) -> Self {
+        policy.to_string()
+    }
+}
+
+impl std::fmt::Display for KeepPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeepPolicy::Oldest => write!(f, "oldest"),
+            KeepPolicy::Newest => write!(f, "newest"),
+            KeepPolicy::PreferPath(text) => write!(f, "path:{}", text),
+        }
+    }
 }
 
 fn default_min_size() -> u64 {
@@ -33,6 +135,26 @@ fn default_max_depth() -> usize {
     3
 }
 
+fn default_scanner_timeout() -> u64 {
+    300
+}
+
+fn default_max_items() -> usize {
+    1000
+}
+
+fn default_large_file_min_size() -> u64 {
+    100 * 1024 * 1024
+}
+
+fn default_large_file_min_age_days() -> u64 {
+    30
+}
+
+fn default_large_file_max_results() -> usize {
+    100
+}
+
 impl Default for ScanConfig {
     fn default() -> Self {
         Self {
@@ -40,10 +162,29 @@ impl Default for ScanConfig {
             max_depth: default_max_depth(),
             excluded_paths: Vec::new(),
             scan_paths: Vec::new(),
+            scanner_timeout_secs: default_scanner_timeout(),
+            scan_threads: 0,
+            follow_symlinks: false,
+            include_hidden: false,
+            max_items: default_max_items(),
+            max_items_per_scanner: BTreeMap::new(),
+            enabled_scanners: Vec::new(),
+            duplicate_keep: KeepPolicy::default(),
+            large_file_min_size: default_large_file_min_size(),
+            large_file_min_age_days: default_large_file_min_age_days(),
+            large_file_max_results: default_large_file_max_results(),
         }
     }
 }
 
+impl ScanConfig {
+    // scanner_timeout_secs == 0 disables the watchdog
+    pub fn scanner_timeout(&self) -> Option<std::time::Duration> {
+        (self.scanner_timeout_secs > 0)
+            .then(|| std::time::Duration::from_secs(self.scanner_timeout_secs))
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CleanConfig {
     #[serde(default)]
@@ -52,6 +193,15 @@ pub struct CleanConfig {
     pub log_history: bool,
     #[serde(default)]
     pub confirm_before_clean: bool,
+    #[serde(default)]
+    pub skip_open_files: bool,
+    // false makes every clean behave as if --safe-only was given.
+    #[serde(default = "default_true")]
+    pub clean_caution: bool,
+    // Scanner ids an unattended `apply --yes`/`clean --execute` may clean;
+    // everything else is left for review. Empty means no limit.
+    #[serde(default)]
+    pub auto_confirm_categories: Vec<String>,
 }
 
 fn default_true() -> bool {
@@ -64,6 +214,9 @@ impl Default for CleanConfig {
             dry_run_by_default: true,
             log_history: true,
             confirm_before_clean: true,
+            skip_open_files: false,
+            clean_caution: true,
+            auto_confirm_categories: Vec::new(),
         }
     }
 }
@@ -74,6 +227,19 @@ pub struct UiConfig {
     pub show_sizes_in_bytes: bool,
     #[serde(default = "default_true")]
     pub color_output: bool,
+    #[serde(default = "default_true")]
+    pub dashboard_after_scan: bool,
+    // Ask before `q` throws away a non-empty selection in Review.
+    #[serde(default = "default_true")]
+    pub confirm_quit_with_selection: bool,
+    // Items listed per category by `scan` on a terminal; 0 lists them all.
+    // Redirected output always lists everything.
+    #[serde(default = "default_scan_items_shown")]
+    pub scan_items_shown: usize,
+}
+
+fn default_scan_items_shown() -> usize {
+    10
 }
 
 impl Default for UiConfig {
@@ -81,6 +247,56 @@ impl Default for UiConfig {
         Self {
             show_sizes_in_bytes: false,
             color_output: true,
+            dashboard_after_scan: true,
+            confirm_quit_with_selection: true,
+            scan_items_shown: default_scan_items_shown(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl ThemeName {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ThemeName::Dark => "dark",
+            ThemeName::Light => "light",
+            ThemeName::HighContrast => "high-contrast",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThemeConfig {
+    #[serde(default)]
+    pub name: ThemeName,
+    #[serde(default = "default_size_large")]
+    pub size_large_bytes: u64,
+    #[serde(default = "default_size_medium")]
+    pub size_medium_bytes: u64,
+}
+
+fn default_size_large() -> u64 {
+    1024 * 1024 * 1024
+}
+
+fn default_size_medium() -> u64 {
+    100 * 1024 * 1024
+}
+
+impl Default for ThemeConfig {
+    fn default() -> Self {
+        Self {
+            name: ThemeName::default(),
+            size_large_bytes: default_size_large(),
+            size_medium_bytes: default_size_medium(),
         }
     }
 }
@@ -101,15 +317,15 @@ impl Config {
     }
 
     pub fn save(&self) -> Result<()> {
-        let config_path = Self::config_path();
-
-        if let Some(parent) = config_path.parent() {
-            fs::create_dir_all(parent)?;
-        }
+        self.save_to(&Self::config_path())
+    }
 
+    fn save_to(&self, config_path: &Path) -> Result<()> {
         let content = toml::to_string_pretty(self)?;
-        fs::write(&config_path, content)?;
-        Ok(())
+        let dir = config_path.parent().unwrap_or_else(|| Path::new("."));
+        fs::create_dir_all(dir)?;
+        let _lock = ConfigLock::acquire(dir)?;
+        write_atomic(config_path, |file| file.write_all(content.as_bytes()))
     }
 
     fn config_path() -> PathBuf {
@@ -119,6 +335,110 @@ impl Config {
             .join("config.toml")
     }
 
+    // False until the first command has written config.toml.
+    pub fn exists() -> bool {
+        Self::config_path().exists()
+    }
+
+    // User-defined path scanners, see scanner::custom.
+    pub fn scanners_path() -> PathBuf {
+        Self::config_path().with_file_name("scanners.toml")
+    }
+
+    // Taken by commands that delete, see cleaner::CleanLock.
+    pub fn clean_lock_path() -> PathBuf {
+        Self::config_path().with_file_name("clean.lock")
+    }
+
+    // Directory sizes from the last scan, kept next to config.toml.
+    pub fn scan_cache_path() -> PathBuf {
+        Self::config_path().with_file_name("scan-cache.json")
+    }
+
+    // No config lock here: the export is not our config file, and the lock
+    // would leave a stray config.lock next to it.
+    pub fn export_to(&self, path: &Path) -> Result<()> {
+        let content = self.to_toml()?;
+        write_atomic(path, |file| file.write_all(content.as_bytes()))
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string_pretty(self)?)
+    }
+
+    // Reads a shared config file. With `merge`, list settings like exclusions are
+    // unioned with ours and every value the file sets overrides ours; otherwise
+    // the file replaces the current config outright.
+    pub fn import_from(&self, path: &Path, merge: bool) -> Result<Config> {
+        let content = fs::read_to_string(path)?;
+        let incoming: toml::Table = toml::from_str(&content)?;
+
+        let config: Config = if merge {
+            let mut current = toml::Table::try_from(self)?;
+            merge_tables(&mut current, incoming);
+            current.try_into()?
+        } else {
+            toml::Value::Table(incoming).try_into()?
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.scan.excluded_paths.iter().any(|p| p.trim().is_empty()) {
+            bail!("scan.excluded_paths contains an empty path");
+        }
+        if self.scan.scan_paths.iter().any(|p| p.trim().is_empty()) {
+            bail!("scan.scan_paths contains an empty path");
+        }
+        if self.theme.size_medium_bytes > self.theme.size_large_bytes {
+            bail!("theme.size_medium_bytes must not exceed theme.size_large_bytes");
+        }
+        if let Some((id, _)) = self.retention.iter().find(|(_, days)| **days == 0) {
+            bail!("retention.{} must be at least 1 day", id);
+        }
+        let now = chrono::Utc::now();
+        if let Some((id, _)) = self
+            .retention
+            .iter()
+            .find(|(_, days)| crate::utils::days_before(now, **days).is_none())
+        {
+            bail!("retention.{} is too many days to compare dates", id);
+        }
+        Ok(())
+    }
+
+    pub fn allows_scanner(&self, id: &str) -> bool {
+        self.allowed_scanners
+            .as_ref()
+            .map(|allowed| allowed.iter().any(|a| a == id))
+            .unwrap_or(true)
+    }
+
+    pub fn enables_scanner(&self, id: &str) -> bool {
+        self.scan.enabled_scanners.is_empty() || self.scan.enabled_scanners.iter().any(|e| e == id)
+    }
+
+    // "dev_junk,trash" for `config set enabled_scanners`; "" or "all" turns
+    // every scanner back on.
+    pub fn set_enabled_scanners(&mut self, value: &str, known: &[&str]) -> Result<()> {
+        let mut ids: Vec<String> = Vec::new();
+        for id in value.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+            if id == "all" {
+                ids.clear();
+                break;
+            }
+            if !known.contains(&id) {
+                bail!("unknown scanner '{}' (known: {})", id, known.join(", "));
+            }
+            if !ids.iter().any(|e| e == id) {
+                ids.push(id.to_string());
+            }
+        }
+        self.scan.enabled_scanners = ids;
+        Ok(())
+    }
+
     pub fn add_excluded_path(&mut self, path: String) {
         if !self.scan.excluded_paths.contains(&path) {
             self.scan.excluded_paths.push(path);
@@ -132,12 +452,201 @@ impl Config {
     }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            scan: ScanConfig::default(),
-            clean: CleanConfig::default(),
-            ui: UiConfig::default(),
+// Lists unioned by `config import --merge`; other arrays (like allowed_scanners,
+// a policy ceiling) are replaced so an import can't silently widen them.
+const UNION_KEYS: &[&str] = &["excluded_paths", "scan_paths"];
+
+fn merge_tables(current: &mut toml::Table, incoming: toml::Table) {
+    for (key, value) in incoming {
+        match (current.get_mut(&key), value) {
+            (Some(toml::Value::Table(ours)), toml::Value::Table(theirs)) => {
+                merge_tables(ours, theirs)
+            }
+            (Some(toml::Value::Array(ours)), toml::Value::Array(theirs))
+                if UNION_KEYS.contains(&key.as_str()) =>
+            {
+                for item in theirs {
+                    if !ours.contains(&item) {
+                        ours.push(item);
+                    }
+                }
+            }
+            (_, value) => {
+                current.insert(key, value);
+            }
+        }
+    }
+}
+
+// Held while writing so concurrent instances don't interleave saves.
+struct ConfigLock {
+    _file: File,
+}
+
+impl ConfigLock {
+    fn acquire(dir: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(dir.join("config.lock"))?;
+
+        if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX) } != 0 {
+            return Err(io::Error::last_os_error().into());
         }
+        Ok(Self { _file: file })
+    }
+}
+
+// Write to a sibling temp file and rename it over the target, so a crash
+// mid-write leaves the previous file intact instead of a truncated one.
+fn write_atomic(path: &Path, write: impl FnOnce(&mut File) -> io::Result<()>) -> Result<()> {
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(dir)?;
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let tmp_path = dir.join(format!(".{}.tmp-{}", file_name, std::process::id()));
+
+    let result = (|| -> io::Result<()> {
+        let mut file = File::create(&tmp_path)?;
+        write(&mut file)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    Ok(result?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cleanmac-config-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("config.toml")
+    }
+
+    #[test]
+    fn interrupted_write_keeps_previous_config() {
+        let path = temp_config_path("partial");
+
+        let mut config = Config::default();
+        config.scan.max_depth = 7;
+        config.save_to(&path).unwrap();
+
+        let content = toml::to_string_pretty(&Config::default()).unwrap();
+        let result = write_atomic(&path, |file| {
+            file.write_all(&content.as_bytes()[..content.len() / 2])?;
+            Err(io::Error::other("killed mid-write"))
+        });
+        assert!(result.is_err());
+
+        let survived: Config = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(survived.scan.max_depth, 7);
+
+        let leftovers: Vec<_> = fs::read_dir(path.parent().unwrap())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().contains(".tmp-"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn allowed_scanners_is_a_ceiling_only_when_set() {
+        let config = Config::default();
+        assert!(config.allows_scanner("duplicates"));
+        assert!(!toml::to_string_pretty(&config)
+            .unwrap()
+            .contains("allowed_scanners"));
+
+        let path = temp_config_path("allowed");
+        let content =
+            "allowed_scanners = [\"system_caches\", \"trash\"]\n\n[scan]\nmax_depth = 2\n";
+        fs::write(&path, content).unwrap();
+        let config: Config = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(config.allows_scanner("trash"));
+        assert!(!config.allows_scanner("dev_junk"));
+
+        config.save_to(&path).unwrap();
+        let reloaded: Config = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(reloaded.allowed_scanners, config.allowed_scanners);
+    }
+
+    #[test]
+    fn enabled_scanners_are_set_from_a_comma_list() {
+        let known = ["system_caches", "trash", "dev_junk"];
+        let mut config = Config::default();
+
+        config
+            .set_enabled_scanners("dev_junk, trash,dev_junk", &known)
+            .unwrap();
+        assert_eq!(config.scan.enabled_scanners, vec!["dev_junk", "trash"]);
+        assert!(config.enables_scanner("trash"));
+        assert!(!config.enables_scanner("system_caches"));
+
+        let err = config
+            .set_enabled_scanners("trash,nope", &known)
+            .unwrap_err();
+        assert!(err.to_string().starts_with("unknown scanner 'nope'"));
+        assert_eq!(config.scan.enabled_scanners, vec!["dev_junk", "trash"]);
+
+        config.set_enabled_scanners("all", &known).unwrap();
+        assert!(config.scan.enabled_scanners.is_empty());
+    }
+
+    #[test]
+    fn export_then_import_roundtrips() {
+        let path = temp_config_path("export");
+
+        let mut config = Config::default();
+        config.scan.max_depth = 5;
+        config.add_excluded_path("/Users/shared/keep".to_string());
+        config.retention.insert("system_logs".to_string(), 14);
+        config.scan.duplicate_keep = KeepPolicy::PreferPath("Documents/".to_string());
+        config.export_to(&path).unwrap();
+        assert!(!path.with_file_name("config.lock").exists());
+
+        let imported = Config::default().import_from(&path, false).unwrap();
+        assert_eq!(imported.to_toml().unwrap(), config.to_toml().unwrap());
+        assert_eq!(imported.scan.duplicate_keep, config.scan.duplicate_keep);
+        assert!("path:".parse::<KeepPolicy>().is_err());
+    }
+
+    #[test]
+    fn merge_import_unions_exclusions_and_overrides_scalars() {
+        let path = temp_config_path("merge");
+        fs::write(
+            &path,
+            "[scan]\nmax_depth = 6\nexcluded_paths = [\"/b\", \"/c\"]\n",
+        )
+        .unwrap();
+
+        let mut current = Config::default();
+        current.add_excluded_path("/a".to_string());
+        current.add_excluded_path("/b".to_string());
+        current.clean.skip_open_files = true;
+
+        let merged = current.import_from(&path, true).unwrap();
+        assert_eq!(merged.scan.excluded_paths, vec!["/a", "/b", "/c"]);
+        assert_eq!(merged.scan.max_depth, 6);
+        assert!(merged.clean.skip_open_files);
+
+        let replaced = current.import_from(&path, false).unwrap();
+        assert_eq!(replaced.scan.excluded_paths, vec!["/b", "/c"]);
+        assert!(!replaced.clean.skip_open_files);
+
+        fs::write(&path, "[retention]\nsystem_logs = 0\n").unwrap();
+        assert!(current.import_from(&path, true).is_err());
+        fs::write(&path, "[retention]\nsystem_logs = 1000000000\n").unwrap();
+        assert!(current.import_from(&path, true).is_err());
     }
 }
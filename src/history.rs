@@ -1,15 +1,22 @@
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fs::{self, OpenOptions};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct HistoryEntry {
     pub timestamp: DateTime<Utc>,
     pub action: String,
     pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<u64>,
+    // Shared by everything one clean removed, so `undo` can restore the batch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
 }
 
 impl HistoryEntry {
@@ -19,6 +26,7 @@ impl HistoryEntry {
             action: action.into(),
             path,
             size: None,
+            run_id: None,
         }
     }
 
@@ -27,29 +35,168 @@ impl HistoryEntry {
         self
     }
 
+    pub fn with_run_id(mut self, run_id: impl Into<String>) -> Self {
+        self.run_id = Some(run_id.into());
+        self
+    }
+
+    // DELETE is gone for good; TRASH went to the Trash and can be put back.
+    pub fn removed_something(&self) -> bool {
+        self.action == "DELETE" || self.action == "TRASH"
+    }
+
     pub fn to_log_line(&self) -> String {
         let size_str = self
             .size
             .map(|s| format!(" size={}", s))
             .unwrap_or_default();
-        format!(
-            "{} {} {}{}\n",
+        let run_str = self
+            .run_id
+            .as_ref()
+            .map(|id| format!(" run={}", id))
+            .unwrap_or_default();
+        let body = format!(
+            "{} {} {}{}{}",
             self.timestamp.to_rfc3339(),
             self.action,
             self.path.display(),
-            size_str
+            size_str,
+            run_str
+        );
+        format!("{} sum={}\n", body, line_checksum(&body))
+    }
+}
+
+// Short SHA-256 of a log line, appended as ` sum=` so a damaged line can be told
+// apart from a good one. Lines written before checksums existed have none.
+fn line_checksum(body: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(body.as_bytes());
+    digest[..4].iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// A random-looking UUID (version 4 layout) from the clock and pid; unique
+// enough to tell one clean run from another.
+pub fn new_run_id() -> String {
+    use sha2::{Digest, Sha256};
+
+    let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let digest = Sha256::digest(format!("{}-{}", nanos, std::process::id()).as_bytes());
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+fn is_run_id(value: &str) -> bool {
+    value.len() == 36 && value.bytes().all(|b| b.is_ascii_hexdigit() || b == b'-')
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DateRange {
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    pub fn parse(since: Option<&str>, until: Option<&str>) -> Result<Self> {
+        Ok(Self {
+            since: since.map(|s| parse_date_bound(s, false)).transpose()?,
+            until: until.map(|s| parse_date_bound(s, true)).transpose()?,
+        })
+    }
+
+    pub fn contains(&self, timestamp: &DateTime<Utc>) -> bool {
+        self.since.map(|s| *timestamp >= s).unwrap_or(true)
+            && self.until.map(|u| *timestamp <= u).unwrap_or(true)
+    }
+}
+
+fn parse_date_bound(value: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| {
+        anyhow::anyhow!(
+            "Invalid date '{}': expected YYYY-MM-DD or RFC 3339 timestamp",
+            value
         )
+    })?;
+    let time = if end_of_day {
+        date.and_hms_opt(23, 59, 59)
+    } else {
+        date.and_hms_opt(0, 0, 0)
+    };
+
+    time.map(|t| t.and_utc())
+        .ok_or_else(|| anyhow::anyhow!("Invalid date '{}'", value))
+}
+
+// Freed bytes for one day, plus the last reclaimable total a full scan saw that day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendPoint {
+    pub date: NaiveDate,
+    pub freed_bytes: u64,
+    pub reclaimable_bytes: Option<u64>,
+}
+
+pub fn daily_trend(entries: &[HistoryEntry], scans: &[(DateTime<Utc>, u64)]) -> Vec<TrendPoint> {
+    fn day(days: &mut BTreeMap<NaiveDate, TrendPoint>, date: NaiveDate) -> &mut TrendPoint {
+        days.entry(date).or_insert(TrendPoint {
+            date,
+            freed_bytes: 0,
+            reclaimable_bytes: None,
+        })
     }
+
+    let mut days = BTreeMap::new();
+    for entry in entries.iter().filter(|e| e.removed_something()) {
+        day(&mut days, entry.timestamp.date_naive()).freed_bytes += entry.size.unwrap_or(0);
+    }
+    let mut scans = scans.to_vec();
+    scans.sort_by_key(|(at, _)| *at);
+    for (at, total) in scans {
+        day(&mut days, at.date_naive()).reclaimable_bytes = Some(total);
+    }
+
+    days.into_values().collect()
 }
 
 pub struct HistoryLogger {
     log_path: PathBuf,
+    // Damaged lines the last read skipped; see skipped_lines().
+    skipped: AtomicUsize,
 }
 
 impl HistoryLogger {
     pub fn new() -> Self {
         let log_path = Config::data_dir().join("history.log");
-        Self { log_path }
+        Self::with_path(log_path)
+    }
+
+    pub fn with_path(log_path: PathBuf) -> Self {
+        Self {
+            log_path,
+            skipped: AtomicUsize::new(0),
+        }
+    }
+
+    // How many lines the last read_history call couldn't use: a line cut short
+    // by a crash mid-write, a checksum that doesn't match, or garbage.
+    pub fn skipped_lines(&self) -> usize {
+        self.skipped.load(Ordering::SeqCst)
     }
 
     pub fn log(&self, entry: &HistoryEntry) -> Result<()> {
@@ -66,23 +213,103 @@ impl HistoryLogger {
         Ok(())
     }
 
-    pub fn log_delete(&self, path: &PathBuf, size: Option<u64>) -> Result<()> {
-        let mut entry = HistoryEntry::new("DELETE", path.clone());
-        if let Some(s) = size {
-            entry = entry.with_size(s);
-        }
+    pub fn log_removal(&self, path: &Path, size: u64, mode: TrashMode, run_id: &str) -> Result<()> {
+        let action = match mode {
+            TrashMode::MoveToTrash => "TRASH",
+            TrashMode::Permanent => "DELETE",
+        };
+        self.log(
+            &HistoryEntry::new(action, path.to_path_buf())
+                .with_size(size)
+                .with_run_id(run_id),
+        )
+    }
+
+    // Marks `removal` as undone; `undo` skips it from then on.
+    pub fn log_restore(&self, removal: &HistoryEntry) -> Result<()> {
+        let mut entry = HistoryEntry::new("RESTORE", removal.path.clone());
+        entry.size = removal.size;
+        entry.run_id = removal.run_id.clone();
         self.log(&entry)
     }
 
+    // Reclaimable totals from full scans live next to the history log, so
+    // `history` itself stays a list of deletions.
+    fn scan_totals_path(&self) -> PathBuf {
+        self.log_path.with_file_name("scan-totals.log")
+    }
+
+    pub fn log_scan_total(&self, total_bytes: u64) -> Result<()> {
+        let path = self.scan_totals_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{} {}", Utc::now().to_rfc3339(), total_bytes)?;
+        Ok(())
+    }
+
+    pub fn read_scan_totals(&self, range: &DateRange) -> Result<Vec<(DateTime<Utc>, u64)>> {
+        let path = self.scan_totals_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        Ok(fs::read_to_string(path)?
+            .lines()
+            .filter_map(|line| {
+                let (at, total) = line.split_once(' ')?;
+                let at = DateTime::parse_from_rfc3339(at).ok()?.with_timezone(&Utc);
+                Some((at, total.trim().parse().ok()?))
+            })
+            .filter(|(at, _)| range.contains(at))
+            .collect())
+    }
+
+    pub fn log_archive(&self, archive_path: &Path, compressed_size: u64) -> Result<()> {
+        self.log(
+            &HistoryEntry::new("ARCHIVE", archive_path.to_path_buf()).with_size(compressed_size),
+        )
+    }
+
+    pub fn log_empty_trash(&self, trash_path: &Path, freed: u64) -> Result<()> {
+        self.log(&HistoryEntry::new("EMPTY_TRASH", trash_path.to_path_buf()).with_size(freed))
+    }
+
     pub fn read_history(&self, limit: Option<usize>) -> Result<Vec<HistoryEntry>> {
+        self.read_history_in_range(limit, &DateRange::default())
+    }
+
+    pub fn read_history_in_range(
+        &self,
+        limit: Option<usize>,
+        range: &DateRange,
+    ) -> Result<Vec<HistoryEntry>> {
+        self.skipped.store(0, Ordering::SeqCst);
         if !self.log_path.exists() {
             return Ok(Vec::new());
         }
 
-        let content = fs::read_to_string(&self.log_path)?;
-        let entries: Vec<HistoryEntry> = content
-            .lines()
-            .filter_map(|line| self.parse_line(line))
+        // Read as bytes so one bad line can't fail the whole read. Every write
+        // ends in a newline, so a last line without one was cut off mid-write.
+        let content = fs::read(&self.log_path)?;
+        let complete = content.ends_with(b"\n");
+        let mut lines: Vec<&[u8]> = content.split(|b| *b == b'\n').collect();
+        let cut_off = lines.pop().filter(|last| !complete && !last.is_empty());
+
+        let mut skipped = usize::from(cut_off.is_some());
+        let mut entries = Vec::new();
+        for line in lines.into_iter().filter(|line| !line.is_empty()) {
+            match std::str::from_utf8(line).ok().and_then(parse_line) {
+                Some(entry) => entries.push(entry),
+                None => skipped += 1,
+            }
+        }
+        self.skipped.store(skipped, Ordering::SeqCst);
+
+        let entries: Vec<HistoryEntry> = entries
+            .into_iter()
+            .filter(|entry| range.contains(&entry.timestamp))
             .collect();
 
         let result = if let Some(n) = limit {
@@ -93,29 +320,47 @@ impl HistoryLogger {
 
         Ok(result)
     }
+}
 
-    fn parse_line(&self, line: &str) -> Option<HistoryEntry> {
-        let parts: Vec<&str> = line.splitn(4, ' ').collect();
-        if parts.len() < 3 {
-            return None;
+// `<rfc3339> <ACTION> <path>[ size=N][ run=UUID][ sum=XXXXXXXX]`. The path
+// runs up to the optional fields, so it may contain spaces.
+fn parse_line(line: &str) -> Option<HistoryEntry> {
+    let body = match line.rsplit_once(" sum=") {
+        Some((body, sum)) if sum.len() == 8 && sum.bytes().all(|b| b.is_ascii_hexdigit()) => {
+            if line_checksum(body) != sum {
+                return None;
+            }
+            body
         }
+        _ => line,
+    };
 
-        let timestamp = DateTime::parse_from_rfc3339(parts[0])
-            .ok()?
-            .with_timezone(&Utc);
-        let action = parts[1].to_string();
-        let path = PathBuf::from(parts[2]);
-        let size = parts
-            .get(3)
-            .and_then(|s| s.strip_prefix("size=").and_then(|s| s.parse::<u64>().ok()));
-
-        Some(HistoryEntry {
-            timestamp,
-            action,
-            path,
-            size,
-        })
-    }
+    let mut parts = body.splitn(3, ' ');
+    let timestamp = DateTime::parse_from_rfc3339(parts.next()?)
+        .ok()?
+        .with_timezone(&Utc);
+    let action = parts.next()?.to_string();
+    let rest = parts.next().filter(|rest| !rest.is_empty())?;
+
+    let (rest, run_id) = match rest.rsplit_once(" run=") {
+        Some((head, id)) if is_run_id(id) => (head, Some(id.to_string())),
+        _ => (rest, None),
+    };
+    let (path, size) = match rest.rsplit_once(" size=") {
+        Some((path, size)) => match size.parse::<u64>() {
+            Ok(size) => (path, Some(size)),
+            Err(_) => (rest, None),
+        },
+        None => (rest, None),
+    };
+
+    Some(HistoryEntry {
+        timestamp,
+        action,
+        path: PathBuf::from(path),
+        size,
+        run_id,
+    })
 }
 
 impl Default for HistoryLogger {
@@ -125,3 +370,149 @@ impl Default for HistoryLogger {
 }
 
 use crate::config::Config;
+use crate::plugin::TrashMode;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn synthetic_log(name: &str) -> HistoryLogger {
+        let dir = std::env::temp_dir().join(format!("cleanmac-history-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let log_path = dir.join("history.log");
+        fs::write(
+            &log_path,
+            "2026-02-16T09:00:00+00:00 DELETE /tmp/a size=10\n\
+             2026-02-17T12:30:00+00:00 DELETE /tmp/b size=20\n\
+             2026-02-17T23:10:00+00:00 DELETE /tmp/c\n\
+             2026-02-18T08:00:00+00:00 DELETE /tmp/d size=40\n\
+             2026-02-20T18:45:00+00:00 DELETE /tmp/e size=50\n",
+        )
+        .unwrap();
+        HistoryLogger::with_path(log_path)
+    }
+
+    #[test]
+    fn test_since_and_until_dates_are_inclusive() {
+        let logger = synthetic_log("range");
+        let range = DateRange::parse(Some("2026-02-17"), Some("2026-02-18")).unwrap();
+        let entries = logger.read_history_in_range(None, &range).unwrap();
+
+        let paths: Vec<_> = entries.iter().map(|e| e.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/tmp/b"),
+                PathBuf::from("/tmp/c"),
+                PathBuf::from("/tmp/d")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_open_ended_range_with_limit() {
+        let logger = synthetic_log("limit");
+        let range = DateRange::parse(Some("2026-02-17T13:00:00Z"), None).unwrap();
+        let entries = logger.read_history_in_range(Some(2), &range).unwrap();
+
+        let paths: Vec<_> = entries.iter().map(|e| e.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from("/tmp/e"), PathBuf::from("/tmp/d")]
+        );
+    }
+
+    #[test]
+    fn test_daily_trend_joins_deletions_and_scan_totals() {
+        let logger = synthetic_log("trend");
+        let entries = logger.read_history(None).unwrap();
+        assert!(daily_trend(&[], &[]).is_empty());
+
+        let at = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        let scans = vec![
+            (at("2026-02-19T10:00:00Z"), 900),
+            (at("2026-02-17T20:00:00Z"), 700),
+            (at("2026-02-17T08:00:00Z"), 800),
+        ];
+        let trend = daily_trend(&entries, &scans);
+
+        let rows: Vec<_> = trend
+            .iter()
+            .map(|p| (p.date.to_string(), p.freed_bytes, p.reclaimable_bytes))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("2026-02-16".to_string(), 10, None),
+                ("2026-02-17".to_string(), 20, Some(700)),
+                ("2026-02-18".to_string(), 40, None),
+                ("2026-02-19".to_string(), 0, Some(900)),
+                ("2026-02-20".to_string(), 50, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_totals_roundtrip() {
+        let logger = synthetic_log("totals");
+        assert!(logger
+            .read_scan_totals(&DateRange::default())
+            .unwrap()
+            .is_empty());
+        logger.log_scan_total(1234).unwrap();
+        let totals = logger.read_scan_totals(&DateRange::default()).unwrap();
+        assert_eq!(totals.len(), 1);
+        assert_eq!(totals[0].1, 1234);
+    }
+
+    #[test]
+    fn test_invalid_date_is_rejected() {
+        assert!(DateRange::parse(Some("last tuesday"), None).is_err());
+    }
+
+    #[test]
+    fn damaged_lines_are_skipped_and_counted() {
+        let dir = std::env::temp_dir().join("cleanmac-history-damaged");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let logger = HistoryLogger::with_path(dir.join("history.log"));
+
+        logger
+            .log(&HistoryEntry::new("DELETE", PathBuf::from("/tmp/with space")).with_size(10))
+            .unwrap();
+        logger
+            .log(&HistoryEntry::new("DELETE", PathBuf::from("/tmp/b")))
+            .unwrap();
+        let good = fs::read_to_string(&logger.log_path).unwrap();
+        // A legacy line without a checksum, one whose checksum no longer
+        // matches, then a write cut off by a crash.
+        let tampered = HistoryEntry::new("DELETE", PathBuf::from("/tmp/c"))
+            .to_log_line()
+            .replace("/tmp/c", "/tmp/x");
+        fs::write(
+            &logger.log_path,
+            format!(
+                "{}2026-02-16T09:00:00+00:00 DELETE /tmp/legacy size=5\n{}2026-02-17T12:3",
+                good, tampered
+            ),
+        )
+        .unwrap();
+
+        let entries = logger.read_history(None).unwrap();
+        let paths: Vec<_> = entries.iter().map(|e| e.path.clone()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("/tmp/with space"),
+                PathBuf::from("/tmp/b"),
+                PathBuf::from("/tmp/legacy")
+            ]
+        );
+        assert_eq!(entries[0].size, Some(10));
+        assert_eq!(entries[2].size, Some(5));
+        assert_eq!(logger.skipped_lines(), 2);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
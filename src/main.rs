@@ -1,47 +1,61 @@
+mod audit;
 mod cleaner;
 mod cli;
 mod config;
 mod history;
 mod mcp;
 mod metadata;
+mod onboarding;
 mod output;
 mod plugin;
+mod reclaim;
 mod safety;
 mod scanner;
+mod schedule;
+mod suggest;
 mod tui;
 mod uninstaller;
+mod user;
 mod utils;
+mod watch;
 
 use anyhow::Result;
 use chrono::Utc;
-use cleaner::DefaultCleaner;
-use cli::{Cli, Commands, ConfigActions, OutputFormat, ReportFormat};
-use config::Config;
-use crossterm::{
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+use clap::ValueEnum;
+use cleaner::{CleanLock, DefaultCleaner};
+use cli::{
+    AppSort, Cli, Commands, ConfigActions, McpTransport, OutputFormat, ReportFormat, ScanGroupBy,
+    ScanSort, ScheduleActions,
 };
-use history::HistoryLogger;
+use config::Config;
+use history::{DateRange, HistoryEntry, HistoryLogger};
 use output::{
-    CategoryExecutionResult, CategoryPlanResult, CategoryScanResult as JsonCategoryScanResult,
-    ExecutionResult, ExecutionStatus, FailedItem, PlanItem, PlanResult, ScanItem,
-    ScanResult as JsonScanResult,
+    AppCopyInfo, ArchiveInfo, BriefScanResult, CategoryExecutionResult, CategoryPlanResult,
+    CategoryScanResult as JsonCategoryScanResult, CommandResult, DuplicateAppGroup,
+    DuplicateAppsResult, ExecutionResult, ExecutionStatus, ExplainResult, FailedItem, InfoResult,
+    IntegrationInfo, PlanItem, PlanResult, RelatedFileInfo, RelatedFilesResult, RunMeta, ScanItem,
+    ScanResult as JsonScanResult, ScannerInfo, SkippedItem, TrendPointOutput, TrendReport,
+    UninstallPreviewGroup, UninstallPreviewResult,
 };
-use plugin::{CleanConfig, Cleaner, PluginRegistry, ScanConfig};
-use ratatui::{backend::CrosstermBackend, Terminal};
+use plugin::{CleanConfig, CleanResult, Cleaner, PluginRegistry, ScanConfig, TrashMode};
 use std::collections::HashMap;
 use std::fs;
-use std::io;
+use std::io::{IsTerminal, Write};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::sync::Arc;
 use std::time::Instant;
-use tui::App;
+use tui::{App, SortMode, TerminalGuard};
 use utils::format_size;
 
 fn main() -> ExitCode {
     let cli = Cli::parse_args();
 
+    // Load creates config.toml, so look before it does.
+    let first_run = !Config::exists();
     let result = match Config::load() {
-        Ok(config) => run(cli, config),
+        Ok(config) => run(cli, config, first_run),
         Err(e) => Err(e),
     };
 
@@ -54,136 +68,474 @@ fn main() -> ExitCode {
     }
 }
 
-fn run(cli: Cli, config: Config) -> Result<ExitCode> {
+fn run(cli: Cli, mut config: Config, first_run: bool) -> Result<ExitCode> {
+    if let Some(name) = &cli.user {
+        user::set_target_user(name)?;
+    }
+    // A one-off override; `config` subcommands save what they load, so leave them alone.
+    if !matches!(cli.command, Some(Commands::Config { .. })) {
+        if cli.include_hidden {
+            config.scan.include_hidden = true;
+        }
+        if let Some(policy) = cli.keep_duplicate.clone() {
+            config.scan.duplicate_keep = policy;
+        }
+        if let Some(threads) = cli.scan_threads {
+            config.scan.scan_threads = threads;
+        }
+    }
+    if cli.audit {
+        audit::enable();
+    }
+    // The cleaner and uninstaller refuse to delete on their own; turning the
+    // flags off here just keeps the output saying "dry run" instead of "freed".
+    let live = !audit::is_active();
+    if !live {
+        eprintln!("Audit mode: nothing will be deleted; --execute and --yes only preview.");
+    }
+
     match cli.command {
         None => run_tui(config)?,
+        Some(Commands::Scan {
+            category,
+            format,
+            explain: Some(target),
+            ..
+        }) => run_explain(&target, &category, &config, format)?,
         Some(Commands::Scan {
             category,
             format,
             out,
             metadata,
-        }) => run_scan(&category, &config, format, out.as_deref(), metadata)?,
+            timing,
+            sort,
+            group_by,
+            brief,
+            all,
+            explain: None,
+            unique_size,
+            no_cache,
+            paths_only,
+            reconfigure,
+        }) => {
+            // Scripts asking for one category or machine output never see the prompt.
+            let plain = category == "all"
+                && matches!(format, OutputFormat::Human)
+                && out.is_none()
+                && !brief
+                && !paths_only;
+            if plain && onboarding::should_prompt(first_run, reconfigure) {
+                onboarding::prompt_scanner_selection(&mut config)?;
+            } else if reconfigure {
+                onboarding::warn_reconfigure_ignored();
+            }
+            run_scan(
+                &category,
+                &config,
+                format,
+                out.as_deref(),
+                metadata,
+                timing,
+                sort,
+                group_by,
+                brief.then_some(all),
+                unique_size,
+                !no_cache,
+                paths_only,
+            )?
+        }
         Some(Commands::Plan {
             from,
             category,
             format,
             out,
-        }) => run_plan(from.as_deref(), category.as_deref(), format, out.as_deref())?,
+            paths_only,
+        }) => run_plan(
+            from.as_deref(),
+            category.as_deref(),
+            format,
+            out.as_deref(),
+            paths_only,
+        )?,
         Some(Commands::Apply {
             plan,
+            resume,
             category,
             yes,
             format,
             out,
+            skip_open_files,
+            only_safe,
+            trash: _,
+            permanent,
         }) => run_apply(
             plan.as_deref(),
+            resume.as_deref(),
             category.as_deref(),
-            yes,
+            yes && live,
+            skip_open_files,
+            only_safe,
+            TrashMode::from_flag(permanent),
             &config,
             format,
             out.as_deref(),
         )?,
-        Some(Commands::Report { from, format, out }) => run_report(&from, format, out.as_deref())?,
-        Some(Commands::Clean { category, execute }) => run_clean(&category, execute, &config)?,
-        Some(Commands::Uninstall { name, execute }) => run_uninstall(&name, execute)?,
-        Some(Commands::Apps) => run_apps_tui()?,
+        Some(Commands::Report {
+            from,
+            trend,
+            format,
+            out,
+        }) => {
+            if trend {
+                run_trend_report(format, out.as_deref())?
+            } else if let Some(from) = from {
+                run_report(&from, format, out.as_deref())?
+            }
+        }
+        Some(Commands::Clean {
+            category,
+            execute,
+            skip_open_files,
+            keep_recent,
+            only_safe,
+            trash: _,
+            permanent,
+            throttle,
+            archive,
+            format,
+            reconfigure,
+        }) => {
+            let plain = category == "all"
+                && matches!(format, OutputFormat::Human)
+                && !execute
+                && archive.is_none();
+            if plain && onboarding::should_prompt(first_run, reconfigure) {
+                onboarding::prompt_scanner_selection(&mut config)?;
+            } else if reconfigure {
+                onboarding::warn_reconfigure_ignored();
+            }
+            run_clean(
+                &category,
+                execute && live,
+                skip_open_files,
+                keep_recent,
+                only_safe,
+                TrashMode::from_flag(permanent),
+                throttle,
+                archive.as_deref(),
+                format,
+                &config,
+            )?
+        }
+        Some(Commands::SafeClean { execute, log_age }) => {
+            run_safe_clean(execute && live, log_age, &config)?
+        }
+        Some(Commands::EnforceRetention { execute }) => {
+            run_enforce_retention(execute && live, &config)?
+        }
+        Some(Commands::Schedule { action }) => run_schedule(action)?,
+        Some(Commands::EmptyTrash { execute }) => run_empty_trash(execute && live, &config)?,
+        Some(Commands::Uninstall {
+            name,
+            execute,
+            confirm_each,
+            list_related,
+            format,
+        }) => {
+            if list_related {
+                run_list_related(&name, format)?
+            } else {
+                run_uninstall(&name, execute && live, confirm_each, format)?
+            }
+        }
+        Some(Commands::Apps {
+            list,
+            duplicates,
+            format,
+            sort,
+        }) => {
+            if duplicates {
+                run_apps_duplicates(format)?
+            } else if list {
+                run_apps_list(format, sort)?
+            } else {
+                run_apps_tui()?
+            }
+        }
         Some(Commands::Space {
             path,
             single,
             threads,
-        }) => run_space_tui(path.as_deref(), single, threads)?,
+            recent_days,
+            cache_ttl,
+        }) => run_space_tui(
+            path.as_deref(),
+            single,
+            threads,
+            recent_days,
+            cache_ttl,
+            config,
+        )?,
         Some(Commands::Config { action }) => run_config(action, config)?,
-        Some(Commands::History { limit }) => run_history(limit)?,
-        Some(Commands::Mcp) => {
-            tokio::runtime::Runtime::new()
-                .map_err(|e| anyhow::anyhow!("Failed to create tokio runtime: {}", e))?
-                .block_on(mcp::run_mcp_server())?;
-        }
+        Some(Commands::History {
+            limit,
+            format,
+            since,
+            until,
+        }) => run_history(limit, format, since.as_deref(), until.as_deref())?,
+        Some(Commands::Undo {
+            run_id,
+            count,
+            since,
+        }) => run_undo(run_id.as_deref(), count, since.as_deref(), &config)?,
+        Some(Commands::Watch {
+            category,
+            interval,
+            format,
+        }) => run_watch(&category, &interval, format, &config)?,
+        Some(Commands::Suggest { target_gb, format }) => run_suggest(target_gb, format, &config)?,
+        Some(Commands::Info { format }) => run_info(format, &config)?,
+        Some(Commands::Validate { file }) => return run_validate(&file),
+        Some(Commands::Mcp {
+            transport,
+            bind,
+            port,
+        }) => run_mcp(transport, &bind, port)?,
     }
 
     Ok(ExitCode::SUCCESS)
 }
 
-fn run_tui(config: Config) -> Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+fn run_mcp(transport: McpTransport, bind: &str, port: u16) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()
+        .map_err(|e| anyhow::anyhow!("Failed to create tokio runtime: {}", e))?;
+
+    match transport {
+        McpTransport::Stdio => runtime.block_on(mcp::run_mcp_server())?,
+        McpTransport::Tcp => {
+            let ip: std::net::IpAddr = bind
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid bind address: {}", bind))?;
+            if !ip.is_loopback() {
+                eprintln!(
+                    "Warning: binding MCP to {} exposes cleanup tools to the network without authentication",
+                    ip
+                );
+            }
+            runtime.block_on(mcp::run_mcp_tcp_server((ip, port).into()))?;
+        }
+    }
 
-    let mut app = App::new(config);
-    let result = app.run(&mut terminal);
+    Ok(())
+}
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+fn run_tui(config: Config) -> Result<()> {
+    let mut guard = TerminalGuard::new()?;
 
-    result
+    let mut app = App::new(config);
+    app.run(&mut guard.terminal)
 }
 
 fn run_apps_tui() -> Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    let mut guard = TerminalGuard::new()?;
 
     let mut app = App::new_apps_mode();
-    let result = app.run(&mut terminal);
+    app.run(&mut guard.terminal)
+}
+
+fn run_apps_list(format: OutputFormat, sort: AppSort) -> Result<()> {
+    use rayon::prelude::*;
+    use uninstaller::AppDetector;
+
+    let mut apps: Vec<mcp::AppOutput> = AppDetector::new()
+        .list_all()
+        .into_par_iter()
+        .map(|app| {
+            let info = app.info();
+            mcp::AppOutput {
+                name: app.name().to_string(),
+                path: app.path.to_string_lossy().to_string(),
+                size_bytes: app.size(),
+                bundle_id: info.as_ref().map(|i| i.bundle_id.clone()),
+                version: info.map(|i| i.version),
+            }
+        })
+        .collect();
+
+    match sort {
+        AppSort::Size => apps.sort_by_key(|a| std::cmp::Reverse(a.size_bytes)),
+        AppSort::Name => apps.sort_by_key(|a| a.name.to_lowercase()),
+    }
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&apps)?),
+        OutputFormat::Human => {
+            for app in &apps {
+                println!(
+                    "{:<40} {:>12}  {}",
+                    app.name,
+                    format_size(app.size_bytes),
+                    app.version.as_deref().unwrap_or("-")
+                );
+            }
+            println!(
+                "\nTotal: {} apps, {}",
+                apps.len(),
+                format_size(apps.iter().map(|a| a.size_bytes).sum())
+            );
+        }
+    }
 
-    result
+    Ok(())
 }
 
-fn run_space_tui(path: Option<&str>, single: bool, threads: usize) -> Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+fn run_apps_duplicates(format: OutputFormat) -> Result<()> {
+    use uninstaller::AppDetector;
+
+    let groups: Vec<DuplicateAppGroup> = AppDetector::new()
+        .find_duplicate_installs()
+        .into_iter()
+        .map(|group| {
+            let older = group.older_copy().map(|copy| copy.app.path.clone());
+            DuplicateAppGroup {
+                copies: group
+                    .copies
+                    .iter()
+                    .map(|copy| AppCopyInfo {
+                        name: copy.app.name().to_string(),
+                        path: copy.app.path.clone(),
+                        app_version: Some(copy.version.clone()).filter(|v| !v.is_empty()),
+                        size_bytes: copy.app.size(),
+                        older: older.as_ref() == Some(&copy.app.path),
+                    })
+                    .collect(),
+                bundle_id: group.bundle_id,
+            }
+        })
+        .collect();
 
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    match format {
+        OutputFormat::Json => {
+            let result = DuplicateAppsResult {
+                version: output::SCHEMA_VERSION.to_string(),
+                groups,
+            };
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+        OutputFormat::Human => {
+            if groups.is_empty() {
+                println!("No apps are installed more than once.");
+                return Ok(());
+            }
+            for group in &groups {
+                println!("{} ({} copies)", group.bundle_id, group.copies.len());
+                for copy in &group.copies {
+                    println!(
+                        "  {:<10} {:>12}  {}{}",
+                        copy.app_version.as_deref().unwrap_or("-"),
+                        format_size(copy.size_bytes),
+                        copy.path.display(),
+                        if copy.older { "  (older copy)" } else { "" }
+                    );
+                }
+            }
+            if groups.iter().flat_map(|g| &g.copies).any(|c| c.older) {
+                println!();
+                println!(
+                    "Run `cleanmac apps` and press o on a duplicate to remove its older copy."
+                );
+            }
+        }
+    }
 
-    let mut app = App::new_space_lens_mode(path);
-    app.space_lens.parallel_scan = !single;
-    app.space_lens.thread_count = threads.max(1);
-    let result = app.run(&mut terminal);
+    Ok(())
+}
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+fn run_space_tui(
+    path: Option<&str>,
+    single: bool,
+    threads: usize,
+    recent_days: i64,
+    cache_ttl: u64,
+    config: Config,
+) -> Result<()> {
+    let mut guard = TerminalGuard::new()?;
 
-    result
+    let mut app = App::new_space_lens_mode(path, config);
+    app.space_lens.parallel_scan = !single;
+    app.space_lens.thread_count = threads.max(1);
+    app.space_lens.recent_days = recent_days.max(0);
+    app.space_lens.cache_ttl = std::time::Duration::from_secs(cache_ttl);
+    app.run(&mut guard.terminal)
 }
 
+// `brief` is Some(all) for --brief: one summary line, slow scanners only with --all.
+#[allow(clippy::too_many_arguments)]
 fn run_scan(
     category: &str,
     config: &Config,
     format: OutputFormat,
     out: Option<&str>,
     collect_metadata: bool,
+    timing: bool,
+    sort: ScanSort,
+    group_by: ScanGroupBy,
+    brief: Option<bool>,
+    unique_size: bool,
+    use_cache: bool,
+    paths_only: bool,
 ) -> Result<()> {
+    // Groups other than categories have no scanner of their own to time.
+    if timing && group_by != ScanGroupBy::Category {
+        anyhow::bail!("--timing only applies with --group-by category");
+    }
     let start = Instant::now();
 
-    let registry = PluginRegistry::default();
-    let scan_config = ScanConfig {
-        min_size: config.scan.min_size_bytes,
-        max_depth: config.scan.max_depth,
-        excluded_paths: config
-            .scan
-            .excluded_paths
-            .iter()
-            .map(|s| s.into())
-            .collect(),
-        progress_callback: None,
-        item_callback: None,
+    let mut registry = PluginRegistry::from_config(config);
+    registry.register_requested(category, config);
+    if category == "all" {
+        registry.retain_enabled(config);
+    }
+    ensure_scanners(&registry, config)?;
+    let skipped = match brief {
+        Some(false) => registry.retain_cheap(),
+        _ => Vec::new(),
     };
+    let scan_config = ScanConfig::from_config(config);
 
-    let report = registry.scan_all(&scan_config)?;
+    // --no-cache still refreshes the cache for the next run.
+    let cache_path = Config::scan_cache_path();
+    scanner::size_cache::activate(Arc::new(if use_cache {
+        scanner::size_cache::SizeCache::load(&cache_path)
+    } else {
+        Default::default()
+    }));
+    let scanned = registry.scan_all(&scan_config);
+    if let Some(cache) = scanner::size_cache::deactivate() {
+        if let Err(e) = cache.save(&cache_path) {
+            eprintln!("Warning: could not save scan cache: {}", e);
+        }
+    }
+    let mut report = scanned?;
+    report.cap_items(&scan_config);
+
+    warn_scan_problems(&report);
+
+    if brief.is_some() {
+        report
+            .categories
+            .retain(|c| category == "all" || c.scanner_id.contains(&category.to_lowercase()));
+        return print_brief_scan(&report, skipped, start, format, out);
+    }
+
+    let sort_mode = match sort {
+        ScanSort::Size => SortMode::SizeDesc,
+        ScanSort::Name => SortMode::NameAsc,
+        ScanSort::Age => SortMode::AgeAsc,
+        ScanSort::Count => SortMode::CountDesc,
+    };
+    tui::apply_sort(&mut report, sort_mode);
+    tui::sort_categories(&mut report.categories, sort_mode);
 
     let categories: Vec<JsonCategoryScanResult> = report
         .categories
@@ -206,11 +558,13 @@ fn run_scan(
                     };
 
                     ScanItem {
+                        id: Some(item.id.clone()),
                         path: item.path.clone(),
                         size_bytes: item.size,
                         modified: item.last_modified.unwrap_or_else(Utc::now),
                         last_used,
                         use_count,
+                        safety_reason: Some(item.safety_reason().to_string()),
                     }
                 })
                 .collect();
@@ -221,12 +575,54 @@ fn run_scan(
                 description: String::new(),
                 size_bytes: cat_result.total_size(),
                 item_count: items.len(),
+                unique_size_bytes: unique_size
+                    .then(|| unique_reclaimable_of(cat_result.items.iter())),
                 items,
+                scan_duration_ms: cat_result.scan_duration.as_millis() as u64,
+                unreadable_count: cat_result.unreadable,
+                omitted_count: cat_result.omitted,
             }
         })
         .collect();
 
-    let scan_result = JsonScanResult::new(categories, start.elapsed().as_millis() as u64);
+    let mut scan_result = JsonScanResult::new(categories, start.elapsed().as_millis() as u64)
+        .with_meta(RunMeta::from_config(config));
+    // Links can span categories, so the total is worked out over everything at once.
+    if unique_size {
+        scan_result.unique_size_bytes = Some(unique_reclaimable_of(
+            report
+                .categories
+                .iter()
+                .filter(|c| category == "all" || c.scanner_id.contains(&category.to_lowercase()))
+                .flat_map(|c| c.items.iter()),
+        ));
+    }
+
+    // Full scans feed the reclaimable line of `report --trend`.
+    if category == "all" && config.clean.log_history {
+        if let Err(e) = HistoryLogger::new().log_scan_total(scan_result.total_size_bytes) {
+            eprintln!("Warning: could not record scan total: {}", e);
+        }
+    }
+
+    let grouping = match group_by {
+        ScanGroupBy::Category => None,
+        ScanGroupBy::Path => Some(output::Grouping::Path),
+        ScanGroupBy::SizeBucket => Some(output::Grouping::SizeBucket),
+    };
+    if let Some(grouping) = grouping {
+        output::regroup(&mut scan_result, grouping, &user::home_dir());
+    }
+
+    if paths_only {
+        return print_paths(
+            scan_result
+                .categories
+                .iter()
+                .flat_map(|c| c.items.iter())
+                .map(|item| item.path.as_path()),
+        );
+    }
 
     match format {
         OutputFormat::Json => {
@@ -238,65 +634,196 @@ fn run_scan(
             }
         }
         OutputFormat::Human => {
-            for cat_result in &scan_result.categories {
-                println!("{}:", cat_result.name);
-                println!("  Items: {}", cat_result.item_count);
-                println!("  Size: {}", format_size(cat_result.size_bytes));
-                println!();
+            let interactive = std::io::stdout().is_terminal();
+            if !interactive {
+                eprintln!(
+                    "Note: stdout is not a terminal, so every item is listed; use --format json for machine-readable output"
+                );
+            }
+            let shown = output::items_shown(config.ui.scan_items_shown, interactive);
+            let mut stdout = std::io::stdout().lock();
+            match output::write_scan_summary(&mut stdout, &scan_result, shown, timing) {
+                Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => {}
+                written => written?,
+            }
+        }
+    }
 
-                for item in cat_result.items.iter().take(10) {
-                    println!(
-                        "  - {} ({})",
-                        item.path.display(),
-                        format_size(item.size_bytes)
-                    );
-                }
+    Ok(())
+}
 
-                if cat_result.items.len() > 10 {
-                    println!("  ... and {} more", cat_result.items.len() - 10);
-                }
-                println!();
-            }
+// An empty registry would print an empty report that looks like a clean Mac.
+fn ensure_scanners(registry: &PluginRegistry, config: &Config) -> Result<()> {
+    if !registry.scanners().is_empty() {
+        return Ok(());
+    }
+    if config.scan.enabled_scanners.is_empty() {
+        anyhow::bail!("No scanners enabled: allowed_scanners in the config leaves none to run");
+    }
+    anyhow::bail!(
+        "No scanners enabled: none of [scan] enabled_scanners ({}) can run here; pick again with --reconfigure",
+        config.scan.enabled_scanners.join(", ")
+    )
+}
 
-            println!(
-                "Total: {} items, {} (in {}ms)",
-                scan_result.total_item_count,
-                format_size(scan_result.total_size_bytes),
-                scan_result.scan_duration_ms
-            );
-        }
+// One path per line and nothing else on stdout. Written as raw bytes so names
+// that aren't UTF-8 survive the trip to xargs; a closed pipe (`| head`) is not an error.
+fn print_paths<'a>(mut paths: impl Iterator<Item = &'a Path>) -> Result<()> {
+    let mut out = std::io::BufWriter::new(std::io::stdout().lock());
+    let written = paths
+        .try_for_each(|path| {
+            out.write_all(path.as_os_str().as_bytes())?;
+            out.write_all(b"\n")
+        })
+        .and_then(|()| out.flush());
+
+    match written {
+        Err(e) if e.kind() == std::io::ErrorKind::BrokenPipe => Ok(()),
+        written => Ok(written?),
+    }
+}
+
+fn unique_reclaimable_of<'a>(items: impl Iterator<Item = &'a plugin::ScanResult>) -> u64 {
+    let paths: Vec<&Path> = items.map(|item| item.path.as_path()).collect();
+    reclaim::unique_reclaimable(&paths)
+}
+
+fn run_explain(target: &str, category: &str, config: &Config, format: OutputFormat) -> Result<()> {
+    let registry = PluginRegistry::from_config(config);
+    let report = registry.scan_all(&ScanConfig::from_config(config))?;
+    warn_scan_problems(&report);
+
+    let found = report
+        .categories
+        .iter()
+        .filter(|c| category == "all" || c.scanner_id.contains(&category.to_lowercase()))
+        .flat_map(|c| c.items.iter().map(move |item| (c, item)))
+        .find(|(_, item)| item.id == target || item.path == Path::new(target));
+    let Some((cat_result, item)) = found else {
+        anyhow::bail!(
+            "No item with id or path {} in this scan (ids are listed by `scan -F json`)",
+            target
+        );
+    };
+
+    let explained = ExplainResult {
+        version: output::SCHEMA_VERSION.to_string(),
+        id: item.id.clone(),
+        name: item.name.clone(),
+        path: item.path.clone(),
+        size_bytes: item.size,
+        scanner_id: cat_result.scanner_id.clone(),
+        scanner_name: cat_result.name.clone(),
+        safety_level: item.safety_level.to_string(),
+        safety_reason: item.safety_reason().to_string(),
+        explanation: item.explanation().map(str::to_string),
+        warnings: scanner::login_launch_warning(item).into_iter().collect(),
+    };
+
+    if matches!(format, OutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&explained)?);
+        return Ok(());
+    }
+
+    println!("{}", explained.name);
+    println!("  Id: {}", explained.id);
+    println!("  Path: {}", explained.path.display());
+    println!("  Size: {}", format_size(explained.size_bytes));
+    println!(
+        "  Found by: {} ({})",
+        explained.scanner_name, explained.scanner_id
+    );
+    println!();
+    println!(
+        "Why it was flagged:\n  {}",
+        explained
+            .explanation
+            .as_deref()
+            .unwrap_or("This scanner does not describe its items yet.")
+    );
+    println!();
+    println!(
+        "Safety: {}\n  {}",
+        explained.safety_level, explained.safety_reason
+    );
+    for warning in &explained.warnings {
+        println!("\nWarning:\n  {}", warning);
     }
 
     Ok(())
 }
 
+fn print_brief_scan(
+    report: &plugin::registry::ScanReport,
+    skipped_scanners: Vec<String>,
+    start: Instant,
+    format: OutputFormat,
+    out: Option<&str>,
+) -> Result<()> {
+    let brief = BriefScanResult {
+        version: output::SCHEMA_VERSION.to_string(),
+        category_count: report
+            .categories
+            .iter()
+            .filter(|c| !c.items.is_empty())
+            .count(),
+        total_item_count: report.categories.iter().map(|c| c.items.len()).sum(),
+        total_size_bytes: report.categories.iter().map(|c| c.total_size()).sum(),
+        scan_duration_ms: start.elapsed().as_millis() as u64,
+        skipped_scanners,
+    };
+
+    let line = match format {
+        OutputFormat::Json => serde_json::to_string(&brief)?,
+        OutputFormat::Human => format!(
+            "{} categories · {} items · {} reclaimable",
+            brief.category_count,
+            utils::format_number(brief.total_item_count as u64),
+            format_size(brief.total_size_bytes)
+        ),
+    };
+    match out {
+        Some(path) => fs::write(path, format!("{}\n", line))?,
+        None => println!("{}", line),
+    }
+    Ok(())
+}
+
 fn run_plan(
     from: Option<&str>,
     category: Option<&str>,
     format: OutputFormat,
     out: Option<&str>,
+    paths_only: bool,
 ) -> Result<()> {
+    // A saved scan carries no scanner verdicts; a live one does.
+    let mut verdicts: HashMap<PathBuf, (plugin::SafetyLevel, plugin::ScannerCategory)> =
+        HashMap::new();
     let scan_result = if let Some(path) = from {
         let content = fs::read_to_string(path)?;
-        serde_json::from_str::<JsonScanResult>(&content)?
+        let scan_result = serde_json::from_str::<JsonScanResult>(&content)?;
+        if let Some(grouping) = &scan_result.grouped_by {
+            anyhow::bail!(
+                "{} is grouped by {}; plan from a scan saved without --group-by",
+                path,
+                grouping
+            );
+        }
+        scan_result
     } else {
         let config = Config::load()?;
-        let registry = PluginRegistry::default();
-        let scan_config = ScanConfig {
-            min_size: config.scan.min_size_bytes,
-            max_depth: config.scan.max_depth,
-            excluded_paths: config
-                .scan
-                .excluded_paths
-                .iter()
-                .map(|s| s.into())
-                .collect(),
-            progress_callback: None,
-            item_callback: None,
-        };
+        let registry = PluginRegistry::from_config(&config);
+        let scan_config = ScanConfig::from_config(&config);
 
         let report = registry.scan_all(&scan_config)?;
 
+        warn_scan_problems(&report);
+        verdicts.extend(report.categories.iter().flat_map(|c| {
+            c.items
+                .iter()
+                .map(|item| (item.path.clone(), (item.safety_level, item.category)))
+        }));
+
         let categories: Vec<JsonCategoryScanResult> = report
             .categories
             .iter()
@@ -311,21 +838,28 @@ fn run_plan(
                 description: String::new(),
                 size_bytes: cat_result.total_size(),
                 item_count: cat_result.items.len(),
+                unique_size_bytes: None,
                 items: cat_result
                     .items
                     .iter()
                     .map(|item| ScanItem {
+                        id: Some(item.id.clone()),
                         path: item.path.clone(),
                         size_bytes: item.size,
                         modified: Utc::now(),
                         last_used: None,
                         use_count: None,
+                        safety_reason: Some(item.safety_reason().to_string()),
                     })
                     .collect(),
+                scan_duration_ms: cat_result.scan_duration.as_millis() as u64,
+                unreadable_count: cat_result.unreadable,
+                omitted_count: cat_result.omitted,
             })
             .collect();
 
         JsonScanResult::new(categories, report.duration.as_millis() as u64)
+            .with_meta(RunMeta::from_config(&config))
     };
 
     let categories: Vec<CategoryPlanResult> = scan_result
@@ -337,15 +871,32 @@ fn run_plan(
             items: cat
                 .items
                 .iter()
-                .map(|item| PlanItem {
-                    path: item.path.clone(),
-                    size_bytes: item.size_bytes,
+                .map(|item| {
+                    let verdict = verdicts.get(&item.path);
+                    PlanItem {
+                        path: item.path.clone(),
+                        size_bytes: item.size_bytes,
+                        safety_level: verdict.map(|v| v.0),
+                        category: verdict.map(|v| v.1),
+                    }
                 })
                 .collect(),
         })
         .collect();
 
-    let plan_result = PlanResult::new(categories, from.map(|s| s.to_string()));
+    // A plan is produced by its scan, so it carries that scan's meta.
+    let mut plan_result = PlanResult::new(categories, from.map(|s| s.to_string()));
+    plan_result.meta = scan_result.meta.clone();
+
+    if paths_only {
+        return print_paths(
+            plan_result
+                .categories
+                .iter()
+                .flat_map(|c| c.items.iter())
+                .map(|item| item.path.as_path()),
+        );
+    }
 
     match format {
         OutputFormat::Json => {
@@ -379,57 +930,78 @@ fn run_plan(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_apply(
     plan_path: Option<&str>,
+    resume_path: Option<&str>,
     category: Option<&str>,
     yes: bool,
+    skip_open_files: bool,
+    only_safe: bool,
+    trash_mode: TrashMode,
     config: &Config,
     format: OutputFormat,
     out: Option<&str>,
 ) -> Result<()> {
     let start = Instant::now();
+    let _lock = yes.then(CleanLock::acquire).transpose()?;
+
+    let items_to_clean: Vec<plugin::ScanResult> = if let Some(path) = resume_path {
+        let content = fs::read_to_string(path)?;
+        let previous: ExecutionResult = serde_json::from_str(&content)?;
+
+        // "all" names no scanner, so it only passes when nothing is restricted.
+        let blocked: Vec<&str> = previous
+            .categories
+            .iter()
+            .map(|c| c.id.as_str())
+            .filter(|id| !config.allows_scanner(id))
+            .collect();
+        if !blocked.is_empty() {
+            anyhow::bail!(
+                "Execution file includes categories outside allowed_scanners: {}",
+                blocked.join(", ")
+            );
+        }
 
-    let items_to_clean: Vec<plugin::ScanResult> = if let Some(path) = plan_path {
+        let (retry, gone) = cleaner::failed_from_execution(&previous);
+        if !gone.is_empty() {
+            eprintln!("--resume: {} failed items no longer exist", gone.len());
+        }
+        retry
+    } else if let Some(path) = plan_path {
         let content = fs::read_to_string(path)?;
         let plan: PlanResult = serde_json::from_str(&content)?;
 
-        plan.categories
+        let blocked: Vec<&str> = plan
+            .categories
             .iter()
-            .flat_map(|cat| cat.items.iter())
-            .map(|item| plugin::ScanResult {
-                id: item.path.to_string_lossy().to_string(),
-                name: item
-                    .path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_default(),
-                path: item.path.clone(),
-                size: item.size_bytes,
-                file_count: 1,
-                last_accessed: None,
-                last_modified: None,
-                safety_level: plugin::SafetyLevel::Safe,
-                category: plugin::ScannerCategory::System,
-                metadata: HashMap::new(),
-            })
-            .collect()
+            .map(|c| c.id.as_str())
+            .filter(|id| !config.allows_scanner(id))
+            .collect();
+        if !blocked.is_empty() {
+            anyhow::bail!(
+                "Plan includes categories outside allowed_scanners: {}",
+                blocked.join(", ")
+            );
+        }
+
+        cleaner::items_from_plan(&plan)
     } else {
-        let registry = PluginRegistry::default();
-        let scan_config = ScanConfig {
-            min_size: config.scan.min_size_bytes,
-            max_depth: config.scan.max_depth,
-            excluded_paths: config
-                .scan
-                .excluded_paths
-                .iter()
-                .map(|s| s.into())
-                .collect(),
-            progress_callback: None,
-            item_callback: None,
-        };
+        let mut registry = PluginRegistry::from_config(config);
+        if let Some(category) = category {
+            registry.register_requested(category, config);
+        }
+        if category.is_none() {
+            registry.retain_enabled(config);
+        }
+        ensure_scanners(&registry, config)?;
+        let scan_config = ScanConfig::from_config(config);
 
         let report = registry.scan_all(&scan_config)?;
 
+        warn_scan_problems(&report);
+
         report
             .categories
             .iter()
@@ -442,6 +1014,22 @@ fn run_apply(
             .collect()
     };
 
+    let items_to_clean = if only_safe {
+        let (safe, dropped) = cleaner::only_safe(items_to_clean);
+        if dropped > 0 {
+            eprintln!("--only-safe: skipping {} non-Safe items", dropped);
+        }
+        safe
+    } else {
+        items_to_clean
+    };
+
+    let (items_to_clean, needs_review) = if yes {
+        unattended_split(items_to_clean, config)
+    } else {
+        (items_to_clean, Vec::new())
+    };
+
     if !yes {
         println!(
             "Found {} items to clean ({})",
@@ -452,41 +1040,30 @@ fn run_apply(
         return Ok(());
     }
 
-    let cleaner = DefaultCleaner::new();
+    let cleaner = DefaultCleaner::new().with_quiet(matches!(format, OutputFormat::Json));
     let clean_config = CleanConfig {
         dry_run: false,
         log_history: config.clean.log_history,
+        skip_open_files: skip_open_files || config.clean.skip_open_files,
+        throttle_ms: None,
+        clean_caution: !only_safe && config.clean.clean_caution,
+        trash_mode,
     };
 
     let result = cleaner.clean(&items_to_clean, &clean_config)?;
 
-    let category_results = vec![CategoryExecutionResult {
-        id: "all".to_string(),
-        status: if result.failed_count == 0 {
-            ExecutionStatus::Success
-        } else if result.success_count > 0 {
-            ExecutionStatus::Partial
-        } else {
-            ExecutionStatus::Failed
-        },
-        deleted_count: result.success_count,
-        deleted_size_bytes: result.total_freed,
-        failed_count: result.failed_count,
-        failed_items: result
-            .failed_items
-            .iter()
-            .map(|(path, error)| FailedItem {
-                path: path.clone(),
-                error: error.clone(),
-            })
-            .collect(),
-    }];
+    let mut category_result = category_execution_result("all", &result);
+    category_result
+        .skipped_items
+        .extend(needs_review.iter().map(review_skip));
+    let category_results = vec![category_result];
 
     let exec_result = ExecutionResult::new(
-        plan_path.map(|s| s.to_string()),
+        plan_path.or(resume_path).map(|s| s.to_string()),
         category_results,
         start.elapsed().as_millis() as u64,
-    );
+    )
+    .with_meta(RunMeta::from_config(config));
 
     match format {
         OutputFormat::Json => {
@@ -501,6 +1078,26 @@ fn run_apply(
             println!("\nResults:");
             println!("  Cleaned: {} items", exec_result.total_deleted_size);
             println!("  Status: {:?}", exec_result.status);
+            if !result.skipped_items.is_empty() {
+                println!("  Skipped (in use): {} items", result.skipped_items.len());
+            }
+            if !result.excluded_caution.is_empty() {
+                println!(
+                    "  Skipped (caution level excluded): {} items",
+                    result.excluded_caution.len()
+                );
+            }
+            if !result.ignored.is_empty() {
+                println!(
+                    "  Skipped (.cleanmacignore): {} items",
+                    result.ignored.len()
+                );
+            }
+            if !result.already_gone.is_empty() {
+                println!("  Already gone: {} items", result.already_gone.len());
+            }
+            print_needs_review(&needs_review);
+            print_trash_outcome(&result);
             println!("  Duration: {}ms", exec_result.duration_ms);
         }
     }
@@ -508,11 +1105,536 @@ fn run_apply(
     Ok(())
 }
 
-fn run_report(from: &str, format: ReportFormat, out: Option<&str>) -> Result<()> {
-    let content = fs::read_to_string(from)?;
-
-    let report = if let Ok(scan) = serde_json::from_str::<JsonScanResult>(&content) {
-        generate_scan_report(&scan, &format)
+fn category_execution_result(id: &str, result: &CleanResult) -> CategoryExecutionResult {
+    CategoryExecutionResult {
+        id: id.to_string(),
+        status: if result.failed_count == 0 {
+            ExecutionStatus::Success
+        } else if result.success_count > 0 {
+            ExecutionStatus::Partial
+        } else {
+            ExecutionStatus::Failed
+        },
+        deleted_count: result.success_count,
+        deleted_size_bytes: result.total_freed,
+        moved_to_trash_bytes: result.moved_to_trash,
+        failed_count: result.failed_count,
+        failed_items: result
+            .failed_items
+            .iter()
+            .map(|(path, error)| FailedItem {
+                path: path.clone(),
+                error: error.clone(),
+            })
+            .collect(),
+        skipped_items: result
+            .skipped_items
+            .iter()
+            .map(|(path, process)| SkippedItem {
+                path: path.clone(),
+                reason: "open_by_process".to_string(),
+                metadata: HashMap::from([("process".to_string(), process.clone())]),
+            })
+            .chain(result.excluded_caution.iter().map(|path| SkippedItem {
+                path: path.clone(),
+                reason: "caution_level_excluded".to_string(),
+                metadata: HashMap::new(),
+            }))
+            .chain(result.ignored.iter().map(|path| SkippedItem {
+                path: path.clone(),
+                reason: "cleanmacignore".to_string(),
+                metadata: HashMap::new(),
+            }))
+            .collect(),
+        already_gone: result.already_gone.clone(),
+        trash_mode: Some(result.trash_mode),
+        deleted_permanently: result.deleted_permanently.clone(),
+        archive: None,
+        commands: result
+            .commands
+            .iter()
+            .map(|c| CommandResult {
+                task: c.task.clone(),
+                command: c.command.clone(),
+                exit_code: c.exit_code,
+                stdout: c.stdout.clone(),
+                stderr: c.stderr.clone(),
+                freed_bytes: c.freed,
+            })
+            .collect(),
+    }
+}
+
+// With nobody at the terminal, only categories in [clean] auto_confirm_categories
+// go ahead; the rest come back as (to_clean, needs_review).
+fn unattended_split(
+    items: Vec<plugin::ScanResult>,
+    config: &Config,
+) -> (Vec<plugin::ScanResult>, Vec<plugin::ScanResult>) {
+    // Items that carry a warning (a live VM disk) are never removed unseen.
+    let (warned, items): (Vec<_>, Vec<_>) = items
+        .into_iter()
+        .partition(|item| item.metadata.contains_key("warning"));
+    let (confirmed, mut needs_review) = if std::io::stdin().is_terminal() {
+        (items, Vec::new())
+    } else {
+        cleaner::auto_confirmed(items, &config.clean.auto_confirm_categories)
+    };
+    needs_review.extend(warned);
+    (confirmed, needs_review)
+}
+
+fn review_skip(item: &plugin::ScanResult) -> SkippedItem {
+    SkippedItem {
+        path: item.path.clone(),
+        reason: "requires_review".to_string(),
+        metadata: item
+            .metadata
+            .get("scanner_id")
+            .map(|id| HashMap::from([("category".to_string(), id.clone())]))
+            .unwrap_or_default(),
+    }
+}
+
+fn print_needs_review(needs_review: &[plugin::ScanResult]) {
+    if !needs_review.is_empty() {
+        println!(
+            "  Requires review (has a warning or not in auto_confirm_categories): {} items",
+            needs_review.len()
+        );
+    }
+}
+
+// Trash moves only free space once the Trash is emptied, so they are
+// reported apart from what was actually freed.
+fn print_reclaimed(freed: u64, moved_to_trash: u64) {
+    if moved_to_trash > 0 {
+        println!("  Moved to Trash: {}", format_size(moved_to_trash));
+    }
+    println!("  Freed: {}", format_size(freed));
+}
+
+fn print_trash_outcome(result: &CleanResult) {
+    if result.success_count == 0 || result.dry_run {
+        return;
+    }
+    match result.trash_mode {
+        TrashMode::MoveToTrash => println!("  Moved to: Trash (put back from Finder)"),
+        TrashMode::Permanent => println!("  Moved to: nowhere, deleted permanently"),
+    }
+    if !result.deleted_permanently.is_empty() {
+        println!(
+            "  Deleted permanently (could not move to Trash): {} items",
+            result.deleted_permanently.len()
+        );
+    }
+    if let (TrashMode::MoveToTrash, Some(run_id)) = (result.trash_mode, &result.run_id) {
+        println!("  Undo with: cleanmac undo {}", run_id);
+    }
+}
+
+fn warn_skipped_history(logger: &HistoryLogger) {
+    let skipped = logger.skipped_lines();
+    if skipped > 0 {
+        eprintln!(
+            "Warning: skipped {} damaged line(s) in the history log",
+            skipped
+        );
+    }
+}
+
+fn warn_scan_problems(report: &plugin::registry::ScanReport) {
+    for name in &report.timed_out {
+        eprintln!("Warning: {} timed out; its results were skipped", name);
+    }
+    for warning in report.warnings() {
+        eprintln!("Warning: {}", warning);
+    }
+}
+
+fn run_watch(category: &str, interval: &str, format: OutputFormat, config: &Config) -> Result<()> {
+    let interval = watch::parse_interval(interval)?;
+    watch::install_interrupt_handler();
+
+    // Scans run on a worker so Ctrl-C stops the watch even mid-scan.
+    let registry = Arc::new(PluginRegistry::from_config(config));
+    let scan_config = ScanConfig::from_config(config);
+    let scan = || {
+        let registry = Arc::clone(&registry);
+        let scan_config = scan_config.clone();
+        watch::run_interruptible(move || registry.scan_all(&scan_config))
+    };
+
+    let Some(report) = scan() else {
+        return Ok(());
+    };
+    let report = report?;
+    warn_scan_problems(&report);
+    let start = watch::Snapshot::from_report(&report, category);
+    if start.sizes.is_empty() {
+        anyhow::bail!("No category matches '{}'", category);
+    }
+
+    if let OutputFormat::Human = format {
+        println!(
+            "Watching {} categories every {:?} (Ctrl-C to stop)\n",
+            start.sizes.len(),
+            interval
+        );
+    }
+
+    let mut previous = start.clone();
+    let mut tick = 0u64;
+
+    while !watch::interrupted() {
+        watch::sleep_interruptible(interval);
+        if watch::interrupted() {
+            break;
+        }
+
+        let Some(report) = scan() else {
+            break;
+        };
+        let current = watch::Snapshot::from_report(&report?, category);
+        let changes = watch::diff(&start, &previous, &current);
+        tick += 1;
+
+        match format {
+            OutputFormat::Json => {
+                let line = serde_json::json!({
+                    "tick": tick,
+                    "timestamp": Utc::now(),
+                    "categories": changes,
+                });
+                println!("{}", serde_json::to_string(&line)?);
+            }
+            OutputFormat::Human => {
+                println!("[{}] tick {}", Utc::now().format("%H:%M:%S"), tick);
+                for change in &changes {
+                    let marker = if change.delta_bytes > 0 { "▲" } else { " " };
+                    println!(
+                        "  {} {:<24} {:>12}  {:>12}",
+                        marker,
+                        change.name,
+                        format_size(change.size_bytes),
+                        watch::format_delta(change.delta_bytes)
+                    );
+                }
+            }
+        }
+
+        previous = current;
+    }
+
+    let summary = watch::diff(&start, &start, &previous);
+    match format {
+        OutputFormat::Json => {
+            let line = serde_json::json!({
+                "summary": true,
+                "ticks": tick,
+                "categories": summary,
+            });
+            println!("{}", serde_json::to_string(&line)?);
+        }
+        OutputFormat::Human => {
+            println!("\nNet change over {} ticks:", tick);
+            for change in &summary {
+                println!(
+                    "  {:<24} {:>12}",
+                    change.name,
+                    watch::format_delta(change.total_delta_bytes)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_suggest(target_gb: Option<f64>, format: OutputFormat, config: &Config) -> Result<()> {
+    const DEFAULT_FREE_RATIO: f64 = 0.15;
+
+    let home = crate::user::home_dir();
+    let space = suggest::disk_space_with_purgeable(&home)?;
+    let target = match target_gb {
+        Some(gb) => (gb * 1024.0 * 1024.0 * 1024.0) as u64,
+        None => (space.total_bytes as f64 * DEFAULT_FREE_RATIO) as u64,
+    };
+    // macOS clears purgeable space itself when a write needs it.
+    let needed = target.saturating_sub(space.available_bytes());
+
+    let registry = PluginRegistry::from_config(config);
+    let scan_config = ScanConfig::from_config(config);
+    let report = registry.scan_all(&scan_config)?;
+    warn_scan_problems(&report);
+
+    let suggestions = suggest::rank_categories(&report.categories);
+    let steps = if needed > 0 {
+        suggest::steps_for_target(&suggestions, needed)
+    } else {
+        suggestions.len()
+    };
+    let plan = &suggestions[..steps];
+    let reclaimable: u64 = plan.iter().map(|s| s.reclaimable()).sum();
+
+    match format {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "disk": space,
+                "target_free_bytes": target,
+                "needed_bytes": needed,
+                "reclaimable_bytes": reclaimable,
+                "steps": plan,
+            });
+            println!("{}", serde_json::to_string_pretty(&output)?);
+        }
+        OutputFormat::Human => {
+            println!(
+                "Free space: {} of {}",
+                format_size(space.free_bytes),
+                format_size(space.total_bytes)
+            );
+            match space.purgeable_bytes {
+                Some(purgeable) if purgeable > 0 => println!(
+                    "Purgeable: about {} more that macOS frees on demand",
+                    format_size(purgeable)
+                ),
+                Some(_) => {}
+                None => println!("Purgeable: unknown (counted as none)"),
+            }
+            if needed > 0 {
+                println!(
+                    "You need {} more to reach {} free. Here's how to get it:\n",
+                    format_size(needed),
+                    format_size(target)
+                );
+            } else {
+                println!(
+                    "Already above the {} target. Quick wins if you want them:\n",
+                    format_size(target)
+                );
+            }
+
+            for (i, s) in plan.iter().enumerate() {
+                let caution = if s.caution_bytes > 0 {
+                    format!(", {} needs review", format_size(s.caution_bytes))
+                } else {
+                    String::new()
+                };
+                println!(
+                    "{}. {} - {} in {} items{}",
+                    i + 1,
+                    s.name,
+                    format_size(s.reclaimable()),
+                    s.item_count,
+                    caution
+                );
+                println!("   $ {}", s.command);
+            }
+
+            if plan.is_empty() {
+                println!("Nothing to clean.");
+            } else if reclaimable < needed {
+                println!(
+                    "\nThese steps free about {}; the remaining {} will need manual cleanup.",
+                    format_size(reclaimable),
+                    format_size(needed - reclaimable)
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_info(format: OutputFormat, config: &Config) -> Result<()> {
+    let registry = PluginRegistry::from_config(config);
+
+    let info = InfoResult {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: output::SCHEMA_VERSION.to_string(),
+        output_formats: OutputFormat::value_variants()
+            .iter()
+            .filter_map(|v| v.to_possible_value())
+            .map(|v| v.get_name().to_string())
+            .collect(),
+        report_formats: ReportFormat::value_variants()
+            .iter()
+            .filter_map(|v| v.to_possible_value())
+            .map(|v| v.get_name().to_string())
+            .collect(),
+        scanners: registry
+            .scanners()
+            .iter()
+            .map(|s| ScannerInfo {
+                id: s.id().to_string(),
+                name: s.name().to_string(),
+                category: s.category().to_string().to_lowercase(),
+                default_safety: s.default_safety().to_string().to_lowercase(),
+                available: s.is_available(),
+            })
+            .collect(),
+        integrations: ["docker", "brew", "xcrun"]
+            .iter()
+            .map(|name| {
+                let path = utils::find_in_path(name);
+                IntegrationInfo {
+                    name: name.to_string(),
+                    available: path.is_some(),
+                    path,
+                    note: None,
+                }
+            })
+            .chain(std::iter::once({
+                let status = uninstaller::automation_status(&uninstaller::SystemRunner);
+                IntegrationInfo {
+                    name: "osascript".to_string(),
+                    available: status == uninstaller::AutomationStatus::Available,
+                    path: utils::find_in_path("osascript"),
+                    note: (status != uninstaller::AutomationStatus::Available)
+                        .then(|| status.describe().to_string()),
+                }
+            }))
+            .collect(),
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&info)?),
+        OutputFormat::Human => {
+            println!("cleanmac {} (schema {})", info.version, info.schema_version);
+            println!("\nScanners:");
+            if config.allowed_scanners.is_some() {
+                println!("  (limited by allowed_scanners in config)");
+            }
+            for s in &info.scanners {
+                println!(
+                    "  {:<16} {:<12} {:<8}{}",
+                    s.id,
+                    s.category,
+                    s.default_safety,
+                    if s.available { "" } else { " (unavailable)" }
+                );
+            }
+            println!("\nIntegrations:");
+            for i in &info.integrations {
+                match &i.path {
+                    Some(path) => println!("  {:<9} {}", i.name, path.display()),
+                    None => println!("  {:<9} not found", i.name),
+                }
+                if let Some(note) = &i.note {
+                    println!("  {:<9} ! {}", "", note);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn run_validate(file: &str) -> Result<ExitCode> {
+    let content = fs::read_to_string(file)?;
+    let report = output::validate_json(&content);
+
+    match report.schema {
+        Some(kind) => println!(
+            "{}: {} result (version {})",
+            file,
+            kind.name(),
+            report.version.as_deref().unwrap_or("missing")
+        ),
+        None => println!("{}: unknown format", file),
+    }
+
+    for problem in &report.problems {
+        println!("  - {}", problem);
+    }
+
+    if report.is_valid() {
+        println!("OK");
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::from(1))
+    }
+}
+
+fn run_trend_report(format: ReportFormat, out: Option<&str>) -> Result<()> {
+    let logger = HistoryLogger::new();
+    let range = DateRange::default();
+    let points = history::daily_trend(
+        &logger.read_history_in_range(None, &range)?,
+        &logger.read_scan_totals(&range)?,
+    );
+    warn_skipped_history(&logger);
+
+    let trend = TrendReport {
+        version: output::SCHEMA_VERSION.to_string(),
+        total_freed_bytes: points.iter().map(|p| p.freed_bytes).sum(),
+        points: points
+            .iter()
+            .map(|p| TrendPointOutput {
+                date: p.date,
+                freed_bytes: p.freed_bytes,
+                reclaimable_bytes: p.reclaimable_bytes,
+            })
+            .collect(),
+    };
+
+    let report = match format {
+        ReportFormat::Json => serde_json::to_string_pretty(&trend)?,
+        ReportFormat::Md | ReportFormat::Txt => {
+            let mut text = String::new();
+            text.push_str(if matches!(format, ReportFormat::Md) {
+                "# CleanMac Cleanup Trend\n\n"
+            } else {
+                "CleanMac Cleanup Trend\n======================\n\n"
+            });
+
+            if trend.points.is_empty() {
+                text.push_str("No cleanup history yet. Run a clean or a full scan first.\n");
+            } else {
+                let first = trend.points[0].date;
+                let last = trend.points[trend.points.len() - 1].date;
+                let freed: Vec<u64> = trend.points.iter().map(|p| p.freed_bytes).collect();
+                text.push_str(&format!("{} .. {}\n\n", first, last));
+                text.push_str(&format!(
+                    "Freed        {}  {} total\n",
+                    utils::sparkline(&freed),
+                    format_size(trend.total_freed_bytes)
+                ));
+
+                // Days without a scan carry nothing to chart, so only scanned days are drawn.
+                let reclaimable: Vec<u64> = trend
+                    .points
+                    .iter()
+                    .filter_map(|p| p.reclaimable_bytes)
+                    .collect();
+                match reclaimable.last() {
+                    Some(latest) => text.push_str(&format!(
+                        "Reclaimable  {}  {} at last scan\n",
+                        utils::sparkline(&reclaimable),
+                        format_size(*latest)
+                    )),
+                    None => text.push_str("Reclaimable  (no full scans recorded yet)\n"),
+                }
+            }
+            text
+        }
+    };
+
+    if let Some(path) = out {
+        fs::write(path, &report)?;
+    } else {
+        println!("{}", report);
+    }
+
+    Ok(())
+}
+
+fn run_report(from: &str, format: ReportFormat, out: Option<&str>) -> Result<()> {
+    let content = fs::read_to_string(from)?;
+
+    let report = if let Ok(scan) = serde_json::from_str::<JsonScanResult>(&content) {
+        generate_scan_report(&scan, &format)
     } else if let Ok(exec) = serde_json::from_str::<ExecutionResult>(&content) {
         generate_exec_report(&exec, &format)
     } else {
@@ -598,6 +1720,12 @@ fn generate_exec_report(exec: &ExecutionResult, format: &ReportFormat) -> String
                 "**Freed**: {}\n\n",
                 format_size(exec.total_deleted_size)
             ));
+            if exec.total_moved_to_trash > 0 {
+                md.push_str(&format!(
+                    "**Moved to Trash**: {}\n\n",
+                    format_size(exec.total_moved_to_trash)
+                ));
+            }
 
             for cat in &exec.categories {
                 md.push_str(&format!("## {} - {:?}\n\n", cat.id, cat.status));
@@ -609,7 +1737,7 @@ fn generate_exec_report(exec: &ExecutionResult, format: &ReportFormat) -> String
                 if cat.failed_count > 0 {
                     md.push_str(&format!("Failed: {} items\n", cat.failed_count));
                 }
-                md.push_str("\n");
+                md.push('\n');
             }
 
             md
@@ -627,6 +1755,12 @@ fn generate_exec_report(exec: &ExecutionResult, format: &ReportFormat) -> String
                 "Freed: {}\n\n",
                 format_size(exec.total_deleted_size)
             ));
+            if exec.total_moved_to_trash > 0 {
+                txt.push_str(&format!(
+                    "Moved to Trash: {}\n\n",
+                    format_size(exec.total_moved_to_trash)
+                ));
+            }
 
             for cat in &exec.categories {
                 txt.push_str(&format!("{} - {:?}\n", cat.id, cat.status));
@@ -638,7 +1772,7 @@ fn generate_exec_report(exec: &ExecutionResult, format: &ReportFormat) -> String
                 if cat.failed_count > 0 {
                     txt.push_str(&format!("  Failed: {} items\n", cat.failed_count));
                 }
-                txt.push_str("\n");
+                txt.push('\n');
             }
 
             txt
@@ -646,65 +1780,668 @@ fn generate_exec_report(exec: &ExecutionResult, format: &ReportFormat) -> String
     }
 }
 
-fn run_clean(category: &str, execute: bool, config: &Config) -> Result<()> {
-    let registry = PluginRegistry::default();
-    let cleaner = DefaultCleaner::new();
-
-    println!("{} mode\n", if execute { "Execute" } else { "Dry-run" });
+#[allow(clippy::too_many_arguments)]
+fn run_clean(
+    category: &str,
+    execute: bool,
+    skip_open_files: bool,
+    keep_recent: Option<usize>,
+    only_safe: bool,
+    trash_mode: TrashMode,
+    throttle_ms: Option<u64>,
+    archive_dir: Option<&str>,
+    format: OutputFormat,
+    config: &Config,
+) -> Result<()> {
+    let start = Instant::now();
+    let _lock = execute.then(CleanLock::acquire).transpose()?;
+    let json = matches!(format, OutputFormat::Json);
+    let registry = match archive_dir {
+        // Large & Old Files is not in the default registry, so archive mode scans it alone.
+        Some(_) => {
+            if category != "all" && !"large_old_files".contains(&category.to_lowercase()) {
+                anyhow::bail!("--archive only applies to the large_old_files category");
+            }
+            if !config.allows_scanner("large_old_files") {
+                anyhow::bail!("large_old_files is not in allowed_scanners");
+            }
+            let mut registry = PluginRegistry::new();
+            registry.register_scanner(Box::new(scanner::LargeOldFilesScanner::new()));
+            registry
+        }
+        None => {
+            let mut registry = PluginRegistry::from_config(config);
+            registry.register_requested(category, config);
+            if category == "all" {
+                registry.retain_enabled(config);
+            }
+            ensure_scanners(&registry, config)?;
+            registry
+        }
+    };
+    let cleaner = DefaultCleaner::new().with_quiet(json);
 
-    let scan_config = ScanConfig {
-        min_size: config.scan.min_size_bytes,
-        max_depth: config.scan.max_depth,
-        excluded_paths: config
-            .scan
-            .excluded_paths
-            .iter()
-            .map(|s| s.into())
-            .collect(),
-        progress_callback: None,
-        item_callback: None,
+    // JSON goes to stdout, so progress notes move to stderr
+    let note = |line: String| {
+        if json {
+            eprintln!("{}", line);
+        } else {
+            println!("{}", line);
+        }
     };
 
+    note(format!(
+        "{} mode\n",
+        if execute { "Execute" } else { "Dry-run" }
+    ));
+
+    let scan_config = ScanConfig::from_config(config);
+
     let report = registry.scan_all(&scan_config)?;
 
+    warn_scan_problems(&report);
+
     let mut all_items = Vec::new();
     for cat_result in &report.categories {
         if category != "all" && !cat_result.scanner_id.contains(&category.to_lowercase()) {
             continue;
         }
-        all_items.extend(cat_result.items.clone());
+
+        match keep_recent {
+            Some(keep) => {
+                let (to_clean, kept) = cleaner::keep_recent(&cat_result.items, keep);
+                note(format!(
+                    "{}: keeping {} most recent, cleaning {}",
+                    cat_result.name,
+                    kept.len(),
+                    to_clean.len()
+                ));
+                all_items.extend(to_clean);
+            }
+            None => all_items.extend(cat_result.items.clone()),
+        }
+    }
+
+    if keep_recent.is_some() {
+        note(String::new());
     }
 
+    if only_safe {
+        let (safe, dropped) = cleaner::only_safe(all_items);
+        note(format!(
+            "Only Safe items: skipping {} Caution/Protected items\n",
+            dropped
+        ));
+        all_items = safe;
+    }
+
+    let needs_review = if execute {
+        let (confirmed, needs_review) = unattended_split(all_items, config);
+        all_items = confirmed;
+        needs_review
+    } else {
+        Vec::new()
+    };
+
     let clean_config = CleanConfig {
         dry_run: !execute,
         log_history: config.clean.log_history,
+        skip_open_files: skip_open_files || config.clean.skip_open_files,
+        throttle_ms,
+        clean_caution: !only_safe && config.clean.clean_caution,
+        // Archived originals are already kept in the archive.
+        trash_mode: if archive_dir.is_some() {
+            TrashMode::Permanent
+        } else {
+            trash_mode
+        },
+    };
+
+    // The originals are only touched once the archive has been written and verified.
+    let archived = match archive_dir {
+        Some(dir) if execute && !all_items.is_empty() => {
+            let archived = cleaner::archive_items(&all_items, Path::new(dir))?;
+            note(format!(
+                "Archived {} items to {}\n",
+                archived.entries,
+                archived.archive_path.display()
+            ));
+            Some(archived)
+        }
+        Some(dir) => {
+            note(format!(
+                "Would archive {} items into {}\n",
+                all_items.len(),
+                dir
+            ));
+            None
+        }
+        None => None,
     };
 
     let result = cleaner.clean(&all_items, &clean_config)?;
 
+    if let Some(archived) = &archived {
+        if config.clean.log_history {
+            if let Err(e) =
+                HistoryLogger::new().log_archive(&archived.archive_path, archived.compressed_size)
+            {
+                eprintln!("Warning: could not record archive in history: {}", e);
+            }
+        }
+    }
+
+    if json {
+        let mut category_result = category_execution_result(category, &result);
+        category_result
+            .skipped_items
+            .extend(needs_review.iter().map(review_skip));
+        category_result.archive = archived.as_ref().map(|a| ArchiveInfo {
+            path: a.archive_path.clone(),
+            original_size_bytes: a.original_size,
+            compressed_size_bytes: a.compressed_size,
+        });
+        let mut exec_result = ExecutionResult::new(
+            None,
+            vec![category_result],
+            start.elapsed().as_millis() as u64,
+        )
+        .with_meta(RunMeta::from_config(config));
+        if !execute {
+            exec_result = exec_result.into_dry_run();
+        }
+        println!("{}", serde_json::to_string_pretty(&exec_result)?);
+        return Ok(());
+    }
+
     println!();
     println!("Results:");
     println!("  Cleaned: {} items", result.success_count);
     println!("  Failed: {} items", result.failed_count);
-    println!("  Freed: {}", format_size(result.total_freed));
-    println!("  Duration: {:?}", result.duration);
-
-    if !result.failed_items.is_empty() {
-        println!("\nFailed items:");
-        for (path, error) in &result.failed_items {
-            println!("  - {}: {}", path.display(), error);
-        }
+    if !result.skipped_items.is_empty() {
+        println!("  Skipped: {} items", result.skipped_items.len());
     }
+    if !result.excluded_caution.is_empty() {
+        println!(
+            "  Skipped (caution level excluded): {} items",
+            result.excluded_caution.len()
+        );
+    }
+    if !result.ignored.is_empty() {
+        println!(
+            "  Skipped (.cleanmacignore): {} items",
+            result.ignored.len()
+        );
+    }
+    if !result.already_gone.is_empty() {
+        println!("  Already gone: {} items", result.already_gone.len());
+    }
+    print_needs_review(&needs_review);
+    print_trash_outcome(&result);
+    print_reclaimed(result.total_freed, result.moved_to_trash);
+    if let Some(archived) = &archived {
+        let ratio = if archived.original_size > 0 {
+            archived.compressed_size as f64 / archived.original_size as f64 * 100.0
+        } else {
+            0.0
+        };
+        println!("  Archive: {}", archived.archive_path.display());
+        println!(
+            "  Original {} → Compressed {} ({:.0}%)",
+            format_size(archived.original_size),
+            format_size(archived.compressed_size),
+            ratio
+        );
+        println!(
+            "  Net freed: {}",
+            format_size(result.total_freed.saturating_sub(archived.compressed_size))
+        );
+    }
+    println!("  Duration: {:?}", result.duration);
+
+    if !result.skipped_items.is_empty() {
+        println!("\nSkipped items (open by a running process):");
+        for (path, process) in &result.skipped_items {
+            println!("  - {} ({})", path.display(), process);
+        }
+    }
+
+    if !result.failed_items.is_empty() {
+        println!("\nFailed items:");
+        for (path, error) in &result.failed_items {
+            println!("  - {}: {}", path.display(), error);
+        }
+    }
+
+    Ok(())
+}
+
+// An opinionated `clean` for people who don't want to review anything: the
+// regenerable categories only, Safe items only, open files left alone.
+fn run_safe_clean(execute: bool, log_age_days: u64, config: &Config) -> Result<()> {
+    let _lock = execute.then(CleanLock::acquire).transpose()?;
+    let mut registry = PluginRegistry::from_config(config);
+    registry.retain_ids(&cleaner::SAFE_CLEAN_SCANNERS);
+    ensure_scanners(&registry, config)?;
+
+    println!(
+        "{} mode: caches, browser caches, logs older than {} days and the Trash\n",
+        if execute { "Execute" } else { "Dry-run" },
+        log_age_days
+    );
+
+    let report = registry.scan_all(&ScanConfig::from_config(config))?;
+    warn_scan_problems(&report);
+
+    let items = cleaner::safe_clean_items(&report.categories, log_age_days, Utc::now())?;
+    // Sizes count overlapping items (a cache folder and a file in it) once.
+    for category in &report.categories {
+        let picked: Vec<_> = items
+            .iter()
+            .filter(|i| i.metadata.get("scanner_id") == Some(&category.scanner_id))
+            .collect();
+        if picked.is_empty() {
+            continue;
+        }
+        println!(
+            "  {}: {} items, {}",
+            category.name,
+            picked.len(),
+            format_size(unique_reclaimable_of(picked.into_iter()))
+        );
+    }
+    println!(
+        "\nTotal: {} in {} items",
+        format_size(unique_reclaimable_of(items.iter())),
+        items.len()
+    );
+
+    if !execute {
+        if !items.is_empty() {
+            println!("Run again with --execute to clean them.");
+        }
+        return Ok(());
+    }
+
+    let (items, needs_review) = unattended_split(items, config);
+    let clean_config = CleanConfig {
+        dry_run: false,
+        log_history: config.clean.log_history,
+        skip_open_files: true,
+        throttle_ms: None,
+        clean_caution: false,
+        trash_mode: TrashMode::MoveToTrash,
+    };
+    let result = DefaultCleaner::new().clean(&items, &clean_config)?;
+
+    println!();
+    println!("Results:");
+    println!("  Cleaned: {} items", result.success_count);
+    println!("  Failed: {} items", result.failed_count);
+    if !result.skipped_items.is_empty() {
+        println!(
+            "  Skipped (open by a running process): {} items",
+            result.skipped_items.len()
+        );
+    }
+    print_needs_review(&needs_review);
+    print_trash_outcome(&result);
+    print_reclaimed(result.total_freed, result.moved_to_trash);
+
+    for (path, error) in &result.failed_items {
+        println!("  - {}: {}", path.display(), error);
+    }
+
+    Ok(())
+}
+
+fn run_enforce_retention(execute: bool, config: &Config) -> Result<()> {
+    if config.retention.is_empty() {
+        println!("No retention policies configured.");
+        println!("Add a [retention] section to the config, e.g. system_logs = 30");
+        return Ok(());
+    }
+
+    let _lock = execute.then(CleanLock::acquire).transpose()?;
+    let registry = PluginRegistry::from_config(config);
+    for id in config.retention.keys() {
+        if !config.allows_scanner(id) {
+            eprintln!(
+                "Warning: '{}' is not in allowed_scanners, policy ignored",
+                id
+            );
+        } else if !registry.scanners().iter().any(|s| s.id() == id) {
+            eprintln!("Warning: no scanner with id '{}', policy ignored", id);
+        }
+    }
+
+    println!("{} mode\n", if execute { "Execute" } else { "Dry-run" });
+
+    let scan_config = ScanConfig::from_config(config);
+
+    let report = registry.scan_all(&scan_config)?;
+    warn_scan_problems(&report);
+
+    let cleaner = DefaultCleaner::new();
+    let clean_config = CleanConfig {
+        dry_run: !execute,
+        log_history: config.clean.log_history,
+        skip_open_files: config.clean.skip_open_files,
+        throttle_ms: None,
+        clean_caution: config.clean.clean_caution,
+        trash_mode: TrashMode::MoveToTrash,
+    };
+    let now = Utc::now();
+    let (mut total_freed, mut total_moved) = (0, 0);
+
+    for cat_result in &report.categories {
+        let Some(&max_age_days) = config.retention.get(&cat_result.scanner_id) else {
+            continue;
+        };
+
+        let (expired, kept) = cleaner::older_than(&cat_result.items, max_age_days, now)?;
+        println!(
+            "{} (older than {} days): {} expired, {} kept",
+            cat_result.name,
+            max_age_days,
+            expired.len(),
+            kept.len()
+        );
+        if expired.is_empty() {
+            println!();
+            continue;
+        }
+
+        let result = cleaner.clean(&expired, &clean_config)?;
+        total_freed += result.total_freed;
+        total_moved += result.moved_to_trash;
+        println!(
+            "  Cleaned: {}, Failed: {}",
+            result.success_count, result.failed_count
+        );
+        print_reclaimed(result.total_freed, result.moved_to_trash);
+        println!();
+        for (path, error) in &result.failed_items {
+            println!("  - {}: {}", path.display(), error);
+        }
+    }
+
+    println!("Total:");
+    print_reclaimed(total_freed, total_moved);
 
     Ok(())
 }
 
-fn run_uninstall(name: &str, execute: bool) -> Result<()> {
+fn run_schedule(action: ScheduleActions) -> Result<()> {
+    match action {
+        ScheduleActions::Install {
+            interval,
+            category,
+            report_dir,
+        } => {
+            let report_dir = match report_dir {
+                Some(dir) => std::path::absolute(dir)?,
+                None => user::home_dir().join("cleanmac-reports"),
+            };
+            let spec = schedule::ScheduleSpec {
+                interval,
+                categories: category,
+                report_dir,
+                binary: std::env::current_exe()?,
+            };
+            let path = schedule::install(&spec)?;
+            println!("Installed {}", path.display());
+            println!(
+                "Scans {} {} into {}",
+                spec.categories.join(", "),
+                format!("{:?}", interval).to_lowercase(),
+                spec.report_dir.display()
+            );
+            println!("Only scans run on schedule; nothing is deleted.");
+        }
+        ScheduleActions::Uninstall => {
+            if schedule::uninstall()? {
+                println!("Removed the scheduled scan.");
+            } else {
+                println!("No scheduled scan is installed.");
+            }
+        }
+        ScheduleActions::Status => {
+            let path = schedule::agent_path();
+            if !path.exists() {
+                println!("No scheduled scan is installed.");
+                return Ok(());
+            }
+            println!("Installed: {}", path.display());
+            println!(
+                "Loaded: {}",
+                if schedule::is_loaded() { "yes" } else { "no" }
+            );
+        }
+    }
+    Ok(())
+}
+
+fn run_empty_trash(execute: bool, config: &Config) -> Result<()> {
+    let locations = cleaner::trash_locations();
+    if locations.is_empty() {
+        println!("Trash is already empty.");
+        return Ok(());
+    }
+
+    println!("{} mode\n", if execute { "Execute" } else { "Dry-run" });
+    for location in &locations {
+        println!(
+            "  {:>12}  {:>8} items  {}",
+            format_size(location.size),
+            utils::format_number(location.entries as u64),
+            location.path.display()
+        );
+    }
+    let total_size: u64 = locations.iter().map(|l| l.size).sum();
+    let total_entries: usize = locations.iter().map(|l| l.entries).sum();
+    println!(
+        "\nTotal: {} in {} items",
+        format_size(total_size),
+        utils::format_number(total_entries as u64)
+    );
+
+    if !execute {
+        println!("\nRun with --execute to empty the Trash.");
+        return Ok(());
+    }
+
+    let _lock = CleanLock::acquire()?;
+    let result = cleaner::empty_trash(&locations);
+    if config.clean.log_history {
+        let logger = HistoryLogger::new();
+        for (path, freed) in &result.emptied {
+            if let Err(e) = logger.log_empty_trash(path, *freed) {
+                eprintln!("Warning: could not record emptied trash in history: {}", e);
+            }
+        }
+    }
+
+    println!(
+        "\nEmptied{}: {} items, Freed: {}",
+        if result.via_finder {
+            " (via Finder)"
+        } else {
+            ""
+        },
+        result.removed,
+        format_size(result.freed)
+    );
+    if !result.errors.is_empty() {
+        println!("Could not remove {} items:", result.errors.len());
+        for error in &result.errors {
+            println!("  - {}", error);
+        }
+    }
+
+    Ok(())
+}
+
+// Read-only: never constructs an Uninstaller.
+fn run_list_related(name: &str, format: OutputFormat) -> Result<()> {
+    use uninstaller::{AppDetector, RelatedFileDetector};
+
+    let Some(app) = AppDetector::new().find_by_name(name) else {
+        anyhow::bail!("App not found: {}", name);
+    };
+    let related_files = RelatedFileDetector::new().find_related_files(&app);
+
+    let related: Vec<RelatedFileInfo> = related_files.iter().map(related_file_info).collect();
+    let app_size = app.size();
+    let result = RelatedFilesResult {
+        version: output::SCHEMA_VERSION.to_string(),
+        app: app.name().to_string(),
+        path: app.path.clone(),
+        size_bytes: app_size,
+        bundle_id: app.info().map(|i| i.bundle_id),
+        total_size_bytes: app_size + related.iter().map(|r| r.size_bytes).sum::<u64>(),
+        related,
+    };
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&result)?),
+        OutputFormat::Human => {
+            println!(
+                "{} ({}) {}",
+                result.app,
+                result.path.display(),
+                format_size(result.size_bytes)
+            );
+            if let Some(bundle_id) = &result.bundle_id {
+                println!("  Bundle ID: {}", bundle_id);
+            }
+
+            if result.related.is_empty() {
+                println!("\nNo related files found.");
+            } else {
+                println!("\nRelated files ({}):", result.related.len());
+                for file in &result.related {
+                    println!(
+                        "  {:<28} {:>10}  {}{}",
+                        file.category,
+                        format_size(file.size_bytes),
+                        file.path.display(),
+                        if file.protected { " (Protected)" } else { "" }
+                    );
+                }
+            }
+            println!("\nTotal: {}", format_size(result.total_size_bytes));
+        }
+    }
+
+    Ok(())
+}
+
+fn related_file_info(file: &uninstaller::RelatedFile) -> RelatedFileInfo {
+    RelatedFileInfo {
+        path: file.path.clone(),
+        category: file.category.display_name().to_string(),
+        size_bytes: file.size,
+        protected: file.category.is_protected(),
+    }
+}
+
+fn uninstall_preview(
+    app: &uninstaller::AppBundle,
+    related_files: &[uninstaller::RelatedFile],
+) -> UninstallPreviewResult {
+    let (groups, protected) = uninstaller::group_related(related_files);
+    let groups: Vec<UninstallPreviewGroup> = groups
+        .into_iter()
+        .map(|(category, files)| UninstallPreviewGroup {
+            category: category.display_name().to_string(),
+            size_bytes: files.iter().map(|f| f.size).sum(),
+            files: files.into_iter().map(related_file_info).collect(),
+        })
+        .collect();
+    let app_size = app.size();
+
+    UninstallPreviewResult {
+        version: output::SCHEMA_VERSION.to_string(),
+        app: app.name().to_string(),
+        path: app.path.clone(),
+        size_bytes: app_size,
+        bundle_id: app.info().map(|i| i.bundle_id),
+        total_size_bytes: app_size + groups.iter().map(|g| g.size_bytes).sum::<u64>(),
+        groups,
+        protected: protected.into_iter().map(related_file_info).collect(),
+    }
+}
+
+fn print_uninstall_preview(preview: &UninstallPreviewResult) {
+    println!("Will delete:");
+    println!(
+        "  {:<40} {:>10}",
+        format!("{}.app", preview.app),
+        format_size(preview.size_bytes)
+    );
+    println!("    {}", preview.path.display());
+
+    for group in &preview.groups {
+        println!(
+            "  {:<40} {:>10}",
+            format!("{} ({})", group.category, group.files.len()),
+            format_size(group.size_bytes)
+        );
+        for file in &group.files {
+            println!(
+                "    {} ({})",
+                file.path.display(),
+                format_size(file.size_bytes)
+            );
+        }
+    }
+
+    if !preview.protected.is_empty() {
+        println!("\nSkipped (protected):");
+        for file in &preview.protected {
+            println!(
+                "    {} [{}] ({})",
+                file.path.display(),
+                file.category,
+                format_size(file.size_bytes)
+            );
+        }
+    }
+
+    println!("\nTotal to free: {}", format_size(preview.total_size_bytes));
+}
+
+fn run_uninstall(
+    name: &str,
+    execute: bool,
+    confirm_each: bool,
+    format: OutputFormat,
+) -> Result<()> {
     use uninstaller::{AppDetector, RelatedFileDetector, Uninstaller};
 
+    let json = matches!(format, OutputFormat::Json);
+    if json && execute {
+        anyhow::bail!("--format json is only supported for the dry-run preview");
+    }
+    if confirm_each && execute && !std::io::stdin().is_terminal() {
+        anyhow::bail!("--confirm-each needs a terminal to answer its prompts");
+    }
+    let _lock = execute.then(CleanLock::acquire).transpose()?;
+
     let detector = AppDetector::new();
     let uninstaller = Uninstaller::new(!execute);
 
+    if json {
+        let Some(app) = detector.find_by_name(name) else {
+            anyhow::bail!("App not found: {}", name);
+        };
+        let related_files = RelatedFileDetector::new().find_related_files(&app);
+        let preview = uninstall_preview(&app, &related_files);
+        println!("{}", serde_json::to_string_pretty(&preview)?);
+        return Ok(());
+    }
+
     println!("Searching for app: {}\n", name);
 
     match detector.find_by_name(name) {
@@ -720,26 +2457,26 @@ fn run_uninstall(name: &str, execute: bool) -> Result<()> {
             let related_detector = RelatedFileDetector::new();
             let related_files = related_detector.find_related_files(&app);
 
-            if related_files.is_empty() {
-                println!("No related files found.");
-            } else {
-                println!("Related files ({}):", related_files.len());
-                for file in &related_files {
-                    let protected = if file.category.is_protected() {
-                        " (Protected)"
-                    } else {
-                        ""
-                    };
+            println!();
+            print_uninstall_preview(&uninstall_preview(&app, &related_files));
+
+            if !execute {
+                if uninstaller.is_system_app(&app) {
                     println!(
-                        "  - {} [{}] {}{}",
-                        file.path.display(),
-                        file.category.display_name(),
-                        format_size(file.size),
-                        protected
+                        "\nNote: {} is a system app and cannot be uninstalled.",
+                        app.name()
                     );
                 }
+                println!("\nDry run: nothing was deleted. Use --execute to uninstall.");
+                return Ok(());
             }
 
+            let related_files = if confirm_each {
+                confirm_related_categories(related_files)?
+            } else {
+                related_files
+            };
+
             println!();
             let result = uninstaller.uninstall(&app, &related_files)?;
 
@@ -767,18 +2504,97 @@ fn run_uninstall(name: &str, execute: bool) -> Result<()> {
     Ok(())
 }
 
+// Asks once per related-file category and keeps only what was confirmed.
+// Protected categories stay in the list so the uninstall reports them skipped.
+fn confirm_related_categories(
+    related_files: Vec<uninstaller::RelatedFile>,
+) -> Result<Vec<uninstaller::RelatedFile>> {
+    use std::io::BufRead;
+
+    let (groups, protected) = uninstaller::group_related(&related_files);
+    let mut confirmed = Vec::new();
+    println!();
+    for (category, files) in &groups {
+        print!(
+            "Delete {} ({} items, {})? [Y/n] ",
+            category.display_name(),
+            files.len(),
+            format_size(files.iter().map(|f| f.size).sum())
+        );
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        // Closed stdin is a "no" to this group and every one after it.
+        if std::io::stdin().lock().read_line(&mut answer)? == 0 {
+            println!();
+            break;
+        }
+        if uninstaller::confirm_category(*category, &answer) {
+            confirmed.push(*category);
+        }
+    }
+    if !protected.is_empty() {
+        println!("Skipping {} protected items", protected.len());
+    }
+
+    Ok(related_files
+        .into_iter()
+        .filter(|f| f.category.is_protected() || confirmed.contains(&f.category))
+        .collect())
+}
+
 fn run_config(action: ConfigActions, mut config: Config) -> Result<()> {
     match action {
         ConfigActions::Show => {
             println!("Current configuration:");
             println!("  Min size: {}", format_size(config.scan.min_size_bytes));
-            println!("  Max depth: {}", config.scan.max_depth);
+            if config.scan.max_depth == 0 {
+                println!("  Max depth: unlimited (0)");
+            } else {
+                println!("  Max depth: {}", config.scan.max_depth);
+            }
+            println!("  Follow symlinks: {}", config.scan.follow_symlinks);
+            println!("  Include hidden: {}", config.scan.include_hidden);
+            println!("  Keep duplicate: {}", config.scan.duplicate_keep);
+            if config.scan.max_items == 0 {
+                println!("  Max items per scanner: unlimited (0)");
+            } else {
+                println!("  Max items per scanner: {}", config.scan.max_items);
+            }
+            for (id, limit) in &config.scan.max_items_per_scanner {
+                println!("    - {}: {}", id, limit);
+            }
+            match config.scan.scanner_timeout() {
+                Some(timeout) => println!("  Scanner timeout: {}s", timeout.as_secs()),
+                None => println!("  Scanner timeout: disabled (0)"),
+            }
+            if config.scan.enabled_scanners.is_empty() {
+                println!("  Enabled scanners: all");
+            } else {
+                println!(
+                    "  Enabled scanners: {}",
+                    config.scan.enabled_scanners.join(", ")
+                );
+            }
             println!("  Excluded paths:");
             for path in &config.scan.excluded_paths {
                 println!("    - {}", path);
             }
             println!("  Dry run by default: {}", config.clean.dry_run_by_default);
             println!("  Log history: {}", config.clean.log_history);
+            println!("  Clean Caution items: {}", config.clean.clean_caution);
+            if !config.clean.auto_confirm_categories.is_empty() {
+                println!(
+                    "  Unattended runs only clean: {}",
+                    config.clean.auto_confirm_categories.join(", ")
+                );
+            }
+            println!("  Theme: {}", config.theme.name.as_str());
+            if !config.retention.is_empty() {
+                println!("  Retention:");
+                for (id, days) in &config.retention {
+                    println!("    - {}: {} days", id, days);
+                }
+            }
         }
         ConfigActions::Set { key, value } => match key.as_str() {
             "min_size" => {
@@ -791,9 +2607,69 @@ fn run_config(action: ConfigActions, mut config: Config) -> Result<()> {
                 config.save()?;
                 println!("Set max_depth to {}", value);
             }
+            "follow_symlinks" => {
+                config.scan.follow_symlinks = value.parse()?;
+                config.save()?;
+                println!("Set follow_symlinks to {}", value);
+            }
+            "include_hidden" => {
+                config.scan.include_hidden = value.parse()?;
+                config.save()?;
+                println!("Set include_hidden to {}", value);
+            }
+            "duplicate_keep" => {
+                config.scan.duplicate_keep = value.parse().map_err(anyhow::Error::msg)?;
+                config.save()?;
+                println!("Set duplicate_keep to {}", value);
+            }
+            "max_items" => {
+                config.scan.max_items = value.parse()?;
+                config.save()?;
+                println!("Set max_items to {}", value);
+            }
+            "large_file_min_size" => {
+                config.scan.large_file_min_size = value.parse()?;
+                config.save()?;
+                println!("Set large_file_min_size to {}", value);
+            }
+            "large_file_min_age_days" => {
+                let days: i64 = value.parse()?;
+                if days < 0 {
+                    anyhow::bail!("large_file_min_age_days must not be negative");
+                }
+                config.scan.large_file_min_age_days = days as u64;
+                config.save()?;
+                println!("Set large_file_min_age_days to {}", value);
+            }
+            "large_file_max_results" => {
+                config.scan.large_file_max_results = value.parse()?;
+                config.save()?;
+                println!("Set large_file_max_results to {}", value);
+            }
+            "enabled_scanners" => {
+                let registry = PluginRegistry::from_config(&config);
+                let known: Vec<&str> = registry.scanners().iter().map(|s| s.id()).collect();
+                config.set_enabled_scanners(&value, &known)?;
+                config.save()?;
+                if config.scan.enabled_scanners.is_empty() {
+                    println!("Set enabled_scanners to all");
+                } else {
+                    println!(
+                        "Set enabled_scanners to {}",
+                        config.scan.enabled_scanners.join(", ")
+                    );
+                }
+            }
+            "clean_caution" => {
+                config.clean.clean_caution = value.parse()?;
+                config.save()?;
+                println!("Set clean_caution to {}", value);
+            }
             _ => {
                 println!("Unknown key: {}", key);
-                println!("Available keys: min_size, max_depth");
+                println!(
+                    "Available keys: min_size, max_depth, follow_symlinks, include_hidden, duplicate_keep, max_items, large_file_min_size, large_file_min_age_days, large_file_max_results, enabled_scanners, clean_caution"
+                );
             }
         },
         ConfigActions::AddExclude { path } => {
@@ -801,14 +2677,42 @@ fn run_config(action: ConfigActions, mut config: Config) -> Result<()> {
             config.save()?;
             println!("Added exclusion: {}", path);
         }
+        ConfigActions::Export { file } => match file {
+            Some(file) => {
+                config.export_to(Path::new(&file))?;
+                eprintln!("Exported configuration to {}", file);
+            }
+            None => print!("{}", config.to_toml()?),
+        },
+        ConfigActions::Import { file, merge } => {
+            let imported = config.import_from(Path::new(&file), merge)?;
+            imported.save()?;
+            println!(
+                "{} configuration from {}",
+                if merge { "Merged" } else { "Imported" },
+                file
+            );
+        }
     }
 
     Ok(())
 }
 
-fn run_history(limit: usize) -> Result<()> {
+fn run_history(
+    limit: usize,
+    format: OutputFormat,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> Result<()> {
+    let range = DateRange::parse(since, until)?;
     let logger = HistoryLogger::new();
-    let entries = logger.read_history(Some(limit))?;
+    let entries = logger.read_history_in_range(Some(limit), &range)?;
+    warn_skipped_history(&logger);
+
+    if let OutputFormat::Json = format {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
 
     if entries.is_empty() {
         println!("No history found.");
@@ -827,6 +2731,90 @@ fn run_history(limit: usize) -> Result<()> {
         if let Some(size) = entry.size {
             println!("    Size: {}", format_size(size));
         }
+        if let Some(run_id) = &entry.run_id {
+            println!("    Run: {}", run_id);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_undo(
+    run_id: Option<&str>,
+    count: Option<usize>,
+    since: Option<&str>,
+    config: &Config,
+) -> Result<()> {
+    let _lock = CleanLock::acquire()?;
+    let logger = HistoryLogger::new();
+    let entries = logger.read_history(None)?;
+    warn_skipped_history(&logger);
+    let pending = cleaner::pending_removals(&entries);
+
+    let (title, selected): (String, Vec<&HistoryEntry>) = if count.is_some() || since.is_some() {
+        let range = DateRange::parse(since, None)?;
+        let mut selected: Vec<_> = pending
+            .into_iter()
+            .filter(|e| range.contains(&e.timestamp))
+            .collect();
+        if let Some(count) = count {
+            selected = selected.split_off(selected.len().saturating_sub(count));
+        }
+        ("Undo".to_string(), selected)
+    } else {
+        let run_id = match run_id {
+            Some(id) if entries.iter().any(|e| e.run_id.as_deref() == Some(id)) => id,
+            Some(id) => anyhow::bail!("No run {} in history", id),
+            None => cleaner::last_run_id(&pending).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No clean run left to undo; older history has no run ids, use --count or --since"
+                )
+            })?,
+        };
+        let selected: Vec<_> = pending
+            .into_iter()
+            .filter(|e| e.run_id.as_deref() == Some(run_id))
+            .collect();
+        (format!("Undo run {}", run_id), selected)
+    };
+    if selected.is_empty() {
+        println!("Nothing to undo.");
+        return Ok(());
+    }
+
+    let result = cleaner::undo_entries(&selected, &cleaner::home_trash());
+    if config.clean.log_history {
+        for entry in &result.reversed {
+            if let Err(e) = logger.log_restore(entry) {
+                eprintln!("Warning: could not record restore in history: {}", e);
+            }
+        }
+    }
+
+    println!("{}\n", title);
+    println!("  Restored: {} items", result.restored.len());
+    let lists = [
+        ("Not in the Trash anymore", &result.missing),
+        (
+            "Left in the Trash (something else is at the original path)",
+            &result.occupied,
+        ),
+        ("Cannot restore (permanent delete)", &result.permanent),
+    ];
+    for (label, paths) in lists {
+        if paths.is_empty() {
+            continue;
+        }
+        println!("  {}: {} items", label, paths.len());
+        for path in paths {
+            println!("    - {}", path.display());
+        }
+    }
+    if !result.failed.is_empty() {
+        println!("  Failed: {} items", result.failed.len());
+        for (path, error) in &result.failed {
+            println!("    - {}: {}", path.display(), error);
+        }
     }
 
     Ok(())
@@ -1,3 +1,3 @@
 mod server;
 
-pub use server::run_mcp_server;
+pub use server::{run_mcp_server, run_mcp_tcp_server, AppOutput};
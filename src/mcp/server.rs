@@ -1,23 +1,48 @@
 use crate::config::Config;
 use crate::metadata;
 use crate::output::{
-    CategoryScanResult as JsonCategoryScanResult, ScanItem, ScanResult as JsonScanResult,
+    CategoryScanResult as JsonCategoryScanResult, RunMeta, ScanItem, ScanResult as JsonScanResult,
 };
-use crate::plugin::{PluginRegistry, ScanConfig};
+use crate::plugin::registry::ScanReport;
+use crate::plugin::{PluginRegistry, ScanConfig, ScanResult};
 use rmcp::{
-    ErrorData as McpError, ServerHandler, ServiceExt,
+    ErrorData as McpError, Peer, RoleServer, ServerHandler, ServiceExt,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
-    model::{CallToolResult, Content, ServerCapabilities, ServerInfo},
+    model::{
+        CallToolResult, Content, Meta, ProgressNotificationParam, ProgressToken,
+        ServerCapabilities, ServerInfo,
+    },
     schemars::{self, JsonSchema},
     tool, tool_handler, tool_router,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// How long scan_system and scan_status hold a call open before answering with
+// partial totals and a token to poll.
+const DEFAULT_WAIT_SECS: u64 = 10;
+// Longer waits are cut to this; a client can always poll scan_status again.
+const MAX_WAIT_SECS: u64 = 300;
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+// How long a finished scan waits for its final scan_status before it is dropped.
+const FINISHED_JOB_TTL: Duration = Duration::from_secs(600);
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ScanInput {
     #[serde(default)]
     pub categories: Option<Vec<String>>,
+    #[serde(default)]
+    pub wait_seconds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScanStatusInput {
+    pub token: String,
+    #[serde(default)]
+    pub wait_seconds: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -48,6 +73,11 @@ pub struct ScanOutput {
     pub categories: Vec<CategoryOutput>,
     pub total_size_bytes: u64,
     pub total_items: usize,
+    // false while the scan is still running: the totals so far count every item
+    // found, and `token` is what scan_status takes to fetch the rest.
+    pub complete: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
     pub cli_command: String,
 }
 
@@ -112,9 +142,184 @@ pub struct HistoryEntryOutput {
     pub size: Option<u64>,
 }
 
-#[derive(Debug, Clone)]
+fn wants_category(filter: &[String], scanner_id: &str) -> bool {
+    filter.is_empty()
+        || filter
+            .iter()
+            .any(|c| scanner_id.contains(&c.to_lowercase()))
+}
+
+fn scan_output(
+    categories: Vec<CategoryOutput>,
+    complete: bool,
+    token: Option<String>,
+) -> ScanOutput {
+    let total_size: u64 = categories.iter().map(|c| c.size_bytes).sum();
+    let total_items: usize = categories.iter().map(|c| c.item_count).sum();
+
+    ScanOutput {
+        categories,
+        total_size_bytes: total_size,
+        total_items,
+        complete,
+        token,
+        cli_command: "cleanmac scan --format json".to_string(),
+    }
+}
+
+// A scan_system run on its own thread. Items are tallied per scanner as the
+// item callback reports them, so a poll can answer before the scan finishes.
+struct ScanJob {
+    filter: Vec<String>,
+    names: HashMap<String, String>,
+    found: Mutex<BTreeMap<String, (u64, usize)>>,
+    result: Mutex<Option<Result<ScanOutput, String>>>,
+    finished_at: Mutex<Option<Instant>>,
+}
+
+impl ScanJob {
+    fn new(filter: Vec<String>, names: HashMap<String, String>) -> Self {
+        Self {
+            filter,
+            names,
+            found: Mutex::new(BTreeMap::new()),
+            result: Mutex::new(None),
+            finished_at: Mutex::new(None),
+        }
+    }
+
+    fn record(&self, item: &ScanResult) {
+        let Some(scanner_id) = item.metadata.get("scanner_id") else {
+            return;
+        };
+        let mut found = self.found.lock().unwrap();
+        let entry = found.entry(scanner_id.clone()).or_default();
+        entry.0 += item.size;
+        entry.1 += 1;
+    }
+
+    fn finish(&self, report: anyhow::Result<ScanReport>) {
+        let output = report.map(|report| {
+            let categories = report
+                .categories
+                .iter()
+                .filter(|cat| wants_category(&self.filter, &cat.scanner_id))
+                .map(|cat| CategoryOutput {
+                    id: cat.scanner_id.clone(),
+                    name: cat.name.clone(),
+                    size_bytes: cat.total_size(),
+                    item_count: cat.items.len(),
+                })
+                .collect();
+            scan_output(categories, true, None)
+        });
+        *self.result.lock().unwrap() = Some(output.map_err(|e| e.to_string()));
+        *self.finished_at.lock().unwrap() = Some(Instant::now());
+    }
+
+    fn expired(&self, now: Instant) -> bool {
+        self.finished_at
+            .lock()
+            .unwrap()
+            .is_some_and(|at| now.duration_since(at) > FINISHED_JOB_TTL)
+    }
+
+    fn is_done(&self) -> bool {
+        self.result.lock().unwrap().is_some()
+    }
+
+    fn items_found(&self) -> usize {
+        self.found
+            .lock()
+            .unwrap()
+            .values()
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    // Totals so far count every item found; the final result drops whatever
+    // max_items trims, so it can come out smaller.
+    fn partial(&self, token: &str) -> ScanOutput {
+        let categories = self
+            .found
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(id, _)| wants_category(&self.filter, id))
+            .map(|(id, (size, count))| CategoryOutput {
+                id: id.clone(),
+                name: self.names.get(id).cloned().unwrap_or_else(|| id.clone()),
+                size_bytes: *size,
+                item_count: *count,
+            })
+            .collect();
+        scan_output(categories, false, Some(token.to_string()))
+    }
+}
+
+fn wait_duration(wait_seconds: Option<u64>) -> Duration {
+    Duration::from_secs(wait_seconds.unwrap_or(DEFAULT_WAIT_SECS).min(MAX_WAIT_SECS))
+}
+
+// Waits for the job to finish or `wait` to pass, sending a progress notification
+// whenever the item count moves if the client asked for them.
+async fn wait_for_job(
+    job: &ScanJob,
+    wait: Duration,
+    progress: Option<(ProgressToken, Peer<RoleServer>)>,
+) {
+    let deadline = Instant::now() + wait;
+    let mut reported = 0;
+    while !job.is_done() && Instant::now() < deadline {
+        if let Some((token, peer)) = &progress {
+            let found = job.items_found();
+            if found != reported {
+                reported = found;
+                let _ = peer
+                    .notify_progress(ProgressNotificationParam {
+                        progress_token: token.clone(),
+                        progress: found as f64,
+                        total: None,
+                        message: Some(format!("{} items found", found)),
+                    })
+                    .await;
+            }
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[derive(Clone)]
 pub struct CleanMacServer {
     tool_router: ToolRouter<Self>,
+    scan_jobs: Arc<Mutex<HashMap<String, Arc<ScanJob>>>>,
+    next_token: Arc<AtomicU64>,
+}
+
+impl CleanMacServer {
+    // A finished job is forgotten once its final result has been handed out, so
+    // each token yields `complete: true` exactly once.
+    fn job_response(&self, token: &str, job: &ScanJob) -> Result<CallToolResult, McpError> {
+        let result = job.result.lock().unwrap().clone();
+        let output = match result {
+            Some(result) => {
+                self.scan_jobs.lock().unwrap().remove(token);
+                result.map_err(|e| McpError::internal_error(e, None))?
+            }
+            None => job.partial(token),
+        };
+        Ok(CallToolResult::success(vec![Content::json(output)?]))
+    }
+
+    // Drops finished jobs nobody came back for, so abandoned tokens don't
+    // pile up for the life of the server.
+    fn prune_jobs(&self) {
+        let now = Instant::now();
+        self.scan_jobs
+            .lock()
+            .unwrap()
+            .retain(|_, job| !job.expired(now));
+    }
 }
 
 #[tool_router]
@@ -122,60 +327,93 @@ impl CleanMacServer {
     pub fn new() -> Self {
         Self {
             tool_router: Self::tool_router(),
+            scan_jobs: Arc::new(Mutex::new(HashMap::new())),
+            next_token: Arc::new(AtomicU64::new(1)),
         }
     }
 
-    #[tool(description = "Scan the system for cleanable items")]
+    #[tool(
+        description = "Scan the system for cleanable items. Waits up to wait_seconds (default 10, at most 300); if the scan is still running it returns complete: false with partial totals and a token to pass to scan_status. Sends progress notifications when the request carries a progress token."
+    )]
     pub async fn scan_system(
         &self,
         input: Parameters<ScanInput>,
+        meta: Meta,
+        peer: Peer<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         let input = input.0;
         let config = Config::load().map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
-        let registry = PluginRegistry::default();
-        let scan_config = ScanConfig {
-            min_size: config.scan.min_size_bytes,
-            max_depth: config.scan.max_depth,
-            excluded_paths: config.scan.excluded_paths.iter().map(|s| s.into()).collect(),
-            progress_callback: None,
-            item_callback: None,
-        };
-
-        let report = registry
-            .scan_all(&scan_config)
-            .map_err(|e| McpError::internal_error(e.to_string(), None))?;
-
-        let filter_categories = input.categories.unwrap_or_default();
-
-        let categories: Vec<CategoryOutput> = report
-            .categories
+        let categories = input.categories.unwrap_or_default();
+        let mut registry = PluginRegistry::from_config(&config);
+        if categories.is_empty() {
+            registry.retain_enabled(&config);
+        }
+        let names = registry
+            .scanners()
             .iter()
-            .filter(|cat| {
-                filter_categories.is_empty()
-                    || filter_categories
-                        .iter()
-                        .any(|c| cat.scanner_id.contains(&c.to_lowercase()))
-            })
-            .map(|cat| CategoryOutput {
-                id: cat.scanner_id.clone(),
-                name: cat.name.clone(),
-                size_bytes: cat.total_size(),
-                item_count: cat.items.len(),
-            })
+            .map(|s| (s.id().to_string(), s.name().to_string()))
             .collect();
+        let job = Arc::new(ScanJob::new(categories, names));
 
-        let total_size: u64 = categories.iter().map(|c| c.size_bytes).sum();
-        let total_items: usize = categories.iter().map(|c| c.item_count).sum();
-
-        let output = ScanOutput {
-            categories,
-            total_size_bytes: total_size,
-            total_items,
-            cli_command: "cleanmac scan --format json".to_string(),
+        let sink = Arc::clone(&job);
+        let scan_config = ScanConfig {
+            item_callback: Some(Arc::new(move |item: ScanResult| sink.record(&item))),
+            ..ScanConfig::from_config(&config)
         };
 
-        Ok(CallToolResult::success(vec![Content::json(output)?]))
+        let token = format!("scan-{}", self.next_token.fetch_add(1, Ordering::SeqCst));
+        self.prune_jobs();
+        self.scan_jobs
+            .lock()
+            .unwrap()
+            .insert(token.clone(), Arc::clone(&job));
+
+        let worker = Arc::clone(&job);
+        std::thread::spawn(move || {
+            let scanned = registry.scan_all(&scan_config).map(|mut report| {
+                report.cap_items(&scan_config);
+                report
+            });
+            worker.finish(scanned)
+        });
+
+        let wait = wait_duration(input.wait_seconds);
+        let progress = meta.get_progress_token().map(|t| (t, peer));
+        wait_for_job(&job, wait, progress).await;
+
+        self.job_response(&token, &job)
+    }
+
+    #[tool(
+        description = "Check on a scan started by scan_system. Waits up to wait_seconds (default 10, at most 300) for it to finish; returns complete: false with the totals so far, or complete: true with the final totals, after which the token is no longer valid. A finished scan that is not collected within 10 minutes is dropped."
+    )]
+    pub async fn scan_status(
+        &self,
+        input: Parameters<ScanStatusInput>,
+        meta: Meta,
+        peer: Peer<RoleServer>,
+    ) -> Result<CallToolResult, McpError> {
+        let input = input.0;
+        self.prune_jobs();
+        let job = self
+            .scan_jobs
+            .lock()
+            .unwrap()
+            .get(&input.token)
+            .cloned()
+            .ok_or_else(|| {
+                McpError::invalid_params(
+                    format!("Unknown or already finished scan token: {}", input.token),
+                    None,
+                )
+            })?;
+
+        let wait = wait_duration(input.wait_seconds);
+        let progress = meta.get_progress_token().map(|t| (t, peer));
+        wait_for_job(&job, wait, progress).await;
+
+        self.job_response(&input.token, &job)
     }
 
     #[tool(description = "Scan a specific category for cleanable items with metadata")]
@@ -187,18 +425,13 @@ impl CleanMacServer {
         let config = Config::load().map_err(|e| McpError::internal_error(e.to_string(), None))?;
         let start = std::time::Instant::now();
 
-        let registry = PluginRegistry::default();
-        let scan_config = ScanConfig {
-            min_size: config.scan.min_size_bytes,
-            max_depth: config.scan.max_depth,
-            excluded_paths: config.scan.excluded_paths.iter().map(|s| s.into()).collect(),
-            progress_callback: None,
-            item_callback: None,
-        };
+        let registry = PluginRegistry::from_config(&config);
+        let scan_config = ScanConfig::from_config(&config);
 
-        let report = registry
+        let mut report = registry
             .scan_all(&scan_config)
             .map_err(|e| McpError::internal_error(e.to_string(), None))?;
+        report.cap_items(&scan_config);
 
         let collect_metadata = input.collect_metadata.unwrap_or(false);
         let categories: Vec<JsonCategoryScanResult> = report
@@ -220,11 +453,13 @@ impl CleanMacServer {
                         };
 
                         ScanItem {
+                            id: Some(item.id.clone()),
                             path: item.path.clone(),
                             size_bytes: item.size,
                             modified: item.last_modified.unwrap_or_else(chrono::Utc::now),
                             last_used,
                             use_count,
+                            safety_reason: Some(item.safety_reason().to_string()),
                         }
                     })
                     .collect();
@@ -235,12 +470,17 @@ impl CleanMacServer {
                     description: String::new(),
                     size_bytes: cat_result.total_size(),
                     item_count: items.len(),
+                    unique_size_bytes: None,
                     items,
+                    scan_duration_ms: cat_result.scan_duration.as_millis() as u64,
+                    unreadable_count: cat_result.unreadable,
+                    omitted_count: cat_result.omitted,
                 }
             })
             .collect();
 
-        let output = JsonScanResult::new(categories, start.elapsed().as_millis() as u64);
+        let output = JsonScanResult::new(categories, start.elapsed().as_millis() as u64)
+            .with_meta(RunMeta::from_config(&config));
         Ok(CallToolResult::success(vec![Content::json(output)?]))
     }
 
@@ -302,7 +542,7 @@ impl CleanMacServer {
             })
             .collect();
 
-        children_output.sort_by(|a, b| b.size_bytes.cmp(&a.size_bytes));
+        children_output.sort_by_key(|b| std::cmp::Reverse(b.size_bytes));
 
         let output = DiskAnalysisOutput {
             path: input.path,
@@ -366,14 +606,8 @@ impl CleanMacServer {
         let input = input.0;
         let config = Config::load().map_err(|e| McpError::internal_error(e.to_string(), None))?;
 
-        let registry = PluginRegistry::default();
-        let scan_config = ScanConfig {
-            min_size: config.scan.min_size_bytes,
-            max_depth: config.scan.max_depth,
-            excluded_paths: config.scan.excluded_paths.iter().map(|s| s.into()).collect(),
-            progress_callback: None,
-            item_callback: None,
-        };
+        let registry = PluginRegistry::from_config(&config);
+        let scan_config = ScanConfig::from_config(&config);
 
         let report = registry
             .scan_all(&scan_config)
@@ -409,6 +643,13 @@ impl CleanMacServer {
             }
         }
 
+        if crate::audit::is_active() {
+            warnings.insert(
+                "Audit mode is on: the CLI command will only preview, nothing can be deleted"
+                    .to_string(),
+            );
+        }
+
         let category_list = input.categories.join(",");
         let cli_command = format!("cleanmac apply --category {} --yes", category_list);
 
@@ -449,3 +690,94 @@ pub async fn run_mcp_server() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+// One server instance per connection; each client gets its own session.
+pub async fn run_mcp_tcp_server(addr: std::net::SocketAddr) -> anyhow::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    eprintln!("MCP server listening on tcp://{}", listener.local_addr()?);
+
+    loop {
+        // A failed accept (out of file descriptors, a client that reset
+        // early, ...) only loses that connection; back off a little so a
+        // persistent error doesn't spin.
+        let (stream, peer) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                eprintln!("MCP accept failed: {}", e);
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+        tokio::spawn(async move {
+            let (read, write) = stream.into_split();
+            match CleanMacServer::new().serve((read, write)).await {
+                Ok(service) => {
+                    let _ = service.waiting().await;
+                }
+                Err(e) => eprintln!("MCP session with {} failed: {}", peer, e),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::{SafetyLevel, ScannerCategory};
+    use std::path::PathBuf;
+
+    fn item(scanner_id: &str, size: u64) -> ScanResult {
+        let mut item = ScanResult::new(scanner_id, "item", PathBuf::from("/tmp/item"))
+            .with_size(size)
+            .with_category(ScannerCategory::System)
+            .with_safety(SafetyLevel::Safe);
+        item.metadata
+            .insert("scanner_id".to_string(), scanner_id.to_string());
+        item
+    }
+
+    #[test]
+    fn scan_job_reports_partial_totals_until_finished() {
+        let names = HashMap::from([("trash".to_string(), "Trash".to_string())]);
+        let job = ScanJob::new(vec!["trash".to_string()], names);
+        job.record(&item("trash", 10));
+        job.record(&item("trash", 5));
+        job.record(&item("browser_cache", 99));
+
+        let partial = job.partial("scan-1");
+        assert!(!partial.complete);
+        assert_eq!(partial.token.as_deref(), Some("scan-1"));
+        assert_eq!(partial.total_items, 2);
+        assert_eq!(partial.total_size_bytes, 15);
+        assert_eq!(partial.categories[0].name, "Trash");
+        assert!(!job.is_done());
+
+        job.finish(Ok(ScanReport {
+            categories: Vec::new(),
+            total_size: 0,
+            total_items: 0,
+            duration: Duration::ZERO,
+            timed_out: Vec::new(),
+        }));
+        let result = job.result.lock().unwrap().clone().unwrap().unwrap();
+        assert!(result.complete);
+        assert!(result.token.is_none());
+    }
+
+    #[test]
+    fn wait_seconds_are_capped() {
+        assert_eq!(wait_duration(None), Duration::from_secs(10));
+        assert_eq!(wait_duration(Some(u64::MAX)), Duration::from_secs(300));
+    }
+
+    #[test]
+    fn finished_jobs_expire_after_the_ttl() {
+        let job = ScanJob::new(Vec::new(), HashMap::new());
+        let later = Instant::now() + FINISHED_JOB_TTL + Duration::from_secs(1);
+        assert!(!job.expired(later));
+
+        job.finish(Err(anyhow::anyhow!("boom")));
+        assert!(!job.expired(Instant::now()));
+        assert!(job.expired(later));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::config::Config;
+use crate::plugin::PluginRegistry;
+use anyhow::{bail, Result};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+// Only ask when someone is there to answer: a first run (or --reconfigure)
+// with both ends of the terminal attached.
+pub fn should_prompt(first_run: bool, reconfigure: bool) -> bool {
+    (first_run || reconfigure) && io::stdin().is_terminal() && io::stdout().is_terminal()
+}
+
+// "1,3 4" -> [0, 2, 3]. Blank or "all" picks everything.
+pub fn parse_selection(input: &str, count: usize) -> Result<Vec<usize>> {
+    let input = input.trim();
+    if input.is_empty() || input.eq_ignore_ascii_case("all") {
+        return Ok((0..count).collect());
+    }
+
+    let mut picked = Vec::new();
+    for part in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        if part.is_empty() {
+            continue;
+        }
+        let n: usize = match part.parse() {
+            Ok(n) => n,
+            Err(_) => bail!("'{}' is not a number", part),
+        };
+        if n == 0 || n > count {
+            bail!("{} is out of range (1-{})", n, count);
+        }
+        if !picked.contains(&(n - 1)) {
+            picked.push(n - 1);
+        }
+    }
+    if picked.is_empty() {
+        bail!("pick at least one scanner");
+    }
+    picked.sort_unstable();
+    Ok(picked)
+}
+
+// Machine output, --execute, a single category or a missing terminal never
+// prompt; say so instead of dropping --reconfigure silently.
+pub fn warn_reconfigure_ignored() {
+    eprintln!(
+        "Note: --reconfigure ignored; it only prompts for a plain `scan` or `clean` in a terminal"
+    );
+}
+
+// Lists the scanners the policy allows, saves the picked ids as
+// [scan] enabled_scanners and returns once config.toml is written. Only that
+// key is written: `config` may carry one-off flags like --include-hidden.
+pub fn prompt_scanner_selection(config: &mut Config) -> Result<()> {
+    let registry = PluginRegistry::from_config(config);
+    let scanners: Vec<(String, String)> = registry
+        .scanners()
+        .iter()
+        .map(|s| (s.id().to_string(), s.name().to_string()))
+        .collect();
+    if scanners.is_empty() {
+        return Ok(());
+    }
+
+    println!("Which scanners should `scan` and `clean` run by default?\n");
+    for (i, (id, name)) in scanners.iter().enumerate() {
+        let mark = if config.enables_scanner(id) { "x" } else { " " };
+        println!("  [{}] {:>2}. {} ({})", mark, i + 1, name, id);
+    }
+    println!();
+
+    let stdin = io::stdin();
+    let picked = loop {
+        print!("Numbers separated by commas, Enter for all: ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        // Ctrl-D keeps every scanner rather than aborting the scan.
+        if stdin.lock().read_line(&mut line)? == 0 {
+            println!();
+            break (0..scanners.len()).collect::<Vec<_>>();
+        }
+        match parse_selection(&line, scanners.len()) {
+            Ok(picked) => break picked,
+            Err(e) => println!("{}", e),
+        }
+    };
+
+    // Everything picked is stored as "all", so scanners added later still run.
+    config.scan.enabled_scanners = if picked.len() == scanners.len() {
+        Vec::new()
+    } else {
+        picked.iter().map(|&i| scanners[i].0.clone()).collect()
+    };
+    let mut saved = Config::load()?;
+    saved.scan.enabled_scanners = config.scan.enabled_scanners.clone();
+    saved.save()?;
+
+    println!(
+        "Saved {} of {} scanners. Run with --reconfigure to change this.\n",
+        picked.len(),
+        scanners.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selection_accepts_commas_spaces_and_all() {
+        assert_eq!(parse_selection("", 3).unwrap(), vec![0, 1, 2]);
+        assert_eq!(parse_selection(" all\n", 3).unwrap(), vec![0, 1, 2]);
+        assert_eq!(parse_selection("3, 1 3", 3).unwrap(), vec![0, 2]);
+        assert!(parse_selection("0", 3).is_err());
+        assert!(parse_selection("4", 3).is_err());
+        assert!(parse_selection("two", 3).is_err());
+        assert!(parse_selection(",", 3).is_err());
+    }
+}
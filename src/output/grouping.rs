@@ -0,0 +1,180 @@
+use super::{CategoryScanResult, ScanItem, ScanResult};
+use std::collections::BTreeMap;
+use std::path::{Component, Path};
+
+const MB: u64 = 1024 * 1024;
+
+// (id, name, smallest size that lands here), largest bucket first.
+const SIZE_BUCKETS: [(&str, &str, u64); 3] = [
+    ("over_100mb", "Over 100 MB", 100 * MB),
+    ("10mb_to_100mb", "10-100 MB", 10 * MB),
+    ("under_10mb", "Under 10 MB", 0),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Grouping {
+    Path,
+    SizeBucket,
+}
+
+impl Grouping {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Grouping::Path => "path",
+            Grouping::SizeBucket => "size-bucket",
+        }
+    }
+}
+
+// Top-level directory an item lives under: the first folder below home
+// (~/Library) or below / (/private) for everything else.
+fn top_level_dir(path: &Path, home: &Path) -> String {
+    let (prefix, rest) = match path.strip_prefix(home) {
+        Ok(rest) => ("~/", rest),
+        Err(_) => ("/", path),
+    };
+    match rest
+        .components()
+        .find(|c| matches!(c, Component::Normal(_)))
+    {
+        Some(first) => format!("{}{}", prefix, first.as_os_str().to_string_lossy()),
+        None => prefix.trim_end_matches('/').to_string(),
+    }
+}
+
+fn size_bucket(size: u64) -> &'static str {
+    SIZE_BUCKETS
+        .iter()
+        .find(|(_, _, min)| size >= *min)
+        .map_or("under_10mb", |(id, _, _)| id)
+}
+
+fn group(id: String, name: String, items: Vec<ScanItem>) -> CategoryScanResult {
+    CategoryScanResult {
+        id,
+        name,
+        description: String::new(),
+        size_bytes: items.iter().map(|i| i.size_bytes).sum(),
+        item_count: items.len(),
+        unique_size_bytes: None,
+        items,
+        scan_duration_ms: 0,
+        unreadable_count: 0,
+        omitted_count: 0,
+    }
+}
+
+// Regroups the flat item set after scanning. Totals, warnings and the overall
+// unique size stay as scanned; items keep the order --sort gave them.
+pub fn regroup(scan: &mut ScanResult, grouping: Grouping, home: &Path) {
+    let items = std::mem::take(&mut scan.categories)
+        .into_iter()
+        .flat_map(|c| c.items);
+
+    scan.categories = match grouping {
+        Grouping::Path => {
+            let mut by_dir: BTreeMap<String, Vec<ScanItem>> = BTreeMap::new();
+            for item in items {
+                by_dir
+                    .entry(top_level_dir(&item.path, home))
+                    .or_default()
+                    .push(item);
+            }
+            let mut groups: Vec<_> = by_dir
+                .into_iter()
+                .map(|(dir, items)| group(dir.clone(), dir, items))
+                .collect();
+            groups.sort_by_key(|g| std::cmp::Reverse(g.size_bytes));
+            groups
+        }
+        Grouping::SizeBucket => {
+            let mut by_bucket: BTreeMap<&str, Vec<ScanItem>> = BTreeMap::new();
+            for item in items {
+                by_bucket
+                    .entry(size_bucket(item.size_bytes))
+                    .or_default()
+                    .push(item);
+            }
+            SIZE_BUCKETS
+                .iter()
+                .filter_map(|(id, name, _)| {
+                    by_bucket
+                        .remove(id)
+                        .map(|items| group(id.to_string(), name.to_string(), items))
+                })
+                .collect()
+        }
+    };
+    scan.grouped_by = Some(grouping.as_str().to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn item(path: &str, size: u64) -> ScanItem {
+        ScanItem {
+            id: None,
+            path: PathBuf::from(path),
+            size_bytes: size,
+            modified: Utc::now(),
+            last_used: None,
+            use_count: None,
+            safety_reason: None,
+        }
+    }
+
+    fn scan() -> ScanResult {
+        let caches = group(
+            "system_caches".to_string(),
+            "System Caches".to_string(),
+            vec![
+                item("/Users/me/Library/Caches/a", 200 * MB),
+                item("/Library/Caches/b", 5 * MB),
+            ],
+        );
+        let dev = group(
+            "dev_junk".to_string(),
+            "Development Junk".to_string(),
+            vec![
+                item("/Users/me/code/app/node_modules", 50 * MB),
+                item("/Users/me/Library/Developer/x", 10 * MB),
+            ],
+        );
+        ScanResult::new(vec![caches, dev], 0)
+    }
+
+    #[test]
+    fn regroups_by_top_level_dir_and_size_bucket() {
+        let home = Path::new("/Users/me");
+
+        let mut by_path = scan();
+        regroup(&mut by_path, Grouping::Path, home);
+        let dirs: Vec<(&str, usize)> = by_path
+            .categories
+            .iter()
+            .map(|c| (c.id.as_str(), c.item_count))
+            .collect();
+        assert_eq!(dirs, vec![("~/Library", 2), ("~/code", 1), ("/Library", 1)]);
+        assert_eq!(by_path.total_size_bytes, 265 * MB);
+        assert_eq!(by_path.grouped_by.as_deref(), Some("path"));
+
+        let mut by_size = scan();
+        regroup(&mut by_size, Grouping::SizeBucket, home);
+        let buckets: Vec<(&str, u64)> = by_size
+            .categories
+            .iter()
+            .map(|c| (c.id.as_str(), c.size_bytes))
+            .collect();
+        assert_eq!(
+            buckets,
+            vec![
+                ("over_100mb", 200 * MB),
+                ("10mb_to_100mb", 60 * MB),
+                ("under_10mb", 5 * MB)
+            ]
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use super::ScanResult;
+use crate::utils::format_size;
+use std::io::{self, Write};
+
+// How many items per category the human summary lists. Everything goes out
+// when stdout isn't a terminal, so a redirected report never drops items.
+pub fn items_shown(configured: usize, interactive: bool) -> Option<usize> {
+    (interactive && configured > 0).then_some(configured)
+}
+
+pub fn write_scan_summary(
+    out: &mut impl Write,
+    scan_result: &ScanResult,
+    shown: Option<usize>,
+    timing: bool,
+) -> io::Result<()> {
+    for cat_result in &scan_result.categories {
+        writeln!(out, "{}:", cat_result.name)?;
+        if cat_result.omitted_count > 0 {
+            writeln!(
+                out,
+                "  Items: showing top {} of {}",
+                cat_result.item_count,
+                cat_result.item_count + cat_result.omitted_count
+            )?;
+        } else {
+            writeln!(out, "  Items: {}", cat_result.item_count)?;
+        }
+        writeln!(out, "  Size: {}", format_size(cat_result.size_bytes))?;
+        if let Some(unique) = cat_result.unique_size_bytes {
+            writeln!(out, "  Unique reclaimable: {}", format_size(unique))?;
+        }
+        writeln!(out)?;
+
+        let limit = shown.unwrap_or(usize::MAX);
+        for item in cat_result.items.iter().take(limit) {
+            writeln!(
+                out,
+                "  - {} ({})",
+                item.path.display(),
+                format_size(item.size_bytes)
+            )?;
+        }
+
+        if cat_result.items.len() > limit {
+            writeln!(out, "  ... and {} more", cat_result.items.len() - limit)?;
+        }
+        writeln!(out)?;
+    }
+
+    writeln!(
+        out,
+        "Total: {} items, {} (in {}ms)",
+        scan_result.total_item_count,
+        format_size(scan_result.total_size_bytes),
+        scan_result.scan_duration_ms
+    )?;
+    if let Some(unique) = scan_result.unique_size_bytes {
+        writeln!(
+            out,
+            "Nominal: {}, Unique reclaimable: {} (hard links and APFS clones counted once)",
+            format_size(scan_result.total_size_bytes),
+            format_size(unique)
+        )?;
+    }
+
+    if timing {
+        let mut timings: Vec<_> = scan_result
+            .categories
+            .iter()
+            .map(|c| (c.name.as_str(), c.scan_duration_ms))
+            .collect();
+        timings.sort_by_key(|(_, ms)| std::cmp::Reverse(*ms));
+
+        writeln!(out, "\nTiming (slowest first):")?;
+        for (name, ms) in timings {
+            writeln!(out, "  {:<24} {:>8}ms", name, ms)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{CategoryScanResult, ScanItem};
+    use chrono::Utc;
+    use std::path::PathBuf;
+
+    fn scan_with(items: usize) -> ScanResult {
+        let items: Vec<ScanItem> = (0..items)
+            .map(|i| ScanItem {
+                id: None,
+                path: PathBuf::from(format!("/tmp/item-{}", i)),
+                size_bytes: 1024,
+                modified: Utc::now(),
+                last_used: None,
+                use_count: None,
+                safety_reason: None,
+            })
+            .collect();
+        let category = CategoryScanResult {
+            id: "caches".to_string(),
+            name: "Caches".to_string(),
+            description: String::new(),
+            size_bytes: 1024 * items.len() as u64,
+            item_count: items.len(),
+            unique_size_bytes: None,
+            items,
+            scan_duration_ms: 0,
+            unreadable_count: 0,
+            omitted_count: 0,
+        };
+        ScanResult::new(vec![category], 0)
+    }
+
+    fn render(scan: &ScanResult, shown: Option<usize>) -> String {
+        let mut out = Vec::new();
+        write_scan_summary(&mut out, scan, shown, false).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn redirected_output_lists_every_item() {
+        let scan = scan_with(15);
+
+        let shown = items_shown(10, false);
+        assert_eq!(shown, None);
+        let full = render(&scan, shown);
+        assert_eq!(full.matches("  - /tmp/item-").count(), 15);
+        assert!(!full.contains("more"));
+
+        let truncated = render(&scan, items_shown(10, true));
+        assert_eq!(truncated.matches("  - /tmp/item-").count(), 10);
+        assert!(truncated.contains("... and 5 more"));
+
+        assert_eq!(items_shown(0, true), None);
+    }
+}
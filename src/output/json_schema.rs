@@ -1,8 +1,14 @@
-use chrono::{DateTime, Utc};
+use crate::config::Config;
+use crate::plugin::registry::{omitted_warning, unreadable_warning};
+use crate::plugin::{SafetyLevel, ScannerCategory, TrashMode};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+pub const SCHEMA_VERSION: &str = "1.0";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ScanResult {
     pub version: String,
     pub timestamp: DateTime<Utc>,
@@ -10,20 +16,112 @@ pub struct ScanResult {
     pub total_size_bytes: u64,
     pub total_item_count: usize,
     pub scan_duration_ms: u64,
+    // Only with `scan --unique-size`: what deleting would really free.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique_size_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    // Set by `scan --group-by path|size-bucket`; `categories` then holds those
+    // groups instead of scanners.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grouped_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RunMeta>,
+}
+
+// How a scan, plan or execution was produced. Absent in files written before
+// it existed, and in results built without a config (tests, conversions).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunMeta {
+    pub cleanmac_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_version: Option<String>,
+    pub config: RunConfig,
+}
+
+// The [scan] settings that decide what a run finds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunConfig {
+    pub min_size_bytes: u64,
+    pub max_depth: usize,
+    pub excluded_paths: Vec<String>,
+    // Empty means every scanner.
+    pub enabled_scanners: Vec<String>,
+}
+
+impl RunMeta {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            cleanmac_version: env!("CARGO_PKG_VERSION").to_string(),
+            os_version: crate::utils::os_version(),
+            config: RunConfig {
+                min_size_bytes: config.scan.min_size_bytes,
+                max_depth: config.scan.max_depth,
+                excluded_paths: config.scan.excluded_paths.clone(),
+                enabled_scanners: config.scan.enabled_scanners.clone(),
+            },
+        }
+    }
+}
+
+// `scan --explain <id>`: everything cleanmac knows about why one item was flagged.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ExplainResult {
+    pub version: String,
+    pub id: String,
+    pub name: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub scanner_id: String,
+    pub scanner_name: String,
+    pub safety_level: String,
+    pub safety_reason: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+// `scan --brief`: totals only, for prompts and status bars.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BriefScanResult {
+    pub version: String,
+    pub category_count: usize,
+    pub total_item_count: usize,
+    pub total_size_bytes: u64,
+    pub scan_duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_scanners: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CategoryScanResult {
     pub id: String,
     pub name: String,
     pub description: String,
     pub size_bytes: u64,
     pub item_count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unique_size_bytes: Option<u64>,
     pub items: Vec<ScanItem>,
+    #[serde(default)]
+    pub scan_duration_ms: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub unreadable_count: usize,
+    // Items past the scanner's max_items; `items` holds the largest item_count of them.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub omitted_count: usize,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+fn is_zero<T: Default + PartialEq>(n: &T) -> bool {
+    *n == T::default()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ScanItem {
+    // Pass to `scan --explain`. Missing in files written before it existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub path: PathBuf,
     pub size_bytes: u64,
     pub modified: DateTime<Utc>,
@@ -31,9 +129,11 @@ pub struct ScanItem {
     pub last_used: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub use_count: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safety_reason: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PlanResult {
     pub version: String,
     pub timestamp: DateTime<Utc>,
@@ -41,22 +141,45 @@ pub struct PlanResult {
     pub categories: Vec<CategoryPlanResult>,
     pub total_size_bytes: u64,
     pub warnings: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RunMeta>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CategoryPlanResult {
     pub id: String,
     pub action: String,
     pub items: Vec<PlanItem>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PlanItem {
     pub path: PathBuf,
     pub size_bytes: u64,
+    // The scanner's verdict; absent in plans made from a saved scan or
+    // written before plans carried it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safety_level: Option<SafetyLevel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<ScannerCategory>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrendReport {
+    pub version: String,
+    pub total_freed_bytes: u64,
+    pub points: Vec<TrendPointOutput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrendPointOutput {
+    pub date: NaiveDate,
+    pub freed_bytes: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reclaimable_bytes: Option<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ExecutionResult {
     pub version: String,
     pub timestamp: DateTime<Utc>,
@@ -64,25 +187,84 @@ pub struct ExecutionResult {
     pub status: ExecutionStatus,
     pub categories: Vec<CategoryExecutionResult>,
     pub total_deleted_size: u64,
+    // Not in `total_deleted_size`: still on disk until the Trash is emptied.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub total_moved_to_trash: u64,
     pub duration_ms: u64,
+    // Set by `clean -F json` without --execute; nothing was deleted.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub dry_run: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RunMeta>,
+}
+
+fn is_false(b: &bool) -> bool {
+    !*b
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct CategoryExecutionResult {
     pub id: String,
     pub status: ExecutionStatus,
     pub deleted_count: usize,
     pub deleted_size_bytes: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub moved_to_trash_bytes: u64,
     pub failed_count: usize,
     pub failed_items: Vec<FailedItem>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_items: Vec<SkippedItem>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub already_gone: Vec<PathBuf>,
+    // Absent in files written before cleaned items went to the Trash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trash_mode: Option<TrashMode>,
+    // Meant for the Trash but deleted outright.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deleted_permanently: Vec<PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive: Option<ArchiveInfo>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<CommandResult>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// A maintenance task that ran: what it printed, how it exited and, for tasks
+// that clear a cache, how much free space it made.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommandResult {
+    pub task: String,
+    pub command: String,
+    pub exit_code: Option<i32>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stdout: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub stderr: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freed_bytes: Option<u64>,
+}
+
+// Set when `clean --archive` packed the items before removing them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ArchiveInfo {
+    pub path: PathBuf,
+    pub original_size_bytes: u64,
+    pub compressed_size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct FailedItem {
     pub path: PathBuf,
     pub error: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SkippedItem {
+    pub path: PathBuf,
+    pub reason: String,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecutionStatus {
@@ -92,20 +274,133 @@ pub enum ExecutionStatus {
     Cancelled,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InfoResult {
+    pub version: String,
+    pub schema_version: String,
+    pub output_formats: Vec<String>,
+    pub report_formats: Vec<String>,
+    pub scanners: Vec<ScannerInfo>,
+    pub integrations: Vec<IntegrationInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ScannerInfo {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub default_safety: String,
+    pub available: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct IntegrationInfo {
+    pub name: String,
+    pub available: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<PathBuf>,
+    // Why a present tool still can't be fully used, e.g. automation denied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelatedFilesResult {
+    pub version: String,
+    pub app: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    pub related: Vec<RelatedFileInfo>,
+    pub total_size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelatedFileInfo {
+    pub path: PathBuf,
+    pub category: String,
+    pub size_bytes: u64,
+    pub protected: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UninstallPreviewResult {
+    pub version: String,
+    pub app: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bundle_id: Option<String>,
+    pub groups: Vec<UninstallPreviewGroup>,
+    pub protected: Vec<RelatedFileInfo>,
+    pub total_size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UninstallPreviewGroup {
+    pub category: String,
+    pub size_bytes: u64,
+    pub files: Vec<RelatedFileInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DuplicateAppsResult {
+    pub version: String,
+    pub groups: Vec<DuplicateAppGroup>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DuplicateAppGroup {
+    pub bundle_id: String,
+    pub copies: Vec<AppCopyInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AppCopyInfo {
+    pub name: String,
+    pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app_version: Option<String>,
+    pub size_bytes: u64,
+    #[serde(default)]
+    pub older: bool,
+}
+
 impl ScanResult {
     pub fn new(categories: Vec<CategoryScanResult>, duration_ms: u64) -> Self {
         let total_size_bytes = categories.iter().map(|c| c.size_bytes).sum();
         let total_item_count = categories.iter().map(|c| c.item_count).sum();
+        let warnings = categories
+            .iter()
+            .filter(|c| c.unreadable_count > 0)
+            .map(|c| unreadable_warning(&c.name, c.unreadable_count))
+            .chain(
+                categories
+                    .iter()
+                    .filter(|c| c.omitted_count > 0)
+                    .map(|c| omitted_warning(&c.id, &c.name, c.item_count, c.omitted_count)),
+            )
+            .collect();
 
         Self {
-            version: "1.0".to_string(),
+            version: SCHEMA_VERSION.to_string(),
             timestamp: Utc::now(),
             categories,
             total_size_bytes,
             total_item_count,
             scan_duration_ms: duration_ms,
+            unique_size_bytes: None,
+            warnings,
+            grouped_by: None,
+            meta: None,
         }
     }
+
+    pub fn with_meta(mut self, meta: RunMeta) -> Self {
+        self.meta = Some(meta);
+        self
+    }
 }
 
 impl PlanResult {
@@ -116,12 +411,13 @@ impl PlanResult {
             .sum();
 
         Self {
-            version: "1.0".to_string(),
+            version: SCHEMA_VERSION.to_string(),
             timestamp: Utc::now(),
             scan_file,
             categories,
             total_size_bytes,
             warnings: Vec::new(),
+            meta: None,
         }
     }
 }
@@ -133,6 +429,7 @@ impl ExecutionResult {
         duration_ms: u64,
     ) -> Self {
         let total_deleted_size = categories.iter().map(|c| c.deleted_size_bytes).sum();
+        let total_moved_to_trash = categories.iter().map(|c| c.moved_to_trash_bytes).sum();
         let status = if categories
             .iter()
             .all(|c| c.status == ExecutionStatus::Success)
@@ -148,13 +445,198 @@ impl ExecutionResult {
         };
 
         Self {
-            version: "1.0".to_string(),
+            version: SCHEMA_VERSION.to_string(),
             timestamp: Utc::now(),
             plan_file,
             status,
             categories,
             total_deleted_size,
+            total_moved_to_trash,
             duration_ms,
+            dry_run: false,
+            meta: None,
+        }
+    }
+
+    // A dry run only says what would go, so it reports nothing as deleted.
+    pub fn into_dry_run(mut self) -> Self {
+        for category in &mut self.categories {
+            category.deleted_count = 0;
+            category.deleted_size_bytes = 0;
+            category.moved_to_trash_bytes = 0;
         }
+        self.total_deleted_size = 0;
+        self.total_moved_to_trash = 0;
+        self.dry_run = true;
+        self
+    }
+
+    pub fn with_meta(mut self, meta: RunMeta) -> Self {
+        self.meta = Some(meta);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+
+    fn roundtrip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &T) {
+        let json = serde_json::to_string_pretty(value).unwrap();
+        let parsed: T = serde_json::from_str(&json).unwrap();
+        assert_eq!(&parsed, value);
+    }
+
+    #[test]
+    fn scan_result_roundtrips() {
+        roundtrip(&ScanResult::new(
+            vec![CategoryScanResult {
+                id: "system_caches".to_string(),
+                name: "System Caches".to_string(),
+                description: String::new(),
+                size_bytes: 2048,
+                item_count: 2,
+                unique_size_bytes: Some(1024),
+                items: vec![
+                    ScanItem {
+                        id: Some("cache_0123456789ab".to_string()),
+                        path: PathBuf::from("/tmp/a"),
+                        size_bytes: 1024,
+                        modified: Utc::now(),
+                        last_used: Some(Utc::now()),
+                        use_count: Some(3),
+                        safety_reason: Some("App cache; apps rebuild it on demand".to_string()),
+                    },
+                    ScanItem {
+                        id: None,
+                        path: PathBuf::from("/tmp/b"),
+                        size_bytes: 1024,
+                        modified: Utc::now(),
+                        last_used: None,
+                        use_count: None,
+                        safety_reason: None,
+                    },
+                ],
+                scan_duration_ms: 12,
+                unreadable_count: 1204,
+                omitted_count: 3,
+            }],
+            42,
+        ));
+    }
+
+    #[test]
+    fn plan_result_roundtrips() {
+        let mut plan = PlanResult::new(
+            vec![CategoryPlanResult {
+                id: "system_logs".to_string(),
+                action: "delete".to_string(),
+                items: vec![PlanItem {
+                    path: PathBuf::from("/tmp/log"),
+                    size_bytes: 512,
+                    safety_level: Some(SafetyLevel::Caution),
+                    category: Some(ScannerCategory::System),
+                }],
+            }],
+            Some("scan.json".to_string()),
+        );
+        plan.warnings.push("check me".to_string());
+        roundtrip(&plan);
+    }
+
+    #[test]
+    fn execution_result_roundtrips() {
+        roundtrip(&ExecutionResult::new(
+            Some("plan.json".to_string()),
+            vec![CategoryExecutionResult {
+                id: "all".to_string(),
+                status: ExecutionStatus::Partial,
+                deleted_count: 1,
+                deleted_size_bytes: 512,
+                moved_to_trash_bytes: 1024,
+                failed_count: 1,
+                failed_items: vec![FailedItem {
+                    path: PathBuf::from("/tmp/locked"),
+                    error: "Permission denied".to_string(),
+                }],
+                skipped_items: vec![SkippedItem {
+                    path: PathBuf::from("/tmp/open"),
+                    reason: "open_by_process".to_string(),
+                    metadata: HashMap::from([("process".to_string(), "Chrome".to_string())]),
+                }],
+                already_gone: vec![PathBuf::from("/tmp/gone")],
+                trash_mode: Some(TrashMode::MoveToTrash),
+                deleted_permanently: vec![PathBuf::from("/Volumes/USB/old.iso")],
+                archive: None,
+                commands: vec![CommandResult {
+                    task: "Clear Font Cache".to_string(),
+                    command: "atsutil databases -remove".to_string(),
+                    exit_code: Some(0),
+                    stdout: "Removing font databases".to_string(),
+                    stderr: String::new(),
+                    freed_bytes: Some(4096),
+                }],
+            }],
+            10,
+        ));
+    }
+
+    #[test]
+    fn dry_run_reports_nothing_deleted() {
+        let exec = ExecutionResult::new(
+            None,
+            vec![CategoryExecutionResult {
+                id: "all".to_string(),
+                status: ExecutionStatus::Success,
+                deleted_count: 3,
+                deleted_size_bytes: 2048,
+                moved_to_trash_bytes: 0,
+                failed_count: 0,
+                failed_items: Vec::new(),
+                skipped_items: Vec::new(),
+                already_gone: Vec::new(),
+                trash_mode: None,
+                deleted_permanently: Vec::new(),
+                archive: None,
+                commands: Vec::new(),
+            }],
+            10,
+        )
+        .into_dry_run();
+
+        assert!(exec.dry_run);
+        assert_eq!(exec.total_deleted_size, 0);
+        assert_eq!(exec.categories[0].deleted_count, 0);
+        assert_eq!(exec.categories[0].deleted_size_bytes, 0);
+        roundtrip(&exec);
+    }
+
+    #[test]
+    fn run_meta_roundtrips_and_older_files_still_parse() {
+        let mut config = Config::default();
+        config.scan.max_depth = 5;
+        config.scan.excluded_paths = vec!["/Users/me/keep".to_string()];
+        config.scan.enabled_scanners = vec!["trash".to_string()];
+
+        let scan = ScanResult::new(Vec::new(), 3).with_meta(RunMeta::from_config(&config));
+        roundtrip(&scan);
+
+        let json: serde_json::Value = serde_json::to_value(&scan).unwrap();
+        let meta = &json["meta"];
+        assert_eq!(meta["cleanmac_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(meta["config"]["min_size_bytes"], 1024 * 1024);
+        assert_eq!(meta["config"]["max_depth"], 5);
+        assert_eq!(meta["config"]["excluded_paths"][0], "/Users/me/keep");
+        assert_eq!(meta["config"]["enabled_scanners"][0], "trash");
+
+        let mut older = json.clone();
+        older.as_object_mut().unwrap().remove("meta");
+        let parsed: ScanResult = serde_json::from_value(older).unwrap();
+        assert_eq!(parsed.meta, None);
+
+        roundtrip(
+            &ExecutionResult::new(None, Vec::new(), 1).with_meta(RunMeta::from_config(&config)),
+        );
     }
 }
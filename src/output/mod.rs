@@ -1,3 +1,9 @@
+mod grouping;
+mod human;
 mod json_schema;
+mod validate;
 
+pub use grouping::{regroup, Grouping};
+pub use human::{items_shown, write_scan_summary};
 pub use json_schema::*;
+pub use validate::validate_json;
@@ -0,0 +1,277 @@
+use super::json_schema::{ExecutionResult, PlanResult, ScanResult, SCHEMA_VERSION};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SchemaKind {
+    Scan,
+    Plan,
+    Execution,
+}
+
+impl SchemaKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            SchemaKind::Scan => "scan",
+            SchemaKind::Plan => "plan",
+            SchemaKind::Execution => "execution",
+        }
+    }
+
+    fn required_fields(&self) -> &'static [&'static str] {
+        match self {
+            SchemaKind::Scan => &[
+                "version",
+                "timestamp",
+                "categories",
+                "total_size_bytes",
+                "total_item_count",
+                "scan_duration_ms",
+            ],
+            SchemaKind::Plan => &[
+                "version",
+                "timestamp",
+                "scan_file",
+                "categories",
+                "total_size_bytes",
+                "warnings",
+            ],
+            SchemaKind::Execution => &[
+                "version",
+                "timestamp",
+                "plan_file",
+                "status",
+                "categories",
+                "total_deleted_size",
+                "duration_ms",
+            ],
+        }
+    }
+
+    fn category_fields(&self) -> &'static [&'static str] {
+        match self {
+            SchemaKind::Scan => &[
+                "id",
+                "name",
+                "description",
+                "size_bytes",
+                "item_count",
+                "items",
+            ],
+            SchemaKind::Plan => &["id", "action", "items"],
+            SchemaKind::Execution => &[
+                "id",
+                "status",
+                "deleted_count",
+                "deleted_size_bytes",
+                "failed_count",
+                "failed_items",
+            ],
+        }
+    }
+
+    fn item_fields(&self) -> &'static [&'static str] {
+        match self {
+            SchemaKind::Scan => &["path", "size_bytes", "modified"],
+            SchemaKind::Plan => &["path", "size_bytes"],
+            SchemaKind::Execution => &[],
+        }
+    }
+
+    // Each schema has at least one top-level field the others do not.
+    fn detect(root: &serde_json::Map<String, Value>) -> Option<Self> {
+        if root.contains_key("scan_duration_ms") || root.contains_key("total_item_count") {
+            Some(SchemaKind::Scan)
+        } else if root.contains_key("plan_file") || root.contains_key("total_deleted_size") {
+            Some(SchemaKind::Execution)
+        } else if root.contains_key("scan_file") || root.contains_key("warnings") {
+            Some(SchemaKind::Plan)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ValidationReport {
+    pub schema: Option<SchemaKind>,
+    pub version: Option<String>,
+    pub problems: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.schema.is_some() && self.problems.is_empty()
+    }
+}
+
+pub fn validate_json(content: &str) -> ValidationReport {
+    let mut report = ValidationReport {
+        schema: None,
+        version: None,
+        problems: Vec::new(),
+    };
+
+    let value: Value = match serde_json::from_str(content) {
+        Ok(v) => v,
+        Err(e) => {
+            report.problems.push(format!("not valid JSON: {}", e));
+            return report;
+        }
+    };
+
+    let Some(root) = value.as_object() else {
+        report
+            .problems
+            .push("top-level value is not an object".to_string());
+        return report;
+    };
+
+    let Some(kind) = SchemaKind::detect(root) else {
+        report
+            .problems
+            .push("does not look like a scan, plan or execution result".to_string());
+        return report;
+    };
+    report.schema = Some(kind);
+
+    report.version = root
+        .get("version")
+        .and_then(|v| v.as_str())
+        .map(str::to_string);
+    if let Some(version) = &report.version {
+        if major(version) != major(SCHEMA_VERSION) {
+            report.problems.push(format!(
+                "incompatible version {} (supported: {})",
+                version, SCHEMA_VERSION
+            ));
+        }
+    }
+
+    check_fields(root, kind.required_fields(), "", &mut report.problems);
+
+    let categories = root.get("categories").and_then(|c| c.as_array());
+    for (i, category) in categories.into_iter().flatten().enumerate() {
+        let prefix = format!("categories[{}].", i);
+        let Some(category) = category.as_object() else {
+            report
+                .problems
+                .push(format!("{} is not an object", prefix.trim_end_matches('.')));
+            continue;
+        };
+        check_fields(
+            category,
+            kind.category_fields(),
+            &prefix,
+            &mut report.problems,
+        );
+
+        let items = category.get("items").and_then(|c| c.as_array());
+        for (j, item) in items.into_iter().flatten().enumerate() {
+            if let Some(item) = item.as_object() {
+                let prefix = format!("{}items[{}].", prefix, j);
+                check_fields(item, kind.item_fields(), &prefix, &mut report.problems);
+            }
+        }
+    }
+
+    // Field presence is fine but types may still be off; let serde have the final word.
+    if report.problems.is_empty() {
+        let typed = match kind {
+            SchemaKind::Scan => parse_error::<ScanResult>(content),
+            SchemaKind::Plan => parse_error::<PlanResult>(content),
+            SchemaKind::Execution => parse_error::<ExecutionResult>(content),
+        };
+        report.problems.extend(typed);
+    }
+
+    report
+}
+
+fn check_fields(
+    object: &serde_json::Map<String, Value>,
+    fields: &[&str],
+    prefix: &str,
+    problems: &mut Vec<String>,
+) {
+    for field in fields {
+        if !object.contains_key(*field) {
+            problems.push(format!("missing field: {}{}", prefix, field));
+        }
+    }
+}
+
+fn parse_error<T: DeserializeOwned>(content: &str) -> Option<String> {
+    serde_json::from_str::<T>(content)
+        .err()
+        .map(|e| format!("schema mismatch: {}", e))
+}
+
+fn major(version: &str) -> &str {
+    version.split('.').next().unwrap_or(version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::{CategoryPlanResult, PlanItem};
+    use std::path::PathBuf;
+
+    #[test]
+    fn recognises_generated_plan() {
+        let plan = PlanResult::new(
+            vec![CategoryPlanResult {
+                id: "system_caches".to_string(),
+                action: "delete".to_string(),
+                items: vec![PlanItem {
+                    path: PathBuf::from("/tmp/a"),
+                    size_bytes: 1,
+                    safety_level: None,
+                    category: None,
+                }],
+            }],
+            None,
+        );
+        let report = validate_json(&serde_json::to_string(&plan).unwrap());
+
+        assert_eq!(report.schema, Some(SchemaKind::Plan));
+        assert_eq!(report.version.as_deref(), Some(SCHEMA_VERSION));
+        assert!(report.is_valid(), "{:?}", report.problems);
+    }
+
+    #[test]
+    fn reports_missing_fields_and_version_drift() {
+        let content = r#"{
+            "version": "2.0",
+            "timestamp": "2026-02-19T10:30:00Z",
+            "categories": [{"id": "logs", "name": "Logs", "size_bytes": 1, "item_count": 1,
+                            "items": [{"path": "/tmp/x", "modified": "2026-02-19T10:30:00Z"}]}],
+            "total_size_bytes": 1,
+            "total_item_count": 1
+        }"#;
+        let report = validate_json(content);
+
+        assert_eq!(report.schema, Some(SchemaKind::Scan));
+        assert!(!report.is_valid());
+        assert!(report
+            .problems
+            .iter()
+            .any(|p| p.contains("incompatible version 2.0")));
+        assert!(report
+            .problems
+            .contains(&"missing field: scan_duration_ms".to_string()));
+        assert!(report
+            .problems
+            .contains(&"missing field: categories[0].description".to_string()));
+        assert!(report
+            .problems
+            .contains(&"missing field: categories[0].items[0].size_bytes".to_string()));
+    }
+
+    #[test]
+    fn rejects_unknown_documents() {
+        let report = validate_json(r#"{"hello": "world"}"#);
+        assert!(report.schema.is_none());
+        assert!(!report.is_valid());
+    }
+}
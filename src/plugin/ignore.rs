@@ -0,0 +1,137 @@
+use glob::Pattern;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+pub const IGNORE_FILE_NAME: &str = ".cleanmacignore";
+
+#[derive(Debug)]
+struct IgnoreFile {
+    dir: PathBuf,
+    patterns: Vec<Pattern>,
+}
+
+impl IgnoreFile {
+    fn load(dir: &Path) -> Option<Self> {
+        let content = fs::read_to_string(dir.join(IGNORE_FILE_NAME)).ok()?;
+        let patterns = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+            .filter_map(|l| Pattern::new(l.trim_start_matches('/').trim_end_matches('/')).ok())
+            .collect();
+
+        Some(Self {
+            dir: dir.to_path_buf(),
+            patterns,
+        })
+    }
+
+    fn matches(&self, path: &Path) -> bool {
+        let Ok(relative) = path.strip_prefix(&self.dir) else {
+            return false;
+        };
+
+        // A match on any leading part of the path excludes everything below it.
+        let mut prefix = PathBuf::new();
+        for component in relative.components() {
+            prefix.push(component);
+            let prefix_str = prefix.to_string_lossy();
+            let name = component.as_os_str().to_string_lossy();
+
+            for pattern in &self.patterns {
+                let anchored = pattern.as_str().contains('/');
+                if pattern.matches(&prefix_str) || (!anchored && pattern.matches(&name)) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
+// Maps a directory to the nearest `.cleanmacignore` at or above it (None if there is none).
+#[derive(Debug, Default)]
+pub struct IgnoreRules {
+    cache: RwLock<HashMap<PathBuf, Option<Arc<IgnoreFile>>>>,
+}
+
+impl IgnoreRules {
+    pub fn discover<P: AsRef<Path>>(roots: &[P]) -> Self {
+        let rules = Self::default();
+        rules.nearest(&crate::user::home_dir());
+        for root in roots {
+            rules.nearest(root.as_ref());
+        }
+        rules
+    }
+
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        path.parent()
+            .and_then(|parent| self.nearest(parent))
+            .is_some_and(|file| file.matches(path))
+    }
+
+    fn nearest(&self, dir: &Path) -> Option<Arc<IgnoreFile>> {
+        if let Some(cached) = self.cache.read().ok()?.get(dir) {
+            return cached.clone();
+        }
+
+        let found = match IgnoreFile::load(dir) {
+            Some(file) => Some(Arc::new(file)),
+            None => dir.parent().and_then(|parent| self.nearest(parent)),
+        };
+
+        if let Ok(mut cache) = self.cache.write() {
+            cache.insert(dir.to_path_buf(), found.clone());
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(name: &str, ignore: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("cleanmac-ignore-{}", name));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("keep/nested")).unwrap();
+        fs::create_dir_all(root.join("build/cache")).unwrap();
+        fs::write(root.join(IGNORE_FILE_NAME), ignore).unwrap();
+        root
+    }
+
+    #[test]
+    fn ignores_listed_subdirectory_and_its_contents() {
+        let root = project("subdir", "# keep this one\nkeep/\n");
+        let rules = IgnoreRules::discover(&[&root]);
+
+        assert!(rules.is_ignored(&root.join("keep")));
+        assert!(rules.is_ignored(&root.join("keep/nested")));
+        assert!(rules.is_ignored(&root.join("keep/nested/file.bin")));
+        assert!(!rules.is_ignored(&root.join("build/cache")));
+    }
+
+    #[test]
+    fn unanchored_patterns_match_at_any_depth() {
+        let root = project("glob", "*.sqlite\ncache\n");
+        let rules = IgnoreRules::discover(&[&root]);
+
+        assert!(rules.is_ignored(&root.join("build/cache")));
+        assert!(rules.is_ignored(&root.join("keep/nested/db.sqlite")));
+        assert!(!rules.is_ignored(&root.join("keep/nested/db.sqlite-wal")));
+    }
+
+    #[test]
+    fn nearest_ancestor_file_wins() {
+        let root = project("nearest", "keep/\n");
+        fs::write(root.join("build").join(IGNORE_FILE_NAME), "other\n").unwrap();
+        let rules = IgnoreRules::discover(&[&root]);
+
+        assert!(rules.is_ignored(&root.join("keep/nested")));
+        assert!(rules.is_ignored(&root.join("build/other")));
+        assert!(!rules.is_ignored(&root.join("build/cache")));
+    }
+}
@@ -1,5 +1,8 @@
+pub mod ignore;
+pub mod progress;
 pub mod registry;
 pub mod traits;
 
+pub use progress::{ScanPause, PROGRESS_INTERVAL};
 pub use registry::PluginRegistry;
 pub use traits::*;
@@ -0,0 +1,183 @@
+use super::traits::ProgressCallback;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+// ~10 updates per second is plenty for a "currently scanning" line.
+pub const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+// Forwards at most one path per interval; the latest skipped path is kept so
+// flush() can report where the scanner actually finished.
+pub struct ProgressThrottle {
+    callback: ProgressCallback,
+    interval: Duration,
+    state: Mutex<ThrottleState>,
+}
+
+struct ThrottleState {
+    last_sent: Option<Instant>,
+    pending: String,
+    has_pending: bool,
+}
+
+impl ProgressThrottle {
+    pub fn new(callback: ProgressCallback, interval: Duration) -> Arc<Self> {
+        Arc::new(Self {
+            callback,
+            interval,
+            state: Mutex::new(ThrottleState {
+                last_sent: None,
+                pending: String::new(),
+                has_pending: false,
+            }),
+        })
+    }
+
+    pub fn report(&self, path: &str) {
+        let now = Instant::now();
+        {
+            let mut state = self.state.lock().unwrap();
+            let due = state
+                .last_sent
+                .is_none_or(|last| now.duration_since(last) >= self.interval);
+            if !due {
+                state.pending.clear();
+                state.pending.push_str(path);
+                state.has_pending = true;
+                return;
+            }
+            state.last_sent = Some(now);
+            state.has_pending = false;
+        }
+        (self.callback)(path);
+    }
+
+    pub fn flush(&self) {
+        let pending = {
+            let mut state = self.state.lock().unwrap();
+            if !state.has_pending {
+                return;
+            }
+            state.has_pending = false;
+            state.last_sent = Some(Instant::now());
+            std::mem::take(&mut state.pending)
+        };
+        (self.callback)(&pending);
+    }
+}
+
+// Shared pause switch for a running scan. Scanners block in their progress and
+// item callbacks while it is set, so a paused walk stops at its next report.
+#[derive(Debug, Clone, Default)]
+pub struct ScanPause(Arc<AtomicBool>);
+
+impl ScanPause {
+    const POLL: Duration = Duration::from_millis(50);
+
+    pub fn is_paused(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    pub fn set(&self, paused: bool) {
+        self.0.store(paused, Ordering::SeqCst);
+    }
+
+    // Returns the new state.
+    pub fn toggle(&self) -> bool {
+        !self.0.fetch_xor(true, Ordering::SeqCst)
+    }
+
+    pub fn wait_while_paused(&self) {
+        while self.is_paused() {
+            std::thread::sleep(Self::POLL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::ScanConfig;
+
+    #[test]
+    fn throttles_a_large_tree_but_reports_the_last_path() {
+        let seen: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&seen);
+        let config = ScanConfig {
+            progress_callback: Some(Arc::new(move |path: &str| {
+                sink.lock().unwrap().push(path.to_string());
+            })),
+            ..ScanConfig::default()
+        };
+
+        let (throttled, throttle) = config.throttled(PROGRESS_INTERVAL);
+        let total = 100_000;
+        for i in 0..total {
+            throttled.report_progress(&format!("/tree/dir_{}/file_{}", i / 100, i));
+        }
+        throttle.unwrap().flush();
+
+        let seen = seen.lock().unwrap();
+        assert!(seen.len() < total / 100, "sent {} of {}", seen.len(), total);
+        assert_eq!(seen.first().unwrap(), "/tree/dir_0/file_0");
+        assert_eq!(
+            seen.last().unwrap(),
+            &format!("/tree/dir_{}/file_{}", (total - 1) / 100, total - 1)
+        );
+    }
+
+    #[test]
+    fn flush_without_skipped_paths_sends_nothing() {
+        let count = Arc::new(Mutex::new(0));
+        let sink = Arc::clone(&count);
+        let throttle = ProgressThrottle::new(
+            Arc::new(move |_: &str| *sink.lock().unwrap() += 1),
+            PROGRESS_INTERVAL,
+        );
+
+        throttle.report("/only");
+        throttle.flush();
+
+        assert_eq!(*count.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn pausing_halts_progress_until_resumed() {
+        use std::sync::atomic::AtomicUsize;
+
+        let reported = Arc::new(AtomicUsize::new(0));
+        let sink = Arc::clone(&reported);
+        let pause = ScanPause::default();
+        let config = ScanConfig {
+            progress_callback: Some(Arc::new(move |_: &str| {
+                sink.fetch_add(1, Ordering::SeqCst);
+            })),
+            ..ScanConfig::default()
+        }
+        .pausable(&pause);
+
+        let done = Arc::new(AtomicBool::new(false));
+        let stop = Arc::clone(&done);
+        let walker = std::thread::spawn(move || {
+            while !stop.load(Ordering::SeqCst) {
+                config.report_progress("/tree/file");
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        });
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(pause.toggle());
+        // Let a report that was already past the checkpoint land.
+        std::thread::sleep(Duration::from_millis(50));
+        let while_paused = reported.load(Ordering::SeqCst);
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(reported.load(Ordering::SeqCst), while_paused);
+
+        assert!(!pause.toggle());
+        std::thread::sleep(Duration::from_millis(150));
+        assert!(reported.load(Ordering::SeqCst) > while_paused);
+
+        done.store(true, Ordering::SeqCst);
+        walker.join().unwrap();
+    }
+}
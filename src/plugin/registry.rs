@@ -1,11 +1,21 @@
+use super::progress::PROGRESS_INTERVAL;
 use super::traits::{ScanConfig, ScanResult, Scanner};
-use crate::scanner::{BrowserCacheScanner, CacheScanner, DevJunkScanner, LogScanner, TrashScanner};
+use crate::config::Config;
+use crate::scanner::{
+    load_user_scanners, BrowserCacheScanner, CacheScanner, ContainerScanner, DevJunkScanner,
+    DsStoreScanner, DuplicatesScanner, LargeOldFilesScanner, LogScanner, MailAttachmentsScanner,
+    MaintenanceScanner, MusicJunkScanner, PhotoJunkScanner, PrivacyScanner,
+    SpotlightMetadataScanner, StartupItemsScanner, TrashScanner, XcodeJunkScanner,
+};
 use anyhow::Result;
-use rayon::prelude::*;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 pub struct PluginRegistry {
-    scanners: Vec<Box<dyn Scanner>>,
+    scanners: Vec<Arc<dyn Scanner>>,
 }
 
 impl PluginRegistry {
@@ -15,27 +25,157 @@ impl PluginRegistry {
         }
     }
 
+    // Scanners outside `allowed_scanners` are left out entirely, so nothing
+    // downstream can scan or clean them.
+    pub fn from_config(config: &Config) -> Self {
+        let mut registry = Self::new();
+
+        let mut scanners: Vec<Box<dyn Scanner>> = vec![
+            Box::new(CacheScanner::new()),
+            Box::new(LogScanner::new()),
+            Box::new(TrashScanner::new()),
+            Box::new(BrowserCacheScanner::new()),
+            Box::new(DevJunkScanner::new()),
+            Box::new(XcodeJunkScanner::new()),
+            Box::new(SpotlightMetadataScanner::new()),
+            Box::new(DsStoreScanner::new()),
+        ];
+        // A broken scanners.toml shouldn't stop the built-in scanners.
+        match load_user_scanners() {
+            Ok(custom) => {
+                scanners.extend(custom.into_iter().map(|s| Box::new(s) as Box<dyn Scanner>))
+            }
+            Err(e) => eprintln!("Warning: {:#}", e),
+        }
+        for scanner in scanners {
+            if config.allows_scanner(scanner.id()) {
+                registry.register_scanner(scanner);
+            }
+        }
+
+        registry
+    }
+
+    // Every compiled-in scanner, including the ones only the TUI or a named
+    // category runs. User scanners may not reuse any of these ids.
+    pub fn builtin_ids() -> Vec<String> {
+        let scanners: Vec<Box<dyn Scanner>> = vec![
+            Box::new(CacheScanner::new()),
+            Box::new(LogScanner::new()),
+            Box::new(TrashScanner::new()),
+            Box::new(BrowserCacheScanner::new()),
+            Box::new(DevJunkScanner::new()),
+            Box::new(XcodeJunkScanner::new()),
+            Box::new(ContainerScanner::new()),
+            Box::new(LargeOldFilesScanner::new()),
+            Box::new(MailAttachmentsScanner::new()),
+            Box::new(PhotoJunkScanner::new()),
+            Box::new(MusicJunkScanner::new()),
+            Box::new(SpotlightMetadataScanner::new()),
+            Box::new(DsStoreScanner::new()),
+            Box::new(DuplicatesScanner::new()),
+            Box::new(PrivacyScanner::new()),
+            Box::new(MaintenanceScanner::new()),
+            Box::new(StartupItemsScanner::new()),
+        ];
+        scanners.iter().map(|s| s.id().to_string()).collect()
+    }
+
+    // Container Runtimes can wipe every image and volume, so outside the TUI it
+    // only runs when named outright (`-c container_junk`), never as part of "all".
+    pub fn register_requested(&mut self, category: &str, config: &Config) {
+        if category.eq_ignore_ascii_case("container_junk")
+            && config.allows_scanner("container_junk")
+        {
+            self.register_scanner(Box::new(ContainerScanner::new()));
+        }
+    }
+
+    // Narrows to the scanners the user picked as their default set.
+    pub fn retain_enabled(&mut self, config: &Config) {
+        self.scanners.retain(|s| config.enables_scanner(s.id()));
+    }
+
+    pub fn retain_ids(&mut self, ids: &[&str]) {
+        self.scanners.retain(|s| ids.contains(&s.id()));
+    }
+
     pub fn register_scanner(&mut self, scanner: Box<dyn Scanner>) {
-        self.scanners.push(scanner);
+        self.scanners.push(Arc::from(scanner));
     }
 
+    pub fn scanners(&self) -> &[Arc<dyn Scanner>] {
+        &self.scanners
+    }
+
+    pub fn retain_cheap(&mut self) -> Vec<String> {
+        let skipped = self
+            .scanners
+            .iter()
+            .filter(|s| s.is_expensive())
+            .map(|s| s.id().to_string())
+            .collect();
+        self.scanners.retain(|s| !s.is_expensive());
+        skipped
+    }
+
+    // Runs the available scanners on up to `config.threads` workers. Results
+    // keep registration order whichever scanner finishes first.
     pub fn scan_all(&self, config: &ScanConfig) -> Result<ScanReport> {
         let start = Instant::now();
 
-        let category_results: Vec<_> = self
-            .scanners
-            .par_iter()
-            .filter(|s| s.is_available())
-            .map(|scanner| {
-                let results = scanner.scan(config).unwrap_or_default();
-                CategoryScanResult {
-                    scanner_id: scanner.id().to_string(),
-                    name: scanner.name().to_string(),
-                    category: scanner.category(),
-                    items: results,
+        let scanners: Arc<Vec<Arc<dyn Scanner>>> = Arc::new(
+            self.scanners
+                .iter()
+                .filter(|s| s.is_available())
+                .cloned()
+                .collect(),
+        );
+        let workers = config
+            .threads
+            .unwrap_or(scanners.len())
+            .clamp(1, scanners.len().max(1));
+        let next = Arc::new(AtomicUsize::new(0));
+        let (tx, rx) = channel();
+        for _ in 0..workers {
+            let scanners = Arc::clone(&scanners);
+            let next = Arc::clone(&next);
+            let tx = tx.clone();
+            let config = config.clone();
+            thread::spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::SeqCst);
+                let Some(scanner) = scanners.get(index) else {
+                    break;
+                };
+                let _ = tx.send((index, run_scanner(scanner, &config)));
+            });
+        }
+        drop(tx);
+
+        let mut outcomes: Vec<Option<ScanOutcome>> = scanners.iter().map(|_| None).collect();
+        for (index, outcome) in rx {
+            outcomes[index] = Some(outcome);
+        }
+
+        let mut category_results = Vec::new();
+        let mut timed_out = Vec::new();
+        for (scanner, outcome) in scanners.iter().zip(outcomes) {
+            match outcome {
+                Some(ScanOutcome::Done(items, scan_duration, unreadable)) => {
+                    category_results.push(CategoryScanResult {
+                        scanner_id: scanner.id().to_string(),
+                        name: scanner.name().to_string(),
+                        category: scanner.category(),
+                        items,
+                        scan_duration,
+                        unreadable,
+                        omitted: 0,
+                    })
                 }
-            })
-            .collect();
+                Some(ScanOutcome::TimedOut) => timed_out.push(scanner.name().to_string()),
+                Some(ScanOutcome::Failed) | None => {}
+            }
+        }
 
         let total_size: u64 = category_results
             .iter()
@@ -49,21 +189,53 @@ impl PluginRegistry {
             total_size,
             total_items,
             duration: start.elapsed(),
+            timed_out,
         })
     }
 }
 
-impl Default for PluginRegistry {
-    fn default() -> Self {
-        let mut registry = Self::new();
+enum ScanOutcome {
+    Done(Vec<ScanResult>, Duration, usize),
+    TimedOut,
+    // The scanner thread panicked.
+    Failed,
+}
 
-        registry.register_scanner(Box::new(CacheScanner::new()));
-        registry.register_scanner(Box::new(LogScanner::new()));
-        registry.register_scanner(Box::new(TrashScanner::new()));
-        registry.register_scanner(Box::new(BrowserCacheScanner::new()));
-        registry.register_scanner(Box::new(DevJunkScanner::new()));
+// Runs one scanner on a thread of its own so a hung one can be abandoned at
+// the timeout, which counts from when this scanner started.
+fn run_scanner(scanner: &Arc<dyn Scanner>, config: &ScanConfig) -> ScanOutcome {
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let (counting, unreadable) = config.counting_unreadable();
+    let (throttled, throttle) = counting.guarded(&cancelled).throttled(PROGRESS_INTERVAL);
+    let worker = Arc::clone(scanner);
+    let (tx, rx) = channel();
+    thread::spawn(move || {
+        let started = Instant::now();
+        let items = worker.scan(&throttled).unwrap_or_default();
+        if let Some(throttle) = throttle {
+            throttle.flush();
+        }
+        let unreadable = unreadable.load(Ordering::SeqCst);
+        let _ = tx.send((items, started.elapsed(), unreadable));
+    });
 
-        registry
+    let received = match config.scanner_timeout {
+        Some(timeout) => rx.recv_timeout(timeout),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match received {
+        Ok((items, duration, unreadable)) => ScanOutcome::Done(items, duration, unreadable),
+        Err(RecvTimeoutError::Timeout) => {
+            cancelled.store(true, Ordering::SeqCst);
+            ScanOutcome::TimedOut
+        }
+        Err(RecvTimeoutError::Disconnected) => ScanOutcome::Failed,
+    }
+}
+
+impl Default for PluginRegistry {
+    fn default() -> Self {
+        Self::from_config(&Config::default())
     }
 }
 
@@ -73,6 +245,11 @@ pub struct CategoryScanResult {
     pub name: String,
     pub category: super::traits::ScannerCategory,
     pub items: Vec<ScanResult>,
+    pub scan_duration: std::time::Duration,
+    // Entries skipped because reading them was denied (usually missing Full Disk Access).
+    pub unreadable: usize,
+    // Items found beyond the scanner's max_items and dropped; the largest are kept.
+    pub omitted: usize,
 }
 
 impl CategoryScanResult {
@@ -81,10 +258,346 @@ impl CategoryScanResult {
     }
 }
 
+// Trims `items` to the `limit` largest and returns how many were dropped.
+pub fn keep_largest(items: &mut Vec<ScanResult>, limit: Option<usize>) -> usize {
+    let Some(limit) = limit.filter(|&limit| items.len() > limit) else {
+        return 0;
+    };
+    items.sort_by_key(|item| std::cmp::Reverse(item.size));
+    let omitted = items.len() - limit;
+    items.truncate(limit);
+    omitted
+}
+
+pub fn unreadable_warning(name: &str, count: usize) -> String {
+    format!(
+        "{}: {} items unreadable (grant Full Disk Access)",
+        name,
+        crate::utils::format_number(count as u64)
+    )
+}
+
+pub fn omitted_warning(id: &str, name: &str, shown: usize, omitted: usize) -> String {
+    let setting = match id {
+        "large_old_files" => "scan.large_file_max_results",
+        _ => "scan.max_items",
+    };
+    format!(
+        "{}: showing the largest {} of {} items (raise {} to see more)",
+        name,
+        crate::utils::format_number(shown as u64),
+        crate::utils::format_number((shown + omitted) as u64),
+        setting
+    )
+}
+
 #[derive(Debug)]
 pub struct ScanReport {
     pub categories: Vec<CategoryScanResult>,
     pub total_size: u64,
     pub total_items: usize,
     pub duration: std::time::Duration,
+    pub timed_out: Vec<String>,
+}
+
+impl ScanReport {
+    // Trims each category to its max_items for display. Only output does this:
+    // whatever cleans works from the full report, so the cap never hides items
+    // from clean, apply or retention.
+    pub fn cap_items(&mut self, config: &ScanConfig) {
+        for category in &mut self.categories {
+            category.omitted = keep_largest(
+                &mut category.items,
+                config.max_items_for(&category.scanner_id),
+            );
+        }
+        self.total_size = self.categories.iter().map(|c| c.total_size()).sum();
+        self.total_items = self.categories.iter().map(|c| c.items.len()).sum();
+    }
+
+    pub fn warnings(&self) -> Vec<String> {
+        self.categories
+            .iter()
+            .filter(|c| c.unreadable > 0)
+            .map(|c| unreadable_warning(&c.name, c.unreadable))
+            .chain(
+                self.categories
+                    .iter()
+                    .filter(|c| c.omitted > 0)
+                    .map(|c| omitted_warning(&c.scanner_id, &c.name, c.items.len(), c.omitted)),
+            )
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::ScannerCategory;
+    use std::time::Duration;
+
+    struct SlowScanner;
+
+    impl Scanner for SlowScanner {
+        fn id(&self) -> &str {
+            "slow"
+        }
+
+        fn name(&self) -> &str {
+            "Slow"
+        }
+
+        fn category(&self) -> ScannerCategory {
+            ScannerCategory::System
+        }
+
+        fn scan(&self, config: &ScanConfig) -> Result<Vec<ScanResult>> {
+            thread::sleep(Duration::from_millis(500));
+            let item = ScanResult::new("late", "late", "/tmp/late".into());
+            config.report_item(item.clone());
+            Ok(vec![item])
+        }
+    }
+
+    struct WalkingScanner(std::path::PathBuf);
+
+    impl Scanner for WalkingScanner {
+        fn id(&self) -> &str {
+            "walking"
+        }
+
+        fn name(&self) -> &str {
+            "Mail"
+        }
+
+        fn category(&self) -> ScannerCategory {
+            ScannerCategory::System
+        }
+
+        fn scan(&self, config: &ScanConfig) -> Result<Vec<ScanResult>> {
+            let _ = walkdir::WalkDir::new(&self.0)
+                .into_iter()
+                .filter_map(|e| config.readable(e))
+                .count();
+            // Root reads through chmod 000, so the denial is also fed in directly.
+            config.tally_io_error(&std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+            config.tally_io_error(&std::io::Error::from(std::io::ErrorKind::NotFound));
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn counts_unreadable_entries_per_scanner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let root = std::env::temp_dir().join("cleanmac-unreadable");
+        let _ = std::fs::remove_dir_all(&root);
+        let locked = root.join("locked");
+        std::fs::create_dir_all(locked.join("inner")).unwrap();
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o000)).unwrap();
+
+        let mut registry = PluginRegistry::new();
+        registry.register_scanner(Box::new(WalkingScanner(root.clone())));
+        let report = registry.scan_all(&ScanConfig::default()).unwrap();
+
+        std::fs::set_permissions(&locked, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let _ = std::fs::remove_dir_all(&root);
+
+        let denied_by_walk = if unsafe { libc::geteuid() } == 0 {
+            0
+        } else {
+            1
+        };
+        let expected = 1 + denied_by_walk;
+        assert_eq!(report.categories[0].unreadable, expected);
+        assert_eq!(
+            report.warnings(),
+            vec![format!(
+                "Mail: {} items unreadable (grant Full Disk Access)",
+                expected
+            )]
+        );
+    }
+
+    struct ManyScanner;
+
+    impl Scanner for ManyScanner {
+        fn id(&self) -> &str {
+            "many"
+        }
+
+        fn name(&self) -> &str {
+            "Many"
+        }
+
+        fn category(&self) -> ScannerCategory {
+            ScannerCategory::System
+        }
+
+        fn scan(&self, _config: &ScanConfig) -> Result<Vec<ScanResult>> {
+            Ok((1..=5)
+                .map(|size| {
+                    ScanResult::new(format!("item{}", size), "x", "/tmp/x".into()).with_size(size)
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn output_keeps_only_the_largest_items_up_to_max_items() {
+        let mut registry = PluginRegistry::new();
+        registry.register_scanner(Box::new(ManyScanner));
+
+        let config = ScanConfig {
+            max_items: 2,
+            ..Default::default()
+        };
+        let mut report = registry.scan_all(&config).unwrap();
+        // Cleaners get everything; only output is capped.
+        assert_eq!(report.categories[0].items.len(), 5);
+        report.cap_items(&config);
+        let sizes: Vec<u64> = report.categories[0].items.iter().map(|i| i.size).collect();
+        assert_eq!(sizes, vec![5, 4]);
+        assert_eq!(report.categories[0].omitted, 3);
+        assert_eq!(report.total_size, 9);
+
+        // A per-scanner entry wins, and 0 means unlimited.
+        let config = ScanConfig {
+            max_items: 2,
+            max_items_per_scanner: [("many".to_string(), 0)].into(),
+            ..Default::default()
+        };
+        let mut report = registry.scan_all(&config).unwrap();
+        report.cap_items(&config);
+        assert_eq!(report.categories[0].items.len(), 5);
+        assert_eq!(report.categories[0].omitted, 0);
+    }
+
+    #[test]
+    fn allowed_scanners_limits_what_gets_registered() {
+        let ids = |registry: &PluginRegistry| -> Vec<String> {
+            registry
+                .scanners()
+                .iter()
+                .map(|s| s.id().to_string())
+                .collect()
+        };
+        assert_eq!(ids(&PluginRegistry::default()).len(), 8);
+        assert!(!ids(&PluginRegistry::default()).contains(&"container_junk".to_string()));
+
+        let mut registry = PluginRegistry::default();
+        registry.register_requested("container_junk", &Config::default());
+        assert!(ids(&registry).contains(&"container_junk".to_string()));
+
+        let config = Config {
+            allowed_scanners: Some(vec!["trash".to_string(), "not_a_scanner".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(ids(&PluginRegistry::from_config(&config)), vec!["trash"]);
+    }
+
+    #[test]
+    fn retain_cheap_drops_tree_walking_scanners() {
+        let mut registry = PluginRegistry::default();
+        assert_eq!(
+            registry.retain_cheap(),
+            vec!["dev_junk".to_string(), "ds_store".to_string()]
+        );
+        assert!(registry.scanners().iter().all(|s| !s.is_expensive()));
+        assert_eq!(registry.scanners().len(), 6);
+    }
+
+    #[test]
+    fn slow_scanner_times_out_without_leaking_items() {
+        let mut registry = PluginRegistry::new();
+        registry.register_scanner(Box::new(SlowScanner));
+
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reported);
+        let config = ScanConfig {
+            scanner_timeout: Some(Duration::from_millis(50)),
+            item_callback: Some(Arc::new(move |item: ScanResult| {
+                sink.lock().unwrap().push(item.id);
+            })),
+            ..Default::default()
+        };
+
+        let report = registry.scan_all(&config).unwrap();
+        assert_eq!(report.timed_out, vec!["Slow".to_string()]);
+        assert!(report.categories.is_empty());
+
+        thread::sleep(Duration::from_millis(700));
+        assert!(reported.lock().unwrap().is_empty());
+    }
+
+    // Finishes in the reverse of registration order when run in parallel.
+    struct NappingScanner(usize);
+
+    impl Scanner for NappingScanner {
+        fn id(&self) -> &str {
+            ["nap_a", "nap_b", "nap_c", "nap_d"][self.0]
+        }
+
+        fn name(&self) -> &str {
+            self.id()
+        }
+
+        fn category(&self) -> ScannerCategory {
+            ScannerCategory::System
+        }
+
+        fn scan(&self, _config: &ScanConfig) -> Result<Vec<ScanResult>> {
+            thread::sleep(Duration::from_millis(150 - 30 * self.0 as u64));
+            Ok((0..=self.0)
+                .map(|i| {
+                    let id = format!("{}-{}", self.id(), i);
+                    ScanResult::new(id.clone(), id, format!("/tmp/{}", self.id()).into())
+                        .with_size(i as u64)
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn parallel_scan_matches_sequential_in_content_and_order() {
+        let mut registry = PluginRegistry::new();
+        for i in 0..4 {
+            registry.register_scanner(Box::new(NappingScanner(i)));
+        }
+        let summary = |report: &ScanReport| -> Vec<(String, Vec<String>)> {
+            report
+                .categories
+                .iter()
+                .map(|c| {
+                    let ids = c.items.iter().map(|i| i.id.clone()).collect();
+                    (c.scanner_id.clone(), ids)
+                })
+                .collect()
+        };
+
+        let sequential = registry
+            .scan_all(&ScanConfig {
+                threads: Some(1),
+                ..Default::default()
+            })
+            .unwrap();
+        let parallel = registry
+            .scan_all(&ScanConfig {
+                threads: Some(4),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(summary(&parallel), summary(&sequential));
+        let ids: Vec<&str> = parallel
+            .categories
+            .iter()
+            .map(|c| c.scanner_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["nap_a", "nap_b", "nap_c", "nap_d"]);
+        assert_eq!(parallel.total_items, 10);
+        // 150+120+90+60 ms one after another against the slowest alone.
+        assert!(sequential.duration >= Duration::from_millis(420));
+        assert!(parallel.duration < sequential.duration);
+    }
 }
@@ -1,11 +1,16 @@
+use super::ignore::IgnoreRules;
+use super::progress::{ProgressThrottle, ScanPause};
+use crate::config::{Config, KeepPolicy};
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ScannerCategory {
     System,
     Browser,
@@ -24,20 +29,71 @@ impl std::fmt::Display for ScannerCategory {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum SafetyLevel {
     Safe,
     Caution,
     Protected,
 }
 
+impl SafetyLevel {
+    // Used when a scanner doesn't give its own safety_reason.
+    pub fn default_reason(&self) -> &'static str {
+        match self {
+            SafetyLevel::Safe => "Regenerated automatically; safe to delete",
+            SafetyLevel::Caution => "May contain data you want to keep; review before deleting",
+            SafetyLevel::Protected => "Needed by the system or holds credentials; never deleted",
+        }
+    }
+}
+
+impl std::fmt::Display for SafetyLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SafetyLevel::Safe => write!(f, "Safe"),
+            SafetyLevel::Caution => write!(f, "Caution"),
+            SafetyLevel::Protected => write!(f, "Protected"),
+        }
+    }
+}
+
+pub type ProgressCallback = Arc<dyn Fn(&str) + Send + Sync>;
+pub type ItemCallback = Arc<dyn Fn(ScanResult) + Send + Sync>;
+pub type PhaseCallback = Arc<dyn Fn(ScanPhase) + Send + Sync>;
+
+// A counted stage inside one scanner, e.g. duplicates hashing its candidates,
+// reported apart from the per-path progress so a UI can show "done/total".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanPhase {
+    pub scanner_id: String,
+    pub label: String,
+    // What is being counted, e.g. "candidate files".
+    pub unit: String,
+    pub done: usize,
+    pub total: usize,
+}
+
 #[derive(Clone)]
 pub struct ScanConfig {
     pub min_size: u64,
     pub max_depth: usize,
+    pub follow_symlinks: bool,
+    pub include_hidden: bool,
+    pub max_items: usize,
+    pub max_items_per_scanner: BTreeMap<String, usize>,
     pub excluded_paths: Vec<PathBuf>,
-    pub progress_callback: Option<Arc<dyn Fn(&str) + Send + Sync>>,
-    pub item_callback: Option<Arc<dyn Fn(ScanResult) + Send + Sync>>,
+    pub ignore_rules: Arc<IgnoreRules>,
+    pub scanner_timeout: Option<Duration>,
+    // Scanners run at once by scan_all; None runs every one together.
+    pub threads: Option<usize>,
+    pub progress_callback: Option<ProgressCallback>,
+    pub item_callback: Option<ItemCallback>,
+    pub phase_callback: Option<PhaseCallback>,
+    // Entries the scanner couldn't read for lack of permission; see counting_unreadable().
+    pub unreadable: Option<Arc<AtomicUsize>>,
+    pub duplicate_keep: KeepPolicy,
+    pub large_file_min_size: u64,
+    pub large_file_min_age_days: u64,
 }
 
 impl Default for ScanConfig {
@@ -45,14 +101,91 @@ impl Default for ScanConfig {
         Self {
             min_size: 1024 * 1024,
             max_depth: 3,
+            follow_symlinks: false,
+            include_hidden: false,
+            max_items: 1000,
+            max_items_per_scanner: BTreeMap::new(),
             excluded_paths: Vec::new(),
+            ignore_rules: Arc::new(IgnoreRules::default()),
+            scanner_timeout: None,
+            threads: None,
             progress_callback: None,
             item_callback: None,
+            phase_callback: None,
+            unreadable: None,
+            duplicate_keep: KeepPolicy::default(),
+            large_file_min_size: 100 * 1024 * 1024,
+            large_file_min_age_days: 30,
         }
     }
 }
 
 impl ScanConfig {
+    // The one place the user's [scan] settings become a scan config, so the CLI,
+    // TUI and MCP all honour the same knobs. Callers add callbacks on top.
+    pub fn from_config(config: &Config) -> Self {
+        let mut max_items_per_scanner = config.scan.max_items_per_scanner.clone();
+        max_items_per_scanner
+            .entry("large_old_files".to_string())
+            .or_insert(config.scan.large_file_max_results);
+        Self {
+            min_size: config.scan.min_size_bytes,
+            max_depth: config.scan.max_depth,
+            follow_symlinks: config.scan.follow_symlinks,
+            include_hidden: config.scan.include_hidden,
+            max_items: config.scan.max_items,
+            max_items_per_scanner,
+            excluded_paths: config
+                .scan
+                .excluded_paths
+                .iter()
+                .map(PathBuf::from)
+                .collect(),
+            ignore_rules: Arc::new(IgnoreRules::discover(&config.scan.scan_paths)),
+            scanner_timeout: config.scan.scanner_timeout(),
+            threads: (config.scan.scan_threads > 0).then_some(config.scan.scan_threads),
+            progress_callback: None,
+            item_callback: None,
+            phase_callback: None,
+            unreadable: None,
+            duplicate_keep: config.scan.duplicate_keep.clone(),
+            large_file_min_size: config.scan.large_file_min_size,
+            large_file_min_age_days: config.scan.large_file_min_age_days,
+        }
+    }
+
+    // max_depth == 0 means unlimited
+    pub fn walk_depth(&self) -> usize {
+        if self.max_depth == 0 {
+            usize::MAX
+        } else {
+            self.max_depth
+        }
+    }
+
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        self.excluded_paths.iter().any(|ex| path.starts_with(ex))
+            || self.ignore_rules.is_ignored(path)
+    }
+
+    // None means the scanner may report everything it finds.
+    pub fn max_items_for(&self, scanner_id: &str) -> Option<usize> {
+        let limit = self
+            .max_items_per_scanner
+            .get(scanner_id)
+            .copied()
+            .unwrap_or(self.max_items);
+        (limit > 0).then_some(limit)
+    }
+
+    // Dot-named entries are skipped unless include_hidden is on.
+    pub fn skips_hidden(&self, path: &Path) -> bool {
+        !self.include_hidden
+            && path
+                .file_name()
+                .is_some_and(|n| n.as_encoded_bytes().starts_with(b"."))
+    }
+
     pub fn report_progress(&self, path: &str) {
         if let Some(cb) = &self.progress_callback {
             cb(path);
@@ -64,6 +197,144 @@ impl ScanConfig {
             cb(item);
         }
     }
+
+    pub fn report_phase(
+        &self,
+        scanner_id: &str,
+        label: &str,
+        unit: &str,
+        done: usize,
+        total: usize,
+    ) {
+        if let Some(cb) = &self.phase_callback {
+            cb(ScanPhase {
+                scanner_id: scanner_id.to_string(),
+                label: label.to_string(),
+                unit: unit.to_string(),
+                done,
+                total,
+            });
+        }
+    }
+
+    pub fn tally_io_error(&self, err: &std::io::Error) {
+        if err.kind() == std::io::ErrorKind::PermissionDenied {
+            if let Some(unreadable) = &self.unreadable {
+                unreadable.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+    }
+
+    // For filter_map over a walk: drops errors like `.ok()`, but counts the
+    // permission-denied ones so the report can say what went unread.
+    pub fn readable<T>(&self, entry: walkdir::Result<T>) -> Option<T> {
+        match entry {
+            Ok(entry) => Some(entry),
+            Err(err) => {
+                if let Some(io) = err.io_error() {
+                    self.tally_io_error(io);
+                }
+                None
+            }
+        }
+    }
+
+    // Copy with a fresh unreadable counter for one scanner's run.
+    pub fn counting_unreadable(&self) -> (ScanConfig, Arc<AtomicUsize>) {
+        let unreadable = Arc::new(AtomicUsize::new(0));
+        let config = ScanConfig {
+            unreadable: Some(Arc::clone(&unreadable)),
+            ..self.clone()
+        };
+        (config, unreadable)
+    }
+
+    // Copy whose callbacks go quiet once `cancelled` is set, so a scanner abandoned
+    // by its watchdog can keep running without leaking results into the report.
+    pub fn guarded(&self, cancelled: &Arc<AtomicBool>) -> ScanConfig {
+        let progress_callback = self.progress_callback.clone().map(|cb| {
+            let cancelled = Arc::clone(cancelled);
+            Arc::new(move |path: &str| {
+                if !cancelled.load(Ordering::SeqCst) {
+                    cb(path);
+                }
+            }) as ProgressCallback
+        });
+        let item_callback = self.item_callback.clone().map(|cb| {
+            let cancelled = Arc::clone(cancelled);
+            Arc::new(move |item: ScanResult| {
+                if !cancelled.load(Ordering::SeqCst) {
+                    cb(item);
+                }
+            }) as ItemCallback
+        });
+        let phase_callback = self.phase_callback.clone().map(|cb| {
+            let cancelled = Arc::clone(cancelled);
+            Arc::new(move |phase: ScanPhase| {
+                if !cancelled.load(Ordering::SeqCst) {
+                    cb(phase);
+                }
+            }) as PhaseCallback
+        });
+
+        ScanConfig {
+            progress_callback,
+            item_callback,
+            phase_callback,
+            ..self.clone()
+        }
+    }
+
+    // Copy whose callbacks block while `pause` is set; they are the checkpoints
+    // at which a paused scan stops.
+    pub fn pausable(&self, pause: &ScanPause) -> ScanConfig {
+        let progress_callback = self.progress_callback.clone().map(|cb| {
+            let pause = pause.clone();
+            Arc::new(move |path: &str| {
+                pause.wait_while_paused();
+                cb(path);
+            }) as ProgressCallback
+        });
+        let item_callback = self.item_callback.clone().map(|cb| {
+            let pause = pause.clone();
+            Arc::new(move |item: ScanResult| {
+                pause.wait_while_paused();
+                cb(item);
+            }) as ItemCallback
+        });
+        let phase_callback = self.phase_callback.clone().map(|cb| {
+            let pause = pause.clone();
+            Arc::new(move |phase: ScanPhase| {
+                pause.wait_while_paused();
+                cb(phase);
+            }) as PhaseCallback
+        });
+
+        ScanConfig {
+            progress_callback,
+            item_callback,
+            phase_callback,
+            ..self.clone()
+        }
+    }
+
+    // Copy whose progress reports are rate-limited. Call flush() on the returned
+    // throttle once the scan ends so its final path is still reported.
+    pub fn throttled(&self, interval: Duration) -> (ScanConfig, Option<Arc<ProgressThrottle>>) {
+        let throttle = self
+            .progress_callback
+            .clone()
+            .map(|cb| ProgressThrottle::new(cb, interval));
+        let progress_callback = throttle
+            .clone()
+            .map(|throttle| Arc::new(move |path: &str| throttle.report(path)) as ProgressCallback);
+
+        let config = ScanConfig {
+            progress_callback,
+            ..self.clone()
+        };
+        (config, throttle)
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -78,6 +349,22 @@ pub struct ScanResult {
     pub safety_level: SafetyLevel,
     pub category: ScannerCategory,
     pub metadata: HashMap<String, String>,
+    // Largest entries inside an aggregate item, for drill-down in Review.
+    // Empty for items that cannot be expanded.
+    pub children: Vec<ScanResult>,
+    // Every copy of a duplicate group, oldest first, keeper included. Empty
+    // for everything else.
+    pub members: Vec<PathBuf>,
+}
+
+// Derived from the canonical path so the same file keeps its id across scans.
+pub fn stable_id(prefix: &str, path: &Path) -> String {
+    use sha2::{Digest, Sha256};
+
+    let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let digest = Sha256::digest(canonical.as_os_str().as_encoded_bytes());
+    let hash: String = digest[..6].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}_{}", prefix, hash)
 }
 
 impl ScanResult {
@@ -93,6 +380,8 @@ impl ScanResult {
             safety_level: SafetyLevel::Safe,
             category: ScannerCategory::System,
             metadata: HashMap::new(),
+            children: Vec::new(),
+            members: Vec::new(),
         }
     }
 
@@ -125,6 +414,30 @@ impl ScanResult {
         self.last_modified = dt;
         self
     }
+
+    pub fn with_safety_reason(mut self, reason: impl Into<String>) -> Self {
+        self.metadata
+            .insert("safety_reason".to_string(), reason.into());
+        self
+    }
+
+    // Longer "why is this here" text for `scan --explain`: what the item is,
+    // what recreates it, and what you lose by deleting it.
+    pub fn with_explanation(mut self, text: impl Into<String>) -> Self {
+        self.metadata.insert("explanation".to_string(), text.into());
+        self
+    }
+
+    pub fn explanation(&self) -> Option<&str> {
+        self.metadata.get("explanation").map(|s| s.as_str())
+    }
+
+    pub fn safety_reason(&self) -> &str {
+        self.metadata
+            .get("safety_reason")
+            .map(|s| s.as_str())
+            .unwrap_or_else(|| self.safety_level.default_reason())
+    }
 }
 
 pub trait Scanner: Send + Sync {
@@ -136,12 +449,45 @@ pub trait Scanner: Send + Sync {
     fn is_available(&self) -> bool {
         true
     }
+    fn default_safety(&self) -> SafetyLevel {
+        SafetyLevel::Safe
+    }
+    // Walks whole trees or hashes file contents; skipped by quick modes like `scan --brief`.
+    fn is_expensive(&self) -> bool {
+        false
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct CleanConfig {
     pub dry_run: bool,
     pub log_history: bool,
+    pub skip_open_files: bool,
+    // Pause between deletions so a big clean doesn't saturate the disk.
+    pub throttle_ms: Option<u64>,
+    // Off with --safe-only: Caution items are skipped, only Safe ones go.
+    pub clean_caution: bool,
+    pub trash_mode: TrashMode,
+}
+
+// Where cleaned items go: ~/.Trash, from where they can still be put back,
+// or straight to deletion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashMode {
+    #[default]
+    MoveToTrash,
+    Permanent,
+}
+
+impl TrashMode {
+    pub fn from_flag(permanent: bool) -> Self {
+        if permanent {
+            TrashMode::Permanent
+        } else {
+            TrashMode::MoveToTrash
+        }
+    }
 }
 
 impl Default for CleanConfig {
@@ -149,6 +495,10 @@ impl Default for CleanConfig {
         Self {
             dry_run: true,
             log_history: true,
+            skip_open_files: false,
+            throttle_ms: None,
+            clean_caution: true,
+            trash_mode: TrashMode::MoveToTrash,
         }
     }
 }
@@ -158,17 +508,64 @@ pub struct CleanResult {
     pub success_count: usize,
     pub failed_count: usize,
     pub total_freed: u64,
+    // Sizes of items moved to the Trash. They take up space until the Trash
+    // is emptied, so they are not counted in `total_freed`.
+    pub moved_to_trash: u64,
     pub failed_items: Vec<(PathBuf, String)>,
+    pub skipped_items: Vec<(PathBuf, String)>,
+    // Removed by something else between scan and clean; nothing was freed.
+    pub already_gone: Vec<PathBuf>,
+    // Caution items left alone because clean_caution was off.
+    pub excluded_caution: Vec<PathBuf>,
+    // Matched a .cleanmacignore, e.g. in a plan written before the rule existed.
+    pub ignored: Vec<PathBuf>,
+    // One per maintenance command that actually ran.
+    pub commands: Vec<CommandOutcome>,
+    pub trash_mode: TrashMode,
+    // Nothing was touched: a dry run, or one forced by audit mode.
+    pub dry_run: bool,
+    // Asked to go to the Trash but couldn't (another volume, ...), so deleted.
+    pub deleted_permanently: Vec<PathBuf>,
+    // Set when the removals were written to history; `undo` takes it.
+    pub run_id: Option<String>,
     pub duration: Duration,
 }
 
+// What a maintenance command printed and returned. `freed` is the change in
+// free space on the volume the task clears a cache on, if it clears one.
+#[derive(Debug, Clone)]
+pub struct CommandOutcome {
+    pub task: String,
+    pub command: String,
+    pub exit_code: Option<i32>,
+    pub stdout: String,
+    pub stderr: String,
+    pub freed: Option<u64>,
+}
+
+impl CommandOutcome {
+    pub fn succeeded(&self) -> bool {
+        self.exit_code == Some(0)
+    }
+}
+
 impl CleanResult {
     pub fn new() -> Self {
         Self {
             success_count: 0,
             failed_count: 0,
             total_freed: 0,
+            moved_to_trash: 0,
             failed_items: Vec::new(),
+            skipped_items: Vec::new(),
+            already_gone: Vec::new(),
+            excluded_caution: Vec::new(),
+            ignored: Vec::new(),
+            commands: Vec::new(),
+            trash_mode: TrashMode::MoveToTrash,
+            dry_run: false,
+            deleted_permanently: Vec::new(),
+            run_id: None,
             duration: Duration::ZERO,
         }
     }
@@ -182,7 +579,11 @@ impl Default for CleanResult {
 
 pub trait Cleaner: Send + Sync {
     fn clean(&self, items: &[ScanResult], config: &CleanConfig) -> Result<CleanResult>;
-    fn can_clean(&self, item: &ScanResult) -> bool {
-        item.safety_level == SafetyLevel::Safe
+    fn can_clean(&self, item: &ScanResult, config: &CleanConfig) -> bool {
+        match item.safety_level {
+            SafetyLevel::Safe => true,
+            SafetyLevel::Caution => config.clean_caution,
+            SafetyLevel::Protected => false,
+        }
     }
 }
@@ -0,0 +1,132 @@
+use std::collections::{HashMap, HashSet};
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+struct Inode {
+    links: u64,
+    seen: u64,
+    bytes: u64,
+}
+
+// Bytes that deleting every file under `paths` would really give back. A file
+// with more hard links than we found under `paths` lives on through the other
+// links and frees nothing; files linked several times inside `paths` count once.
+// On APFS only a clone's private (unshared) bytes count, which undercounts when
+// every clone of a file is being deleted, but never promises space that stays.
+pub fn unique_reclaimable(paths: &[&Path]) -> u64 {
+    let mut inodes: HashMap<(u64, u64), Inode> = HashMap::new();
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+
+    for path in paths {
+        for entry in WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+            if !entry.file_type().is_file() || !visited.insert(entry.path().to_path_buf()) {
+                continue;
+            }
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            inodes
+                .entry((meta.dev(), meta.ino()))
+                .or_insert_with(|| Inode {
+                    links: meta.nlink().max(1),
+                    seen: 0,
+                    bytes: private_size(entry.path())
+                        .map_or(meta.len(), |private| private.min(meta.len())),
+                })
+                .seen += 1;
+        }
+    }
+
+    inodes
+        .values()
+        .filter(|inode| inode.seen >= inode.links)
+        .map(|inode| inode.bytes)
+        .sum()
+}
+
+// ATTR_CMNEXT_PRIVATESIZE: the bytes of this file not shared with any clone.
+#[cfg(target_os = "macos")]
+fn private_size(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    #[repr(C, packed(4))]
+    struct AttrBuf {
+        length: u32,
+        private_size: libc::off_t,
+    }
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let mut attrs = libc::attrlist {
+        bitmapcount: libc::ATTR_BIT_MAP_COUNT,
+        reserved: 0,
+        commonattr: 0,
+        volattr: 0,
+        dirattr: 0,
+        fileattr: 0,
+        forkattr: libc::ATTR_CMNEXT_PRIVATESIZE,
+    };
+    let mut buf = AttrBuf {
+        length: 0,
+        private_size: 0,
+    };
+
+    let rc = unsafe {
+        libc::getattrlist(
+            c_path.as_ptr(),
+            &mut attrs as *mut libc::attrlist as *mut libc::c_void,
+            &mut buf as *mut AttrBuf as *mut libc::c_void,
+            std::mem::size_of::<AttrBuf>(),
+            libc::FSOPT_NOFOLLOW | libc::FSOPT_ATTR_CMN_EXTENDED,
+        )
+    };
+    let (length, size) = (buf.length, buf.private_size);
+    (rc == 0 && length as usize >= std::mem::size_of::<AttrBuf>()).then_some(size.max(0) as u64)
+}
+
+#[cfg(not(target_os = "macos"))]
+fn private_size(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("cleanmac-reclaim-{}", name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn hard_links_inside_the_set_count_once() {
+        let dir = temp_dir("inside");
+        fs::write(dir.join("a"), vec![0u8; 1000]).unwrap();
+        fs::hard_link(dir.join("a"), dir.join("b")).unwrap();
+        fs::write(dir.join("c"), vec![0u8; 10]).unwrap();
+
+        assert_eq!(unique_reclaimable(&[dir.as_path()]), 1010);
+        // Naming the same tree twice does not double anything.
+        assert_eq!(unique_reclaimable(&[dir.as_path(), &dir.join("a")]), 1010);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn files_linked_from_outside_free_nothing() {
+        let dir = temp_dir("outside");
+        fs::create_dir_all(dir.join("cleaned")).unwrap();
+        fs::write(dir.join("cleaned/shared"), vec![0u8; 1000]).unwrap();
+        fs::hard_link(dir.join("cleaned/shared"), dir.join("kept")).unwrap();
+        fs::write(dir.join("cleaned/own"), vec![0u8; 10]).unwrap();
+
+        assert_eq!(unique_reclaimable(&[&dir.join("cleaned")]), 10);
+        assert_eq!(unique_reclaimable(&[dir.as_path()]), 1010);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -51,18 +51,27 @@ impl SafetyChecker {
         SafetyLevel::Safe
     }
 
-    fn is_hidden_system(&self, path: &Path) -> bool {
-        path.file_name()
-            .and_then(|n| n.to_str())
-            .map(|n| n.starts_with('.') && !n.starts_with(".."))
-            .unwrap_or(false)
+    // Why check_path didn't rate the path Safe; None when it is Safe.
+    pub fn explain(&self, path: &Path) -> Option<&'static str> {
+        let path_str = path.to_string_lossy();
+
+        if self.protected_paths.iter().any(|p| path_str.starts_with(p)) {
+            return Some("Inside a protected system directory");
+        }
+        if self.critical_patterns.iter().any(|p| path_str.contains(p)) {
+            return Some("Used by Spotlight, Keychain or other core services");
+        }
+        if self.is_hidden_system(path) {
+            return Some("Hidden file that may be used by the system");
+        }
+        None
     }
 
-    pub fn is_safe_to_delete(&self, path: &Path) -> bool {
-        matches!(
-            self.check_path(path),
-            SafetyLevel::Safe | SafetyLevel::Caution
-        )
+    fn is_hidden_system(&self, path: &Path) -> bool {
+        // Bytes, so a hidden name that isn't UTF-8 is still treated as hidden.
+        path.file_name()
+            .map(|n| n.as_encoded_bytes())
+            .is_some_and(|n| n.starts_with(b".") && !n.starts_with(b".."))
     }
 }
 
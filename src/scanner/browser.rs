@@ -1,7 +1,7 @@
 use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
 use crate::plugin::{SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 pub struct BrowserCacheScanner {
     cache_paths: Vec<(String, PathBuf)>,
@@ -9,8 +9,10 @@ pub struct BrowserCacheScanner {
 
 impl BrowserCacheScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        Self::with_home(&crate::user::home_dir())
+    }
 
+    fn with_home(home: &Path) -> Self {
         Self {
             cache_paths: vec![
                 (
@@ -89,11 +91,20 @@ impl Scanner for BrowserCacheScanner {
                 .with_file_count(file_count)
                 .with_category(ScannerCategory::Browser)
                 .with_safety(SafetyLevel::Safe)
+                .with_safety_reason("Browser cache; pages re-download it as needed")
+                .with_explanation(format!(
+                    "{} keeps copies of images, scripts and pages here so sites load faster. \
+                     The browser refills it as you browse. Quit {} first: clearing it while \
+                     running can sign you out of some sites and you may have to log in again.",
+                    browser_name, browser_name
+                ))
                 .with_last_accessed(get_last_accessed(cache_path))
                 .with_last_modified(get_last_modified(cache_path));
 
                 item.metadata
                     .insert("scanner_id".to_string(), self.id().to_string());
+                item.metadata
+                    .insert("clean_mode".to_string(), "contents".to_string());
 
                 config.report_item(item.clone());
                 items.push(item);
@@ -113,3 +124,30 @@ impl Default for BrowserCacheScanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::temp_home;
+
+    #[test]
+    fn browser_cache_explanation_warns_about_logging_in_again() {
+        let home = temp_home("browser-explain");
+        let cache = home.join("Library/Caches/Google/Chrome");
+        std::fs::create_dir_all(&cache).unwrap();
+        std::fs::write(cache.join("data_0"), vec![0u8; 64]).unwrap();
+
+        let scanner = BrowserCacheScanner::with_home(&home);
+        let config = ScanConfig {
+            min_size: 1,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+
+        let explanation = items[0].explanation().expect("no explanation");
+        assert!(explanation.contains("Chrome"));
+        assert!(explanation.contains("log in again"));
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+}
@@ -1,8 +1,8 @@
 use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
-use crate::plugin::{ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::plugin::{stable_id, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use crate::safety::SafetyChecker;
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 pub struct CacheScanner {
@@ -12,13 +12,13 @@ pub struct CacheScanner {
 
 impl CacheScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        Self::with_home(&crate::user::home_dir())
+    }
 
+    fn with_home(home: &Path) -> Self {
         Self {
-            cache_dirs: vec![
-                home.join("Library/Caches"),
-                home.join("Library/Developer/Xcode/DerivedData"),
-            ],
+            // Xcode's DerivedData is left to the xcode scanner.
+            cache_dirs: vec![home.join("Library/Caches")],
             safety_checker: SafetyChecker::new(),
         }
     }
@@ -45,15 +45,18 @@ impl Scanner for CacheScanner {
                 continue;
             }
 
+            // The cache root itself is never an item; its folders are.
             for entry in WalkDir::new(cache_dir)
-                .max_depth(config.max_depth)
+                .min_depth(1)
+                .max_depth(config.walk_depth())
+                .follow_links(config.follow_symlinks)
                 .into_iter()
-                .filter_map(|e| e.ok())
+                .filter_map(|e| config.readable(e))
                 .filter(|e| e.file_type().is_dir())
             {
                 let path = entry.path();
 
-                if config.excluded_paths.iter().any(|ex| path.starts_with(ex)) {
+                if config.is_excluded(path) {
                     continue;
                 }
 
@@ -64,23 +67,37 @@ impl Scanner for CacheScanner {
                 if size >= config.min_size {
                     let name = path
                         .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("unknown")
-                        .to_string();
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| "unknown".to_string());
 
                     let safety_level = self.safety_checker.check_path(path);
 
                     let mut item =
-                        ScanResult::new(format!("cache_{}", items.len()), name, path.to_path_buf())
+                        ScanResult::new(stable_id("cache", path), name, path.to_path_buf())
                             .with_size(size)
                             .with_file_count(count_files(path))
                             .with_category(ScannerCategory::System)
                             .with_safety(safety_level)
+                            .with_safety_reason(
+                                self.safety_checker
+                                    .explain(path)
+                                    .unwrap_or("App cache; apps rebuild it on demand"),
+                            )
+                            .with_explanation(
+                                "Apps store downloaded and precomputed data in ~/Library/Caches \
+                                 to start and work faster. Each app recreates what it needs, so \
+                                 the first launch after cleaning can be slower.",
+                            )
                             .with_last_accessed(get_last_accessed(path))
                             .with_last_modified(get_last_modified(path));
 
                     item.metadata
                         .insert("scanner_id".to_string(), self.id().to_string());
+                    // Per-app folders directly under the root stay in place.
+                    if entry.depth() == 1 {
+                        item.metadata
+                            .insert("clean_mode".to_string(), "contents".to_string());
+                    }
 
                     config.report_item(item.clone());
                     items.push(item);
@@ -88,7 +105,7 @@ impl Scanner for CacheScanner {
             }
         }
 
-        items.sort_by(|a, b| b.size.cmp(&a.size));
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
         items.truncate(100);
 
         Ok(items)
@@ -104,3 +121,62 @@ impl Default for CacheScanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{nested_tree, temp_home};
+
+    #[test]
+    fn max_depth_zero_is_unlimited() {
+        let home = temp_home("caches-depth");
+        let root = nested_tree(&home.join("Library/Caches"), 6);
+        let deepest = root.join("d0/d1/d2/d3/d4/d5");
+        std::fs::write(deepest.join("blob"), vec![0u8; 64]).unwrap();
+
+        let scanner = CacheScanner::with_home(&home);
+        let config = ScanConfig {
+            min_size: 1,
+            max_depth: 0,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+        assert!(items.iter().any(|i| i.path == deepest));
+
+        let shallow = ScanConfig {
+            max_depth: 2,
+            ..config
+        };
+        let items = scanner.scan(&shallow).unwrap();
+        assert!(!items.iter().any(|i| i.path == deepest));
+    }
+
+    #[test]
+    fn app_cache_folders_list_their_largest_entries_on_demand() {
+        let home = temp_home("caches-children");
+        let root = nested_tree(&home.join("Library/Caches"), 1);
+        let app = root.join("d0");
+        std::fs::write(app.join("big.db"), vec![0u8; 300]).unwrap();
+        std::fs::create_dir_all(app.join("blobs")).unwrap();
+        std::fs::write(app.join("blobs/one"), vec![0u8; 100]).unwrap();
+
+        let scanner = CacheScanner::with_home(&home);
+        let config = ScanConfig {
+            min_size: 1,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+        assert!(!items.iter().any(|i| i.path == root));
+        let item = items.iter().find(|i| i.path == app).unwrap();
+        assert_eq!(item.metadata["clean_mode"], "contents");
+        let blobs = items.iter().find(|i| i.path == app.join("blobs")).unwrap();
+        assert!(!blobs.metadata.contains_key("clean_mode"));
+        assert!(item.children.is_empty());
+        let children = crate::scanner::child_items(item);
+        let children: Vec<(&str, u64)> =
+            children.iter().map(|c| (c.name.as_str(), c.size)).collect();
+        assert_eq!(children, vec![("big.db", 300), ("blobs", 100)]);
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+}
@@ -0,0 +1,361 @@
+use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use anyhow::Result;
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+const DOCKER_DATA: &str = "Library/Containers/com.docker.docker/Data";
+const COLIMA_LIMA: &str = ".colima/_lima";
+const PODMAN_MACHINES: &str = ".local/share/containers/podman/machine";
+
+const DISK_EXTENSIONS: [&str; 3] = ["raw", "qcow2", "img"];
+const VM_DISK_WARNING: &str = "deleting this removes all images/volumes";
+
+pub struct ContainerScanner {
+    home: PathBuf,
+}
+
+// VM disks are sparse: a 64 GB Docker.raw may hold 8 GB. Blocks count what
+// the disk actually gives back.
+fn allocated_size(path: &Path) -> u64 {
+    fs::symlink_metadata(path)
+        .map(|m| m.blocks() * 512)
+        .unwrap_or(0)
+}
+
+fn is_disk_image(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| DISK_EXTENSIONS.contains(&e))
+}
+
+fn dir_allocated_size(path: &Path) -> u64 {
+    WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| allocated_size(e.path()))
+        .sum()
+}
+
+impl ContainerScanner {
+    pub fn new() -> Self {
+        Self::with_home(&crate::user::home_dir())
+    }
+
+    fn with_home(home: &Path) -> Self {
+        Self {
+            home: home.to_path_buf(),
+        }
+    }
+
+    fn make_item(
+        &self,
+        path: &Path,
+        name: String,
+        size: u64,
+        reason: &str,
+        explanation: &str,
+    ) -> ScanResult {
+        let file_count = if path.is_dir() { count_files(path) } else { 1 };
+        let mut item = ScanResult::new(stable_id("container_junk", path), name, path.to_path_buf())
+            .with_size(size)
+            .with_file_count(file_count)
+            .with_category(ScannerCategory::Development)
+            .with_safety(SafetyLevel::Caution)
+            .with_safety_reason(reason)
+            .with_explanation(explanation)
+            .with_last_accessed(get_last_accessed(path))
+            .with_last_modified(get_last_modified(path));
+        item.metadata
+            .insert("scanner_id".to_string(), self.id().to_string());
+        item
+    }
+
+    fn vm_disk(&self, path: &Path, name: String, size: u64, runtime: &str) -> ScanResult {
+        let mut item = self.make_item(
+            path,
+            name,
+            size,
+            "Live VM disk holding every image, container and volume",
+            &format!(
+                "The virtual disk {} runs containers from. Deleting it frees the space \
+                 but {} starts over with no images, containers or volumes.",
+                runtime, runtime
+            ),
+        );
+        item.metadata
+            .insert("warning".to_string(), VM_DISK_WARNING.to_string());
+        item.metadata
+            .insert("runtime".to_string(), runtime.to_string());
+        item
+    }
+
+    fn wanted(&self, path: &Path, size: u64, config: &ScanConfig) -> bool {
+        config.report_progress(&path.display().to_string());
+        size >= config.min_size
+    }
+
+    fn scan_docker_desktop(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        let data = self.home.join(DOCKER_DATA);
+        if !data.is_dir() || config.is_excluded(&data) {
+            return;
+        }
+        // Data/vms/0/data/Docker.raw on current releases, Docker.qcow2 on old ones.
+        for entry in WalkDir::new(&data)
+            .max_depth(4)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy();
+            if !(name == "Docker.raw" || name == "Docker.qcow2") || config.is_excluded(path) {
+                continue;
+            }
+            let size = allocated_size(path);
+            if self.wanted(path, size, config) {
+                items.push(self.vm_disk(
+                    path,
+                    format!("Docker Desktop disk ({})", name),
+                    size,
+                    "Docker Desktop",
+                ));
+            }
+        }
+
+        let logs = data.join("log");
+        if logs.is_dir() && !config.is_excluded(&logs) {
+            let size = calculate_dir_size(&logs);
+            if self.wanted(&logs, size, config) {
+                items.push(self.make_item(
+                    &logs,
+                    "Docker Desktop logs".to_string(),
+                    size,
+                    "Docker Desktop's own logs; needed only to report a bug",
+                    "Logs from Docker Desktop and its VM. Docker starts new ones; \
+                     diagnostics for a pending bug report are lost.",
+                ));
+            }
+        }
+    }
+
+    fn scan_docker_cli(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        let dir = self.home.join(".docker");
+        if !dir.is_dir() || config.is_excluded(&dir) {
+            return;
+        }
+        let size = calculate_dir_size(&dir);
+        if self.wanted(&dir, size, config) {
+            items.push(self.make_item(
+                &dir,
+                "Docker CLI data (~/.docker)".to_string(),
+                size,
+                "CLI settings, contexts and registry logins",
+                "Settings, contexts, plugins and caches of the docker command. Images \
+                 live in the VM disk, not here; deleting this signs you out of \
+                 registries and resets contexts.",
+            ));
+        }
+    }
+
+    // ~/.colima/_lima/<profile> holds each VM's disks; _disks holds the
+    // separate data disks newer Colima keeps images on.
+    fn scan_colima(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        let lima = self.home.join(COLIMA_LIMA);
+        let Ok(entries) = fs::read_dir(&lima) else {
+            return;
+        };
+        for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+            let folder = entry.file_name().to_string_lossy().to_string();
+            let dirs: Vec<(PathBuf, String)> = if folder == "_disks" {
+                let Ok(disks) = fs::read_dir(entry.path()) else {
+                    continue;
+                };
+                disks
+                    .flatten()
+                    .filter(|d| d.path().is_dir())
+                    .map(|d| {
+                        let name = d.file_name().to_string_lossy().to_string();
+                        (d.path(), format!("Colima data disk {}", name))
+                    })
+                    .collect()
+            } else if folder.starts_with('_') {
+                continue;
+            } else {
+                vec![(entry.path(), format!("Colima VM {}", folder))]
+            };
+
+            for (path, name) in dirs {
+                if config.is_excluded(&path) {
+                    continue;
+                }
+                let size = dir_allocated_size(&path);
+                if self.wanted(&path, size, config) {
+                    items.push(self.vm_disk(&path, name, size, "Colima"));
+                }
+            }
+        }
+    }
+
+    fn scan_podman(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        let Ok(providers) = fs::read_dir(self.home.join(PODMAN_MACHINES)) else {
+            return;
+        };
+        for provider in providers.flatten().filter(|e| e.path().is_dir()) {
+            let Ok(entries) = fs::read_dir(provider.path()) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_file() || !is_disk_image(&path) || config.is_excluded(&path) {
+                    continue;
+                }
+                let size = allocated_size(&path);
+                if self.wanted(&path, size, config) {
+                    let machine = path
+                        .file_stem()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    items.push(self.vm_disk(
+                        &path,
+                        format!("Podman machine {}", machine),
+                        size,
+                        "Podman",
+                    ));
+                }
+            }
+        }
+    }
+}
+
+impl Scanner for ContainerScanner {
+    fn id(&self) -> &str {
+        "container_junk"
+    }
+
+    fn name(&self) -> &str {
+        "Container Runtimes"
+    }
+
+    fn category(&self) -> ScannerCategory {
+        ScannerCategory::Development
+    }
+
+    fn scan(&self, config: &ScanConfig) -> Result<Vec<ScanResult>> {
+        let mut items = Vec::new();
+        self.scan_docker_desktop(config, &mut items);
+        self.scan_docker_cli(config, &mut items);
+        self.scan_colima(config, &mut items);
+        self.scan_podman(config, &mut items);
+
+        for item in &items {
+            config.report_item(item.clone());
+        }
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
+        Ok(items)
+    }
+
+    fn is_available(&self) -> bool {
+        [DOCKER_DATA, ".docker", COLIMA_LIMA, PODMAN_MACHINES]
+            .iter()
+            .any(|dir| self.home.join(dir).is_dir())
+    }
+
+    fn default_safety(&self) -> SafetyLevel {
+        SafetyLevel::Caution
+    }
+}
+
+impl Default for ContainerScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::temp_home;
+
+    #[test]
+    fn finds_runtime_data_and_flags_vm_disks() {
+        let home = temp_home("containers");
+        for (file, size) in [
+            (
+                "Library/Containers/com.docker.docker/Data/vms/0/data/Docker.raw",
+                8192,
+            ),
+            (
+                "Library/Containers/com.docker.docker/Data/log/vm/init.log",
+                100,
+            ),
+            (".docker/config.json", 200),
+            (".colima/_lima/colima/diffdisk", 4096),
+            (".colima/_lima/_config/override.yaml", 50),
+            (".colima/_lima/_disks/colima/datadisk", 4096),
+            (
+                ".local/share/containers/podman/machine/applehv/podman-machine-default-arm64.raw",
+                4096,
+            ),
+            (
+                ".local/share/containers/podman/machine/applehv/podman-machine-default.json",
+                50,
+            ),
+        ] {
+            let path = home.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, vec![1u8; size]).unwrap();
+        }
+        // Sparse, like a fresh Docker.raw: 1 GB long, 8 KB on disk.
+        let raw = home.join(DOCKER_DATA).join("vms/0/data/Docker.raw");
+        fs::File::options()
+            .write(true)
+            .open(&raw)
+            .unwrap()
+            .set_len(1 << 30)
+            .unwrap();
+
+        let scanner = ContainerScanner::with_home(&home);
+        assert!(scanner.is_available());
+        let config = ScanConfig {
+            min_size: 1,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+
+        let mut found: Vec<(&str, Option<&str>)> = items
+            .iter()
+            .map(|i| {
+                (
+                    i.name.as_str(),
+                    i.metadata.get("warning").map(|w| w.as_str()),
+                )
+            })
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("Colima VM colima", Some(VM_DISK_WARNING)),
+                ("Colima data disk colima", Some(VM_DISK_WARNING)),
+                ("Docker CLI data (~/.docker)", None),
+                ("Docker Desktop disk (Docker.raw)", Some(VM_DISK_WARNING)),
+                ("Docker Desktop logs", None),
+                (
+                    "Podman machine podman-machine-default-arm64",
+                    Some(VM_DISK_WARNING)
+                ),
+            ]
+        );
+        assert!(items.iter().all(|i| i.safety_level == SafetyLevel::Caution
+            && i.metadata["scanner_id"] == "container_junk"));
+
+        let docker = items.iter().find(|i| i.path == raw).unwrap();
+        assert!(docker.size >= 8192 && docker.size < 1 << 20);
+
+        let _ = fs::remove_dir_all(&home);
+    }
+}
@@ -0,0 +1,327 @@
+use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
+use crate::config::Config;
+use crate::plugin::registry::PluginRegistry;
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::safety::SafetyChecker;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScannersFile {
+    #[serde(default)]
+    scanner: Vec<ScannerDef>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScannerDef {
+    id: String,
+    name: String,
+    #[serde(default = "default_category")]
+    category: CategoryDef,
+    entries: Vec<EntryDef>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CategoryDef {
+    System,
+    Browser,
+    Development,
+    Trash,
+}
+
+fn default_category() -> CategoryDef {
+    CategoryDef::System
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SafetyDef {
+    #[default]
+    Safe,
+    Caution,
+    Protected,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EntryDef {
+    label: String,
+    path: String,
+    #[serde(default)]
+    safety: SafetyDef,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    label: String,
+    path: PathBuf,
+    safety: SafetyLevel,
+}
+
+// A scanner defined in scanners.toml: fixed paths, each reported as one item
+// with the label and safety level the user gave it.
+#[derive(Debug, Clone)]
+pub struct ConfigurableScanner {
+    id: String,
+    name: String,
+    category: ScannerCategory,
+    entries: Vec<Entry>,
+}
+
+fn valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// "~/x" or an absolute path, without "..", and never a protected system
+// directory or the whole home folder.
+fn resolve_path(raw: &str, home: &Path, checker: &SafetyChecker) -> Result<PathBuf> {
+    let path = match raw.strip_prefix("~/") {
+        Some(rest) => home.join(rest),
+        None if raw.starts_with('/') => PathBuf::from(raw),
+        None => bail!("'{}' must be absolute or start with ~/", raw),
+    };
+    if path.components().any(|c| c == Component::ParentDir) {
+        bail!("'{}' must not contain ..", raw);
+    }
+    if path.parent().is_none() || path == home || home.starts_with(&path) {
+        bail!("'{}' would cover your whole home folder or disk", raw);
+    }
+    if checker.check_path(&path) == SafetyLevel::Protected {
+        bail!("'{}' is inside a protected system directory", raw);
+    }
+    Ok(path)
+}
+
+pub fn parse_scanners(content: &str, home: &Path) -> Result<Vec<ConfigurableScanner>> {
+    let file: ScannersFile = toml::from_str(content)?;
+    let checker = SafetyChecker::new();
+    let builtin = PluginRegistry::builtin_ids();
+    let mut seen = HashSet::new();
+    let mut scanners = Vec::new();
+
+    for def in file.scanner {
+        if !valid_id(&def.id) {
+            bail!(
+                "scanner id '{}' may only use lowercase letters, digits and _",
+                def.id
+            );
+        }
+        if builtin.contains(&def.id) {
+            bail!(
+                "scanner id '{}' is already used by a built-in scanner",
+                def.id
+            );
+        }
+        if !seen.insert(def.id.clone()) {
+            bail!("scanner id '{}' is defined twice", def.id);
+        }
+        if def.entries.is_empty() {
+            bail!("scanner '{}' has no entries", def.id);
+        }
+
+        let entries = def
+            .entries
+            .into_iter()
+            .map(|e| {
+                let path = resolve_path(&e.path, home, &checker)
+                    .with_context(|| format!("scanner '{}'", def.id))?;
+                let safety = match e.safety {
+                    SafetyDef::Safe => SafetyLevel::Safe,
+                    SafetyDef::Caution => SafetyLevel::Caution,
+                    SafetyDef::Protected => SafetyLevel::Protected,
+                };
+                Ok(Entry {
+                    label: e.label,
+                    path,
+                    safety,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        scanners.push(ConfigurableScanner {
+            id: def.id,
+            name: def.name,
+            category: match def.category {
+                CategoryDef::System => ScannerCategory::System,
+                CategoryDef::Browser => ScannerCategory::Browser,
+                CategoryDef::Development => ScannerCategory::Development,
+                CategoryDef::Trash => ScannerCategory::Trash,
+            },
+            entries,
+        });
+    }
+    Ok(scanners)
+}
+
+// Scanners from scanners.toml next to config.toml; none if the file is absent.
+pub fn load_user_scanners() -> Result<Vec<ConfigurableScanner>> {
+    let path = Config::scanners_path();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(&path)?;
+    parse_scanners(&content, &crate::user::home_dir())
+        .with_context(|| format!("invalid {}", path.display()))
+}
+
+impl Scanner for ConfigurableScanner {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn category(&self) -> ScannerCategory {
+        self.category
+    }
+
+    fn scan(&self, config: &ScanConfig) -> Result<Vec<ScanResult>> {
+        let mut items = Vec::new();
+
+        for entry in &self.entries {
+            let path = entry.path.as_path();
+            if config.is_excluded(path) {
+                continue;
+            }
+            let Ok(metadata) = path.symlink_metadata() else {
+                continue;
+            };
+            config.report_progress(&path.display().to_string());
+
+            let (size, file_count) = if metadata.is_dir() {
+                (calculate_dir_size(path), count_files(path))
+            } else {
+                (metadata.len(), 1)
+            };
+            if size < config.min_size {
+                continue;
+            }
+
+            let mut item = ScanResult::new(
+                stable_id(&self.id, path),
+                entry.label.clone(),
+                path.to_path_buf(),
+            )
+            .with_size(size)
+            .with_file_count(file_count)
+            .with_category(self.category)
+            .with_safety(entry.safety)
+            .with_safety_reason("Added by you in scanners.toml")
+            .with_last_accessed(get_last_accessed(path))
+            .with_last_modified(get_last_modified(path));
+            item.metadata
+                .insert("scanner_id".to_string(), self.id.clone());
+
+            config.report_item(item.clone());
+            items.push(item);
+        }
+
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
+        Ok(items)
+    }
+
+    fn is_available(&self) -> bool {
+        self.entries.iter().any(|e| e.path.exists())
+    }
+
+    fn default_safety(&self) -> SafetyLevel {
+        self.entries
+            .iter()
+            .map(|e| e.safety)
+            .find(|s| *s != SafetyLevel::Safe)
+            .unwrap_or(SafetyLevel::Safe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::temp_home;
+
+    #[test]
+    fn loads_a_two_entry_definition() {
+        let home = temp_home("custom-scanner");
+        std::fs::create_dir_all(home.join("Library/Caches/Acme")).unwrap();
+        std::fs::write(home.join("Library/Caches/Acme/blob"), vec![0u8; 300]).unwrap();
+        std::fs::write(home.join("acme.log"), vec![0u8; 200]).unwrap();
+
+        let scanners = parse_scanners(
+            r#"
+            [[scanner]]
+            id = "acme"
+            name = "Acme Leftovers"
+            category = "development"
+
+            [[scanner.entries]]
+            label = "Acme cache"
+            path = "~/Library/Caches/Acme"
+
+            [[scanner.entries]]
+            label = "Acme log"
+            path = "~/acme.log"
+            safety = "caution"
+            "#,
+            &home,
+        )
+        .unwrap();
+        assert_eq!(scanners.len(), 1);
+        let scanner = &scanners[0];
+        assert_eq!(scanner.id(), "acme");
+        assert_eq!(scanner.category(), ScannerCategory::Development);
+        assert_eq!(scanner.default_safety(), SafetyLevel::Caution);
+
+        let config = ScanConfig {
+            min_size: 1,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+        let found: Vec<(&str, u64, SafetyLevel)> = items
+            .iter()
+            .map(|i| (i.name.as_str(), i.size, i.safety_level))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Acme cache", 300, SafetyLevel::Safe),
+                ("Acme log", 200, SafetyLevel::Caution)
+            ]
+        );
+        assert!(items.iter().all(|i| i.metadata["scanner_id"] == "acme"));
+    }
+
+    #[test]
+    fn rejects_bad_ids_and_paths() {
+        let home = Path::new("/Users/me");
+        let def = |id: &str, path: &str| {
+            format!(
+                "[[scanner]]\nid = \"{}\"\nname = \"X\"\n[[scanner.entries]]\nlabel = \"x\"\npath = \"{}\"\n",
+                id, path
+            )
+        };
+
+        assert!(parse_scanners(&def("acme", "~/Library/Acme"), home).is_ok());
+        assert!(parse_scanners(&def("Acme!", "~/Library/Acme"), home).is_err());
+        assert!(parse_scanners(&def("trash", "~/Library/Acme"), home).is_err());
+        assert!(parse_scanners(&def("browser_cache", "~/Library/Acme"), home).is_err());
+        assert!(parse_scanners(&def("acme", "Library/Acme"), home).is_err());
+        assert!(parse_scanners(&def("acme", "~/Library/../.."), home).is_err());
+        assert!(parse_scanners(&def("acme", "/Users"), home).is_err());
+        assert!(parse_scanners(&def("acme", "/System/Library/Caches"), home).is_err());
+        let twice = format!(
+            "{}{}",
+            def("acme", "~/Library/A"),
+            def("acme", "~/Library/B")
+        );
+        assert!(parse_scanners(&twice, home).is_err());
+    }
+}
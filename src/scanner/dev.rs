@@ -1,29 +1,39 @@
 use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
-use crate::plugin::{ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use crate::safety::SafetyChecker;
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+// Python leaves these next to the sources, so they are hunted with a depth-limited walk
+// instead of a `**` glob.
+const PYTHON_CACHE_DIRS: &[&str] = &["__pycache__", ".pytest_cache", ".mypy_cache"];
 
 pub struct DevJunkScanner {
-    patterns: Vec<(&'static str, &'static str)>,
+    patterns: Vec<(&'static str, &'static str, &'static str)>,
     search_roots: Vec<PathBuf>,
+    // Caches kept by package managers and ML tools at fixed locations. Ones
+    // that also hold installed packages are Caution.
+    tool_caches: Vec<(&'static str, PathBuf, &'static str, SafetyLevel)>,
+    pyenv_root: PathBuf,
     safety_checker: SafetyChecker,
 }
 
 impl DevJunkScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        Self::with_home(&crate::user::home_dir())
+    }
 
+    fn with_home(home: &Path) -> Self {
         Self {
             patterns: vec![
-                ("node_modules", "**/node_modules"),
-                ("target", "**/target"),
-                (".gradle", "**/.gradle"),
-                ("build", "**/build"),
-                ("dist", "**/dist"),
-                (".cache", "**/.cache"),
-                ("__pycache__", "**/__pycache__"),
-                (".venv", "**/.venv"),
+                ("node_modules", "**/node_modules", "node"),
+                ("target", "**/target", "rust"),
+                (".gradle", "**/.gradle", "java"),
+                ("build", "**/build", "generic"),
+                ("dist", "**/dist", "generic"),
+                (".cache", "**/.cache", "generic"),
+                (".venv", "**/.venv", "python"),
             ],
             search_roots: vec![
                 home.join("Documents"),
@@ -33,9 +43,207 @@ impl DevJunkScanner {
                 home.join("src"),
                 home.join("code"),
             ],
+            tool_caches: vec![
+                (
+                    "pip cache",
+                    home.join(".cache/pip"),
+                    "python",
+                    SafetyLevel::Safe,
+                ),
+                (
+                    "pip cache",
+                    home.join("Library/Caches/pip"),
+                    "python",
+                    SafetyLevel::Safe,
+                ),
+                ("RubyGems", home.join(".gem"), "ruby", SafetyLevel::Caution),
+                (
+                    "Bundler cache",
+                    home.join(".bundle/cache"),
+                    "ruby",
+                    SafetyLevel::Safe,
+                ),
+                (
+                    "Hugging Face cache",
+                    home.join(".cache/huggingface"),
+                    "ml",
+                    SafetyLevel::Safe,
+                ),
+                (
+                    "PyTorch cache",
+                    home.join(".cache/torch"),
+                    "ml",
+                    SafetyLevel::Safe,
+                ),
+                (
+                    "Ollama models",
+                    home.join(".ollama/models"),
+                    "ml",
+                    SafetyLevel::Safe,
+                ),
+            ],
+            pyenv_root: home.join(".pyenv"),
             safety_checker: SafetyChecker::new(),
         }
     }
+
+    fn make_item(
+        &self,
+        path: &Path,
+        size: u64,
+        kind: &str,
+        ecosystem: &str,
+        safety_level: SafetyLevel,
+        reason: &str,
+    ) -> ScanResult {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let mut item = ScanResult::new(
+            stable_id(&format!("dev_{}", kind), path),
+            format!("{} ({})", name, kind),
+            path.to_path_buf(),
+        )
+        .with_size(size)
+        .with_file_count(count_files(path))
+        .with_category(ScannerCategory::Development)
+        .with_safety(safety_level)
+        .with_safety_reason(self.safety_checker.explain(path).unwrap_or(reason))
+        .with_explanation(format!(
+            "{} left by {} tooling. It is recreated by the next build, install or run, \
+             which will take longer and may need network access.",
+            kind, ecosystem
+        ))
+        .with_last_accessed(get_last_accessed(path))
+        .with_last_modified(get_last_modified(path));
+
+        item.metadata
+            .insert("scanner_id".to_string(), self.id().to_string());
+        item.metadata
+            .insert("ecosystem".to_string(), ecosystem.to_string());
+        item
+    }
+
+    // A tool cache keeps the level we gave it unless the path itself is protected.
+    fn rated(&self, path: &Path, level: SafetyLevel) -> SafetyLevel {
+        match self.safety_checker.check_path(path) {
+            SafetyLevel::Protected => SafetyLevel::Protected,
+            _ => level,
+        }
+    }
+
+    fn scan_tool_caches(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        for (label, path, ecosystem, level) in &self.tool_caches {
+            if !path.is_dir() || config.is_excluded(path) {
+                continue;
+            }
+            config.report_progress(&path.to_string_lossy());
+
+            let size = calculate_dir_size(path);
+            if size < config.min_size {
+                continue;
+            }
+
+            let mut item = self.make_item(
+                path,
+                size,
+                label,
+                ecosystem,
+                self.rated(path, *level),
+                if *level == SafetyLevel::Safe {
+                    "Package or model cache; downloaded again when needed"
+                } else {
+                    "Installed packages; whatever uses them has to reinstall them"
+                },
+            );
+            item.name = label.to_string();
+            config.report_item(item.clone());
+            items.push(item);
+        }
+    }
+
+    // Every installed pyenv version except the ones selected in ~/.pyenv/version.
+    fn scan_pyenv_versions(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        let versions_dir = self.pyenv_root.join("versions");
+        let Ok(entries) = std::fs::read_dir(&versions_dir) else {
+            return;
+        };
+        let active: Vec<String> = std::fs::read_to_string(self.pyenv_root.join("version"))
+            .unwrap_or_default()
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect();
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let version = entry.file_name().to_string_lossy().to_string();
+            if !path.is_dir() || active.contains(&version) || config.is_excluded(&path) {
+                continue;
+            }
+
+            let size = calculate_dir_size(&path);
+            if size < config.min_size {
+                continue;
+            }
+
+            let item = self.make_item(
+                &path,
+                size,
+                "pyenv",
+                "python",
+                self.rated(&path, SafetyLevel::Caution),
+                "Installed Python version; virtualenvs built on it stop working",
+            );
+            config.report_item(item.clone());
+            items.push(item);
+        }
+    }
+
+    fn scan_python_caches(&self, root: &Path, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        let mut walker = WalkDir::new(root)
+            .max_depth(config.walk_depth())
+            .follow_links(config.follow_symlinks)
+            .into_iter();
+
+        while let Some(entry) = walker.next() {
+            let Some(entry) = config.readable(entry) else {
+                continue;
+            };
+            if !entry.file_type().is_dir() {
+                continue;
+            }
+            let path = entry.path();
+            if config.is_excluded(path) {
+                walker.skip_current_dir();
+                continue;
+            }
+
+            let name = entry.file_name().to_string_lossy();
+            let Some(kind) = PYTHON_CACHE_DIRS.iter().find(|k| **k == name) else {
+                continue;
+            };
+            walker.skip_current_dir();
+
+            let size = calculate_dir_size(path);
+            if size < config.min_size {
+                continue;
+            }
+
+            let item = self.make_item(
+                path,
+                size,
+                kind,
+                "python",
+                self.safety_checker.check_path(path),
+                "Python bytecode or tool cache; recreated on the next run",
+            );
+            config.report_item(item.clone());
+            items.push(item);
+        }
+    }
 }
 
 impl Scanner for DevJunkScanner {
@@ -59,7 +267,7 @@ impl Scanner for DevJunkScanner {
                 continue;
             }
 
-            for (pattern_name, pattern) in &self.patterns {
+            for (pattern_name, pattern, ecosystem) in &self.patterns {
                 let full_pattern = root.join(pattern);
                 config.report_progress(&full_pattern.to_string_lossy());
 
@@ -68,44 +276,36 @@ impl Scanner for DevJunkScanner {
                         continue;
                     }
 
-                    if config.excluded_paths.iter().any(|ex| entry.starts_with(ex)) {
+                    if config.is_excluded(&entry) {
                         continue;
                     }
 
                     let size = calculate_dir_size(&entry);
 
                     if size >= config.min_size {
-                        let name = entry
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("unknown")
-                            .to_string();
-
-                        let safety_level = self.safety_checker.check_path(&entry);
-
-                        let mut item = ScanResult::new(
-                            format!("dev_{}_{}", pattern_name, items.len()),
-                            format!("{} ({})", name, pattern_name),
-                            entry.clone(),
-                        )
-                        .with_size(size)
-                        .with_file_count(count_files(&entry))
-                        .with_category(ScannerCategory::Development)
-                        .with_safety(safety_level)
-                        .with_last_accessed(get_last_accessed(&entry))
-                        .with_last_modified(get_last_modified(&entry));
-
-                        item.metadata
-                            .insert("scanner_id".to_string(), self.id().to_string());
+                        let item = self.make_item(
+                            &entry,
+                            size,
+                            pattern_name,
+                            ecosystem,
+                            self.safety_checker.check_path(&entry),
+                            "Build output or package cache; recreated by the next build",
+                        );
 
                         config.report_item(item.clone());
                         items.push(item);
                     }
                 }
             }
+
+            config.report_progress(&root.to_string_lossy());
+            self.scan_python_caches(root, config, &mut items);
         }
 
-        items.sort_by(|a, b| b.size.cmp(&a.size));
+        self.scan_tool_caches(config, &mut items);
+        self.scan_pyenv_versions(config, &mut items);
+
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
         items.truncate(50);
 
         Ok(items)
@@ -114,6 +314,10 @@ impl Scanner for DevJunkScanner {
     fn is_available(&self) -> bool {
         true
     }
+
+    fn is_expensive(&self) -> bool {
+        true
+    }
 }
 
 impl Default for DevJunkScanner {
@@ -121,3 +325,137 @@ impl Default for DevJunkScanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::temp_home;
+
+    #[test]
+    fn reports_pip_cache_as_safe_python_cache() {
+        let home = temp_home("dev-pip");
+        let pip = home.join(".cache/pip/wheels");
+        std::fs::create_dir_all(&pip).unwrap();
+        std::fs::write(pip.join("pkg.whl"), vec![0u8; 256]).unwrap();
+
+        let scanner = DevJunkScanner::with_home(&home);
+        let config = ScanConfig {
+            min_size: 1,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+
+        let item = items
+            .iter()
+            .find(|i| i.path == home.join(".cache/pip"))
+            .expect("pip cache not reported");
+        assert_eq!(item.safety_level, SafetyLevel::Safe);
+        assert_eq!(item.size, 256);
+        assert_eq!(
+            item.metadata.get("ecosystem").map(|s| s.as_str()),
+            Some("python")
+        );
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn installed_gems_need_review_but_bundler_cache_is_safe() {
+        let home = temp_home("dev-gems");
+        for dir in [".gem/ruby/3.3.0/gems/rake", ".bundle/cache/compact_index"] {
+            std::fs::create_dir_all(home.join(dir)).unwrap();
+            std::fs::write(home.join(dir).join("blob"), vec![0u8; 128]).unwrap();
+        }
+
+        let items = DevJunkScanner::with_home(&home)
+            .scan(&ScanConfig {
+                min_size: 1,
+                ..Default::default()
+            })
+            .unwrap();
+        let level_of = |path: PathBuf| {
+            items
+                .iter()
+                .find(|i| i.path == path)
+                .map(|i| i.safety_level)
+        };
+        assert_eq!(level_of(home.join(".gem")), Some(SafetyLevel::Caution));
+        assert_eq!(
+            level_of(home.join(".bundle/cache")),
+            Some(SafetyLevel::Safe)
+        );
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn finds_pycache_within_depth_and_outside_exclusions() {
+        let home = temp_home("dev-pycache");
+        let pycache = home.join("Projects/app/pkg/__pycache__");
+        std::fs::create_dir_all(&pycache).unwrap();
+        std::fs::write(pycache.join("mod.cpython-312.pyc"), vec![0u8; 128]).unwrap();
+
+        let scanner = DevJunkScanner::with_home(&home);
+        let config = ScanConfig {
+            min_size: 1,
+            max_depth: 3,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+        let found = items
+            .iter()
+            .find(|i| i.path == pycache)
+            .expect("__pycache__ not reported");
+        assert_eq!(
+            found.metadata.get("ecosystem").map(|s| s.as_str()),
+            Some("python")
+        );
+
+        let shallow = ScanConfig {
+            min_size: 1,
+            max_depth: 2,
+            ..Default::default()
+        };
+        let items = scanner.scan(&shallow).unwrap();
+        assert!(!items.iter().any(|i| i.path == pycache));
+
+        let excluded = ScanConfig {
+            min_size: 1,
+            excluded_paths: vec![home.join("Projects/app")],
+            ..Default::default()
+        };
+        let items = scanner.scan(&excluded).unwrap();
+        assert!(!items.iter().any(|i| i.path == pycache));
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn follow_symlinks_setting_reaches_the_walker() {
+        let home = temp_home("dev-symlink");
+        let pycache = home.join("elsewhere/pkg/__pycache__");
+        std::fs::create_dir_all(&pycache).unwrap();
+        std::fs::write(pycache.join("mod.cpython-312.pyc"), vec![0u8; 128]).unwrap();
+        std::fs::create_dir_all(home.join("Projects")).unwrap();
+        std::os::unix::fs::symlink(home.join("elsewhere"), home.join("Projects/linked")).unwrap();
+        let through_link = home.join("Projects/linked/pkg/__pycache__");
+
+        let scanner = DevJunkScanner::with_home(&home);
+        let mut config = crate::config::Config::default();
+        let scan = |config: &crate::config::Config| {
+            let scan_config = ScanConfig {
+                min_size: 1,
+                ..ScanConfig::from_config(config)
+            };
+            scanner.scan(&scan_config).unwrap()
+        };
+
+        assert!(!scan(&config).iter().any(|i| i.path == through_link));
+
+        config.scan.follow_symlinks = true;
+        assert!(ScanConfig::from_config(&config).follow_symlinks);
+        assert!(scan(&config).iter().any(|i| i.path == through_link));
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+}
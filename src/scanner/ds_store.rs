@@ -0,0 +1,187 @@
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+// Packages Finder shows as a single file; whatever is inside belongs to the app.
+const BUNDLE_EXTENSIONS: [&str; 6] = ["app", "framework", "bundle", "plugin", "appex", "kext"];
+
+pub struct DsStoreScanner {
+    home: PathBuf,
+    skipped_dirs: Vec<PathBuf>,
+}
+
+impl DsStoreScanner {
+    pub fn new() -> Self {
+        Self::with_home(&crate::user::home_dir())
+    }
+
+    fn with_home(home: &Path) -> Self {
+        Self {
+            home: home.to_path_buf(),
+            skipped_dirs: vec![home.join("Library"), home.join(".Trash")],
+        }
+    }
+
+    fn skips_dir(&self, path: &Path, config: &ScanConfig) -> bool {
+        self.skipped_dirs.iter().any(|d| path.starts_with(d))
+            || config.is_excluded(path)
+            || config.skips_hidden(path)
+            || path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| BUNDLE_EXTENSIONS.contains(&e.to_lowercase().as_str()))
+    }
+
+    // Finder reads .localized in the standard home folders (Desktop, Documents,
+    // ...) to show their translated names, so those stay.
+    fn is_cruft(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        match name {
+            ".DS_Store" | "Icon\r" => true,
+            ".localized" => path
+                .parent()
+                .and_then(|p| p.parent())
+                .is_some_and(|grandparent| grandparent != self.home),
+            _ => name.len() > 2 && name.starts_with("._"),
+        }
+    }
+}
+
+impl Scanner for DsStoreScanner {
+    fn id(&self) -> &str {
+        "ds_store"
+    }
+
+    fn name(&self) -> &str {
+        ".DS_Store & Cruft"
+    }
+
+    fn category(&self) -> ScannerCategory {
+        ScannerCategory::System
+    }
+
+    // Every match is a few KB at most, so min_size is not applied; the
+    // category total is what matters.
+    fn scan(&self, config: &ScanConfig) -> Result<Vec<ScanResult>> {
+        let mut items = Vec::new();
+
+        let mut count = 0;
+        for entry in WalkDir::new(&self.home)
+            .max_depth(config.walk_depth())
+            .follow_links(config.follow_symlinks)
+            .into_iter()
+            .filter_entry(|e| !(e.file_type().is_dir() && self.skips_dir(e.path(), config)))
+            .filter_map(|e| config.readable(e))
+            .filter(|e| e.file_type().is_file())
+        {
+            let path = entry.path();
+            count += 1;
+            if count % 100 == 0 {
+                config.report_progress(&path.display().to_string());
+            }
+            if !self.is_cruft(path) || config.is_excluded(path) {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+
+            let name = path
+                .strip_prefix(&self.home)
+                .unwrap_or(path)
+                .display()
+                .to_string();
+            let mut item = ScanResult::new(stable_id("ds_store", path), name, path.to_path_buf())
+                .with_size(metadata.len())
+                .with_file_count(1)
+                .with_category(ScannerCategory::System)
+                .with_safety(SafetyLevel::Safe)
+                .with_safety_reason("Finder metadata file; Finder recreates what it needs")
+                .with_explanation(
+                    ".DS_Store keeps a folder's Finder view settings, ._ files carry resource \
+                     forks copied from non-Mac disks, .localized marks a translated folder \
+                     name and Icon\\r holds a custom folder icon. Removing them resets view \
+                     options or a custom icon; no document is affected.",
+                )
+                .with_last_accessed(metadata.accessed().ok().map(|t| t.into()))
+                .with_last_modified(metadata.modified().ok().map(|t| t.into()));
+            item.metadata
+                .insert("scanner_id".to_string(), self.id().to_string());
+
+            config.report_item(item.clone());
+            items.push(item);
+        }
+
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
+        Ok(items)
+    }
+
+    fn is_available(&self) -> bool {
+        self.home.is_dir()
+    }
+
+    fn is_expensive(&self) -> bool {
+        true
+    }
+}
+
+impl Default for DsStoreScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::temp_home;
+    use std::fs;
+
+    #[test]
+    fn finds_finder_cruft_but_not_other_dotfiles() {
+        let home = temp_home("ds-store");
+        for dir in [
+            "Library/Caches",
+            "Desktop",
+            "code/app/.git",
+            "Photos/trip",
+            "Tool.app/Contents",
+        ] {
+            fs::create_dir_all(home.join(dir)).unwrap();
+        }
+        for file in [
+            ".DS_Store",
+            "Library/Caches/.DS_Store",
+            "Desktop/.localized",
+            "Photos/trip/.localized",
+            "Photos/trip/._IMG_0001.jpg",
+            "Photos/trip/Icon\r",
+            "code/app/.gitignore",
+            "code/app/.env",
+            "code/app/.git/.DS_Store",
+            "Tool.app/Contents/.DS_Store",
+            "Photos/trip/IMG_0001.jpg",
+        ] {
+            fs::write(home.join(file), vec![0u8; 64]).unwrap();
+        }
+
+        let items = DsStoreScanner::with_home(&home)
+            .scan(&ScanConfig::default())
+            .unwrap();
+        let mut found: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ".DS_Store",
+                "Photos/trip/._IMG_0001.jpg",
+                "Photos/trip/.localized",
+                "Photos/trip/Icon\r",
+            ]
+        );
+        assert!(items.iter().all(|i| i.safety_level == SafetyLevel::Safe));
+    }
+}
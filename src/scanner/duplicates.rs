@@ -1,20 +1,152 @@
-use crate::plugin::{SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::config::KeepPolicy;
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use anyhow::Result;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 const MIN_SIZE: u64 = 1024;
 
+// Index of the copy that stays; `members` is sorted oldest first.
+fn pick_keeper(members: &[PathBuf], policy: &KeepPolicy) -> usize {
+    match policy {
+        KeepPolicy::Oldest => 0,
+        KeepPolicy::Newest => members.len() - 1,
+        KeepPolicy::PreferPath(text) => members
+            .iter()
+            .position(|p| p.to_string_lossy().contains(text.as_str()))
+            .unwrap_or(0),
+    }
+}
+
+pub fn file_hash(path: &Path) -> Result<String> {
+    use std::fs::File;
+    use std::io::Read;
+
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192];
+
+    loop {
+        let n = file.read(&mut buffer)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+// Makes `keep` the copy that stays and every other member removable. All
+// members have the same size, so the group's size follows from the count.
+pub fn set_keeper(item: &mut ScanResult, keep: &Path) -> bool {
+    if !item.members.iter().any(|p| p == keep) {
+        return false;
+    }
+    let member_size: u64 = item
+        .metadata
+        .get("member_size")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+    let removable: Vec<String> = item
+        .members
+        .iter()
+        .filter(|p| *p != keep)
+        .map(|p| p.display().to_string())
+        .collect();
+
+    item.name = format!(
+        "{} ({} duplicates)",
+        keep.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "?".to_string()),
+        removable.len()
+    );
+    item.path = keep.to_path_buf();
+    item.size = member_size * removable.len() as u64;
+    item.file_count = removable.len() as u64;
+    item.metadata
+        .insert("keep_path".to_string(), keep.display().to_string());
+    item.metadata
+        .insert("original_path".to_string(), keep.display().to_string());
+    item.metadata
+        .insert("duplicate_paths".to_string(), removable.join("|"));
+    true
+}
+
+// Moves the keeper to the next member (wrapping) and returns it.
+pub fn cycle_keeper(item: &mut ScanResult) -> Option<PathBuf> {
+    let current = item.members.iter().position(|p| *p == item.path)?;
+    let next = item.members[(current + 1) % item.members.len()].clone();
+    set_keeper(item, &next).then_some(next)
+}
+
+// Why a copy can't be removed, if it is no longer the file the scan hashed.
+fn refusal(path: &Path, size: u64, hash: &str) -> Option<&'static str> {
+    if !path.is_absolute() {
+        return Some("Duplicate path is not absolute");
+    }
+    match path.symlink_metadata() {
+        Ok(meta) if !meta.is_file() => return Some("Duplicate is no longer a regular file"),
+        Ok(meta) if meta.len() != size => return Some("Duplicate changed since the scan"),
+        Ok(_) => {}
+        // Already gone; the cleaner reports that itself.
+        Err(_) => return None,
+    }
+    match file_hash(path) {
+        Ok(current) if current == hash => None,
+        _ => Some("Duplicate changed since the scan"),
+    }
+}
+
+// What cleaning a group actually removes: one item per non-kept copy, or the
+// path and reason for a copy that no longer matches the group.
+pub fn removable_copies(item: &ScanResult) -> Vec<Result<ScanResult, (PathBuf, String)>> {
+    let size = item
+        .metadata
+        .get("member_size")
+        .and_then(|s| s.parse().ok());
+    let hash = item.metadata.get("member_hash");
+    item.members
+        .iter()
+        .filter(|p| **p != item.path)
+        .map(|path| {
+            let (Some(size), Some(hash)) = (size, hash) else {
+                return Err((
+                    path.clone(),
+                    "Duplicate group has no size or hash".to_string(),
+                ));
+            };
+            if let Some(reason) = refusal(path, size, hash) {
+                return Err((path.clone(), reason.to_string()));
+            }
+            let mut copy = ScanResult::new(stable_id("dup", path), &item.name, path.clone())
+                .with_size(size)
+                .with_file_count(1)
+                .with_category(item.category)
+                .with_safety(item.safety_level);
+            for key in ["scanner_id", "group_id"] {
+                if let Some(value) = item.metadata.get(key) {
+                    copy.metadata.insert(key.to_string(), value.clone());
+                }
+            }
+            Ok(copy)
+        })
+        .collect()
+}
+
 pub struct DuplicatesScanner {
     search_paths: Vec<PathBuf>,
 }
 
 impl DuplicatesScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        Self::with_home(&crate::user::home_dir())
+    }
 
+    fn with_home(home: &Path) -> Self {
         let search_paths = vec![
             home.join("Documents"),
             home.join("Downloads"),
@@ -27,25 +159,6 @@ impl DuplicatesScanner {
         Self { search_paths }
     }
 
-    fn calculate_file_hash(path: &std::path::Path) -> Result<String> {
-        use std::fs::File;
-        use std::io::Read;
-
-        let mut file = File::open(path)?;
-        let mut hasher = Sha256::new();
-        let mut buffer = [0u8; 8192];
-
-        loop {
-            let n = file.read(&mut buffer)?;
-            if n == 0 {
-                break;
-            }
-            hasher.update(&buffer[..n]);
-        }
-
-        Ok(format!("{:x}", hasher.finalize()))
-    }
-
     fn get_file_modified(path: &std::path::Path) -> Option<std::time::SystemTime> {
         path.metadata().ok().and_then(|m| m.modified().ok())
     }
@@ -74,21 +187,20 @@ impl Scanner for DuplicatesScanner {
             }
 
             for entry in WalkDir::new(root)
-                .max_depth(config.max_depth)
+                .max_depth(config.walk_depth())
+                .follow_links(config.follow_symlinks)
                 .into_iter()
-                .filter_map(|e| e.ok())
+                .filter_map(|e| config.readable(e))
                 .filter(|e| e.file_type().is_file())
             {
                 let path = entry.path();
 
-                if config.excluded_paths.iter().any(|ex| path.starts_with(ex)) {
+                if config.is_excluded(path) {
                     continue;
                 }
 
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    if name.starts_with('.') {
-                        continue;
-                    }
+                if config.skips_hidden(path) {
+                    continue;
                 }
 
                 config.report_progress(&path.display().to_string());
@@ -104,88 +216,84 @@ impl Scanner for DuplicatesScanner {
 
         let mut hash_map: HashMap<String, Vec<PathBuf>> = HashMap::new();
 
-        for (size, paths) in size_map {
-            if paths.len() < 2 {
-                continue;
-            }
+        // Hashing is the slow part, so it reports its own count alongside the path.
+        size_map.retain(|_, paths| paths.len() >= 2);
+        let candidates: usize = size_map.values().map(Vec::len).sum();
+        let mut hashed = 0;
+        config.report_phase(self.id(), "Hashing", "candidate files", hashed, candidates);
 
+        for (size, paths) in size_map {
             for path in paths {
-                if let Ok(hash) = Self::calculate_file_hash(&path) {
+                config.report_progress(&path.display().to_string());
+                let hash = file_hash(&path);
+                hashed += 1;
+                config.report_phase(self.id(), "Hashing", "candidate files", hashed, candidates);
+                if let Ok(hash) = hash {
                     let key = format!("{}:{}", size, hash);
                     hash_map.entry(key).or_default().push(path);
                 }
             }
         }
 
-        let mut group_id = 0;
-        for (_key, mut paths) in hash_map {
+        for (key, mut paths) in hash_map {
             if paths.len() < 2 {
                 continue;
             }
 
-            paths.sort_by(|a, b| {
-                let a_time =
-                    Self::get_file_modified(a).unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                let b_time =
-                    Self::get_file_modified(b).unwrap_or(std::time::SystemTime::UNIX_EPOCH);
-                a_time.cmp(&b_time)
+            paths.sort_by_key(|p| {
+                Self::get_file_modified(p).unwrap_or(std::time::SystemTime::UNIX_EPOCH)
             });
 
-            let original = &paths[0];
-            let duplicates = &paths[1..];
+            let original = paths[pick_keeper(&paths, &config.duplicate_keep)].clone();
+            let (member_size, member_hash) = key
+                .split_once(':')
+                .map(|(size, hash)| (size.parse::<u64>().unwrap_or(0), hash.to_string()))
+                .unwrap_or_default();
 
             if let Ok(metadata) = original.metadata() {
-                let total_dup_size: u64 = duplicates
-                    .iter()
-                    .filter_map(|p| p.metadata().ok())
-                    .map(|m| m.len())
-                    .sum();
-
-                let mut item = ScanResult::new(
-                    format!("dup_{}", group_id),
-                    format!(
-                        "{} ({} duplicates)",
-                        original.file_name().and_then(|n| n.to_str()).unwrap_or("?"),
-                        duplicates.len()
-                    ),
-                    original.clone(),
-                )
-                .with_size(total_dup_size)
-                .with_file_count(duplicates.len() as u64)
-                .with_category(ScannerCategory::System)
-                .with_safety(SafetyLevel::Caution)
-                .with_last_accessed(metadata.accessed().ok().map(|t| chrono::DateTime::from(t)))
-                .with_last_modified(metadata.modified().ok().map(|t| chrono::DateTime::from(t)));
+                let group_id = stable_id("dup", &original);
+                let mut item = ScanResult::new(group_id.clone(), "", original.clone())
+                    .with_category(ScannerCategory::System)
+                    .with_safety(SafetyLevel::Caution)
+                    .with_safety_reason("Identical copies of one file; the original is kept")
+                    .with_explanation(
+                        "Files with the same size and SHA-256 hash as this one. One copy is \
+                         kept (the oldest unless [scan] duplicate_keep says otherwise); only \
+                         the others are removed. Nothing recreates them, so check that no app \
+                         expects a copy at its path.",
+                    )
+                    .with_last_accessed(metadata.accessed().ok().map(chrono::DateTime::from))
+                    .with_last_modified(metadata.modified().ok().map(chrono::DateTime::from));
 
                 item.metadata
                     .insert("scanner_id".to_string(), self.id().to_string());
+                item.metadata.insert("group_id".to_string(), group_id);
                 item.metadata
-                    .insert("group_id".to_string(), group_id.to_string());
-                item.metadata.insert(
-                    "duplicate_paths".to_string(),
-                    duplicates
-                        .iter()
-                        .map(|p| p.display().to_string())
-                        .collect::<Vec<_>>()
-                        .join("|"),
-                );
-                item.metadata
-                    .insert("original_path".to_string(), original.display().to_string());
+                    .insert("member_size".to_string(), member_size.to_string());
+                item.metadata.insert("member_hash".to_string(), member_hash);
+                item.members = paths;
+                set_keeper(&mut item, &original);
 
                 config.report_item(item.clone());
                 items.push(item);
             }
-
-            group_id += 1;
         }
 
-        items.sort_by(|a, b| b.size.cmp(&a.size));
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
         Ok(items)
     }
 
     fn is_available(&self) -> bool {
         self.search_paths.iter().any(|p| p.exists())
     }
+
+    fn default_safety(&self) -> SafetyLevel {
+        SafetyLevel::Caution
+    }
+
+    fn is_expensive(&self) -> bool {
+        true
+    }
 }
 
 impl Default for DuplicatesScanner {
@@ -193,3 +301,184 @@ impl Default for DuplicatesScanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{nested_tree, temp_home};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn max_depth_zero_is_unlimited() {
+        let home = temp_home("duplicates-depth");
+        let root = nested_tree(&home.join("Documents"), 6);
+        let content = vec![7u8; 4096];
+        std::fs::write(root.join("d0/copy-a.bin"), &content).unwrap();
+        std::fs::write(root.join("d0/d1/d2/d3/d4/d5/copy-b.bin"), &content).unwrap();
+
+        let scanner = DuplicatesScanner::with_home(&home);
+        let config = ScanConfig {
+            min_size: 1,
+            max_depth: 0,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let shallow = ScanConfig {
+            max_depth: 2,
+            ..config
+        };
+        assert!(scanner.scan(&shallow).unwrap().is_empty());
+    }
+
+    #[test]
+    fn hashing_phase_counts_every_candidate() {
+        let home = temp_home("duplicates-hashing");
+        let root = home.join("Downloads");
+        std::fs::create_dir_all(&root).unwrap();
+        // Three same-size candidates (two identical) and one unique size that
+        // is never hashed.
+        std::fs::write(root.join("a.bin"), vec![1u8; 2048]).unwrap();
+        std::fs::write(root.join("b.bin"), vec![1u8; 2048]).unwrap();
+        std::fs::write(root.join("c.bin"), vec![2u8; 2048]).unwrap();
+        std::fs::write(root.join("d.bin"), vec![1u8; 4096]).unwrap();
+
+        let phases = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&phases);
+        let config = ScanConfig {
+            min_size: 1,
+            phase_callback: Some(Arc::new(move |phase| seen.lock().unwrap().push(phase))),
+            ..Default::default()
+        };
+        let items = DuplicatesScanner::with_home(&home).scan(&config).unwrap();
+        assert_eq!(items.len(), 1);
+
+        let phases = phases.lock().unwrap();
+        let counts: Vec<(usize, usize)> = phases.iter().map(|p| (p.done, p.total)).collect();
+        assert_eq!(counts, vec![(0, 3), (1, 3), (2, 3), (3, 3)]);
+        assert!(phases
+            .iter()
+            .all(|p| p.scanner_id == "duplicates" && p.label == "Hashing"));
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    fn keeper_for(name: &str, policy: KeepPolicy) -> (PathBuf, ScanResult) {
+        let root = temp_home(&format!("duplicates-keep-{}", name));
+        let content = vec![3u8; 2048];
+        for (i, dir) in ["Downloads", "Documents", "Desktop"].iter().enumerate() {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            let path = root.join(dir).join("report.pdf");
+            std::fs::write(&path, &content).unwrap();
+            let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_000 + i as u64);
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(mtime)
+                .unwrap();
+        }
+
+        let config = ScanConfig {
+            min_size: 1,
+            duplicate_keep: policy,
+            ..Default::default()
+        };
+        let mut items = DuplicatesScanner::with_home(&root).scan(&config).unwrap();
+        assert_eq!(items.len(), 1);
+        (root, items.remove(0))
+    }
+
+    fn removed(item: &ScanResult) -> Vec<PathBuf> {
+        removable_copies(item)
+            .into_iter()
+            .map(|c| c.unwrap().path)
+            .collect()
+    }
+
+    #[test]
+    fn oldest_policy_keeps_the_first_copy() {
+        let (root, item) = keeper_for("oldest", KeepPolicy::Oldest);
+        let kept = root.join("Downloads/report.pdf");
+        assert_eq!(item.path, kept);
+        assert_eq!(item.metadata["keep_path"], kept.display().to_string());
+        assert_eq!(item.size, 2 * 2048);
+        assert_eq!(item.members.len(), 3);
+        assert!(!removed(&item).contains(&kept));
+    }
+
+    #[test]
+    fn newest_policy_keeps_the_last_copy() {
+        let (root, item) = keeper_for("newest", KeepPolicy::Newest);
+        let kept = root.join("Desktop/report.pdf");
+        assert_eq!(item.metadata["keep_path"], kept.display().to_string());
+        assert_eq!(
+            removed(&item),
+            vec![
+                root.join("Downloads/report.pdf"),
+                root.join("Documents/report.pdf")
+            ]
+        );
+    }
+
+    #[test]
+    fn prefer_path_policy_keeps_a_matching_copy_or_the_oldest() {
+        let (root, item) = keeper_for("prefer", KeepPolicy::PreferPath("/Documents/".into()));
+        assert_eq!(item.path, root.join("Documents/report.pdf"));
+        assert_eq!(item.metadata["original_path"], item.metadata["keep_path"]);
+
+        let (root, item) = keeper_for("nomatch", KeepPolicy::PreferPath("/Dropbox/".into()));
+        assert_eq!(item.path, root.join("Downloads/report.pdf"));
+    }
+
+    #[test]
+    fn cycling_the_keeper_moves_it_through_every_member() {
+        let (root, mut item) = keeper_for("cycle", KeepPolicy::Oldest);
+        let id = item.id.clone();
+        assert_eq!(
+            cycle_keeper(&mut item),
+            Some(root.join("Documents/report.pdf"))
+        );
+        assert_eq!(
+            cycle_keeper(&mut item),
+            Some(root.join("Desktop/report.pdf"))
+        );
+        assert_eq!(
+            cycle_keeper(&mut item),
+            Some(root.join("Downloads/report.pdf"))
+        );
+        assert_eq!(item.id, id);
+        assert_eq!(removed(&item).len(), 2);
+    }
+
+    #[test]
+    fn copies_that_changed_since_the_scan_are_refused() {
+        let (root, item) = keeper_for("changed", KeepPolicy::Oldest);
+        std::fs::write(root.join("Documents/report.pdf"), vec![4u8; 2048]).unwrap();
+        std::fs::remove_file(root.join("Desktop/report.pdf")).unwrap();
+        std::fs::create_dir(root.join("Desktop/report.pdf")).unwrap();
+
+        let refused: Vec<(PathBuf, String)> = removable_copies(&item)
+            .into_iter()
+            .filter_map(Result::err)
+            .collect();
+        assert_eq!(
+            refused,
+            vec![
+                (
+                    root.join("Documents/report.pdf"),
+                    "Duplicate changed since the scan".to_string()
+                ),
+                (
+                    root.join("Desktop/report.pdf"),
+                    "Duplicate is no longer a regular file".to_string()
+                ),
+            ]
+        );
+
+        let mut relative = item.clone();
+        relative.members[1] = PathBuf::from("Documents/report.pdf");
+        assert!(removable_copies(&relative)[0].is_err());
+    }
+}
@@ -1,12 +1,9 @@
-use crate::plugin::{SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::SystemTime;
 use walkdir::WalkDir;
 
-const DEFAULT_MIN_SIZE: u64 = 100 * 1024 * 1024; // 100MB
-const DEFAULT_MIN_AGE_DAYS: i64 = 30;
-
 pub struct LargeOldFilesScanner {
     home: PathBuf,
     excluded_dirs: Vec<PathBuf>,
@@ -14,8 +11,10 @@ pub struct LargeOldFilesScanner {
 
 impl LargeOldFilesScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        Self::with_home(&crate::user::home_dir())
+    }
 
+    fn with_home(home: &Path) -> Self {
         let excluded_dirs = vec![
             home.join("Library"),
             home.join(".Trash"),
@@ -28,7 +27,7 @@ impl LargeOldFilesScanner {
         ];
 
         Self {
-            home,
+            home: home.to_path_buf(),
             excluded_dirs,
         }
     }
@@ -42,7 +41,7 @@ impl LargeOldFilesScanner {
         false
     }
 
-    fn get_file_age_days(path: &std::path::Path) -> Option<i64> {
+    fn get_file_age_days(path: &std::path::Path) -> Option<u64> {
         let metadata = path.metadata().ok()?;
         let accessed = metadata.accessed().ok()?;
         let modified = metadata.modified().ok()?;
@@ -55,7 +54,7 @@ impl LargeOldFilesScanner {
         let now = SystemTime::now();
         let duration = now.duration_since(older_time).ok()?;
 
-        Some(duration.as_secs() as i64 / 86400)
+        Some(duration.as_secs() / 86400)
     }
 }
 
@@ -74,36 +73,22 @@ impl Scanner for LargeOldFilesScanner {
 
     fn scan(&self, config: &ScanConfig) -> Result<Vec<ScanResult>> {
         let mut items = Vec::new();
-        let min_size = if config.min_size > 0 {
-            config.min_size
-        } else {
-            DEFAULT_MIN_SIZE
-        };
-        let cutoff_days = DEFAULT_MIN_AGE_DAYS;
-
-        let max_depth = if config.max_depth > 0 {
-            config.max_depth
-        } else {
-            10
-        };
+        let min_size = config.large_file_min_size;
+        let cutoff_days = config.large_file_min_age_days;
 
         let mut count = 0;
         for entry in WalkDir::new(&self.home)
-            .max_depth(max_depth)
+            .max_depth(config.walk_depth())
+            .follow_links(config.follow_symlinks)
             .into_iter()
             .filter_entry(|e| {
                 let path = e.path();
-                if self.is_excluded(path) {
+                if self.is_excluded(path) || config.is_excluded(path) {
                     return false;
                 }
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    if name.starts_with('.') && path.is_dir() {
-                        return false;
-                    }
-                }
-                true
+                !(config.skips_hidden(path) && path.is_dir())
             })
-            .filter_map(|e| e.ok())
+            .filter_map(|e| config.readable(e))
             .filter(|e| e.file_type().is_file())
         {
             let path = entry.path();
@@ -134,25 +119,28 @@ impl Scanner for LargeOldFilesScanner {
 
             let file_name = path
                 .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("?")
-                .to_string();
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "?".to_string());
 
             let last_accessed = metadata.accessed().ok().map(|t| t.into());
 
             let last_modified = metadata.modified().ok().map(|t| t.into());
 
-            let mut item = ScanResult::new(
-                format!("large_file_{}", items.len()),
-                file_name,
-                path.to_path_buf(),
-            )
-            .with_size(size)
-            .with_file_count(1)
-            .with_category(ScannerCategory::System)
-            .with_safety(SafetyLevel::Caution)
-            .with_last_accessed(last_accessed)
-            .with_last_modified(last_modified);
+            let mut item =
+                ScanResult::new(stable_id("large_file", path), file_name, path.to_path_buf())
+                    .with_size(size)
+                    .with_file_count(1)
+                    .with_category(ScannerCategory::System)
+                    .with_safety(SafetyLevel::Caution)
+                    .with_safety_reason("Large file not used recently; may be personal data")
+                    .with_explanation(format!(
+                        "A big file in your home folder that has not been opened or changed \
+                         for at least {} days. Nothing recreates it: deleting it is permanent, \
+                         so consider `clean --archive` instead.",
+                        cutoff_days
+                    ))
+                    .with_last_accessed(last_accessed)
+                    .with_last_modified(last_modified);
 
             item.metadata
                 .insert("scanner_id".to_string(), self.id().to_string());
@@ -161,8 +149,9 @@ impl Scanner for LargeOldFilesScanner {
             items.push(item);
         }
 
-        items.sort_by(|a, b| b.size.cmp(&a.size));
-        items.truncate(100);
+        // Output keeps the largest large_file_max_results and reports the rest
+        // as omitted.
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
 
         Ok(items)
     }
@@ -170,6 +159,14 @@ impl Scanner for LargeOldFilesScanner {
     fn is_available(&self) -> bool {
         true
     }
+
+    fn default_safety(&self) -> SafetyLevel {
+        SafetyLevel::Caution
+    }
+
+    fn is_expensive(&self) -> bool {
+        true
+    }
 }
 
 impl Default for LargeOldFilesScanner {
@@ -177,3 +174,166 @@ impl Default for LargeOldFilesScanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{nested_tree, temp_home};
+    use std::fs::{File, FileTimes};
+    use std::time::Duration;
+
+    #[test]
+    fn max_depth_zero_is_unlimited() {
+        let root = nested_tree(&temp_home("large-files-depth"), 12);
+        let deep_file = root.join("d0/d1/d2/d3/d4/d5/d6/d7/d8/d9/d10/d11/old.bin");
+        std::fs::write(&deep_file, vec![0u8; 2048]).unwrap();
+        let old = SystemTime::now() - Duration::from_secs(90 * 86400);
+        File::options()
+            .write(true)
+            .open(&deep_file)
+            .unwrap()
+            .set_times(FileTimes::new().set_accessed(old).set_modified(old))
+            .unwrap();
+
+        let scanner = LargeOldFilesScanner::with_home(&root);
+        let config = ScanConfig {
+            large_file_min_size: 1024,
+            max_depth: 0,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+        assert!(items.iter().any(|i| i.path == deep_file));
+
+        let shallow = ScanConfig {
+            max_depth: 5,
+            ..config
+        };
+        let items = scanner.scan(&shallow).unwrap();
+        assert!(!items.iter().any(|i| i.path == deep_file));
+    }
+
+    #[test]
+    fn include_hidden_reaches_dot_directories() {
+        let root = temp_home("large-files-hidden");
+        let model = root.join(".ollama/models/blob.bin");
+        std::fs::create_dir_all(model.parent().unwrap()).unwrap();
+        std::fs::write(&model, vec![0u8; 2048]).unwrap();
+        let old = SystemTime::now() - Duration::from_secs(90 * 86400);
+        File::options()
+            .write(true)
+            .open(&model)
+            .unwrap()
+            .set_times(FileTimes::new().set_accessed(old).set_modified(old))
+            .unwrap();
+
+        let scanner = LargeOldFilesScanner::with_home(&root);
+        let config = ScanConfig {
+            large_file_min_size: 1024,
+            ..Default::default()
+        };
+        assert!(!scanner
+            .scan(&config)
+            .unwrap()
+            .iter()
+            .any(|i| i.path == model));
+
+        let hidden = ScanConfig {
+            include_hidden: true,
+            ..config.clone()
+        };
+        assert!(scanner
+            .scan(&hidden)
+            .unwrap()
+            .iter()
+            .any(|i| i.path == model));
+
+        let excluded = ScanConfig {
+            excluded_paths: vec![root.join(".ollama")],
+            ..hidden
+        };
+        assert!(!scanner
+            .scan(&excluded)
+            .unwrap()
+            .iter()
+            .any(|i| i.path == model));
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn same_file_keeps_its_id_across_scans() {
+        let root = nested_tree(&temp_home("large-files-stable-id"), 1);
+        let file = root.join("d0/old.bin");
+        std::fs::write(&file, vec![0u8; 2048]).unwrap();
+        let old = SystemTime::now() - Duration::from_secs(90 * 86400);
+        File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_times(FileTimes::new().set_accessed(old).set_modified(old))
+            .unwrap();
+        std::fs::write(root.join("other.bin"), vec![0u8; 4096]).unwrap();
+
+        let scanner = LargeOldFilesScanner::with_home(&root);
+        let config = ScanConfig {
+            large_file_min_size: 1024,
+            ..Default::default()
+        };
+        let id_of = |items: &[ScanResult]| {
+            items
+                .iter()
+                .find(|i| i.path == file)
+                .map(|i| i.id.clone())
+                .unwrap()
+        };
+
+        let first = id_of(&scanner.scan(&config).unwrap());
+        std::fs::remove_file(root.join("other.bin")).unwrap();
+        let second = id_of(&scanner.scan(&config).unwrap());
+
+        assert_eq!(first, second);
+        assert!(first.starts_with("large_file_"));
+    }
+
+    #[test]
+    fn age_cutoff_and_result_cap_come_from_config() {
+        let root = temp_home("large-files-cutoff");
+        let mut age = 10;
+        for name in ["a.bin", "b.bin", "c.bin"] {
+            let file = root.join(name);
+            std::fs::write(&file, vec![0u8; 2048]).unwrap();
+            let then = SystemTime::now() - Duration::from_secs(age * 86400);
+            File::options()
+                .write(true)
+                .open(&file)
+                .unwrap()
+                .set_times(FileTimes::new().set_accessed(then).set_modified(then))
+                .unwrap();
+            age += 10;
+        }
+
+        let mut settings = crate::config::Config::default();
+        settings.scan.large_file_min_size = 1024;
+        settings.scan.large_file_min_age_days = 15;
+        settings.scan.large_file_max_results = 1;
+        let config = ScanConfig::from_config(&settings);
+
+        let mut registry = crate::plugin::PluginRegistry::new();
+        registry.register_scanner(Box::new(LargeOldFilesScanner::with_home(&root)));
+        let mut report = registry.scan_all(&config).unwrap();
+        assert_eq!(report.categories[0].items.len(), 2);
+        report.cap_items(&config);
+        let category = &report.categories[0];
+        assert_eq!(category.items.len(), 1);
+        assert_eq!(category.omitted, 1);
+        assert!(report.warnings()[0].contains("scan.large_file_max_results"));
+
+        settings.scan.large_file_min_age_days = 0;
+        let items = LargeOldFilesScanner::with_home(&root)
+            .scan(&ScanConfig::from_config(&settings))
+            .unwrap();
+        assert_eq!(items.len(), 3);
+
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
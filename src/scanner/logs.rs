@@ -1,8 +1,8 @@
 use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
-use crate::plugin::{ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::plugin::{stable_id, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use crate::safety::SafetyChecker;
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
 pub struct LogScanner {
@@ -12,8 +12,10 @@ pub struct LogScanner {
 
 impl LogScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        Self::with_home(&crate::user::home_dir())
+    }
 
+    fn with_home(home: &Path) -> Self {
         Self {
             log_dirs: vec![home.join("Library/Logs")],
             safety_checker: SafetyChecker::new(),
@@ -43,13 +45,14 @@ impl Scanner for LogScanner {
             }
 
             for entry in WalkDir::new(log_dir)
-                .max_depth(config.max_depth)
+                .max_depth(config.walk_depth())
+                .follow_links(config.follow_symlinks)
                 .into_iter()
-                .filter_map(|e| e.ok())
+                .filter_map(|e| config.readable(e))
             {
                 let path = entry.path();
 
-                if config.excluded_paths.iter().any(|ex| path.starts_with(ex)) {
+                if config.is_excluded(path) {
                     continue;
                 }
 
@@ -67,18 +70,27 @@ impl Scanner for LogScanner {
                 if size >= config.min_size {
                     let name = path
                         .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("unknown")
-                        .to_string();
+                        .map(|n| n.to_string_lossy().into_owned())
+                        .unwrap_or_else(|| "unknown".to_string());
 
                     let safety_level = self.safety_checker.check_path(path);
 
                     let mut item =
-                        ScanResult::new(format!("log_{}", items.len()), name, path.to_path_buf())
+                        ScanResult::new(stable_id("log", path), name, path.to_path_buf())
                             .with_size(size)
                             .with_file_count(file_count)
                             .with_category(ScannerCategory::System)
                             .with_safety(safety_level)
+                            .with_safety_reason(
+                                self.safety_checker.explain(path).unwrap_or(
+                                    "Log output; only used for troubleshooting past events",
+                                ),
+                            )
+                            .with_explanation(
+                                "Diagnostic logs written by apps and macOS. They only matter \
+                                 when investigating a past problem; new logs are written as \
+                                 apps keep running.",
+                            )
                             .with_last_accessed(get_last_accessed(path))
                             .with_last_modified(get_last_modified(path));
 
@@ -91,7 +103,7 @@ impl Scanner for LogScanner {
             }
         }
 
-        items.sort_by(|a, b| b.size.cmp(&a.size));
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
         items.truncate(100);
 
         Ok(items)
@@ -107,3 +119,33 @@ impl Default for LogScanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::{nested_tree, temp_home};
+
+    #[test]
+    fn max_depth_zero_is_unlimited() {
+        let home = temp_home("logs-depth");
+        let root = nested_tree(&home.join("Library/Logs"), 6);
+        let log_file = root.join("d0/d1/d2/d3/d4/d5/app.log");
+        std::fs::write(&log_file, vec![b'x'; 64]).unwrap();
+
+        let scanner = LogScanner::with_home(&home);
+        let config = ScanConfig {
+            min_size: 1,
+            max_depth: 0,
+            ..Default::default()
+        };
+        let items = scanner.scan(&config).unwrap();
+        assert!(items.iter().any(|i| i.path == log_file));
+
+        let shallow = ScanConfig {
+            max_depth: 2,
+            ..config
+        };
+        let items = scanner.scan(&shallow).unwrap();
+        assert!(!items.iter().any(|i| i.path == log_file));
+    }
+}
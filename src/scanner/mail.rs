@@ -1,5 +1,5 @@
 use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
-use crate::plugin::{SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use anyhow::Result;
 use std::path::PathBuf;
 
@@ -9,7 +9,7 @@ pub struct MailAttachmentsScanner {
 
 impl MailAttachmentsScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        let home = crate::user::home_dir();
 
         let search_paths = vec![
             ("Mail Attachments", home.join("Library/Mail")),
@@ -22,14 +22,15 @@ impl MailAttachmentsScanner {
         Self { search_paths }
     }
 
-    fn find_attachment_dirs(&self, base: &PathBuf) -> Vec<PathBuf> {
+    fn find_attachment_dirs(&self, base: &PathBuf, config: &ScanConfig) -> Vec<PathBuf> {
         let mut results = Vec::new();
 
         use walkdir::WalkDir;
         for entry in WalkDir::new(base)
             .max_depth(4)
+            .follow_links(config.follow_symlinks)
             .into_iter()
-            .filter_map(|e| e.ok())
+            .filter_map(|e| config.readable(e))
             .filter(|e| e.file_type().is_dir())
         {
             let path = entry.path();
@@ -67,10 +68,10 @@ impl Scanner for MailAttachmentsScanner {
 
             config.report_progress(&base_path.display().to_string());
 
-            let attachment_dirs = self.find_attachment_dirs(base_path);
+            let attachment_dirs = self.find_attachment_dirs(base_path, config);
 
             for dir in attachment_dirs {
-                if config.excluded_paths.iter().any(|ex| dir.starts_with(ex)) {
+                if config.is_excluded(&dir) {
                     continue;
                 }
 
@@ -81,9 +82,8 @@ impl Scanner for MailAttachmentsScanner {
 
                 let name = dir
                     .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("Attachments")
-                    .to_string();
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "Attachments".to_string());
 
                 let parent_name = dir
                     .parent()
@@ -97,14 +97,21 @@ impl Scanner for MailAttachmentsScanner {
                     format!("{} ({})", name, label)
                 };
 
-                let mut item =
-                    ScanResult::new(format!("mail_{}", items.len()), display_name, dir.clone())
-                        .with_size(size)
-                        .with_file_count(count_files(&dir))
-                        .with_category(ScannerCategory::System)
-                        .with_safety(SafetyLevel::Caution)
-                        .with_last_accessed(get_last_accessed(&dir))
-                        .with_last_modified(get_last_modified(&dir));
+                let mut item = ScanResult::new(stable_id("mail", &dir), display_name, dir.clone())
+                    .with_size(size)
+                    .with_file_count(count_files(&dir))
+                    .with_category(ScannerCategory::System)
+                    .with_safety(SafetyLevel::Caution)
+                    .with_safety_reason(
+                        "Downloaded mail attachments; copies stay on the mail server",
+                    )
+                    .with_explanation(
+                        "Attachments Mail saved when you opened or previewed them. IMAP and \
+                         Exchange accounts download them again on demand; for POP accounts \
+                         this may be the only copy.",
+                    )
+                    .with_last_accessed(get_last_accessed(&dir))
+                    .with_last_modified(get_last_modified(&dir));
 
                 item.metadata
                     .insert("scanner_id".to_string(), self.id().to_string());
@@ -114,13 +121,17 @@ impl Scanner for MailAttachmentsScanner {
             }
         }
 
-        items.sort_by(|a, b| b.size.cmp(&a.size));
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
         Ok(items)
     }
 
     fn is_available(&self) -> bool {
         self.search_paths.iter().any(|(_, p)| p.exists())
     }
+
+    fn default_safety(&self) -> SafetyLevel {
+        SafetyLevel::Caution
+    }
 }
 
 impl Default for MailAttachmentsScanner {
@@ -1,6 +1,29 @@
+use super::calculate_dir_size;
 use crate::plugin::{SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::utils::format_size;
 use anyhow::Result;
-use std::path::PathBuf;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+// Checked as well as $PATH, which can be minimal under launchd.
+const SYSTEM_BIN_DIRS: &[&str] = &["/usr/bin", "/bin", "/usr/sbin", "/sbin"];
+
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+// Absolute paths are checked as-is; bare names are looked up like a shell would.
+fn command_exists(binary: &str) -> bool {
+    if binary.contains('/') {
+        return is_executable(Path::new(binary));
+    }
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    std::env::split_paths(&path_var)
+        .chain(SYSTEM_BIN_DIRS.iter().map(PathBuf::from))
+        .any(|dir| is_executable(&dir.join(binary)))
+}
 
 pub struct MaintenanceScanner {
     tasks: Vec<MaintenanceTask>,
@@ -11,13 +34,20 @@ struct MaintenanceTask {
     name: String,
     description: String,
     command: String,
+    // The program `command` starts with; the task is only offered when it exists.
+    binary: String,
     requires_sudo: bool,
     safety: SafetyLevel,
+    // Shown as a size next to the task; the command rebuilds it, so it is not reclaimable.
+    indicator: Option<PathBuf>,
+    // For tasks that clear a cache: free space on this path's volume is
+    // compared before and after the command runs.
+    measure: Option<PathBuf>,
 }
 
 impl MaintenanceScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        let home = crate::user::home_dir();
 
         let tasks = vec![
             MaintenanceTask {
@@ -25,80 +55,110 @@ impl MaintenanceScanner {
                 name: "Flush DNS Cache".into(),
                 description: "Clear DNS cache to resolve network issues".into(),
                 command: "dscacheutil -flushcache && sudo killall -HUP mDNSResponder".into(),
+                binary: "dscacheutil".into(),
                 requires_sudo: true,
                 safety: SafetyLevel::Safe,
+                indicator: None,
+                measure: None,
             },
             MaintenanceTask {
                 id: "rebuild_launchservices".into(),
                 name: "Rebuild Launch Services".into(),
                 description: "Rebuild Launch Services database to fix app associations".into(),
                 command: "/System/Library/Frameworks/CoreServices.framework/Frameworks/LaunchServices.framework/Support/lsregister -kill -r -domain local -domain system -domain user".into(),
+                binary: "/System/Library/Frameworks/CoreServices.framework/Frameworks/LaunchServices.framework/Support/lsregister".into(),
                 requires_sudo: false,
                 safety: SafetyLevel::Safe,
+                indicator: None,
+                measure: None,
             },
             MaintenanceTask {
                 id: "clear_font_cache".into(),
                 name: "Clear Font Cache".into(),
                 description: "Clear font cache to fix font rendering issues".into(),
                 command: "atsutil databases -remove".into(),
+                binary: "atsutil".into(),
                 requires_sudo: false,
                 safety: SafetyLevel::Safe,
+                indicator: None,
+                measure: Some(PathBuf::from("/private/var/folders")),
             },
             MaintenanceTask {
                 id: "reset_spotlight".into(),
                 name: "Reset Spotlight Index".into(),
                 description: "Reset Spotlight search index (may take time)".into(),
                 command: "sudo mdutil -E /".into(),
+                binary: "mdutil".into(),
                 requires_sudo: true,
                 safety: SafetyLevel::Caution,
+                indicator: Some(PathBuf::from("/.Spotlight-V100")),
+                measure: None,
             },
             MaintenanceTask {
                 id: "purge_memory".into(),
                 name: "Purge Memory".into(),
                 description: "Free up inactive memory".into(),
                 command: "purge".into(),
+                binary: "purge".into(),
                 requires_sudo: false,
                 safety: SafetyLevel::Safe,
+                indicator: None,
+                measure: None,
             },
             MaintenanceTask {
                 id: "clean_tmp".into(),
                 name: "Clean TMP Files".into(),
                 description: "Remove temporary system files".into(),
                 command: format!("rm -rf /tmp/* 2>/dev/null; rm -rf {}/.tmp/* 2>/dev/null", home.display()),
+                binary: "rm".into(),
                 requires_sudo: false,
                 safety: SafetyLevel::Safe,
+                indicator: None,
+                measure: Some(PathBuf::from("/private/tmp")),
             },
             MaintenanceTask {
                 id: "verify_disk".into(),
                 name: "Verify Disk".into(),
                 description: "Verify startup disk for errors".into(),
                 command: "diskutil verifyVolume /".into(),
+                binary: "diskutil".into(),
                 requires_sudo: false,
                 safety: SafetyLevel::Safe,
+                indicator: None,
+                measure: None,
             },
             MaintenanceTask {
                 id: "clear_quicklook".into(),
                 name: "Clear Quick Look Cache".into(),
                 description: "Clear Quick Look thumbnail cache".into(),
                 command: "qlmanage -r cache".into(),
+                binary: "qlmanage".into(),
                 requires_sudo: false,
                 safety: SafetyLevel::Safe,
+                indicator: None,
+                measure: Some(PathBuf::from("/private/var/folders")),
             },
             MaintenanceTask {
                 id: "reset_dock".into(),
                 name: "Reset Dock".into(),
                 description: "Reset Dock to default settings".into(),
                 command: "defaults delete com.apple.dock; killall Dock".into(),
+                binary: "defaults".into(),
                 requires_sudo: false,
                 safety: SafetyLevel::Caution,
+                indicator: None,
+                measure: None,
             },
             MaintenanceTask {
                 id: "reset_finder".into(),
                 name: "Reset Finder".into(),
                 description: "Restart Finder to apply changes".into(),
                 command: "killall Finder".into(),
+                binary: "killall".into(),
                 requires_sudo: false,
                 safety: SafetyLevel::Safe,
+                indicator: None,
+                measure: None,
             },
         ];
 
@@ -123,6 +183,7 @@ impl Scanner for MaintenanceScanner {
         let mut items = Vec::new();
 
         for task in &self.tasks {
+            let available = command_exists(&task.binary);
             let mut item = ScanResult::new(
                 format!("maint_{}", task.id),
                 task.name.clone(),
@@ -131,7 +192,24 @@ impl Scanner for MaintenanceScanner {
             .with_size(0)
             .with_file_count(1)
             .with_category(ScannerCategory::System)
-            .with_safety(task.safety);
+            .with_safety(task.safety)
+            .with_explanation(format!(
+                "Maintenance task, not a file: runs `{}`. {}.",
+                task.command, task.description
+            ));
+            if !available {
+                // Listed so it's clear why it's missing, but never run.
+                item = item
+                    .with_safety(SafetyLevel::Protected)
+                    .with_safety_reason(format!(
+                        "`{}` was not found on this Mac, so this task can't run",
+                        task.binary
+                    ));
+            } else if task.safety == SafetyLevel::Safe {
+                item = item.with_safety_reason(
+                    "Standard macOS maintenance command; resets data the system rebuilds",
+                );
+            }
 
             item.metadata
                 .insert("scanner_id".to_string(), self.id().to_string());
@@ -142,6 +220,20 @@ impl Scanner for MaintenanceScanner {
                 .insert("description".to_string(), task.description.clone());
             item.metadata
                 .insert("requires_sudo".to_string(), task.requires_sudo.to_string());
+            item.metadata
+                .insert("available".to_string(), available.to_string());
+            if let Some(path) = &task.measure {
+                item.metadata
+                    .insert("measure_path".to_string(), path.display().to_string());
+            }
+            // The index is usually root-only; leave the field out rather than report 0.
+            if let Some(path) = task.indicator.as_ref().filter(|p| p.is_dir()) {
+                let size = calculate_dir_size(path);
+                if size > 0 {
+                    item.metadata
+                        .insert("index_size".to_string(), format_size(size));
+                }
+            }
 
             _config.report_item(item.clone());
             items.push(item);
@@ -160,3 +252,55 @@ impl Default for MaintenanceScanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, binary: &str) -> MaintenanceTask {
+        MaintenanceTask {
+            id: id.into(),
+            name: id.into(),
+            description: String::new(),
+            command: format!("{} --version", binary),
+            binary: binary.into(),
+            requires_sudo: false,
+            safety: SafetyLevel::Safe,
+            indicator: None,
+            measure: None,
+        }
+    }
+
+    #[test]
+    fn tasks_with_a_missing_command_are_marked_unavailable() {
+        let scanner = MaintenanceScanner {
+            tasks: vec![
+                task("present", "sh"),
+                task("absolute", "/bin/sh"),
+                task("missing", "cleanmac-no-such-tool"),
+                task("gone", "/nonexistent/bin/tool"),
+            ],
+        };
+        let items = scanner.scan(&ScanConfig::default()).unwrap();
+        let available: Vec<(&str, &str, SafetyLevel)> = items
+            .iter()
+            .map(|i| {
+                (
+                    i.metadata["task_id"].as_str(),
+                    i.metadata["available"].as_str(),
+                    i.safety_level,
+                )
+            })
+            .collect();
+        assert_eq!(
+            available,
+            vec![
+                ("present", "true", SafetyLevel::Safe),
+                ("absolute", "true", SafetyLevel::Safe),
+                ("missing", "false", SafetyLevel::Protected),
+                ("gone", "false", SafetyLevel::Protected),
+            ]
+        );
+        assert!(items[2].safety_reason().contains("cleanmac-no-such-tool"));
+    }
+}
@@ -1,6 +1,9 @@
 pub mod browser;
 pub mod caches;
+pub mod containers;
+pub mod custom;
 pub mod dev;
+pub mod ds_store;
 pub mod duplicates;
 pub mod large_files;
 pub mod logs;
@@ -9,12 +12,18 @@ pub mod maintenance;
 pub mod music;
 pub mod photo;
 pub mod privacy;
+pub mod size_cache;
+pub mod spotlight;
 pub mod startup;
 pub mod trash;
+pub mod xcode;
 
 pub use browser::BrowserCacheScanner;
 pub use caches::CacheScanner;
+pub use containers::ContainerScanner;
+pub use custom::load_user_scanners;
 pub use dev::DevJunkScanner;
+pub use ds_store::DsStoreScanner;
 pub use duplicates::DuplicatesScanner;
 pub use large_files::LargeOldFilesScanner;
 pub use logs::LogScanner;
@@ -23,14 +32,20 @@ pub use maintenance::MaintenanceScanner;
 pub use music::MusicJunkScanner;
 pub use photo::PhotoJunkScanner;
 pub use privacy::PrivacyScanner;
-pub use startup::StartupItemsScanner;
+pub use spotlight::SpotlightMetadataScanner;
+pub use startup::{login_launch_warning, StartupItemsScanner};
 pub use trash::TrashScanner;
+pub use xcode::XcodeJunkScanner;
 
+use crate::plugin::{stable_id, ScanResult};
 use chrono::{DateTime, Utc};
 use std::path::Path;
 use walkdir::WalkDir;
 
-fn calculate_dir_size(path: &Path) -> u64 {
+pub(crate) fn calculate_dir_size(path: &Path) -> u64 {
+    if let Some(cache) = size_cache::active() {
+        return cache.totals(path).0;
+    }
     WalkDir::new(path)
         .into_iter()
         .filter_map(|e| e.ok())
@@ -41,6 +56,9 @@ fn calculate_dir_size(path: &Path) -> u64 {
 }
 
 fn count_files(path: &Path) -> u64 {
+    if let Some(cache) = size_cache::active() {
+        return cache.totals(path).1;
+    }
     WalkDir::new(path)
         .into_iter()
         .filter_map(|e| e.ok())
@@ -48,6 +66,51 @@ fn count_files(path: &Path) -> u64 {
         .count() as u64
 }
 
+// Only the largest entries are kept; a partial selection cleans just those.
+const MAX_CHILDREN: usize = 30;
+
+// Direct entries of `parent`, biggest first, as selectable sub-items that
+// inherit the parent's safety level and scanner. Review calls this when a
+// folder is first expanded, so scans never size every entry up front.
+pub fn child_items(parent: &ScanResult) -> Vec<ScanResult> {
+    let Ok(entries) = std::fs::read_dir(&parent.path) else {
+        return Vec::new();
+    };
+
+    let mut children: Vec<ScanResult> = entries
+        .filter_map(|e| e.ok())
+        .map(|entry| {
+            let path = entry.path();
+            let (size, files) = if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                (calculate_dir_size(&path), count_files(&path))
+            } else {
+                (entry.metadata().map(|m| m.len()).unwrap_or(0), 1)
+            };
+            let mut child = ScanResult::new(
+                stable_id("child", &path),
+                entry.file_name().to_string_lossy(),
+                path.clone(),
+            )
+            .with_size(size)
+            .with_file_count(files)
+            .with_category(parent.category)
+            .with_safety(parent.safety_level)
+            .with_safety_reason(parent.safety_reason())
+            .with_last_modified(get_last_modified(&path));
+            if let Some(scanner_id) = parent.metadata.get("scanner_id") {
+                child
+                    .metadata
+                    .insert("scanner_id".to_string(), scanner_id.clone());
+            }
+            child
+        })
+        .collect();
+
+    children.sort_by_key(|c| std::cmp::Reverse(c.size));
+    children.truncate(MAX_CHILDREN);
+    children
+}
+
 fn get_last_accessed(path: &Path) -> Option<DateTime<Utc>> {
     path.metadata()
         .ok()
@@ -61,3 +124,24 @@ fn get_last_modified(path: &Path) -> Option<DateTime<Utc>> {
         .and_then(|m| m.modified().ok())
         .map(|t| t.into())
 }
+
+// Scanners take their paths from one home folder, so tests lay out a fake
+// home under the temp dir and build the scanner with `with_home`.
+#[cfg(test)]
+pub(crate) fn temp_home(name: &str) -> std::path::PathBuf {
+    let home = std::env::temp_dir().join(format!("cleanmac-home-{}", name));
+    let _ = std::fs::remove_dir_all(&home);
+    std::fs::create_dir_all(&home).unwrap();
+    home
+}
+
+// Creates `root/d0/d1/...` `depth` folders deep and returns `root`.
+#[cfg(test)]
+pub(crate) fn nested_tree(root: &Path, depth: usize) -> std::path::PathBuf {
+    let mut dir = root.to_path_buf();
+    for i in 0..depth {
+        dir = dir.join(format!("d{}", i));
+    }
+    std::fs::create_dir_all(&dir).unwrap();
+    root.to_path_buf()
+}
@@ -1,5 +1,5 @@
 use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
-use crate::plugin::{SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use anyhow::Result;
 use std::path::PathBuf;
 
@@ -9,7 +9,7 @@ pub struct MusicJunkScanner {
 
 impl MusicJunkScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        let home = crate::user::home_dir();
 
         let search_paths = vec![
             (
@@ -88,7 +88,7 @@ impl Scanner for MusicJunkScanner {
 
             config.report_progress(&path.display().to_string());
 
-            if config.excluded_paths.iter().any(|ex| path.starts_with(ex)) {
+            if config.is_excluded(path) {
                 continue;
             }
 
@@ -98,7 +98,7 @@ impl Scanner for MusicJunkScanner {
             }
 
             let mut item = ScanResult::new(
-                format!("music_{}", items.len()),
+                stable_id("music", path),
                 format!("Music - {}", label),
                 path.clone(),
             )
@@ -106,8 +106,15 @@ impl Scanner for MusicJunkScanner {
             .with_file_count(count_files(path))
             .with_category(ScannerCategory::System)
             .with_safety(*safety)
+            .with_explanation(
+                "Music and Podcasts data outside your library: artwork caches and downloaded \
+                 episodes. Caches are rebuilt and streamed episodes can be downloaded again.",
+            )
             .with_last_accessed(get_last_accessed(path))
             .with_last_modified(get_last_modified(path));
+            if *safety == SafetyLevel::Safe {
+                item = item.with_safety_reason("Media cache; rebuilt or re-downloaded on demand");
+            }
 
             item.metadata
                 .insert("scanner_id".to_string(), self.id().to_string());
@@ -116,7 +123,7 @@ impl Scanner for MusicJunkScanner {
             items.push(item);
         }
 
-        items.sort_by(|a, b| b.size.cmp(&a.size));
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
         Ok(items)
     }
 
@@ -1,5 +1,5 @@
 use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
-use crate::plugin::{SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use anyhow::Result;
 use std::path::PathBuf;
 
@@ -9,7 +9,7 @@ pub struct PhotoJunkScanner {
 
 impl PhotoJunkScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        let home = crate::user::home_dir();
         let photos_lib = home.join("Pictures/Photos Library.photoslibrary");
 
         let search_paths = vec![
@@ -60,7 +60,7 @@ impl Scanner for PhotoJunkScanner {
 
             config.report_progress(&path.display().to_string());
 
-            if config.excluded_paths.iter().any(|ex| path.starts_with(ex)) {
+            if config.is_excluded(path) {
                 continue;
             }
 
@@ -70,7 +70,7 @@ impl Scanner for PhotoJunkScanner {
             }
 
             let mut item = ScanResult::new(
-                format!("photo_{}", items.len()),
+                stable_id("photo", path),
                 format!("Photos - {}", label),
                 path.clone(),
             )
@@ -78,6 +78,12 @@ impl Scanner for PhotoJunkScanner {
             .with_file_count(count_files(path))
             .with_category(ScannerCategory::System)
             .with_safety(SafetyLevel::Caution)
+            .with_safety_reason("Photos library cache; Photos rebuilds it, which can take a while")
+            .with_explanation(
+                "Thumbnails, previews and search data inside the Photos library. Your photos \
+                 are not touched, but Photos re-analyses the library afterwards, which can \
+                 take hours on a large library.",
+            )
             .with_last_accessed(get_last_accessed(path))
             .with_last_modified(get_last_modified(path));
 
@@ -88,13 +94,17 @@ impl Scanner for PhotoJunkScanner {
             items.push(item);
         }
 
-        items.sort_by(|a, b| b.size.cmp(&a.size));
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
         Ok(items)
     }
 
     fn is_available(&self) -> bool {
         self.search_paths.iter().any(|(_, p)| p.exists())
     }
+
+    fn default_safety(&self) -> SafetyLevel {
+        SafetyLevel::Caution
+    }
 }
 
 impl Default for PhotoJunkScanner {
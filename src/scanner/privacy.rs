@@ -1,4 +1,4 @@
-use crate::plugin::{SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
 use anyhow::Result;
 use std::path::PathBuf;
 
@@ -8,7 +8,7 @@ pub struct PrivacyScanner {
 
 impl PrivacyScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        let home = crate::user::home_dir();
 
         let search_paths = vec![
             ("Safari Cookies", home.join("Library/Cookies/com.apple.Safari/ Cookies.binarycookies"), SafetyLevel::Caution),
@@ -112,11 +112,7 @@ impl Scanner for PrivacyScanner {
 
             config.report_progress(&actual_path.display().to_string());
 
-            if config
-                .excluded_paths
-                .iter()
-                .any(|ex| actual_path.starts_with(ex))
-            {
+            if config.is_excluded(&actual_path) {
                 continue;
             }
 
@@ -126,7 +122,7 @@ impl Scanner for PrivacyScanner {
             }
 
             let mut item = ScanResult::new(
-                format!("privacy_{}", items.len()),
+                stable_id("privacy", &actual_path),
                 label.to_string(),
                 actual_path.clone(),
             )
@@ -139,15 +135,21 @@ impl Scanner for PrivacyScanner {
                     .metadata()
                     .ok()
                     .and_then(|m| m.accessed().ok())
-                    .map(|t| chrono::DateTime::from(t)),
+                    .map(chrono::DateTime::from),
             )
             .with_last_modified(
                 actual_path
                     .metadata()
                     .ok()
                     .and_then(|m| m.modified().ok())
-                    .map(|t| chrono::DateTime::from(t)),
-            );
+                    .map(chrono::DateTime::from),
+            )
+            .with_safety_reason(privacy_reason(label))
+            .with_explanation(format!(
+                "{} records what you opened or visited. {}.",
+                label,
+                privacy_reason(label)
+            ));
 
             item.metadata
                 .insert("scanner_id".to_string(), self.id().to_string());
@@ -156,7 +158,7 @@ impl Scanner for PrivacyScanner {
             items.push(item);
         }
 
-        items.sort_by(|a, b| b.size.cmp(&a.size));
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
         Ok(items)
     }
 
@@ -170,3 +172,17 @@ impl Default for PrivacyScanner {
         Self::new()
     }
 }
+
+fn privacy_reason(label: &str) -> &'static str {
+    if label.ends_with("Login Data") {
+        "Contains saved logins and passwords"
+    } else if label.ends_with("Cookies") {
+        "Deleting cookies signs you out of websites"
+    } else if label.ends_with("History") {
+        "Deleting clears browsing history"
+    } else if label.ends_with("Cache") {
+        "Preview cache; rebuilt automatically"
+    } else {
+        "Recent-items list; clearing only forgets what you opened"
+    }
+}
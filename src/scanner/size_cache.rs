@@ -0,0 +1,212 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// A directory's mtime doesn't move when a file in it grows in place (a log
+// being appended to), so entries are also read again once they are this old.
+const MAX_AGE_SECS: u64 = 60 * 60;
+
+// What one directory held at the last scan. A directory's mtime only moves when
+// entries are added, removed or renamed in it, so an unchanged mtime means the
+// same files and subdirectories. Files growing in place go unnoticed for up to
+// MAX_AGE_SECS; `scan --no-cache` walks everything.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedDir {
+    mtime_ns: u64,
+    files_size: u64,
+    file_count: u64,
+    subdirs: Vec<String>,
+    // Unix seconds when the directory was read; 0 in caches from older versions.
+    #[serde(default)]
+    read_at: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct SizeCache {
+    dirs: Mutex<HashMap<PathBuf, CachedDir>>,
+}
+
+// The cache scanners use while a `scan` runs; None outside of one.
+static ACTIVE: Mutex<Option<Arc<SizeCache>>> = Mutex::new(None);
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn mtime_ns(meta: &fs::Metadata) -> Option<u64> {
+    let since = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    u64::try_from(since.as_nanos()).ok()
+}
+
+impl SizeCache {
+    pub fn load(path: &Path) -> Self {
+        let dirs = fs::read(path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self {
+            dirs: Mutex::new(dirs),
+        }
+    }
+
+    // Drops entries that have expired or whose directory is gone, so the file
+    // doesn't keep every folder any scan ever saw.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut dirs = self.dirs.lock().unwrap();
+        let now = now_secs();
+        dirs.retain(|dir, cached| cached.is_fresh(now) && dir.is_dir());
+        fs::write(path, serde_json::to_vec(&*dirs)?)?;
+        Ok(())
+    }
+
+    // (bytes, files) under `path`, like walking it without following symlinks.
+    pub fn totals(&self, path: &Path) -> (u64, u64) {
+        let Ok(meta) = fs::symlink_metadata(path) else {
+            return (0, 0);
+        };
+        if meta.is_file() {
+            return (meta.len(), 1);
+        }
+        if !meta.is_dir() {
+            return (0, 0);
+        }
+
+        let dir = match self.lookup(path, &meta) {
+            Some(dir) => dir,
+            None => match self.read(path, &meta) {
+                Some(dir) => dir,
+                None => return walk_totals(path),
+            },
+        };
+        dir.subdirs
+            .iter()
+            .map(|name| self.totals(&path.join(name)))
+            .fold((dir.files_size, dir.file_count), |(size, count), (s, c)| {
+                (size + s, count + c)
+            })
+    }
+
+    fn lookup(&self, path: &Path, meta: &fs::Metadata) -> Option<CachedDir> {
+        let mtime = mtime_ns(meta)?;
+        let dirs = self.dirs.lock().unwrap();
+        let now = now_secs();
+        dirs.get(path)
+            .filter(|d| d.mtime_ns == mtime && d.is_fresh(now))
+            .cloned()
+    }
+
+    // None when a subdirectory name isn't valid UTF-8 and so can't be stored.
+    fn read(&self, path: &Path, meta: &fs::Metadata) -> Option<CachedDir> {
+        let mut dir = CachedDir {
+            mtime_ns: mtime_ns(meta).unwrap_or(0),
+            files_size: 0,
+            file_count: 0,
+            subdirs: Vec::new(),
+            read_at: now_secs(),
+        };
+        for entry in fs::read_dir(path).into_iter().flatten().flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                dir.subdirs.push(entry.file_name().into_string().ok()?);
+            } else if file_type.is_file() {
+                dir.files_size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                dir.file_count += 1;
+            }
+        }
+
+        if dir.mtime_ns > 0 {
+            self.dirs
+                .lock()
+                .unwrap()
+                .insert(path.to_path_buf(), dir.clone());
+        }
+        Some(dir)
+    }
+}
+
+impl CachedDir {
+    fn is_fresh(&self, now: u64) -> bool {
+        now.saturating_sub(self.read_at) < MAX_AGE_SECS
+    }
+}
+
+fn walk_totals(path: &Path) -> (u64, u64) {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .fold((0, 0), |(size, count), m| (size + m.len(), count + 1))
+}
+
+pub fn activate(cache: Arc<SizeCache>) {
+    *ACTIVE.lock().unwrap() = Some(cache);
+}
+
+pub fn deactivate() -> Option<Arc<SizeCache>> {
+    ACTIVE.lock().unwrap().take()
+}
+
+pub(crate) fn active() -> Option<Arc<SizeCache>> {
+    ACTIVE.lock().unwrap().clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_directories_are_served_from_the_cache() {
+        let root = std::env::temp_dir().join("cleanmac-size-cache");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a"), vec![0u8; 100]).unwrap();
+        fs::write(root.join("sub/b"), vec![0u8; 10]).unwrap();
+
+        let cache = SizeCache::default();
+        assert_eq!(cache.totals(&root), (110, 2));
+
+        // Survives a save/load round trip.
+        let file = root.with_extension("json");
+        cache.save(&file).unwrap();
+        let cache = SizeCache::load(&file);
+        let _ = fs::remove_file(&file);
+
+        // Rewriting a file in place leaves the directory's mtime alone, so the
+        // cached size comes back instead of a fresh walk.
+        fs::write(root.join("sub/b"), vec![0u8; 50]).unwrap();
+        assert_eq!(cache.totals(&root), (110, 2));
+
+        // Adding an entry changes the mtime and that directory is read again.
+        fs::write(root.join("sub/c"), vec![0u8; 5]).unwrap();
+        assert_eq!(cache.totals(&root), (155, 3));
+
+        // An expired entry is read again even though its mtime still matches.
+        fs::write(root.join("a"), vec![0u8; 200]).unwrap();
+        cache.dirs.lock().unwrap().get_mut(&root).unwrap().read_at = 0;
+        assert_eq!(cache.totals(&root), (255, 3));
+
+        // Saving forgets directories that no longer exist.
+        fs::remove_dir_all(root.join("sub")).unwrap();
+        cache.save(&file).unwrap();
+        let saved = SizeCache::load(&file);
+        let _ = fs::remove_file(&file);
+        let saved = saved.dirs.lock().unwrap();
+        assert!(saved.contains_key(&root));
+        assert!(!saved.contains_key(&root.join("sub")));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
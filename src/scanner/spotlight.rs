@@ -0,0 +1,334 @@
+use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use crate::uninstaller::{AppBundle, AppDetector};
+use anyhow::Result;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use walkdir::WalkDir;
+
+// CoreSpotlight keeps one index folder per app, named after its bundle id, either
+// directly under the root or one level down inside a data-protection class folder.
+const INDEX_DEPTH: usize = 2;
+
+// Lowercased bundle ids of installed apps. `complete` when LaunchServices'
+// Spotlight index was searched too, so an app outside the Applications
+// folders would have been found.
+struct InstalledApps {
+    ids: HashSet<String>,
+    complete: bool,
+}
+
+pub struct SpotlightMetadataScanner {
+    metadata_root: PathBuf,
+    installed: Option<InstalledApps>,
+}
+
+impl SpotlightMetadataScanner {
+    pub fn new() -> Self {
+        Self::with_home(&crate::user::home_dir())
+    }
+
+    fn with_home(home: &Path) -> Self {
+        Self {
+            metadata_root: home.join("Library/Metadata/CoreSpotlight"),
+            installed: None,
+        }
+    }
+
+    fn installed_apps(&self) -> InstalledApps {
+        if let Some(installed) = &self.installed {
+            return InstalledApps {
+                ids: installed.ids.clone(),
+                complete: installed.complete,
+            };
+        }
+        let indexed = indexed_app_paths();
+        let complete = indexed.is_some();
+        let ids = AppDetector::new()
+            .list_nested()
+            .into_iter()
+            .chain(indexed.unwrap_or_default().into_iter().map(AppBundle::new))
+            .filter_map(|app| app.info())
+            .map(|info| info.bundle_id.to_lowercase())
+            .collect();
+        InstalledApps { ids, complete }
+    }
+}
+
+// Every app Spotlight knows about, wherever it lives. None when mdfind is
+// missing or Spotlight indexing is off.
+fn indexed_app_paths() -> Option<Vec<PathBuf>> {
+    let output = Command::new("mdfind")
+        .arg("kMDItemContentType == 'com.apple.application-bundle'")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let paths: Vec<PathBuf> = output
+        .stdout
+        .split(|b| *b == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| PathBuf::from(OsStr::from_bytes(line)))
+        .collect();
+    (!paths.is_empty()).then_some(paths)
+}
+
+// App-group indexes are named `group.<id>` or `<TEAMID>.<id>`; the app that
+// owns them is usually, but not always, the one with the id after the prefix.
+fn shared_prefix_len(name: &str) -> Option<usize> {
+    if name.starts_with("group.") {
+        return Some("group.".len());
+    }
+    let (team, _) = name.split_once('.')?;
+    (team.len() == 10
+        && team
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()))
+    .then_some(team.len() + 1)
+}
+
+// Apple's own apps live outside the folders AppDetector looks in, so their
+// indexes are never treated as orphans.
+fn looks_like_bundle_id(name: &str) -> bool {
+    name.split('.').count() >= 3
+        && !name.to_lowercase().starts_with("com.apple.")
+        && !name.to_lowercase().contains("spotlight")
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-' || c == '_')
+}
+
+// Apps also index under sub-ids such as `com.example.app.helper`, so an
+// index belongs to an installed app when its id extends that app's bundle id.
+fn belongs_to_installed(name: &str, installed: &HashSet<String>) -> bool {
+    let name = name[shared_prefix_len(name).unwrap_or(0)..].to_lowercase();
+    installed.contains(&name)
+        || installed.iter().any(|id| {
+            name.strip_prefix(id.as_str())
+                .is_some_and(|rest| rest.starts_with('.'))
+        })
+}
+
+// Index folders whose bundle id matches no installed app.
+fn orphaned_indexes(
+    root: &Path,
+    installed: &HashSet<String>,
+    config: &ScanConfig,
+) -> Vec<(PathBuf, String)> {
+    let mut orphans = Vec::new();
+    let mut walker = WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(INDEX_DEPTH)
+        .follow_links(config.follow_symlinks)
+        .into_iter();
+
+    while let Some(entry) = walker.next() {
+        let Some(entry) = config.readable(entry) else {
+            continue;
+        };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !looks_like_bundle_id(&name) {
+            continue;
+        }
+        walker.skip_current_dir();
+
+        if !belongs_to_installed(&name, installed) && !config.is_excluded(entry.path()) {
+            orphans.push((entry.path().to_path_buf(), name));
+        }
+    }
+
+    orphans
+}
+
+impl Scanner for SpotlightMetadataScanner {
+    fn id(&self) -> &str {
+        "spotlight_metadata"
+    }
+
+    fn name(&self) -> &str {
+        "Spotlight Metadata"
+    }
+
+    fn category(&self) -> ScannerCategory {
+        ScannerCategory::System
+    }
+
+    fn scan(&self, config: &ScanConfig) -> Result<Vec<ScanResult>> {
+        let mut items = Vec::new();
+        if !self.metadata_root.is_dir() {
+            return Ok(items);
+        }
+
+        let installed = self.installed_apps();
+        // No apps found means we can't tell what is orphaned; report nothing.
+        if installed.ids.is_empty() {
+            return Ok(items);
+        }
+
+        config.report_progress(&self.metadata_root.display().to_string());
+        for (path, bundle_id) in orphaned_indexes(&self.metadata_root, &installed.ids, config) {
+            let size = calculate_dir_size(&path);
+            if size < config.min_size {
+                continue;
+            }
+
+            // Only an index no app could own, after a search that would have
+            // found the app anywhere, is safe to drop without a look.
+            let (safety, reason) = if installed.complete && shared_prefix_len(&bundle_id).is_none()
+            {
+                (
+                    SafetyLevel::Safe,
+                    "Spotlight index for an app that is no longer installed",
+                )
+            } else {
+                (
+                        SafetyLevel::Caution,
+                        "Spotlight index that matches no app found; check the app isn't installed elsewhere",
+                    )
+            };
+
+            let mut item = ScanResult::new(
+                stable_id("spotlight", &path),
+                format!("{} (index)", bundle_id),
+                path.clone(),
+            )
+            .with_size(size)
+            .with_file_count(count_files(&path))
+            .with_category(ScannerCategory::System)
+            .with_safety(safety)
+            .with_safety_reason(reason)
+            .with_explanation(
+                "Apps donate their content to Spotlight through CoreSpotlight. This index \
+                 belongs to a bundle id that matches no app in /Applications, \
+                 ~/Applications (subfolders included) or Spotlight's list of apps, so \
+                 nothing will read or rebuild it.",
+            )
+            .with_last_accessed(get_last_accessed(&path))
+            .with_last_modified(get_last_modified(&path));
+
+            item.metadata
+                .insert("scanner_id".to_string(), self.id().to_string());
+            item.metadata.insert("bundle_id".to_string(), bundle_id);
+
+            config.report_item(item.clone());
+            items.push(item);
+        }
+
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
+        Ok(items)
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+impl Default for SpotlightMetadataScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::temp_home;
+
+    fn home_with_indexes(name: &str) -> PathBuf {
+        let home = temp_home(&format!("spotlight-{}", name));
+        let root = home.join("Library/Metadata/CoreSpotlight");
+        for dir in [
+            "NSFileProtectionComplete/com.example.gone",
+            "NSFileProtectionComplete/com.example.kept",
+            "NSFileProtectionComplete/com.example.kept.helper",
+            "NSFileProtectionComplete/com.example.keptnot",
+            "NSFileProtectionComplete/com.apple.mail",
+            "com.example.toplevel",
+            "group.com.example.kept",
+            "group.com.example.shared",
+            "index.spotlightV3",
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("store.db"), vec![0u8; 32]).unwrap();
+        }
+        home
+    }
+
+    // AppDetector looks at the real /Applications, so tests name the
+    // installed apps themselves.
+    fn scanner_with_apps(home: &Path, installed: &[&str]) -> SpotlightMetadataScanner {
+        let mut scanner = SpotlightMetadataScanner::with_home(home);
+        scanner.installed = Some(InstalledApps {
+            ids: installed.iter().map(|id| id.to_lowercase()).collect(),
+            complete: true,
+        });
+        scanner
+    }
+
+    #[test]
+    fn reports_only_indexes_of_missing_third_party_apps() {
+        let home = home_with_indexes("orphans");
+        let root = home.join("Library/Metadata/CoreSpotlight");
+        let scanner = scanner_with_apps(&home, &["com.Example.Kept"]);
+        let config = ScanConfig {
+            min_size: 1,
+            ..Default::default()
+        };
+
+        let items = scanner.scan(&config).unwrap();
+        let mut paths: Vec<PathBuf> = items.iter().map(|i| i.path.clone()).collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![
+                root.join("NSFileProtectionComplete/com.example.gone"),
+                root.join("NSFileProtectionComplete/com.example.keptnot"),
+                root.join("com.example.toplevel"),
+                root.join("group.com.example.shared"),
+            ]
+        );
+        let caution: Vec<&str> = items
+            .iter()
+            .filter(|i| i.safety_level == SafetyLevel::Caution)
+            .map(|i| i.metadata["bundle_id"].as_str())
+            .collect();
+        assert_eq!(caution, vec!["group.com.example.shared"]);
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn orphans_need_review_when_spotlight_could_not_be_searched() {
+        let home = home_with_indexes("incomplete");
+        let mut scanner = scanner_with_apps(&home, &["com.example.kept"]);
+        scanner.installed.as_mut().unwrap().complete = false;
+        let config = ScanConfig {
+            min_size: 1,
+            ..Default::default()
+        };
+
+        let items = scanner.scan(&config).unwrap();
+        assert!(!items.is_empty());
+        assert!(items.iter().all(|i| i.safety_level == SafetyLevel::Caution));
+        assert_eq!(shared_prefix_len("ABCDE12345.com.example.app"), Some(11));
+        assert_eq!(shared_prefix_len("com.example.app"), None);
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[test]
+    fn reports_nothing_when_no_apps_were_found() {
+        let home = home_with_indexes("no-apps");
+        let scanner = scanner_with_apps(&home, &[]);
+        assert!(scanner.scan(&ScanConfig::default()).unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+}
@@ -40,7 +40,7 @@ struct StartupItem {
 
 impl StartupItemsScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        let home = crate::user::home_dir();
 
         let search_paths = vec![
             (
@@ -163,11 +163,7 @@ impl Scanner for StartupItemsScanner {
             config.report_progress(&path.display().to_string());
 
             for startup_item in self.scan_directory(path, *category) {
-                if config
-                    .excluded_paths
-                    .iter()
-                    .any(|ex| startup_item.path.starts_with(ex))
-                {
+                if config.is_excluded(&startup_item.path) {
                     continue;
                 }
 
@@ -179,7 +175,21 @@ impl Scanner for StartupItemsScanner {
                 .with_size(0)
                 .with_file_count(1)
                 .with_category(ScannerCategory::System)
-                .with_safety(SafetyLevel::Caution);
+                .with_safety(match category {
+                    StartupCategory::SystemLaunchDaemon => SafetyLevel::Protected,
+                    _ => SafetyLevel::Caution,
+                })
+                .with_safety_reason(match category {
+                    StartupCategory::SystemLaunchDaemon => {
+                        "System launch daemon; required by macOS"
+                    }
+                    _ => "Starts automatically; removing it changes what runs at login",
+                })
+                .with_explanation(format!(
+                    "A {} that launchd starts on its own. Nothing recreates the file, but \
+                     reinstalling or reopening its app may add it back.",
+                    category.display_name()
+                ));
 
                 item.metadata
                     .insert("scanner_id".to_string(), self.id().to_string());
@@ -205,6 +215,34 @@ impl Scanner for StartupItemsScanner {
     fn is_available(&self) -> bool {
         true
     }
+
+    fn default_safety(&self) -> SafetyLevel {
+        SafetyLevel::Caution
+    }
+}
+
+pub fn login_launch_warning(item: &ScanResult) -> Option<String> {
+    if item.metadata.get("scanner_id").map(|s| s.as_str()) != Some("startup_items") {
+        return None;
+    }
+
+    let enabled = item.metadata.get("run_at_load").map(|s| s.as_str()) == Some("true")
+        && item.metadata.get("disabled").map(|s| s.as_str()) != Some("true");
+    if !enabled {
+        return None;
+    }
+
+    let program = item
+        .metadata
+        .get("program")
+        .and_then(|p| std::path::Path::new(p).file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| item.name.clone());
+
+    Some(format!(
+        "This will stop {} from launching at login.",
+        program
+    ))
 }
 
 impl Default for StartupItemsScanner {
@@ -9,7 +9,7 @@ pub struct TrashScanner {
 
 impl TrashScanner {
     pub fn new() -> Self {
-        let home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("/"));
+        let home = crate::user::home_dir();
 
         Self {
             trash_paths: vec![home.join(".Trash")],
@@ -50,6 +50,11 @@ impl Scanner for TrashScanner {
                     .with_file_count(file_count)
                     .with_category(ScannerCategory::Trash)
                     .with_safety(SafetyLevel::Safe)
+                    .with_safety_reason("Files you already moved to the Trash")
+                    .with_explanation(
+                        "Everything in ~/.Trash. Emptying it is the same as Finder's Empty \
+                         Trash: the files cannot be put back afterwards.",
+                    )
                     .with_last_accessed(get_last_accessed(trash_path))
                     .with_last_modified(get_last_modified(trash_path));
 
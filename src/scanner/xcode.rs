@@ -0,0 +1,416 @@
+use super::{calculate_dir_size, count_files, get_last_accessed, get_last_modified};
+use crate::plugin::{stable_id, SafetyLevel, ScanConfig, ScanResult, Scanner, ScannerCategory};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use plist::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DEVICE_SUPPORT_DIRS: [&str; 3] = [
+    "iOS DeviceSupport",
+    "watchOS DeviceSupport",
+    "tvOS DeviceSupport",
+];
+
+// Archives can't be rebuilt, so the CLI holds them for review like a live VM disk.
+const ARCHIVE_WARNING: &str = "this build's dSYMs can't be recreated once deleted";
+
+pub struct XcodeJunkScanner {
+    developer: PathBuf,
+}
+
+// "MyApp-bqxjgfzvhuwlkrsdmcoapeintyhg" -> "MyApp". Xcode names each
+// DerivedData folder after the project plus a 28-letter hash of its path.
+fn project_name(folder: &str) -> Option<&str> {
+    let (name, hash) = folder.rsplit_once('-')?;
+    (!name.is_empty() && hash.len() == 28 && hash.bytes().all(|b| b.is_ascii_lowercase()))
+        .then_some(name)
+}
+
+// A folder's own mtime only moves when entries come or go, so the newest of
+// its direct entries says more about when the project was last built.
+fn last_built(path: &Path) -> Option<DateTime<Utc>> {
+    let newest_entry = fs::read_dir(path)
+        .ok()?
+        .flatten()
+        .filter_map(|e| e.metadata().ok()?.modified().ok())
+        .max()
+        .map(DateTime::<Utc>::from);
+    newest_entry.max(get_last_modified(path))
+}
+
+fn plist_string(path: &Path, key: &str) -> Option<String> {
+    let value = Value::from_file(path).ok()?;
+    Some(value.as_dictionary()?.get(key)?.as_string()?.to_string())
+}
+
+impl XcodeJunkScanner {
+    pub fn new() -> Self {
+        Self::with_home(&crate::user::home_dir())
+    }
+
+    fn with_home(home: &Path) -> Self {
+        Self {
+            developer: home.join("Library/Developer"),
+        }
+    }
+
+    fn make_item(
+        &self,
+        path: &Path,
+        name: String,
+        size: u64,
+        reason: &str,
+        explanation: &str,
+    ) -> ScanResult {
+        // Everything here is rebuilt or downloaded again, but that costs a
+        // clean build, a device reconnect or a multi-GB download, so nothing is
+        // cleaned without the user opting into Caution items.
+        let mut item = ScanResult::new(stable_id("xcode_junk", path), name, path.to_path_buf())
+            .with_size(size)
+            .with_file_count(count_files(path))
+            .with_category(ScannerCategory::Development)
+            .with_safety(SafetyLevel::Caution)
+            .with_safety_reason(reason)
+            .with_explanation(explanation)
+            .with_last_accessed(get_last_accessed(path))
+            .with_last_modified(get_last_modified(path));
+        item.metadata
+            .insert("scanner_id".to_string(), self.id().to_string());
+        item
+    }
+
+    // Bundle ids of the runtimes some simulator device was created with.
+    fn runtimes_in_use(&self) -> HashSet<String> {
+        let Ok(entries) = fs::read_dir(self.developer.join("CoreSimulator/Devices")) else {
+            return HashSet::new();
+        };
+        entries
+            .flatten()
+            .filter_map(|e| plist_string(&e.path().join("device.plist"), "runtime"))
+            .collect()
+    }
+
+    fn scan_derived_data(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        let Ok(entries) = fs::read_dir(self.developer.join("Xcode/DerivedData")) else {
+            return;
+        };
+        for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+            let path = entry.path();
+            if config.is_excluded(&path) {
+                continue;
+            }
+            config.report_progress(&path.display().to_string());
+            let size = calculate_dir_size(&path);
+            if size < config.min_size {
+                continue;
+            }
+
+            let folder = entry.file_name().to_string_lossy().to_string();
+            if folder == "ModuleCache.noindex" {
+                items.push(self.make_item(
+                    &path,
+                    "Module cache (DerivedData)".to_string(),
+                    size,
+                    "Precompiled modules; Xcode rebuilds them",
+                    "Clang and Swift modules shared by every project. The next build \
+                     of any project recompiles the ones it needs.",
+                ));
+                continue;
+            }
+
+            let project = project_name(&folder).unwrap_or(&folder).to_string();
+            let mut item = self.make_item(
+                &path,
+                format!("{} (DerivedData)", project),
+                size,
+                "Build products and indexes; Xcode rebuilds them",
+                "Intermediate build files, products and the index for one project. \
+                 The next build starts from scratch and indexing runs again.",
+            );
+            item.last_modified = last_built(&path);
+            item.metadata.insert("project".to_string(), project);
+            items.push(item);
+        }
+    }
+
+    // Archives/<date>/<name>.xcarchive, one item per archive.
+    fn scan_archives(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        let Ok(days) = fs::read_dir(self.developer.join("Xcode/Archives")) else {
+            return;
+        };
+        for day in days.flatten().filter(|e| e.path().is_dir()) {
+            let Ok(entries) = fs::read_dir(day.path()) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("xcarchive")
+                    || config.is_excluded(&path)
+                {
+                    continue;
+                }
+                config.report_progress(&path.display().to_string());
+                let size = calculate_dir_size(&path);
+                if size < config.min_size {
+                    continue;
+                }
+                let name = plist_string(&path.join("Info.plist"), "Name").unwrap_or_else(|| {
+                    path.file_stem()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default()
+                });
+                let mut item = self.make_item(
+                    &path,
+                    format!("{} archive ({})", name, day.file_name().to_string_lossy()),
+                    size,
+                    "Holds the dSYMs needed to symbolicate crash reports from this build",
+                    "An app build made with Product > Archive, with its debug symbols. \
+                     Nothing recreates it; keep the archives of builds you shipped.",
+                );
+                item.metadata.insert("project".to_string(), name);
+                item.metadata
+                    .insert("warning".to_string(), ARCHIVE_WARNING.to_string());
+                items.push(item);
+            }
+        }
+    }
+
+    fn scan_device_support(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        for dir in DEVICE_SUPPORT_DIRS {
+            let Ok(entries) = fs::read_dir(self.developer.join("Xcode").join(dir)) else {
+                continue;
+            };
+            let platform = dir.trim_end_matches(" DeviceSupport");
+            for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+                let path = entry.path();
+                if config.is_excluded(&path) {
+                    continue;
+                }
+                config.report_progress(&path.display().to_string());
+                let size = calculate_dir_size(&path);
+                if size < config.min_size {
+                    continue;
+                }
+                items.push(self.make_item(
+                    &path,
+                    format!(
+                        "{} {} device support",
+                        platform,
+                        entry.file_name().to_string_lossy()
+                    ),
+                    size,
+                    "Debug symbols copied from a device; copied again on connect",
+                    "Symbols Xcode copied from a device running this OS version. \
+                     Connecting such a device again copies them back, which takes a \
+                     few minutes.",
+                ));
+            }
+        }
+    }
+
+    fn scan_simulator(&self, config: &ScanConfig, items: &mut Vec<ScanResult>) {
+        let caches = self.developer.join("CoreSimulator/Caches");
+        if caches.is_dir() && !config.is_excluded(&caches) {
+            config.report_progress(&caches.display().to_string());
+            let size = calculate_dir_size(&caches);
+            if size >= config.min_size {
+                items.push(self.make_item(
+                    &caches,
+                    "Simulator caches".to_string(),
+                    size,
+                    "Simulator dyld and runtime caches; rebuilt on boot",
+                    "Shared caches the simulators build when they boot. The next \
+                     simulator launch rebuilds them and is slower.",
+                ));
+            }
+        }
+
+        let Ok(entries) = fs::read_dir(self.developer.join("CoreSimulator/Profiles/Runtimes"))
+        else {
+            return;
+        };
+        let in_use = self.runtimes_in_use();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("simruntime")
+                || config.is_excluded(&path)
+            {
+                continue;
+            }
+            config.report_progress(&path.display().to_string());
+            let size = calculate_dir_size(&path);
+            if size < config.min_size {
+                continue;
+            }
+
+            let runtime = plist_string(&path.join("Contents/Info.plist"), "CFBundleIdentifier");
+            let active = runtime.as_ref().is_some_and(|id| in_use.contains(id));
+            let reason = if active {
+                "A simulator device still uses this runtime"
+            } else {
+                "No simulator device uses this runtime"
+            };
+            let name = path
+                .file_stem()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let mut item = self.make_item(
+                &path,
+                format!("{} simulator runtime", name),
+                size,
+                reason,
+                "A simulator OS. Devices created with it stop booting once it is gone; \
+                 Xcode > Settings > Platforms downloads it again.",
+            );
+            if let Some(runtime) = runtime {
+                item.metadata.insert("runtime".to_string(), runtime);
+            }
+            items.push(item);
+        }
+    }
+}
+
+impl Scanner for XcodeJunkScanner {
+    fn id(&self) -> &str {
+        "xcode_junk"
+    }
+
+    fn name(&self) -> &str {
+        "Xcode Junk"
+    }
+
+    fn category(&self) -> ScannerCategory {
+        ScannerCategory::Development
+    }
+
+    fn scan(&self, config: &ScanConfig) -> Result<Vec<ScanResult>> {
+        let mut items = Vec::new();
+        self.scan_derived_data(config, &mut items);
+        self.scan_archives(config, &mut items);
+        self.scan_device_support(config, &mut items);
+        self.scan_simulator(config, &mut items);
+
+        for item in &items {
+            config.report_item(item.clone());
+        }
+        items.sort_by_key(|b| std::cmp::Reverse(b.size));
+        Ok(items)
+    }
+
+    fn is_available(&self) -> bool {
+        self.developer.join("Xcode").is_dir() || self.developer.join("CoreSimulator").is_dir()
+    }
+
+    fn default_safety(&self) -> SafetyLevel {
+        SafetyLevel::Caution
+    }
+}
+
+impl Default for XcodeJunkScanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::temp_home;
+    use plist::Dictionary;
+
+    fn write_plist(path: &Path, key: &str, value: &str) {
+        let mut dict = Dictionary::new();
+        dict.insert(key.to_string(), Value::String(value.to_string()));
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        Value::Dictionary(dict).to_file_xml(path).unwrap();
+    }
+
+    #[test]
+    fn reports_each_xcode_folder_as_caution_with_its_size() {
+        let home = temp_home("xcode-junk");
+        let dev = home.join("Library/Developer");
+        for (file, size) in [
+            (
+                "Xcode/DerivedData/MyApp-bqxjgfzvhuwlkrsdmcoapeintyhg/Build/app",
+                500,
+            ),
+            ("Xcode/DerivedData/ModuleCache.noindex/Foundation.pcm", 300),
+            (
+                "Xcode/Archives/2024-01-15/MyApp 1-15-24, 10.02.xcarchive/dSYMs/MyApp",
+                2000,
+            ),
+            ("Xcode/iOS DeviceSupport/17.2 (21C62)/Symbols/dyld", 400),
+            ("CoreSimulator/Caches/dyld/cache", 200),
+            (
+                "CoreSimulator/Profiles/Runtimes/iOS 16.4.simruntime/Contents/img",
+                700,
+            ),
+            (
+                "CoreSimulator/Profiles/Runtimes/iOS 15.5.simruntime/Contents/img",
+                600,
+            ),
+        ] {
+            let path = dev.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, vec![0u8; size]).unwrap();
+        }
+        let runtimes = dev.join("CoreSimulator/Profiles/Runtimes");
+        write_plist(
+            &runtimes.join("iOS 16.4.simruntime/Contents/Info.plist"),
+            "CFBundleIdentifier",
+            "com.apple.CoreSimulator.SimRuntime.iOS-16-4",
+        );
+        write_plist(
+            &runtimes.join("iOS 15.5.simruntime/Contents/Info.plist"),
+            "CFBundleIdentifier",
+            "com.apple.CoreSimulator.SimRuntime.iOS-15-5",
+        );
+        write_plist(
+            &dev.join("CoreSimulator/Devices/6F1C/device.plist"),
+            "runtime",
+            "com.apple.CoreSimulator.SimRuntime.iOS-16-4",
+        );
+
+        let config = ScanConfig {
+            min_size: 1,
+            ..Default::default()
+        };
+        let scanner = XcodeJunkScanner::with_home(&home);
+        assert!(scanner.is_available());
+        let items = scanner.scan(&config).unwrap();
+
+        let found: Vec<&str> = items.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(
+            found,
+            vec![
+                "MyApp 1-15-24, 10.02 archive (2024-01-15)",
+                "iOS 16.4 simulator runtime",
+                "iOS 15.5 simulator runtime",
+                "MyApp (DerivedData)",
+                "iOS 17.2 (21C62) device support",
+                "Module cache (DerivedData)",
+                "Simulator caches",
+            ]
+        );
+        let sizes: Vec<u64> = items.iter().skip(3).map(|i| i.size).collect();
+        assert_eq!(sizes, vec![500, 400, 300, 200]);
+        assert_eq!(items[0].size, 2000);
+        assert!(items
+            .iter()
+            .all(|i| i.safety_level == SafetyLevel::Caution
+                && i.metadata["scanner_id"] == "xcode_junk"));
+        assert_eq!(
+            items[1].safety_reason(),
+            "A simulator device still uses this runtime"
+        );
+        assert_eq!(items[0].metadata["warning"], ARCHIVE_WARNING);
+        assert!(!items[3].metadata.contains_key("warning"));
+        let my_app = &items[3];
+        assert_eq!(my_app.metadata["project"], "MyApp");
+        assert!(my_app.last_modified.is_some());
+
+        let _ = fs::remove_dir_all(&home);
+    }
+}
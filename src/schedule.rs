@@ -0,0 +1,201 @@
+use crate::cli::ScheduleInterval;
+use anyhow::{bail, Context, Result};
+use plist::{Dictionary, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+pub const LABEL: &str = "com.cleanmac.scheduled-scan";
+
+// Scheduled runs are scan-only on purpose: nothing here ever passes --execute.
+#[derive(Debug, Clone)]
+pub struct ScheduleSpec {
+    pub interval: ScheduleInterval,
+    pub categories: Vec<String>,
+    pub report_dir: PathBuf,
+    pub binary: PathBuf,
+}
+
+pub fn agent_path() -> PathBuf {
+    crate::user::home_dir()
+        .join("Library/LaunchAgents")
+        .join(format!("{}.plist", LABEL))
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+// One dated JSON report per category, e.g. scan-caches-20261016-1000.json.
+// A category that fails does not stop the others.
+fn scan_script(spec: &ScheduleSpec) -> String {
+    let dir = spec.report_dir.display().to_string();
+    let mut script = format!(
+        "mkdir -p {} || exit 1; stamp=$(date +%Y%m%d-%H%M)",
+        shell_quote(&dir)
+    );
+    for category in &spec.categories {
+        script.push_str(&format!(
+            "; {} scan -c {} -F json -o {}\"$stamp\".json",
+            shell_quote(&spec.binary.display().to_string()),
+            shell_quote(category),
+            shell_quote(&format!("{}/scan-{}-", dir, category)),
+        ));
+    }
+    script
+}
+
+fn calendar_interval(interval: ScheduleInterval) -> Dictionary {
+    let mut when = Dictionary::new();
+    match interval {
+        ScheduleInterval::Daily => {}
+        // Monday morning
+        ScheduleInterval::Weekly => {
+            when.insert("Weekday".into(), Value::Integer(1.into()));
+        }
+        ScheduleInterval::Monthly => {
+            when.insert("Day".into(), Value::Integer(1.into()));
+        }
+    }
+    when.insert("Hour".into(), Value::Integer(10.into()));
+    when.insert("Minute".into(), Value::Integer(0.into()));
+    when
+}
+
+pub fn plist_xml(spec: &ScheduleSpec) -> Result<String> {
+    let log = spec.report_dir.join("cleanmac-schedule.log");
+    let mut agent = Dictionary::new();
+    agent.insert("Label".into(), Value::String(LABEL.into()));
+    agent.insert(
+        "ProgramArguments".into(),
+        Value::Array(vec![
+            Value::String("/bin/sh".into()),
+            Value::String("-c".into()),
+            Value::String(scan_script(spec)),
+        ]),
+    );
+    agent.insert(
+        "StartCalendarInterval".into(),
+        Value::Dictionary(calendar_interval(spec.interval)),
+    );
+    agent.insert(
+        "StandardOutPath".into(),
+        Value::String(log.display().to_string()),
+    );
+    agent.insert(
+        "StandardErrorPath".into(),
+        Value::String(log.display().to_string()),
+    );
+    agent.insert("LowPriorityIO".into(), Value::Boolean(true));
+    agent.insert("ProcessType".into(), Value::String("Background".into()));
+
+    let mut xml = Vec::new();
+    Value::Dictionary(agent).to_writer_xml(&mut xml)?;
+    Ok(String::from_utf8(xml)?)
+}
+
+fn launchctl(action: &str, path: &Path) -> Result<()> {
+    let output = Command::new("launchctl")
+        .arg(action)
+        .arg(path)
+        .output()
+        .context("Could not run launchctl")?;
+    if !output.status.success() {
+        bail!(
+            "launchctl {} failed: {}",
+            action,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+// Replaces an existing schedule rather than stacking a second one.
+pub fn install(spec: &ScheduleSpec) -> Result<PathBuf> {
+    let path = agent_path();
+    if path.exists() {
+        let _ = launchctl("unload", &path);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, plist_xml(spec)?)?;
+    launchctl("load", &path)?;
+    Ok(path)
+}
+
+// Ok(false) when nothing was scheduled.
+pub fn uninstall() -> Result<bool> {
+    let path = agent_path();
+    if !path.exists() {
+        return Ok(false);
+    }
+    let _ = launchctl("unload", &path);
+    fs::remove_file(&path)?;
+    Ok(true)
+}
+
+pub fn is_loaded() -> bool {
+    Command::new("launchctl")
+        .args(["list", LABEL])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plist_runs_a_dated_scan_per_category_and_never_executes() {
+        let spec = ScheduleSpec {
+            interval: ScheduleInterval::Weekly,
+            categories: vec!["caches".into(), "trash".into()],
+            report_dir: PathBuf::from("/Users/me/cleanmac reports"),
+            binary: PathBuf::from("/usr/local/bin/cleanmac"),
+        };
+        let xml = plist_xml(&spec).unwrap();
+        let parsed = Value::from_reader_xml(xml.as_bytes()).unwrap();
+        let agent = parsed.as_dictionary().unwrap();
+
+        assert_eq!(agent.get("Label").unwrap().as_string(), Some(LABEL));
+        let args: Vec<&str> = agent
+            .get("ProgramArguments")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.as_string().unwrap())
+            .collect();
+        assert_eq!(&args[..2], &["/bin/sh", "-c"]);
+        assert_eq!(
+            args[2],
+            "mkdir -p '/Users/me/cleanmac reports' || exit 1; stamp=$(date +%Y%m%d-%H%M)\
+             ; '/usr/local/bin/cleanmac' scan -c 'caches' -F json \
+             -o '/Users/me/cleanmac reports/scan-caches-'\"$stamp\".json\
+             ; '/usr/local/bin/cleanmac' scan -c 'trash' -F json \
+             -o '/Users/me/cleanmac reports/scan-trash-'\"$stamp\".json"
+        );
+        assert!(!args[2].contains("--execute"));
+
+        let when = agent
+            .get("StartCalendarInterval")
+            .unwrap()
+            .as_dictionary()
+            .unwrap();
+        assert_eq!(
+            when.get("Weekday").and_then(|v| v.as_signed_integer()),
+            Some(1)
+        );
+        assert_eq!(
+            when.get("Hour").and_then(|v| v.as_signed_integer()),
+            Some(10)
+        );
+    }
+
+    #[test]
+    fn quotes_single_quotes_in_paths() {
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+}
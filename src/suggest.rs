@@ -0,0 +1,286 @@
+use crate::plugin::registry::CategoryScanResult;
+use crate::plugin::SafetyLevel;
+use anyhow::Result;
+use serde::Serialize;
+use std::ffi::CString;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::process::Command;
+
+// Contents of these are rebuilt by the owning app or tool on demand.
+const REGENERABLE: &[&str] = &["system_caches", "browser_cache", "dev_junk"];
+
+const CAUTION_PENALTY: f64 = 4.0;
+const REGENERABLE_BONUS: f64 = 1.5;
+
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct DiskSpace {
+    pub total_bytes: u64,
+    pub free_bytes: u64,
+    // Only filled in by disk_space_with_purgeable; None if macOS didn't say.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purgeable_bytes: Option<u64>,
+}
+
+impl DiskSpace {
+    // Free space plus what macOS would purge to make room for a big write.
+    pub fn available_bytes(&self) -> u64 {
+        self.free_bytes
+            .saturating_add(self.purgeable_bytes.unwrap_or(0))
+    }
+}
+
+pub fn disk_space(path: &Path) -> Result<DiskSpace> {
+    let c_path = CString::new(path.as_os_str().as_bytes())?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+
+    let block = stat.f_frsize as u64;
+    Ok(DiskSpace {
+        total_bytes: stat.f_blocks as u64 * block,
+        free_bytes: stat.f_bavail as u64 * block,
+        purgeable_bytes: None,
+    })
+}
+
+// Asks Foundation for the volume's "available for important usage" capacity,
+// which counts space macOS can reclaim on demand.
+const IMPORTANT_CAPACITY_SCRIPT: &str = r#"function run(argv) {
+    ObjC.import("Foundation");
+    const value = Ref();
+    $.NSURL.fileURLWithPath(argv[0]).getResourceValueForKeyError(
+        value, $.NSURLVolumeAvailableCapacityForImportantUsageKey, null);
+    return ObjC.unwrap(value[0]);
+}"#;
+
+// Purgeable space (iCloud copies, local snapshots, caches macOS clears itself)
+// estimated as the important-usage capacity minus what statvfs calls free.
+// Slower than statvfs, so only commands that show it ask for it.
+pub fn disk_space_with_purgeable(path: &Path) -> Result<DiskSpace> {
+    let mut space = disk_space(path)?;
+    space.purgeable_bytes = Command::new("osascript")
+        .args(["-l", "JavaScript", "-e", IMPORTANT_CAPACITY_SCRIPT])
+        .arg(path)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| parse_capacity(&String::from_utf8_lossy(&output.stdout)))
+        .map(|important| important.saturating_sub(space.free_bytes));
+    Ok(space)
+}
+
+fn parse_capacity(text: &str) -> Option<u64> {
+    let text = text.trim();
+    text.parse::<u64>().ok().or_else(|| {
+        text.parse::<f64>()
+            .ok()
+            .filter(|n| *n >= 0.0)
+            .map(|n| n as u64)
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Suggestion {
+    pub scanner_id: String,
+    pub name: String,
+    pub safe_bytes: u64,
+    pub caution_bytes: u64,
+    pub item_count: usize,
+    pub regenerable: bool,
+    pub score: f64,
+    pub command: String,
+}
+
+impl Suggestion {
+    pub fn reclaimable(&self) -> u64 {
+        self.safe_bytes + self.caution_bytes
+    }
+}
+
+// Bytes weighted by risk: Caution counts for a quarter, Protected not at all,
+// and regenerable caches get a bonus since deleting them costs nothing lasting.
+pub fn rank_categories(categories: &[CategoryScanResult]) -> Vec<Suggestion> {
+    let mut suggestions: Vec<Suggestion> = categories
+        .iter()
+        .filter_map(|cat| {
+            let mut safe_bytes = 0;
+            let mut caution_bytes = 0;
+            let mut item_count = 0;
+
+            for item in &cat.items {
+                match item.safety_level {
+                    SafetyLevel::Safe => safe_bytes += item.size,
+                    SafetyLevel::Caution => caution_bytes += item.size,
+                    SafetyLevel::Protected => continue,
+                }
+                item_count += 1;
+            }
+
+            if safe_bytes + caution_bytes == 0 {
+                return None;
+            }
+
+            let regenerable = REGENERABLE.contains(&cat.scanner_id.as_str());
+            let mut score = safe_bytes as f64 + caution_bytes as f64 / CAUTION_PENALTY;
+            if regenerable {
+                score *= REGENERABLE_BONUS;
+            }
+
+            Some(Suggestion {
+                scanner_id: cat.scanner_id.clone(),
+                name: cat.name.clone(),
+                safe_bytes,
+                caution_bytes,
+                item_count,
+                regenerable,
+                score,
+                command: suggested_command(&cat.scanner_id, safe_bytes, caution_bytes),
+            })
+        })
+        .collect();
+
+    suggestions.sort_by(|a, b| b.score.total_cmp(&a.score));
+    suggestions
+}
+
+// Unattended `--yes` only where every item is Safe. A category with Caution
+// items gets --only-safe, or a dry-run listing to review when nothing is Safe.
+fn suggested_command(scanner_id: &str, safe_bytes: u64, caution_bytes: u64) -> String {
+    if caution_bytes == 0 {
+        format!("cleanmac apply --category {} --yes", scanner_id)
+    } else if safe_bytes > 0 {
+        format!("cleanmac apply --category {} --only-safe --yes", scanner_id)
+    } else {
+        format!("cleanmac clean --category {}", scanner_id)
+    }
+}
+
+// Leading steps whose combined reclaimable size covers `needed` (all steps if it can't be met).
+pub fn steps_for_target(suggestions: &[Suggestion], needed: u64) -> usize {
+    let mut freed = 0;
+    for (i, s) in suggestions.iter().enumerate() {
+        if freed >= needed {
+            return i;
+        }
+        freed += s.reclaimable();
+    }
+    suggestions.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::{ScanResult, ScannerCategory};
+    use std::path::PathBuf;
+
+    fn category(id: &str, items: &[(u64, SafetyLevel)]) -> CategoryScanResult {
+        CategoryScanResult {
+            scanner_id: id.to_string(),
+            name: id.to_string(),
+            category: ScannerCategory::System,
+            items: items
+                .iter()
+                .enumerate()
+                .map(|(i, (size, safety))| {
+                    ScanResult::new(
+                        format!("{}_{}", id, i),
+                        "x".to_string(),
+                        PathBuf::from("/tmp/x"),
+                    )
+                    .with_size(*size)
+                    .with_safety(*safety)
+                })
+                .collect(),
+            scan_duration: std::time::Duration::ZERO,
+            unreadable: 0,
+            omitted: 0,
+        }
+    }
+
+    #[test]
+    fn safe_bytes_outrank_caution_bytes_of_similar_size() {
+        let ranked = rank_categories(&[
+            category("large_old_files", &[(3_000, SafetyLevel::Caution)]),
+            category("trash", &[(1_000, SafetyLevel::Safe)]),
+        ]);
+        assert_eq!(ranked[0].scanner_id, "trash");
+        assert_eq!(ranked[1].scanner_id, "large_old_files");
+    }
+
+    #[test]
+    fn regenerable_categories_get_a_boost() {
+        let ranked = rank_categories(&[
+            category("system_logs", &[(1_200, SafetyLevel::Safe)]),
+            category("system_caches", &[(1_000, SafetyLevel::Safe)]),
+        ]);
+        assert_eq!(ranked[0].scanner_id, "system_caches");
+        assert!(ranked[0].regenerable);
+    }
+
+    #[test]
+    fn protected_items_and_empty_categories_are_dropped() {
+        let ranked = rank_categories(&[
+            category("startup_items", &[(5_000, SafetyLevel::Protected)]),
+            category("trash", &[]),
+            category(
+                "dev_junk",
+                &[(10, SafetyLevel::Safe), (99, SafetyLevel::Protected)],
+            ),
+        ]);
+        assert_eq!(ranked.len(), 1);
+        assert_eq!(ranked[0].reclaimable(), 10);
+        assert_eq!(ranked[0].item_count, 1);
+    }
+
+    #[test]
+    fn only_all_safe_categories_are_suggested_with_yes() {
+        let ranked = rank_categories(&[
+            category("trash", &[(900, SafetyLevel::Safe)]),
+            category(
+                "dev_junk",
+                &[(500, SafetyLevel::Safe), (400, SafetyLevel::Caution)],
+            ),
+            category("large_old_files", &[(100, SafetyLevel::Caution)]),
+        ]);
+        let commands: Vec<&str> = ranked.iter().map(|s| s.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "cleanmac apply --category trash --yes",
+                "cleanmac apply --category dev_junk --only-safe --yes",
+                "cleanmac clean --category large_old_files",
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_the_important_usage_capacity() {
+        assert_eq!(parse_capacity("123456789012\n"), Some(123_456_789_012));
+        assert_eq!(parse_capacity("1.5e12"), Some(1_500_000_000_000));
+        assert_eq!(parse_capacity(""), None);
+        assert_eq!(parse_capacity("undefined"), None);
+
+        let space = DiskSpace {
+            total_bytes: 100,
+            free_bytes: 10,
+            purgeable_bytes: Some(5),
+        };
+        assert_eq!(space.available_bytes(), 15);
+    }
+
+    #[test]
+    fn steps_stop_once_target_is_met() {
+        let ranked = rank_categories(&[
+            category("system_caches", &[(500, SafetyLevel::Safe)]),
+            category("trash", &[(300, SafetyLevel::Safe)]),
+            category("system_logs", &[(100, SafetyLevel::Safe)]),
+        ]);
+        assert_eq!(steps_for_target(&ranked, 0), 0);
+        assert_eq!(steps_for_target(&ranked, 400), 1);
+        assert_eq!(steps_for_target(&ranked, 700), 2);
+        assert_eq!(steps_for_target(&ranked, 10_000), 3);
+    }
+}
@@ -9,23 +9,30 @@ use crate::config::Config;
 use crate::tui::controller::app_list;
 use crate::tui::controller::category_select;
 use crate::tui::controller::common;
+use crate::tui::controller::dashboard;
 use crate::tui::controller::review;
 use crate::tui::controller::space_lens;
 use crate::tui::controller::uninstall;
 use crate::tui::controller::{
-    handle_app_list_key, handle_category_select_key, handle_confirm_key, handle_help_key,
-    handle_result_key, handle_review_key, handle_space_lens_key, handle_uninstall_result_key,
-    handle_uninstall_review_key,
+    handle_app_list_key, handle_category_select_key, handle_confirm_empty_trash_key,
+    handle_confirm_key, handle_confirm_quit_key, handle_confirm_uninstall_key,
+    handle_dashboard_key, handle_help_key, handle_result_key, handle_review_key,
+    handle_space_lens_key, handle_uninstall_result_key, handle_uninstall_review_key,
 };
+use crate::tui::logic::selected_in;
 use crate::tui::service::disk::{poll_space_sizes, start_space_scan};
 use crate::tui::service::scanner::{poll_scan_messages, PollContext};
-use crate::tui::state::{AppMode, AppsModeState};
+use crate::tui::state::app_state::{SELECTION_DELTA_TTL, SELECTION_NOTE_TTL};
+use crate::tui::state::{AppMode, AppsModeState, DashboardState};
+use crate::tui::view::components::footer::render_audit_badge;
 use crate::tui::view::components::modal::{
-    render_confirm_modal, render_help_modal, render_result_modal,
+    render_confirm_modal, render_empty_trash_modal, render_help_modal, render_quit_modal,
+    render_result_modal, ConfirmModalData,
 };
 use crate::tui::view::{
-    render_app_list, render_category_select, render_loading, render_review, render_space_lens,
-    render_uninstall_result, render_uninstall_review, CategorySelectData,
+    render_app_list, render_category_select, render_dashboard, render_loading, render_review,
+    render_space_lens, render_uninstall_confirm_modal, render_uninstall_result,
+    render_uninstall_review, CategorySelectData, Theme,
 };
 use crate::uninstaller::{AppDetector, RelatedFileDetector};
 
@@ -73,17 +80,19 @@ impl App {
             size_receiver: Some(rx),
             ..Default::default()
         };
+        app.apps_mode.refresh_duplicates();
+        app.apps_mode.refresh_view();
         app.list_state = list_state;
         app.available_scanners.clear();
         app
     }
 
-    pub fn new_space_lens_mode(start_path: Option<&str>) -> Self {
-        let mut app = Self::new(Config::default());
+    pub fn new_space_lens_mode(start_path: Option<&str>, config: Config) -> Self {
+        let mut app = Self::new(config);
         app.mode = AppMode::SpaceLens;
         app.space_lens.current_path = start_path
-            .map(|p| std::path::PathBuf::from(p))
-            .unwrap_or_else(|| dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("/")));
+            .map(std::path::PathBuf::from)
+            .unwrap_or_else(crate::user::home_dir);
         app.list_state.select(Some(0));
         // 스캔은 run() 루프에서 첫 프레임 후에 시작
         app.available_scanners.clear();
@@ -99,12 +108,14 @@ impl App {
 
         while !self.should_quit {
             if self.mode == AppMode::LoadingRelatedFiles {
-                terminal.draw(|f| render_loading(f))?;
+                let theme = Theme::from_config(&self.config.theme);
+                terminal.draw(|f| render_loading(f, &theme))?;
                 self.load_related_files();
             }
 
             self.poll_app_sizes();
             self.poll_scan();
+            self.poll_empty_trash();
             poll_space_sizes(&mut self.space_lens);
 
             terminal.draw(|f| self.render(f))?;
@@ -127,7 +138,20 @@ impl App {
         }
     }
 
+    fn poll_empty_trash(&mut self) {
+        let mut ctx = common::EmptyTrashPollContext {
+            mode: &mut self.mode,
+            trash_locations: &self.trash_locations,
+            trash_receiver: &mut self.trash_receiver,
+            report: &self.report,
+            clean_result: &mut self.clean_result,
+            deleted_ids: &mut self.deleted_ids,
+        };
+        common::poll_empty_trash(&mut ctx);
+    }
+
     fn poll_scan(&mut self) {
+        let was_scanning = self.scan_receiver.is_some();
         let mut ctx = PollContext {
             scan_receiver: &mut self.scan_receiver,
             report: &mut self.report,
@@ -135,13 +159,22 @@ impl App {
             list_state: &mut self.list_state,
         };
         poll_scan_messages(&mut ctx);
+
+        let finished = was_scanning && self.scan_receiver.is_none();
+        if finished && self.mode == AppMode::Review && self.config.ui.dashboard_after_scan {
+            self.dashboard = DashboardState::load();
+            self.mode = AppMode::Dashboard;
+        }
     }
 
     fn load_related_files(&mut self) {
         if let Some(idx) = self.apps_mode.selected_app_idx {
             if let Some(app) = self.apps_mode.apps.get(idx) {
                 let detector = RelatedFileDetector::new();
-                self.apps_mode.cached_related_files = detector.find_related_files(app);
+                let mut related = detector.find_related_files(app);
+                // 카테고리별로 묶고 보호 항목은 맨 아래에 둔다.
+                related.sort_by_key(|f| (f.category.is_protected(), f.category));
+                self.apps_mode.cached_related_files = related;
             }
         }
         self.list_state.select(Some(0));
@@ -151,6 +184,9 @@ impl App {
 
     fn select_all_related(&mut self) {
         self.apps_mode.selected_related.insert(0);
+        if self.apps_mode.bundle_only {
+            return;
+        }
         for (i, file) in self.apps_mode.cached_related_files.iter().enumerate() {
             if !file.category.is_protected() {
                 self.apps_mode.selected_related.insert(i + 1);
@@ -158,7 +194,13 @@ impl App {
         }
     }
 
-    fn handle_key(&mut self, code: KeyCode, _modifiers: KeyModifiers) -> Result<()> {
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+        // Ctrl-C는 어느 화면에서든 확인 없이 바로 종료한다.
+        if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('c') {
+            self.should_quit = true;
+            return Ok(());
+        }
+
         match self.mode {
             AppMode::CategorySelect => {
                 let mut ctx = category_select::CategorySelectContext {
@@ -178,6 +220,11 @@ impl App {
                     list_state: &mut self.list_state,
                     selected_category: &mut self.selected_category,
                     selected_items: &mut self.selected_items,
+                    expanded_items: &mut self.expanded_items,
+                    selection_delta: &mut self.selection_delta,
+                    size_prompt: &mut self.size_prompt,
+                    selection_note: &mut self.selection_note,
+                    detail_scroll: &mut self.detail_scroll,
                     report: &mut self.report,
                     mode: &mut self.mode,
                     prev_mode: &mut self.prev_mode,
@@ -188,16 +235,16 @@ impl App {
                     available_scanners: &self.available_scanners,
                     scan_progress: &mut self.scan_progress,
                     scan_receiver: &mut self.scan_receiver,
+                    trash_locations: &mut self.trash_locations,
                 };
-                handle_review_key(&mut ctx, code)
+                handle_review_key(&mut ctx, code, modifiers)
             }
             AppMode::ConfirmClean => {
                 let selected_items: Vec<_> = self
                     .report
                     .iter()
                     .flat_map(|r| r.categories.iter())
-                    .flat_map(|c| c.items.iter())
-                    .filter(|item| self.selected_items.contains(&item.id))
+                    .flat_map(|c| selected_in(&c.items, &self.selected_items))
                     .cloned()
                     .collect();
                 let mut ctx = common::ConfirmContext {
@@ -206,9 +253,29 @@ impl App {
                     report_items: selected_items,
                     clean_result: &mut self.clean_result,
                     deleted_ids: &mut self.deleted_ids,
+                    clean_caution: self.config.clean.clean_caution,
+                    skip_open_files: self.config.clean.skip_open_files,
                 };
                 handle_confirm_key(&mut ctx, code)
             }
+            AppMode::ConfirmEmptyTrash => {
+                let mut ctx = common::EmptyTrashContext {
+                    mode: &mut self.mode,
+                    trash_locations: &self.trash_locations,
+                    trash_receiver: &mut self.trash_receiver,
+                    clean_result: &mut self.clean_result,
+                    deleted_ids: &mut self.deleted_ids,
+                    log_history: self.config.clean.log_history,
+                };
+                handle_confirm_empty_trash_key(&mut ctx, code)
+            }
+            AppMode::ConfirmQuit => {
+                let mut ctx = common::QuitContext {
+                    mode: &mut self.mode,
+                    should_quit: &mut self.should_quit,
+                };
+                handle_confirm_quit_key(&mut ctx, code)
+            }
             AppMode::ResultDisplay => {
                 let mut ctx = common::ResultContext {
                     mode: &mut self.mode,
@@ -245,6 +312,15 @@ impl App {
                 };
                 handle_uninstall_review_key(&mut ctx, code)
             }
+            AppMode::ConfirmUninstall => {
+                let mut ctx = uninstall::UninstallReviewContext {
+                    list_state: &mut self.list_state,
+                    apps_mode: &mut self.apps_mode,
+                    mode: &mut self.mode,
+                    prev_mode: &mut self.prev_mode,
+                };
+                handle_confirm_uninstall_key(&mut ctx, code)
+            }
             AppMode::UninstallResult => {
                 let mut ctx = uninstall::UninstallResultContext {
                     apps_mode: &mut self.apps_mode,
@@ -262,10 +338,22 @@ impl App {
                 };
                 handle_space_lens_key(&mut ctx, code)
             }
+            AppMode::Dashboard => {
+                let mut ctx = dashboard::DashboardContext {
+                    list_state: &mut self.list_state,
+                    selected_category: &mut self.selected_category,
+                    mode: &mut self.mode,
+                    prev_mode: &mut self.prev_mode,
+                    should_quit: &mut self.should_quit,
+                };
+                handle_dashboard_key(&mut ctx, code)
+            }
         }
     }
 
     fn render(&mut self, f: &mut ratatui::Frame) {
+        let theme = Theme::from_config(&self.config.theme);
+
         match self.mode {
             AppMode::CategorySelect => {
                 let mut data = CategorySelectData {
@@ -273,22 +361,25 @@ impl App {
                     available_scanners: &self.available_scanners,
                     report: self.report.as_ref(),
                 };
-                render_category_select(f, &mut data);
+                render_category_select(f, &mut data, &theme);
             }
             AppMode::AppList => {
-                render_app_list(f, &mut self.list_state, &self.apps_mode);
+                render_app_list(f, &mut self.list_state, &self.apps_mode, &theme);
             }
-            AppMode::UninstallReview => {
-                render_uninstall_review(f, &mut self.list_state, &self.apps_mode);
+            AppMode::UninstallReview | AppMode::ConfirmUninstall => {
+                render_uninstall_review(f, &mut self.list_state, &self.apps_mode, &theme);
             }
             AppMode::UninstallResult => {
-                render_uninstall_result(f, &self.apps_mode);
+                render_uninstall_result(f, &self.apps_mode, &theme);
             }
             AppMode::SpaceLens => {
-                render_space_lens(f, &mut self.list_state, &mut self.space_lens);
+                render_space_lens(f, &mut self.list_state, &mut self.space_lens, &theme);
             }
             AppMode::LoadingRelatedFiles => {
-                render_loading(f);
+                render_loading(f, &theme);
+            }
+            AppMode::Dashboard => {
+                render_dashboard(f, self.report.as_ref(), &self.dashboard, &theme);
             }
             _ => {
                 render_review(
@@ -296,39 +387,64 @@ impl App {
                     &mut self.list_state,
                     &mut self.report,
                     &self.selected_items,
+                    &self.expanded_items,
                     &mut self.selected_category,
+                    &mut self.detail_scroll,
+                    self.selection_delta
+                        .filter(|(_, at)| at.elapsed() < SELECTION_DELTA_TTL)
+                        .map(|(delta, _)| delta),
+                    self.size_prompt.as_ref(),
+                    self.selection_note
+                        .as_ref()
+                        .filter(|(_, at)| at.elapsed() < SELECTION_NOTE_TTL)
+                        .map(|(note, _)| note.as_str()),
                     self.sort_mode,
                     &self.scan_progress,
                     self.scan_receiver.is_some(),
+                    !self
+                        .available_scanners
+                        .iter()
+                        .any(|s| s.enabled && self.config.allows_scanner(&s.id)),
+                    &theme,
                 );
             }
         }
 
         match self.mode {
             AppMode::ConfirmClean => {
-                let selected: Vec<_> = self
-                    .report
-                    .iter()
-                    .flat_map(|r| r.categories.iter())
-                    .flat_map(|c| c.items.iter())
-                    .filter(|item| self.selected_items.contains(&item.id))
-                    .collect();
-                let total_size: u64 = selected.iter().map(|i| i.size).sum();
                 render_confirm_modal(
                     f,
-                    &crate::tui::view::components::modal::ConfirmModalData {
-                        selected_count: selected.len(),
-                        total_size,
-                    },
+                    &ConfirmModalData::from_selection(self.report.as_ref(), &self.selected_items),
+                    &theme,
+                );
+            }
+            AppMode::ConfirmQuit => {
+                render_quit_modal(f, self.selected_items.len(), &theme);
+            }
+            AppMode::ConfirmEmptyTrash => {
+                render_empty_trash_modal(
+                    f,
+                    &self.trash_locations,
+                    self.trash_receiver.is_some(),
+                    &theme,
                 );
             }
             AppMode::ResultDisplay => {
-                render_result_modal(f, self.clean_result.as_ref());
+                render_result_modal(f, self.clean_result.as_ref(), &theme);
             }
             AppMode::Help => {
-                render_help_modal(f);
+                render_help_modal(f, &theme);
+            }
+            AppMode::ConfirmUninstall => {
+                if let Some(summary) = self.apps_mode.uninstall_summary() {
+                    render_uninstall_confirm_modal(f, &summary, &theme);
+                }
             }
             _ => {}
         }
+
+        if crate::audit::is_active() {
+            render_audit_badge(f, &theme);
+        }
     }
 }
@@ -22,7 +22,7 @@ pub fn handle_app_list_key(ctx: &mut AppListContext, code: KeyCode) -> Result<()
             }
         }
         KeyCode::Down => {
-            let max = ctx.apps_mode.apps.len().saturating_sub(1);
+            let max = ctx.apps_mode.view.len().saturating_sub(1);
             if let Some(current) = ctx.list_state.selected() {
                 if current < max {
                     ctx.list_state.select(Some(current + 1));
@@ -30,11 +30,40 @@ pub fn handle_app_list_key(ctx: &mut AppListContext, code: KeyCode) -> Result<()
             }
         }
         KeyCode::Enter => {
-            if let Some(idx) = ctx.list_state.selected() {
+            if let Some(&idx) = ctx
+                .list_state
+                .selected()
+                .and_then(|pos| ctx.apps_mode.view.get(pos))
+            {
                 ctx.apps_mode.selected_app_idx = Some(idx);
+                ctx.apps_mode.bundle_only = false;
                 *ctx.mode = AppMode::LoadingRelatedFiles;
             }
         }
+        // Jump to the older copy of a duplicate install and start uninstalling it.
+        KeyCode::Char('o') => {
+            if let Some(older) = ctx
+                .list_state
+                .selected()
+                .and_then(|pos| ctx.apps_mode.view.get(pos))
+                .and_then(|&idx| ctx.apps_mode.older_copy_index(idx))
+            {
+                if let Some(pos) = ctx.apps_mode.view.iter().position(|&i| i == older) {
+                    ctx.list_state.select(Some(pos));
+                }
+                ctx.apps_mode.selected_app_idx = Some(older);
+                ctx.apps_mode.bundle_only = true;
+                *ctx.mode = AppMode::LoadingRelatedFiles;
+            }
+        }
+        KeyCode::Char('d') => {
+            ctx.apps_mode.toggle_sort();
+            ctx.list_state.select(Some(0));
+        }
+        KeyCode::Char('f') => {
+            ctx.apps_mode.cycle_installed_filter();
+            ctx.list_state.select(Some(0));
+        }
         KeyCode::Char('?') => {
             *ctx.prev_mode = Some(*ctx.mode);
             *ctx.mode = AppMode::Help;
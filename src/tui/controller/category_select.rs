@@ -53,10 +53,10 @@ pub fn handle_category_select_key(ctx: &mut CategorySelectContext, code: KeyCode
                 scanner.enabled = false;
             }
         }
-        KeyCode::Enter | KeyCode::Tab => {
-            if ctx.report.is_some() && !ctx.report.as_ref().unwrap().categories.is_empty() {
-                *ctx.mode = AppMode::Review;
-            }
+        KeyCode::Enter | KeyCode::Tab
+            if ctx.report.is_some() && !ctx.report.as_ref().unwrap().categories.is_empty() =>
+        {
+            *ctx.mode = AppMode::Review;
         }
         KeyCode::Char('r') => {
             let enabled_ids: Vec<String> = ctx
@@ -1,10 +1,14 @@
-use crate::cleaner::DefaultCleaner;
+use crate::cleaner::{empty_trash, CleanLock, DefaultCleaner, EmptyTrashResult, TrashLocation};
+use crate::history::HistoryLogger;
 use crate::plugin::registry::ScanReport;
-use crate::plugin::{CleanConfig, Cleaner, ScanResult};
+use crate::plugin::{CleanConfig, Cleaner, ScanResult, TrashMode};
+use crate::scanner::login_launch_warning;
 use crate::tui::state::{AppMode, CleanResultDisplay};
 use anyhow::Result;
 use crossterm::event::KeyCode;
 use std::collections::HashSet;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
 
 pub struct ConfirmContext<'a> {
     pub mode: &'a mut AppMode,
@@ -12,10 +16,19 @@ pub struct ConfirmContext<'a> {
     pub report_items: Vec<ScanResult>,
     pub clean_result: &'a mut Option<CleanResultDisplay>,
     pub deleted_ids: &'a mut HashSet<String>,
+    pub clean_caution: bool,
+    pub skip_open_files: bool,
 }
 
 pub fn handle_confirm_key(ctx: &mut ConfirmContext, code: KeyCode) -> Result<()> {
+    let needs_explicit_confirm = ctx
+        .report_items
+        .iter()
+        .filter(|item| ctx.selected_items.contains(&item.id))
+        .any(|item| login_launch_warning(item).is_some());
+
     match code {
+        KeyCode::Enter if needs_explicit_confirm => {}
         KeyCode::Char('y') | KeyCode::Enter => {
             let items_to_clean: Vec<ScanResult> = ctx
                 .report_items
@@ -24,10 +37,21 @@ pub fn handle_confirm_key(ctx: &mut ConfirmContext, code: KeyCode) -> Result<()>
                 .cloned()
                 .collect();
 
+            let _lock = match CleanLock::acquire() {
+                Ok(lock) => lock,
+                Err(e) => {
+                    refuse(ctx.mode, ctx.clean_result, ctx.deleted_ids, e);
+                    return Ok(());
+                }
+            };
             let cleaner = DefaultCleaner::new();
             let config = CleanConfig {
                 dry_run: false,
                 log_history: true,
+                skip_open_files: ctx.skip_open_files,
+                throttle_ms: None,
+                clean_caution: ctx.clean_caution,
+                trash_mode: TrashMode::MoveToTrash,
             };
 
             let result = cleaner.clean(&items_to_clean, &config)?;
@@ -39,7 +63,10 @@ pub fn handle_confirm_key(ctx: &mut ConfirmContext, code: KeyCode) -> Result<()>
                 success_count: result.success_count,
                 failed_count: result.failed_count,
                 total_freed: result.total_freed,
+                moved_to_trash: result.moved_to_trash,
                 duration: result.duration,
+                commands: result.commands,
+                error: None,
             });
 
             *ctx.mode = AppMode::ResultDisplay;
@@ -52,6 +79,138 @@ pub fn handle_confirm_key(ctx: &mut ConfirmContext, code: KeyCode) -> Result<()>
     Ok(())
 }
 
+pub struct EmptyTrashContext<'a> {
+    pub mode: &'a mut AppMode,
+    pub trash_locations: &'a [TrashLocation],
+    pub trash_receiver: &'a mut Option<Receiver<(EmptyTrashResult, Duration)>>,
+    pub clean_result: &'a mut Option<CleanResultDisplay>,
+    pub deleted_ids: &'a mut HashSet<String>,
+    pub log_history: bool,
+}
+
+// Emptying a big Trash takes a while, so a worker does it (holding the clean
+// lock) and `poll_empty_trash` picks up the result.
+pub fn handle_confirm_empty_trash_key(ctx: &mut EmptyTrashContext, code: KeyCode) -> Result<()> {
+    if ctx.trash_receiver.is_some() {
+        return Ok(());
+    }
+    match code {
+        KeyCode::Char('y') | KeyCode::Enter if !ctx.trash_locations.is_empty() => {
+            let lock = match CleanLock::acquire() {
+                Ok(lock) => lock,
+                Err(e) => {
+                    refuse(ctx.mode, ctx.clean_result, ctx.deleted_ids, e);
+                    return Ok(());
+                }
+            };
+            let (tx, rx) = channel();
+            let locations = ctx.trash_locations.to_vec();
+            let log_history = ctx.log_history;
+            std::thread::spawn(move || {
+                let _lock = lock;
+                let start = Instant::now();
+                let result = empty_trash(&locations);
+                if log_history {
+                    let logger = HistoryLogger::new();
+                    for (path, freed) in &result.emptied {
+                        let _ = logger.log_empty_trash(path, *freed);
+                    }
+                }
+                let _ = tx.send((result, start.elapsed()));
+            });
+            *ctx.trash_receiver = Some(rx);
+        }
+        KeyCode::Char('n') | KeyCode::Esc | KeyCode::Enter => {
+            *ctx.mode = AppMode::Review;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+pub struct EmptyTrashPollContext<'a> {
+    pub mode: &'a mut AppMode,
+    pub trash_locations: &'a [TrashLocation],
+    pub trash_receiver: &'a mut Option<Receiver<(EmptyTrashResult, Duration)>>,
+    pub report: &'a Option<ScanReport>,
+    pub clean_result: &'a mut Option<CleanResultDisplay>,
+    pub deleted_ids: &'a mut HashSet<String>,
+}
+
+pub fn poll_empty_trash(ctx: &mut EmptyTrashPollContext) {
+    let Some(rx) = ctx.trash_receiver.as_ref() else {
+        return;
+    };
+    let (result, duration) = match rx.try_recv() {
+        Ok(done) => done,
+        Err(TryRecvError::Empty) => return,
+        Err(TryRecvError::Disconnected) => {
+            *ctx.trash_receiver = None;
+            refuse(
+                ctx.mode,
+                ctx.clean_result,
+                ctx.deleted_ids,
+                anyhow::anyhow!("Emptying the Trash stopped unexpectedly"),
+            );
+            return;
+        }
+    };
+    *ctx.trash_receiver = None;
+
+    // Trash items in the report are gone now; the result modal drops them.
+    ctx.deleted_ids.clear();
+    ctx.deleted_ids.extend(
+        ctx.report
+            .iter()
+            .flat_map(|r| r.categories.iter())
+            .flat_map(|c| c.items.iter())
+            .filter(|item| {
+                ctx.trash_locations
+                    .iter()
+                    .any(|l| item.path.starts_with(&l.path))
+            })
+            .map(|item| item.id.clone()),
+    );
+
+    *ctx.clean_result = Some(CleanResultDisplay {
+        success_count: result.removed,
+        failed_count: result.errors.len(),
+        total_freed: result.freed,
+        moved_to_trash: 0,
+        duration,
+        commands: Vec::new(),
+        error: None,
+    });
+    *ctx.mode = AppMode::ResultDisplay;
+}
+
+// Shows why nothing was deleted instead of leaving the TUI on an error.
+fn refuse(
+    mode: &mut AppMode,
+    clean_result: &mut Option<CleanResultDisplay>,
+    deleted_ids: &mut HashSet<String>,
+    error: anyhow::Error,
+) {
+    deleted_ids.clear();
+    *clean_result = Some(CleanResultDisplay::refused(error.to_string()));
+    *mode = AppMode::ResultDisplay;
+}
+
+pub struct QuitContext<'a> {
+    pub mode: &'a mut AppMode,
+    pub should_quit: &'a mut bool,
+}
+
+// A second `q` confirms too, so a deliberate double-q still quits quickly.
+pub fn handle_confirm_quit_key(ctx: &mut QuitContext, code: KeyCode) -> Result<()> {
+    match code {
+        KeyCode::Char('y') | KeyCode::Char('q') => *ctx.should_quit = true,
+        KeyCode::Char('n') | KeyCode::Esc => *ctx.mode = AppMode::Review,
+        _ => {}
+    }
+    Ok(())
+}
+
 pub struct ResultContext<'a> {
     pub mode: &'a mut AppMode,
     pub report: &'a mut Option<ScanReport>,
@@ -66,6 +225,17 @@ pub fn handle_result_key(ctx: &mut ResultContext, code: KeyCode) -> Result<()> {
                 category
                     .items
                     .retain(|item| !ctx.deleted_ids.contains(&item.id));
+                // Partially cleaned folders shrink by what their children freed.
+                for item in &mut category.items {
+                    let (gone, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut item.children)
+                        .into_iter()
+                        .partition(|c| ctx.deleted_ids.contains(&c.id));
+                    item.size = item.size.saturating_sub(gone.iter().map(|c| c.size).sum());
+                    item.file_count = item
+                        .file_count
+                        .saturating_sub(gone.iter().map(|c| c.file_count).sum());
+                    item.children = kept;
+                }
             }
             report.categories.retain(|c| !c.items.is_empty());
             report.total_size = report.categories.iter().map(|c| c.total_size()).sum();
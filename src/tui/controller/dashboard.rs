@@ -0,0 +1,32 @@
+use crate::tui::state::AppMode;
+use anyhow::Result;
+use crossterm::event::KeyCode;
+use ratatui::widgets::ListState;
+
+pub struct DashboardContext<'a> {
+    pub list_state: &'a mut ListState,
+    pub selected_category: &'a mut usize,
+    pub mode: &'a mut AppMode,
+    pub prev_mode: &'a mut Option<AppMode>,
+    pub should_quit: &'a mut bool,
+}
+
+pub fn handle_dashboard_key(ctx: &mut DashboardContext, code: KeyCode) -> Result<()> {
+    match code {
+        KeyCode::Char('q') => *ctx.should_quit = true,
+        KeyCode::Enter => {
+            *ctx.selected_category = 0;
+            ctx.list_state.select(Some(0));
+            *ctx.mode = AppMode::Review;
+        }
+        KeyCode::Esc | KeyCode::Tab => {
+            *ctx.mode = AppMode::CategorySelect;
+        }
+        KeyCode::Char('?') => {
+            *ctx.prev_mode = Some(*ctx.mode);
+            *ctx.mode = AppMode::Help;
+        }
+        _ => {}
+    }
+    Ok(())
+}
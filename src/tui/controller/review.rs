@@ -1,24 +1,35 @@
+use crate::cleaner::{trash_locations, TrashLocation};
 use crate::config::Config;
 use crate::plugin::registry::ScanReport;
+use crate::scanner::duplicates::cycle_keeper;
 use crate::tui::logic::{
-    apply_sort, deselect_all, navigate_category_next, navigate_category_prev, navigate_down,
-    navigate_up, select_all_in_category, toggle_selection,
+    apply_sort, category_for_digit, deselect_all, deselect_smaller_than, extend_selection,
+    load_children, navigate_category_next, navigate_category_prev, navigate_category_to,
+    navigate_down, navigate_up, select_all_everywhere, select_all_in_category, select_larger_than,
+    selected_size, toggle_expanded, toggle_selection, visible_rows,
 };
 use crate::tui::service::disk::start_space_scan;
-use crate::tui::service::scanner::{start_scan, ScanStartParams};
+use crate::tui::service::scanner::{scan_pause, start_scan, ScanStartParams};
 use crate::tui::state::{
-    AppMode, ScanMessage, ScanProgress, ScannerInfo, SortMode, SpaceLensState,
+    AppMode, ScanMessage, ScanProgress, ScannerInfo, SizePrompt, SortMode, SpaceLensState,
 };
+use crate::utils::{format_size, parse_size};
 use anyhow::Result;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyModifiers};
 use ratatui::widgets::ListState;
 use std::collections::HashSet;
 use std::sync::mpsc::Receiver;
+use std::time::Instant;
 
 pub struct ReviewContext<'a> {
     pub list_state: &'a mut ListState,
     pub selected_category: &'a mut usize,
     pub selected_items: &'a mut HashSet<String>,
+    pub expanded_items: &'a mut HashSet<String>,
+    pub selection_delta: &'a mut Option<(i64, Instant)>,
+    pub size_prompt: &'a mut Option<SizePrompt>,
+    pub selection_note: &'a mut Option<(String, Instant)>,
+    pub detail_scroll: &'a mut u16,
     pub report: &'a mut Option<ScanReport>,
     pub mode: &'a mut AppMode,
     pub prev_mode: &'a mut Option<AppMode>,
@@ -29,16 +40,147 @@ pub struct ReviewContext<'a> {
     pub available_scanners: &'a [ScannerInfo],
     pub scan_progress: &'a mut ScanProgress,
     pub scan_receiver: &'a mut Option<Receiver<ScanMessage>>,
+    pub trash_locations: &'a mut Vec<TrashLocation>,
 }
 
-pub fn handle_review_key(ctx: &mut ReviewContext, code: KeyCode) -> Result<()> {
+pub fn handle_review_key(
+    ctx: &mut ReviewContext,
+    code: KeyCode,
+    modifiers: KeyModifiers,
+) -> Result<()> {
+    let before = ctx
+        .report
+        .as_ref()
+        .map(|r| selected_size(r, ctx.selected_items));
+    let focused_before = focused_item_id(ctx);
+    handle_key(ctx, code, modifiers)?;
+    let after = ctx
+        .report
+        .as_ref()
+        .map(|r| selected_size(r, ctx.selected_items));
+
+    // Sorting or expanding can move a different item under the cursor without
+    // the index changing, so compare what is focused rather than where.
+    if focused_item_id(ctx) != focused_before {
+        *ctx.detail_scroll = 0;
+    }
+
+    // A rescan drops the report, so only compare when both sides exist.
+    if let (Some(before), Some(after)) = (before, after) {
+        if before != after {
+            *ctx.selection_delta = Some((after as i64 - before as i64, Instant::now()));
+        }
+    }
+    Ok(())
+}
+
+fn focused_item_id(ctx: &ReviewContext) -> Option<(usize, String)> {
+    let category = ctx
+        .report
+        .as_ref()?
+        .categories
+        .get(*ctx.selected_category)?;
+    let rows = visible_rows(&category.items, ctx.expanded_items);
+    let id = ctx
+        .list_state
+        .selected()
+        .and_then(|idx| rows.get(idx))
+        .map(|row| row.item.id.clone())
+        .unwrap_or_default();
+    Some((*ctx.selected_category, id))
+}
+
+// Typing goes into the threshold until Enter applies it to the focused
+// category or Esc drops it.
+fn handle_size_prompt_key(ctx: &mut ReviewContext, code: KeyCode) {
+    let Some(prompt) = ctx.size_prompt.as_mut() else {
+        return;
+    };
     match code {
-        KeyCode::Char('q') => *ctx.should_quit = true,
+        KeyCode::Char(c) if c.is_ascii_alphanumeric() || c == '.' || c == ' ' => {
+            prompt.input.push(c);
+        }
+        KeyCode::Backspace => {
+            prompt.input.pop();
+        }
+        KeyCode::Esc => *ctx.size_prompt = None,
+        KeyCode::Enter => {
+            let Some(prompt) = ctx.size_prompt.take() else {
+                return;
+            };
+            let note = match (
+                parse_size(&prompt.input),
+                ctx.report
+                    .as_ref()
+                    .and_then(|r| r.categories.get(*ctx.selected_category)),
+            ) {
+                (None, _) => format!("Not a size: '{}' (try 500MB or 1.5GB)", prompt.input),
+                (Some(_), None) => return,
+                (Some(threshold), Some(category)) if prompt.select_larger => format!(
+                    "Selected {} items over {}",
+                    select_larger_than(ctx.selected_items, &category.items, threshold),
+                    format_size(threshold)
+                ),
+                (Some(threshold), Some(category)) => format!(
+                    "Deselected {} items under {}",
+                    deselect_smaller_than(ctx.selected_items, &category.items, threshold),
+                    format_size(threshold)
+                ),
+            };
+            *ctx.selection_note = Some((note, Instant::now()));
+        }
+        _ => {}
+    }
+}
+
+fn handle_key(ctx: &mut ReviewContext, code: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    if ctx.size_prompt.is_some() {
+        handle_size_prompt_key(ctx, code);
+        return Ok(());
+    }
+    // Modified keys first, so Ctrl-A and Shift+arrows never fall through to
+    // the plain single-key bindings below.
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        if let KeyCode::Char('a') = code {
+            if let Some(report) = ctx.report.as_ref() {
+                select_all_everywhere(ctx.selected_items, report);
+            }
+        }
+        return Ok(());
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) && matches!(code, KeyCode::Up | KeyCode::Down) {
+        let Some(category) = ctx
+            .report
+            .as_ref()
+            .and_then(|r| r.categories.get(*ctx.selected_category))
+        else {
+            return Ok(());
+        };
+        let rows = visible_rows(&category.items, ctx.expanded_items);
+        let from = ctx.list_state.selected();
+        if code == KeyCode::Up {
+            navigate_up(ctx.list_state);
+        } else {
+            navigate_down(ctx.list_state, rows.len());
+        }
+        extend_selection(ctx.selected_items, &rows, from, ctx.list_state.selected());
+        return Ok(());
+    }
+
+    match code {
+        KeyCode::Char('q') => {
+            if ctx.selected_items.is_empty() || !ctx.config.ui.confirm_quit_with_selection {
+                *ctx.should_quit = true;
+            } else {
+                *ctx.mode = AppMode::ConfirmQuit;
+            }
+        }
         KeyCode::Up => navigate_up(ctx.list_state),
         KeyCode::Down => {
             if let Some(report) = ctx.report.as_ref() {
                 if let Some(category) = report.categories.get(*ctx.selected_category) {
-                    navigate_down(ctx.list_state, category.items.len());
+                    let rows = visible_rows(&category.items, ctx.expanded_items);
+                    navigate_down(ctx.list_state, rows.len());
                 }
             }
         }
@@ -46,13 +188,46 @@ pub fn handle_review_key(ctx: &mut ReviewContext, code: KeyCode) -> Result<()> {
         KeyCode::Right => {
             navigate_category_next(ctx.selected_category, ctx.list_state, ctx.report.as_ref())
         }
+        KeyCode::Char(c) if c.is_ascii_digit() => {
+            if let Some(index) = category_for_digit(c) {
+                navigate_category_to(
+                    ctx.selected_category,
+                    ctx.list_state,
+                    ctx.report.as_ref(),
+                    index,
+                );
+            }
+        }
         KeyCode::Char(' ') => {
-            let focused = get_focused_item(
-                ctx.report.as_ref(),
-                *ctx.selected_category,
-                ctx.list_state.selected(),
-            );
-            toggle_selection(ctx.selected_items, focused.as_ref());
+            if let Some(category) = ctx
+                .report
+                .as_ref()
+                .and_then(|r| r.categories.get(*ctx.selected_category))
+            {
+                let rows = visible_rows(&category.items, ctx.expanded_items);
+                let focused = ctx.list_state.selected().and_then(|idx| rows.get(idx));
+                toggle_selection(ctx.selected_items, focused);
+            }
+        }
+        KeyCode::Char('e') => {
+            if let Some(category) = ctx
+                .report
+                .as_mut()
+                .and_then(|r| r.categories.get_mut(*ctx.selected_category))
+            {
+                load_children(
+                    &mut category.items,
+                    ctx.expanded_items,
+                    ctx.list_state.selected(),
+                );
+                if let Some(idx) = toggle_expanded(
+                    ctx.expanded_items,
+                    &category.items,
+                    ctx.list_state.selected(),
+                ) {
+                    ctx.list_state.select(Some(idx));
+                }
+            }
         }
         KeyCode::Char('a') => {
             if let Some(report) = ctx.report.as_ref() {
@@ -62,11 +237,39 @@ pub fn handle_review_key(ctx: &mut ReviewContext, code: KeyCode) -> Result<()> {
             }
         }
         KeyCode::Char('n') => deselect_all(ctx.selected_items),
-        KeyCode::Enter => {
-            if !ctx.selected_items.is_empty() {
-                *ctx.mode = AppMode::ConfirmClean;
+        KeyCode::Char('[') => *ctx.detail_scroll = ctx.detail_scroll.saturating_sub(1),
+        // The renderer clamps this to the detail text's length.
+        KeyCode::Char(']') => *ctx.detail_scroll = ctx.detail_scroll.saturating_add(1),
+        KeyCode::Char('k') => {
+            if let Some(category) = ctx
+                .report
+                .as_mut()
+                .and_then(|r| r.categories.get_mut(*ctx.selected_category))
+            {
+                let rows = visible_rows(&category.items, ctx.expanded_items);
+                let focused = ctx
+                    .list_state
+                    .selected()
+                    .and_then(|idx| rows.get(idx))
+                    .filter(|row| row.parent.is_none())
+                    .map(|row| row.item.id.clone());
+                let keeper = category
+                    .items
+                    .iter_mut()
+                    .find(|item| Some(&item.id) == focused.as_ref())
+                    .and_then(cycle_keeper);
+                if let Some(keeper) = keeper {
+                    *ctx.selection_note =
+                        Some((format!("Keeping {}", keeper.display()), Instant::now()));
+                }
             }
         }
+        KeyCode::Char(c @ ('>' | '<')) if ctx.report.is_some() => {
+            *ctx.size_prompt = Some(SizePrompt::new(c == '>'));
+        }
+        KeyCode::Enter if !ctx.selected_items.is_empty() && !crate::audit::is_active() => {
+            *ctx.mode = AppMode::ConfirmClean;
+        }
         KeyCode::Char('?') => {
             *ctx.prev_mode = Some(*ctx.mode);
             *ctx.mode = AppMode::Help;
@@ -76,6 +279,7 @@ pub fn handle_review_key(ctx: &mut ReviewContext, code: KeyCode) -> Result<()> {
         }
         KeyCode::Char('r') => {
             ctx.selected_items.clear();
+            ctx.expanded_items.clear();
             *ctx.report = None;
             let enabled_ids: Vec<String> = ctx
                 .available_scanners
@@ -93,16 +297,22 @@ pub fn handle_review_key(ctx: &mut ReviewContext, code: KeyCode) -> Result<()> {
             };
             start_scan(&mut params);
         }
+        KeyCode::Char('p') if ctx.scan_receiver.is_some() => {
+            ctx.scan_progress.paused = scan_pause().toggle();
+        }
         KeyCode::Char('s') => {
             *ctx.sort_mode = ctx.sort_mode.next();
             if let Some(ref mut report) = ctx.report {
                 apply_sort(report, *ctx.sort_mode);
             }
         }
+        KeyCode::Char('T') if !crate::audit::is_active() => {
+            *ctx.trash_locations = trash_locations();
+            *ctx.mode = AppMode::ConfirmEmptyTrash;
+        }
         KeyCode::Char('v') => {
             *ctx.prev_mode = Some(*ctx.mode);
-            ctx.space_lens.current_path =
-                dirs::home_dir().unwrap_or_else(|| std::path::PathBuf::from("/"));
+            ctx.space_lens.current_path = crate::user::home_dir();
             ctx.list_state.select(Some(0));
             start_space_scan(ctx.space_lens);
             *ctx.mode = AppMode::SpaceLens;
@@ -111,14 +321,3 @@ pub fn handle_review_key(ctx: &mut ReviewContext, code: KeyCode) -> Result<()> {
     }
     Ok(())
 }
-
-fn get_focused_item(
-    report: Option<&ScanReport>,
-    selected_category: usize,
-    selected: Option<usize>,
-) -> Option<crate::plugin::ScanResult> {
-    let report = report?;
-    let category = report.categories.get(selected_category)?;
-    let idx = selected?;
-    category.items.get(idx).cloned()
-}
@@ -1,12 +1,12 @@
+use crate::cleaner::{home_trash, remove_path, CleanLock};
+use crate::plugin::TrashMode;
 use crate::tui::service::disk::start_space_scan;
 use crate::tui::state::{
     AppMode, CachedScan, DeleteResult, FolderEntry, SpaceLensMode, SpaceLensState,
 };
-use crate::utils::format_size;
 use anyhow::Result;
 use crossterm::event::KeyCode;
 use ratatui::widgets::ListState;
-use std::fs;
 
 pub struct SpaceLensContext<'a> {
     pub list_state: &'a mut ListState,
@@ -21,17 +21,28 @@ fn cache_current_if_needed(state: &mut SpaceLensState) {
         let path = state.current_path.clone();
         if !state.cache.contains_key(&path) {
             state.cache.insert(
-                path,
-                CachedScan {
-                    entries: state.entries.clone(),
-                    total_size: state.total_size,
-                    was_loading: state.loading,
-                },
+                path.clone(),
+                CachedScan::new(
+                    &path,
+                    state.entries.clone(),
+                    state.total_size,
+                    state.loading,
+                ),
             );
         }
     }
 }
 
+fn go_to_parent(ctx: &mut SpaceLensContext) {
+    if let Some(parent) = ctx.space_lens.current_path.parent() {
+        let new_path = parent.to_path_buf();
+        cache_current_if_needed(ctx.space_lens);
+        ctx.space_lens.current_path = new_path;
+        ctx.list_state.select(Some(0));
+        start_space_scan(ctx.space_lens);
+    }
+}
+
 pub fn handle_space_lens_key(ctx: &mut SpaceLensContext, code: KeyCode) -> Result<()> {
     match ctx.space_lens.delete_mode {
         SpaceLensMode::ConfirmDelete => handle_confirm_key(ctx, code),
@@ -58,7 +69,7 @@ fn handle_browse_key(ctx: &mut SpaceLensContext, code: KeyCode) -> Result<()> {
             }
         }
         KeyCode::Down => {
-            let max = ctx.space_lens.entries.len().saturating_sub(1);
+            let max = ctx.space_lens.row_count().saturating_sub(1);
             if let Some(current) = ctx.list_state.selected() {
                 if current < max {
                     ctx.list_state.select(Some(current + 1));
@@ -66,8 +77,10 @@ fn handle_browse_key(ctx: &mut SpaceLensContext, code: KeyCode) -> Result<()> {
             }
         }
         KeyCode::Enter => {
-            if let Some(idx) = ctx.list_state.selected() {
-                if let Some(entry) = ctx.space_lens.entries.get(idx) {
+            if let Some(row) = ctx.list_state.selected() {
+                if ctx.space_lens.is_parent_row(row) {
+                    go_to_parent(ctx);
+                } else if let Some(entry) = ctx.space_lens.entry_at_row(row) {
                     if entry.is_dir {
                         let new_path = entry.path.clone();
                         cache_current_if_needed(ctx.space_lens);
@@ -79,17 +92,8 @@ fn handle_browse_key(ctx: &mut SpaceLensContext, code: KeyCode) -> Result<()> {
             }
         }
         KeyCode::Esc | KeyCode::Backspace => {
-            if let Some(parent) = ctx.space_lens.current_path.parent() {
-                if parent != ctx.space_lens.current_path {
-                    let new_path = parent.to_path_buf();
-                    cache_current_if_needed(ctx.space_lens);
-                    ctx.space_lens.current_path = new_path;
-                    ctx.list_state.select(Some(0));
-                    start_space_scan(ctx.space_lens);
-                } else if let Some(prev) = *ctx.prev_mode {
-                    *ctx.mode = prev;
-                    *ctx.prev_mode = None;
-                }
+            if ctx.space_lens.has_parent_entry() {
+                go_to_parent(ctx);
             } else if let Some(prev) = *ctx.prev_mode {
                 *ctx.mode = prev;
                 *ctx.prev_mode = None;
@@ -107,9 +111,9 @@ fn handle_browse_key(ctx: &mut SpaceLensContext, code: KeyCode) -> Result<()> {
                 ctx.space_lens.pending_scans.clear();
             }
         }
-        KeyCode::Char('d') => {
-            if let Some(idx) = ctx.list_state.selected() {
-                if let Some(entry) = ctx.space_lens.entries.get(idx).cloned() {
+        KeyCode::Char('d') if !crate::audit::is_active() => {
+            if let Some(row) = ctx.list_state.selected() {
+                if let Some(entry) = ctx.space_lens.entry_at_row(row).cloned() {
                     ctx.space_lens.pending_delete = Some(entry);
                     ctx.space_lens.delete_mode = SpaceLensMode::ConfirmDelete;
                 }
@@ -133,9 +137,9 @@ fn handle_confirm_key(ctx: &mut SpaceLensContext, code: KeyCode) -> Result<()> {
                 ctx.space_lens.total_size = ctx.space_lens.entries.iter().map(|e| e.size).sum();
                 ctx.space_lens.cache.remove(&ctx.space_lens.current_path);
 
-                if ctx.list_state.selected().unwrap_or(0) >= ctx.space_lens.entries.len() {
-                    ctx.list_state
-                        .select(Some(ctx.space_lens.entries.len().saturating_sub(1)));
+                let rows = ctx.space_lens.row_count();
+                if ctx.list_state.selected().unwrap_or(0) >= rows {
+                    ctx.list_state.select(Some(rows.saturating_sub(1)));
                 }
 
                 ctx.space_lens.delete_result = Some(result);
@@ -165,26 +169,40 @@ fn delete_entry(entry: &FolderEntry) -> DeleteResult {
     let path = &entry.path;
     let size = entry.size;
 
-    let result = if path.is_dir() {
-        fs::remove_dir_all(path)
-    } else if path.exists() {
-        fs::remove_file(path)
+    let result = if crate::audit::is_active() {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            crate::audit::REFUSED,
+        ))
+    } else if let Some(reason) = crate::user::ownership_refusal(path) {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            reason,
+        ))
     } else {
-        Ok(())
+        match CleanLock::acquire() {
+            Ok(_lock) => remove_path(path, TrashMode::MoveToTrash, &home_trash(), None),
+            Err(e) => Err(std::io::Error::new(
+                std::io::ErrorKind::WouldBlock,
+                e.to_string(),
+            )),
+        }
     };
 
     match result {
-        Ok(()) => DeleteResult {
+        Ok(mode) => DeleteResult {
             path: path.clone(),
             success: true,
             size,
             error: None,
+            trashed: mode == TrashMode::MoveToTrash,
         },
         Err(e) => DeleteResult {
             path: path.clone(),
             success: false,
             size: 0,
             error: Some(e.to_string()),
+            trashed: false,
         },
     }
 }
@@ -1,3 +1,4 @@
+use crate::cleaner::CleanLock;
 use crate::tui::state::{AppMode, AppsModeState, UninstallResultDisplay};
 use crate::uninstaller::Uninstaller;
 use anyhow::Result;
@@ -53,7 +54,11 @@ pub fn handle_uninstall_review_key(ctx: &mut UninstallReviewContext, code: KeyCo
         KeyCode::Char('n') => {
             ctx.apps_mode.selected_related.clear();
         }
-        KeyCode::Enter => execute_uninstall(ctx)?,
+        KeyCode::Enter
+            if ctx.apps_mode.selected_app_idx.is_some() && !crate::audit::is_active() =>
+        {
+            *ctx.mode = AppMode::ConfirmUninstall;
+        }
         KeyCode::Char('?') => {
             *ctx.prev_mode = Some(*ctx.mode);
             *ctx.mode = AppMode::Help;
@@ -63,6 +68,18 @@ pub fn handle_uninstall_review_key(ctx: &mut UninstallReviewContext, code: KeyCo
     Ok(())
 }
 
+// Only an explicit 'y' deletes; a stray Enter from the review list does nothing.
+pub fn handle_confirm_uninstall_key(ctx: &mut UninstallReviewContext, code: KeyCode) -> Result<()> {
+    match code {
+        KeyCode::Char('y') | KeyCode::Char('Y') => execute_uninstall(ctx)?,
+        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+            *ctx.mode = AppMode::UninstallReview;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 fn execute_uninstall(ctx: &mut UninstallReviewContext) -> Result<()> {
     let app_idx = match ctx.apps_mode.selected_app_idx {
         Some(idx) => idx,
@@ -83,6 +100,19 @@ fn execute_uninstall(ctx: &mut UninstallReviewContext) -> Result<()> {
         .map(|(_, f)| f.clone())
         .collect();
 
+    let _lock = match CleanLock::acquire() {
+        Ok(lock) => lock,
+        Err(e) => {
+            ctx.apps_mode.uninstall_result = Some(UninstallResultDisplay {
+                app_deleted: false,
+                related_deleted: 0,
+                total_freed: 0,
+                errors: vec![e.to_string()],
+            });
+            *ctx.mode = AppMode::UninstallResult;
+            return Ok(());
+        }
+    };
     let uninstaller = Uninstaller::new(false);
     let result = uninstaller.uninstall(&app, &selected_related)?;
 
@@ -95,6 +125,8 @@ fn execute_uninstall(ctx: &mut UninstallReviewContext) -> Result<()> {
 
     if result.deleted_app {
         ctx.apps_mode.apps.remove(app_idx);
+        ctx.apps_mode.refresh_duplicates();
+        ctx.apps_mode.refresh_view();
     }
 
     *ctx.mode = AppMode::UninstallResult;
@@ -1,7 +1,16 @@
 pub mod navigation;
+pub mod rows;
 pub mod selection;
 pub mod sorting;
 
-pub use navigation::{navigate_category_next, navigate_category_prev, navigate_down, navigate_up};
-pub use selection::{deselect_all, select_all_in_category, toggle_selection};
+pub use navigation::{
+    category_for_digit, navigate_category_next, navigate_category_prev, navigate_category_to,
+    navigate_down, navigate_up,
+};
+pub use rows::{is_expandable, load_children, toggle_expanded, visible_rows};
+pub use selection::{
+    deselect_all, deselect_smaller_than, extend_selection, is_partially_selected,
+    select_all_everywhere, select_all_in_category, select_larger_than, selected_in, selected_size,
+    toggle_selection,
+};
 pub use sorting::apply_sort;
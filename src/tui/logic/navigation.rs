@@ -25,6 +25,27 @@ pub fn navigate_category_prev(selected_category: &mut usize, list_state: &mut Li
     }
 }
 
+// Digit keys jump straight to a category: '1'-'9' are the first nine, '0' the tenth.
+pub fn category_for_digit(c: char) -> Option<usize> {
+    match c.to_digit(10)? {
+        0 => Some(9),
+        n => Some(n as usize - 1),
+    }
+}
+
+// Out-of-range jumps are ignored, like stepping past the last category.
+pub fn navigate_category_to(
+    selected_category: &mut usize,
+    list_state: &mut ListState,
+    report: Option<&ScanReport>,
+    index: usize,
+) {
+    if report.is_some_and(|r| index < r.categories.len()) {
+        *selected_category = index;
+        list_state.select(Some(0));
+    }
+}
+
 pub fn navigate_category_next(
     selected_category: &mut usize,
     list_state: &mut ListState,
@@ -37,3 +58,52 @@ pub fn navigate_category_next(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::registry::CategoryScanResult;
+    use crate::plugin::ScannerCategory;
+    use std::time::Duration;
+
+    fn report(categories: usize) -> ScanReport {
+        ScanReport {
+            categories: (0..categories)
+                .map(|i| CategoryScanResult {
+                    scanner_id: format!("scanner_{}", i),
+                    name: format!("Scanner {}", i),
+                    category: ScannerCategory::System,
+                    items: Vec::new(),
+                    scan_duration: Duration::ZERO,
+                    unreadable: 0,
+                    omitted: 0,
+                })
+                .collect(),
+            total_size: 0,
+            total_items: 0,
+            duration: Duration::ZERO,
+            timed_out: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn digit_keys_jump_to_existing_categories_only() {
+        assert_eq!(category_for_digit('1'), Some(0));
+        assert_eq!(category_for_digit('0'), Some(9));
+        assert_eq!(category_for_digit('x'), None);
+
+        let report = report(3);
+        let mut selected = 0;
+        let mut list_state = ListState::default();
+        list_state.select(Some(5));
+
+        navigate_category_to(&mut selected, &mut list_state, Some(&report), 2);
+        assert_eq!(selected, 2);
+        assert_eq!(list_state.selected(), Some(0));
+
+        list_state.select(Some(4));
+        navigate_category_to(&mut selected, &mut list_state, Some(&report), 9);
+        assert_eq!(selected, 2);
+        assert_eq!(list_state.selected(), Some(4));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::plugin::ScanResult;
+use crate::scanner::child_items;
+use std::collections::HashSet;
+
+// One line in the Review item list: a top-level item, or a child of an
+// expanded item.
+#[derive(Debug, Clone, Copy)]
+pub struct VisibleRow<'a> {
+    pub item: &'a ScanResult,
+    pub parent: Option<&'a ScanResult>,
+}
+
+pub fn visible_rows<'a>(
+    items: &'a [ScanResult],
+    expanded: &HashSet<String>,
+) -> Vec<VisibleRow<'a>> {
+    let mut rows = Vec::with_capacity(items.len());
+    for item in items {
+        rows.push(VisibleRow { item, parent: None });
+        if expanded.contains(&item.id) {
+            rows.extend(item.children.iter().map(|child| VisibleRow {
+                item: child,
+                parent: Some(item),
+            }));
+        }
+    }
+    rows
+}
+
+// Folders cleaned by their contents can be expanded before their entries
+// have been listed.
+pub fn is_expandable(item: &ScanResult) -> bool {
+    !item.children.is_empty()
+        || item
+            .metadata
+            .get("clean_mode")
+            .is_some_and(|m| m == "contents")
+}
+
+// Lists the focused folder's entries the first time it is expanded; the scan
+// leaves them out so it doesn't have to size every entry of every folder.
+pub fn load_children(items: &mut [ScanResult], expanded: &HashSet<String>, focused: Option<usize>) {
+    let target = {
+        let rows = visible_rows(items, expanded);
+        match focused.and_then(|idx| rows.get(idx)) {
+            Some(row) if row.parent.is_none() && row.item.children.is_empty() => {
+                row.item.id.clone()
+            }
+            _ => return,
+        }
+    };
+    if let Some(item) = items
+        .iter_mut()
+        .find(|item| item.id == target && is_expandable(item))
+    {
+        item.children = child_items(item);
+    }
+}
+
+// Expanding works from a child row too: it collapses the parent and returns
+// the parent's row index so focus can move back onto it.
+pub fn toggle_expanded(
+    expanded: &mut HashSet<String>,
+    items: &[ScanResult],
+    focused: Option<usize>,
+) -> Option<usize> {
+    let rows = visible_rows(items, expanded);
+    let row = rows.get(focused?)?;
+    let target = row.parent.unwrap_or(row.item);
+    if target.children.is_empty() {
+        return None;
+    }
+
+    if !expanded.remove(&target.id) {
+        expanded.insert(target.id.clone());
+    }
+    rows.iter()
+        .position(|r| r.parent.is_none() && r.item.id == target.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn parent_with_children() -> Vec<ScanResult> {
+        let child = |name: &str, size| {
+            ScanResult::new(name, name, PathBuf::from(format!("/tmp/cache/{}", name)))
+                .with_size(size)
+        };
+        let mut cache =
+            ScanResult::new("cache", "cache", PathBuf::from("/tmp/cache")).with_size(300);
+        cache.children = vec![child("big", 200), child("small", 100)];
+        vec![
+            cache,
+            ScanResult::new("flat", "flat", PathBuf::from("/tmp/flat")).with_size(50),
+        ]
+    }
+
+    #[test]
+    fn expanding_inserts_children_under_their_parent() {
+        let items = parent_with_children();
+        let mut expanded = HashSet::new();
+        assert_eq!(visible_rows(&items, &expanded).len(), 2);
+
+        assert_eq!(toggle_expanded(&mut expanded, &items, Some(0)), Some(0));
+        let rows = visible_rows(&items, &expanded);
+        let ids: Vec<&str> = rows.iter().map(|r| r.item.id.as_str()).collect();
+        assert_eq!(ids, vec!["cache", "big", "small", "flat"]);
+        assert_eq!(rows[2].parent.map(|p| p.id.as_str()), Some("cache"));
+
+        // Collapsing from a child row lands back on the parent.
+        assert_eq!(toggle_expanded(&mut expanded, &items, Some(2)), Some(0));
+        assert!(expanded.is_empty());
+
+        // Items without children do not expand.
+        assert_eq!(toggle_expanded(&mut expanded, &items, Some(1)), None);
+        assert!(expanded.is_empty());
+    }
+
+    #[test]
+    fn contents_folders_list_their_entries_when_first_expanded() {
+        let dir = std::env::temp_dir().join(format!("cleanmac-rows-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("big.db"), vec![0u8; 300]).unwrap();
+
+        let mut folder = ScanResult::new("app", "app", dir.clone()).with_size(300);
+        folder
+            .metadata
+            .insert("clean_mode".to_string(), "contents".to_string());
+        let mut items = vec![folder];
+        let mut expanded = HashSet::new();
+        assert!(is_expandable(&items[0]));
+
+        load_children(&mut items, &expanded, Some(0));
+        assert_eq!(toggle_expanded(&mut expanded, &items, Some(0)), Some(0));
+        let rows = visible_rows(&items, &expanded);
+        let names: Vec<&str> = rows.iter().map(|r| r.item.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "big.db"]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
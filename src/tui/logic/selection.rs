@@ -1,23 +1,249 @@
+use crate::plugin::registry::ScanReport;
 use crate::plugin::ScanResult;
+use crate::tui::logic::rows::VisibleRow;
 use std::collections::HashSet;
 
-pub fn toggle_selection(selected_items: &mut HashSet<String>, focused_item: Option<&ScanResult>) {
-    if let Some(item) = focused_item {
-        let id = item.id.clone();
-        if selected_items.contains(&id) {
-            selected_items.remove(&id);
-        } else {
-            selected_items.insert(id);
+// A selected parent stands for all of it, so its children's ids never sit in
+// the set alongside it. Deselecting one child of a selected parent turns the
+// selection into "every other listed child".
+pub fn toggle_selection(selected_items: &mut HashSet<String>, focused_row: Option<&VisibleRow>) {
+    let Some(row) = focused_row else {
+        return;
+    };
+    match row.parent {
+        None => {
+            if !selected_items.remove(&row.item.id) && is_selectable(row.item) {
+                select_whole(selected_items, row.item);
+            }
+        }
+        Some(parent) => {
+            if selected_items.remove(&parent.id) {
+                for child in parent.children.iter().filter(|c| c.id != row.item.id) {
+                    selected_items.insert(child.id.clone());
+                }
+            } else if !selected_items.remove(&row.item.id) {
+                selected_items.insert(row.item.id.clone());
+            }
         }
     }
 }
 
+// Maintenance tasks whose command is missing are shown but can't be picked.
+fn is_selectable(item: &ScanResult) -> bool {
+    item.metadata.get("available").map(|s| s.as_str()) != Some("false")
+}
+
+fn select_whole(selected_items: &mut HashSet<String>, item: &ScanResult) {
+    if !is_selectable(item) {
+        return;
+    }
+    for child in &item.children {
+        selected_items.remove(&child.id);
+    }
+    selected_items.insert(item.id.clone());
+}
+
 pub fn select_all_in_category(selected_items: &mut HashSet<String>, items: &[ScanResult]) {
     for item in items {
-        selected_items.insert(item.id.clone());
+        select_whole(selected_items, item);
     }
 }
 
 pub fn deselect_all(selected_items: &mut HashSet<String>) {
     selected_items.clear();
 }
+
+pub fn select_all_everywhere(selected_items: &mut HashSet<String>, report: &ScanReport) {
+    for category in &report.categories {
+        select_all_in_category(selected_items, &category.items);
+    }
+}
+
+// Shift+arrow selects both the row left and the row landed on, so holding it
+// grows one contiguous run.
+pub fn extend_selection(
+    selected_items: &mut HashSet<String>,
+    rows: &[VisibleRow],
+    from: Option<usize>,
+    to: Option<usize>,
+) {
+    for idx in [from, to].into_iter().flatten() {
+        let Some(row) = rows.get(idx) else {
+            continue;
+        };
+        match row.parent {
+            None => select_whole(selected_items, row.item),
+            Some(parent) if selected_items.contains(&parent.id) => {}
+            Some(_) => {
+                selected_items.insert(row.item.id.clone());
+            }
+        }
+    }
+}
+
+// `>`: selects every item in the category larger than `threshold`. Returns
+// how many weren't selected before.
+pub fn select_larger_than(
+    selected_items: &mut HashSet<String>,
+    items: &[ScanResult],
+    threshold: u64,
+) -> usize {
+    let mut count = 0;
+    for item in items.iter().filter(|i| i.size > threshold) {
+        if !selected_items.contains(&item.id) && is_selectable(item) {
+            select_whole(selected_items, item);
+            count += 1;
+        }
+    }
+    count
+}
+
+// `<`: drops items smaller than `threshold`, children included. Returns how
+// many had anything selected.
+pub fn deselect_smaller_than(
+    selected_items: &mut HashSet<String>,
+    items: &[ScanResult],
+    threshold: u64,
+) -> usize {
+    let mut count = 0;
+    for item in items.iter().filter(|i| i.size < threshold) {
+        let mut removed = selected_items.remove(&item.id);
+        for child in &item.children {
+            removed |= selected_items.remove(&child.id);
+        }
+        if removed {
+            count += 1;
+        }
+    }
+    count
+}
+
+// What actually gets cleaned: each selected item whole, otherwise just its
+// selected children.
+pub fn selected_in<'a>(
+    items: &'a [ScanResult],
+    selected_items: &HashSet<String>,
+) -> Vec<&'a ScanResult> {
+    let mut selected = Vec::new();
+    for item in items {
+        if selected_items.contains(&item.id) {
+            selected.push(item);
+        } else {
+            selected.extend(
+                item.children
+                    .iter()
+                    .filter(|c| selected_items.contains(&c.id)),
+            );
+        }
+    }
+    selected
+}
+
+pub fn selected_size(report: &ScanReport, selected_items: &HashSet<String>) -> u64 {
+    report
+        .categories
+        .iter()
+        .flat_map(|c| selected_in(&c.items, selected_items))
+        .map(|i| i.size)
+        .sum()
+}
+
+pub fn is_partially_selected(item: &ScanResult, selected_items: &HashSet<String>) -> bool {
+    !selected_items.contains(&item.id)
+        && item.children.iter().any(|c| selected_items.contains(&c.id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::logic::rows::visible_rows;
+    use std::path::PathBuf;
+
+    fn items() -> Vec<ScanResult> {
+        let child = |name: &str, size| {
+            ScanResult::new(name, name, PathBuf::from(format!("/tmp/cache/{}", name)))
+                .with_size(size)
+        };
+        let mut cache =
+            ScanResult::new("cache", "cache", PathBuf::from("/tmp/cache")).with_size(600);
+        cache.children = vec![child("a", 300), child("b", 200), child("c", 100)];
+        vec![cache]
+    }
+
+    fn ids(selected: Vec<&ScanResult>) -> Vec<&str> {
+        selected.iter().map(|i| i.id.as_str()).collect()
+    }
+
+    #[test]
+    fn deselecting_a_child_keeps_its_siblings() {
+        let items = items();
+        let expanded = HashSet::from(["cache".to_string()]);
+        let rows = visible_rows(&items, &expanded);
+        let mut selected = HashSet::new();
+
+        toggle_selection(&mut selected, rows.first());
+        assert_eq!(ids(selected_in(&items, &selected)), vec!["cache"]);
+
+        // rows: cache, a, b, c
+        toggle_selection(&mut selected, rows.get(2));
+        assert!(is_partially_selected(&items[0], &selected));
+        assert_eq!(ids(selected_in(&items, &selected)), vec!["a", "c"]);
+
+        // Selecting the parent again takes the whole folder.
+        toggle_selection(&mut selected, rows.first());
+        assert_eq!(selected, HashSet::from(["cache".to_string()]));
+    }
+
+    #[test]
+    fn children_can_be_picked_one_by_one() {
+        let items = items();
+        let expanded = HashSet::from(["cache".to_string()]);
+        let rows = visible_rows(&items, &expanded);
+        let mut selected = HashSet::new();
+
+        toggle_selection(&mut selected, rows.get(1));
+        extend_selection(&mut selected, &rows, Some(1), Some(2));
+        assert_eq!(ids(selected_in(&items, &selected)), vec!["a", "b"]);
+
+        toggle_selection(&mut selected, rows.get(1));
+        assert_eq!(ids(selected_in(&items, &selected)), vec!["b"]);
+    }
+
+    #[test]
+    fn size_threshold_selects_large_and_drops_small() {
+        let sized = |name: &str, size| {
+            ScanResult::new(name, name, PathBuf::from(format!("/tmp/{}", name))).with_size(size)
+        };
+        let mut items = items();
+        items.extend([sized("big", 5000), sized("small", 10)]);
+        let mut selected = HashSet::from(["a".to_string()]);
+
+        // "cache" (600) takes over its selected child; "small" stays out.
+        assert_eq!(select_larger_than(&mut selected, &items, 500), 2);
+        assert_eq!(
+            selected,
+            HashSet::from(["cache".to_string(), "big".to_string()])
+        );
+        assert_eq!(select_larger_than(&mut selected, &items, 500), 0);
+
+        selected.insert("small".to_string());
+        assert_eq!(deselect_smaller_than(&mut selected, &items, 1000), 2);
+        assert_eq!(selected, HashSet::from(["big".to_string()]));
+    }
+
+    #[test]
+    fn unavailable_tasks_are_never_selected() {
+        let mut task = ScanResult::new("maint_purge", "Purge", PathBuf::from("purge"));
+        task.metadata
+            .insert("available".to_string(), "false".to_string());
+        let items = vec![task, ScanResult::new("ok", "ok", PathBuf::from("/tmp/ok"))];
+
+        let mut selected = HashSet::new();
+        select_all_in_category(&mut selected, &items);
+        assert_eq!(selected, HashSet::from(["ok".to_string()]));
+
+        let rows = visible_rows(&items, &HashSet::new());
+        toggle_selection(&mut selected, rows.first());
+        assert!(!selected.contains("maint_purge"));
+    }
+}
@@ -1,47 +1,140 @@
-use crate::plugin::registry::ScanReport;
+use crate::plugin::registry::{CategoryScanResult, ScanReport};
+use crate::plugin::ScanResult;
 use crate::tui::state::SortMode;
+use std::cmp::{Ordering, Reverse};
+
+fn file_name_key(item: &ScanResult) -> String {
+    item.path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+// Oldest first; items without a modification time go last.
+fn by_age(
+    a: Option<chrono::DateTime<chrono::Utc>>,
+    b: Option<chrono::DateTime<chrono::Utc>>,
+) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+pub fn sort_items(items: &mut [ScanResult], sort_mode: SortMode) {
+    match sort_mode {
+        SortMode::SizeDesc => items.sort_by_key(|b| Reverse(b.size)),
+        SortMode::SizeAsc => items.sort_by_key(|a| a.size),
+        SortMode::NameAsc => items.sort_by_key(file_name_key),
+        SortMode::NameDesc => items.sort_by_key(|b| Reverse(file_name_key(b))),
+        SortMode::AgeAsc => items.sort_by(|a, b| by_age(a.last_modified, b.last_modified)),
+        SortMode::CountDesc => items.sort_by_key(|b| Reverse(b.file_count)),
+    }
+}
 
 pub fn apply_sort(report: &mut ScanReport, sort_mode: SortMode) {
     for category in &mut report.categories {
-        match sort_mode {
-            SortMode::SizeDesc => {
-                category.items.sort_by(|a, b| b.size.cmp(&a.size));
-            }
-            SortMode::SizeAsc => {
-                category.items.sort_by(|a, b| a.size.cmp(&b.size));
-            }
-            SortMode::NameAsc => {
-                category.items.sort_by(|a, b| {
-                    a.path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("")
-                        .to_lowercase()
-                        .cmp(
-                            &b.path
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("")
-                                .to_lowercase(),
-                        )
-                });
-            }
-            SortMode::NameDesc => {
-                category.items.sort_by(|a, b| {
-                    b.path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("")
-                        .to_lowercase()
-                        .cmp(
-                            &a.path
-                                .file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("")
-                                .to_lowercase(),
-                        )
-                });
-            }
+        sort_items(&mut category.items, sort_mode);
+    }
+}
+
+// The TUI keeps categories in scanner order so the selected index stays put;
+// CLI output reorders them with the same key as their items.
+pub fn sort_categories(categories: &mut [CategoryScanResult], sort_mode: SortMode) {
+    match sort_mode {
+        SortMode::SizeDesc => categories.sort_by_key(|c| Reverse(c.total_size())),
+        SortMode::SizeAsc => categories.sort_by_key(|c| c.total_size()),
+        SortMode::NameAsc => categories.sort_by_key(|c| c.name.to_lowercase()),
+        SortMode::NameDesc => categories.sort_by_key(|c| Reverse(c.name.to_lowercase())),
+        SortMode::AgeAsc => categories.sort_by(|a, b| {
+            let oldest =
+                |c: &CategoryScanResult| c.items.iter().filter_map(|i| i.last_modified).min();
+            by_age(oldest(a), oldest(b))
+        }),
+        SortMode::CountDesc => categories.sort_by_key(|c| Reverse(c.items.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::ScannerCategory;
+    use chrono::{Duration, Utc};
+    use std::path::PathBuf;
+
+    fn item(name: &str, size: u64, files: u64, age_days: Option<i64>) -> ScanResult {
+        ScanResult::new(name, name, PathBuf::from(format!("/tmp/{}", name)))
+            .with_size(size)
+            .with_file_count(files)
+            .with_last_modified(age_days.map(|d| Utc::now() - Duration::days(d)))
+    }
+
+    fn mixed() -> Vec<ScanResult> {
+        vec![
+            item("beta", 300, 2, Some(5)),
+            item("Alpha", 100, 9, None),
+            item("gamma", 200, 1, Some(40)),
+        ]
+    }
+
+    fn names(items: &[ScanResult]) -> Vec<&str> {
+        items.iter().map(|i| i.name.as_str()).collect()
+    }
+
+    fn category(name: &str, items: Vec<ScanResult>) -> CategoryScanResult {
+        CategoryScanResult {
+            scanner_id: name.to_lowercase(),
+            name: name.to_string(),
+            category: ScannerCategory::System,
+            items,
+            scan_duration: std::time::Duration::ZERO,
+            unreadable: 0,
+            omitted: 0,
+        }
+    }
+
+    #[test]
+    fn sorts_items_by_each_key() {
+        let cases = [
+            (SortMode::SizeDesc, vec!["beta", "gamma", "Alpha"]),
+            (SortMode::SizeAsc, vec!["Alpha", "gamma", "beta"]),
+            (SortMode::NameAsc, vec!["Alpha", "beta", "gamma"]),
+            (SortMode::NameDesc, vec!["gamma", "beta", "Alpha"]),
+            (SortMode::AgeAsc, vec!["gamma", "beta", "Alpha"]),
+            (SortMode::CountDesc, vec!["Alpha", "beta", "gamma"]),
+        ];
+        for (mode, expected) in cases {
+            let mut items = mixed();
+            sort_items(&mut items, mode);
+            assert_eq!(names(&items), expected, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn sorts_categories_by_each_key() {
+        let build = || {
+            vec![
+                category("Logs", vec![item("a", 50, 1, Some(2))]),
+                category(
+                    "caches",
+                    vec![item("b", 500, 1, Some(1)), item("c", 10, 1, None)],
+                ),
+                category("Trash", vec![item("d", 100, 1, Some(90))]),
+            ]
+        };
+        let cases = [
+            (SortMode::SizeDesc, vec!["caches", "Trash", "Logs"]),
+            (SortMode::NameAsc, vec!["caches", "Logs", "Trash"]),
+            (SortMode::AgeAsc, vec!["Trash", "Logs", "caches"]),
+            (SortMode::CountDesc, vec!["caches", "Logs", "Trash"]),
+        ];
+        for (mode, expected) in cases {
+            let mut categories = build();
+            sort_categories(&mut categories, mode);
+            let order: Vec<&str> = categories.iter().map(|c| c.name.as_str()).collect();
+            assert_eq!(order, expected, "{:?}", mode);
         }
     }
 }
@@ -3,6 +3,9 @@ mod controller;
 mod logic;
 mod service;
 mod state;
+mod terminal;
 mod view;
 
-pub use state::App;
+pub use logic::sorting::{apply_sort, sort_categories};
+pub use state::{App, SortMode};
+pub use terminal::TerminalGuard;
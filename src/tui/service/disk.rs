@@ -1,4 +1,5 @@
 use crate::tui::state::{CachedScan, FolderEntry, SpaceLensState};
+use chrono::{DateTime, Utc};
 use std::os::unix::fs::MetadataExt;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -44,6 +45,7 @@ impl ThreadPool {
                         counter.fetch_add(1, Ordering::SeqCst);
                         if is_dir {
                             let mut current_size: u64 = 0;
+                            let mut latest_modified = None;
                             for e in walkdir::WalkDir::new(&path)
                                 .same_file_system(true)
                                 .into_iter()
@@ -60,12 +62,15 @@ impl ThreadPool {
                                 if let Ok(metadata) = e.metadata() {
                                     if metadata.is_file() {
                                         current_size += metadata.len();
+                                        latest_modified =
+                                            newer(latest_modified, modified_time(&metadata));
                                         let _ = result_tx.send(FolderEntry {
                                             name: name.clone(),
                                             path: path.clone(),
                                             size: current_size,
                                             is_dir,
                                             scanning: true,
+                                            modified: latest_modified,
                                         });
                                     }
                                 }
@@ -76,6 +81,7 @@ impl ThreadPool {
                                 size: current_size,
                                 is_dir,
                                 scanning: false,
+                                modified: latest_modified,
                             });
                         } else if let Ok(metadata) = path.metadata() {
                             let _ = result_tx.send(FolderEntry {
@@ -84,6 +90,7 @@ impl ThreadPool {
                                 size: metadata.len(),
                                 is_dir,
                                 scanning: false,
+                                modified: modified_time(&metadata),
                             });
                         }
                         counter.fetch_sub(1, Ordering::SeqCst);
@@ -113,16 +120,40 @@ fn get_thread_pool(size: usize) -> &'static ThreadPool {
     }
 }
 
+fn modified_time(metadata: &std::fs::Metadata) -> Option<DateTime<Utc>> {
+    metadata.modified().ok().map(DateTime::from)
+}
+
+fn newer(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>) -> Option<DateTime<Utc>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
 pub fn start_space_scan(state: &mut SpaceLensState) {
     let path = state.current_path.clone();
     let thread_count = state.thread_count;
+    let cache_ttl = state.cache_ttl;
 
     // 캐시 확인
+    state.serving_stale = false;
     let should_rescan = if let Some(cached) = state.cache.get(&path).cloned() {
+        let stale = !cached.was_loading && cached.is_stale(&path, state.cache_ttl);
         state.entries = cached.entries;
         state.total_size = cached.total_size;
-        // 로딩 중이었으면 스캔 재개 필요
-        cached.was_loading
+        if stale {
+            // 오래된 캐시는 그대로 보여주면서 전체 재스캔 (삭제된 항목은 제거)
+            state.cache.remove(&path);
+            state.entries.retain(|e| e.path.exists());
+            state.total_size = state.entries.iter().map(|e| e.size).sum();
+            for entry in &mut state.entries {
+                entry.scanning = true;
+            }
+            state.serving_stale = true;
+        }
+        // 로딩 중이었거나 만료되었으면 스캔 재개 필요
+        cached.was_loading || stale
     } else {
         // 캐시 없으면 새 스캔
         state.entries.clear();
@@ -212,16 +243,21 @@ pub fn start_space_scan(state: &mut SpaceLensState) {
 
         let name = entry_path
             .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("?")
-            .to_string();
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "?".to_string());
         let is_dir = entry_path.is_dir();
+        let modified = if is_dir {
+            None
+        } else {
+            entry.metadata().ok().and_then(|m| modified_time(&m))
+        };
         let _ = tx.send(FolderEntry {
             name,
             path: entry_path.clone(),
             size: 0,
             is_dir,
             scanning: true,
+            modified,
         });
     }
 
@@ -233,9 +269,8 @@ pub fn start_space_scan(state: &mut SpaceLensState) {
             let entry_path = entry.path();
             let name = entry_path
                 .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("?")
-                .to_string();
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "?".to_string());
             let is_dir = entry_path.is_dir();
 
             // 이미 entries에 있고 스캔 완료된 항목은 skip
@@ -250,13 +285,14 @@ pub fn start_space_scan(state: &mut SpaceLensState) {
             // 캐시에 이미 완료된 스캔이 있으면 재사용
             if is_dir {
                 if let Some(cached) = state.cache.get(&entry_path) {
-                    if !cached.was_loading {
+                    if cached.is_reusable(&entry_path, cache_ttl) {
                         let _ = tx.send(FolderEntry {
                             name,
                             path: entry_path.clone(),
                             size: cached.total_size,
                             is_dir,
                             scanning: false,
+                            modified: cached.latest_modified(),
                         });
                         continue 'outer;
                     }
@@ -276,9 +312,8 @@ pub fn start_space_scan(state: &mut SpaceLensState) {
                 let entry_path = entry.path();
                 let name = entry_path
                     .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("?")
-                    .to_string();
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "?".to_string());
                 let is_dir = entry_path.is_dir();
 
                 // 이미 entries에 있고 스캔 완료된 항목은 skip
@@ -293,13 +328,14 @@ pub fn start_space_scan(state: &mut SpaceLensState) {
                 // 캐시에 이미 완료된 스캔이 있으면 재사용
                 if is_dir {
                     if let Some(cached) = cache_clone.get(&entry_path) {
-                        if !cached.was_loading {
+                        if cached.is_reusable(&entry_path, cache_ttl) {
                             let _ = tx.send(FolderEntry {
                                 name,
                                 path: entry_path.clone(),
                                 size: cached.total_size,
                                 is_dir,
                                 scanning: false,
+                                modified: cached.latest_modified(),
                             });
                             continue 'outer;
                         }
@@ -308,6 +344,7 @@ pub fn start_space_scan(state: &mut SpaceLensState) {
 
                 if is_dir {
                     let mut current_size: u64 = 0;
+                    let mut latest_modified = None;
                     for e in walkdir::WalkDir::new(&entry_path)
                         .same_file_system(true)
                         .into_iter()
@@ -324,12 +361,14 @@ pub fn start_space_scan(state: &mut SpaceLensState) {
                         if let Ok(metadata) = e.metadata() {
                             if metadata.is_file() {
                                 current_size += metadata.len();
+                                latest_modified = newer(latest_modified, modified_time(&metadata));
                                 let _ = tx.send(FolderEntry {
                                     name: name.clone(),
                                     path: entry_path.clone(),
                                     size: current_size,
                                     is_dir,
                                     scanning: true,
+                                    modified: latest_modified,
                                 });
                             }
                         }
@@ -341,6 +380,7 @@ pub fn start_space_scan(state: &mut SpaceLensState) {
                         size: current_size,
                         is_dir,
                         scanning: false,
+                        modified: latest_modified,
                     });
                 } else if let Ok(metadata) = entry_path.metadata() {
                     let _ = tx.send(FolderEntry {
@@ -349,6 +389,7 @@ pub fn start_space_scan(state: &mut SpaceLensState) {
                         size: metadata.len(),
                         is_dir,
                         scanning: false,
+                        modified: modified_time(&metadata),
                     });
                 }
             }
@@ -376,11 +417,14 @@ pub fn poll_space_sizes(state: &mut SpaceLensState) {
                             }
                             // scanning 상태 업데이트
                             existing.scanning = entry.scanning;
+                            if entry.modified.is_some() {
+                                existing.modified = entry.modified;
+                            }
                         } else {
                             state.total_size += entry.size;
                             state.entries.push(entry);
                         }
-                        state.entries.sort_by(|a, b| b.size.cmp(&a.size));
+                        state.entries.sort_by_key(|b| std::cmp::Reverse(b.size));
                     }
                 }
                 Err(TryRecvError::Empty) => break,
@@ -388,13 +432,15 @@ pub fn poll_space_sizes(state: &mut SpaceLensState) {
                     completed_paths.push(path.clone());
                     if *path == current_path {
                         state.loading = false;
+                        state.serving_stale = false;
                         state.cache.insert(
                             current_path.clone(),
-                            CachedScan {
-                                entries: state.entries.clone(),
-                                total_size: state.total_size,
-                                was_loading: false,
-                            },
+                            CachedScan::new(
+                                &current_path,
+                                state.entries.clone(),
+                                state.total_size,
+                                false,
+                            ),
                         );
                     }
                     break;
@@ -1,21 +1,21 @@
 use crate::config::Config;
 use crate::plugin::{
-    registry::{CategoryScanResult, ScanReport},
-    ScanConfig, Scanner, ScannerCategory,
+    registry::{keep_largest, CategoryScanResult, ScanReport},
+    ScanConfig, ScanPause, Scanner, ScannerCategory, PROGRESS_INTERVAL,
 };
 use crate::scanner::{
-    BrowserCacheScanner, CacheScanner, DevJunkScanner, DuplicatesScanner, LargeOldFilesScanner,
-    LogScanner, MailAttachmentsScanner, MaintenanceScanner, MusicJunkScanner, PhotoJunkScanner,
-    PrivacyScanner, StartupItemsScanner, TrashScanner,
+    load_user_scanners, BrowserCacheScanner, CacheScanner, ContainerScanner, DevJunkScanner,
+    DsStoreScanner, DuplicatesScanner, LargeOldFilesScanner, LogScanner, MailAttachmentsScanner,
+    MaintenanceScanner, MusicJunkScanner, PhotoJunkScanner, PrivacyScanner,
+    SpotlightMetadataScanner, StartupItemsScanner, TrashScanner, XcodeJunkScanner,
 };
 use crate::tui::state::{AppMode, ScanMessage, ScanProgress};
 use ratatui::widgets::ListState;
-use std::path::PathBuf;
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender, TryRecvError};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 const DEFAULT_POOL_SIZE: usize = 4;
 
@@ -29,16 +29,24 @@ type ScannerJob = (
 
 struct ScannerPool {
     job_sender: Sender<ScannerJob>,
+    pause: ScanPause,
 }
 
 impl ScannerPool {
     fn new(size: usize) -> Self {
         let (job_tx, job_rx): (Sender<ScannerJob>, Receiver<ScannerJob>) = channel();
         let job_rx = Arc::new(Mutex::new(job_rx));
+        let pause = ScanPause::default();
 
         for _ in 0..size {
             let rx = Arc::clone(&job_rx);
+            let pause = pause.clone();
             thread::spawn(move || loop {
+                // 일시정지 중에는 새 작업을 가져가지 않는다.
+                if pause.is_paused() {
+                    thread::sleep(Duration::from_millis(50));
+                    continue;
+                }
                 let job = {
                     let rx = rx.lock().unwrap();
                     rx.try_recv()
@@ -52,14 +60,43 @@ impl ScannerPool {
                             name: scanner_name.clone(),
                         });
 
-                        let _ = scanner.scan(&scan_config);
-
-                        let _ = tx.send(ScanMessage::ScannerDone {
-                            scanner_id,
-                            name: scanner_name.clone(),
-                            category,
+                        // The scan runs on its own thread so a hung scanner can be
+                        // abandoned; its callbacks are silenced once cancelled is set.
+                        let cancelled = Arc::new(AtomicBool::new(false));
+                        let (counting, unreadable) = scan_config.counting_unreadable();
+                        let (throttled, throttle) =
+                            counting.guarded(&cancelled).throttled(PROGRESS_INTERVAL);
+                        let throttled = throttled.pausable(&pause);
+                        let (done_tx, done_rx) = channel();
+                        thread::spawn(move || {
+                            let started = Instant::now();
+                            let _ = scanner.scan(&throttled);
+                            if let Some(throttle) = throttle {
+                                throttle.flush();
+                            }
+                            let _ = done_tx.send(started.elapsed());
                         });
 
+                        let max_items = scan_config.max_items_for(&scanner_id);
+                        let finished = wait_for_scan(&done_rx, scan_config.scanner_timeout, &pause);
+
+                        if let Some(duration) = finished {
+                            let _ = tx.send(ScanMessage::ScannerDone {
+                                scanner_id,
+                                name: scanner_name.clone(),
+                                category,
+                                duration,
+                                unreadable: unreadable.load(Ordering::SeqCst),
+                                max_items,
+                            });
+                        } else {
+                            cancelled.store(true, Ordering::SeqCst);
+                            let _ = tx.send(ScanMessage::ScannerTimedOut {
+                                scanner_id,
+                                name: scanner_name.clone(),
+                            });
+                        }
+
                         completed.fetch_add(1, Ordering::SeqCst);
                     }
                     Err(TryRecvError::Empty) => {
@@ -70,7 +107,10 @@ impl ScannerPool {
             });
         }
 
-        ScannerPool { job_sender: job_tx }
+        ScannerPool {
+            job_sender: job_tx,
+            pause,
+        }
     }
 
     fn submit(&self, job: ScannerJob) {
@@ -78,12 +118,45 @@ impl ScannerPool {
     }
 }
 
+// Waits for a scan to finish. Time spent paused doesn't count against the timeout,
+// so pausing never makes a scanner look hung.
+fn wait_for_scan(
+    done_rx: &Receiver<Duration>,
+    timeout: Option<Duration>,
+    pause: &ScanPause,
+) -> Option<Duration> {
+    let Some(timeout) = timeout else {
+        return Some(done_rx.recv().unwrap_or_default());
+    };
+
+    let mut remaining = timeout;
+    loop {
+        let slice = remaining.min(Duration::from_millis(100));
+        match done_rx.recv_timeout(slice) {
+            Ok(elapsed) => return Some(elapsed),
+            Err(RecvTimeoutError::Disconnected) => return Some(Duration::ZERO),
+            Err(RecvTimeoutError::Timeout) => {
+                if !pause.is_paused() {
+                    remaining = remaining.saturating_sub(slice);
+                    if remaining.is_zero() {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+}
+
 static POOL: OnceLock<ScannerPool> = OnceLock::new();
 
 fn get_scanner_pool() -> &'static ScannerPool {
     POOL.get_or_init(|| ScannerPool::new(DEFAULT_POOL_SIZE))
 }
 
+pub fn scan_pause() -> &'static ScanPause {
+    &get_scanner_pool().pause
+}
+
 pub struct ScanStartParams<'a> {
     pub config: &'a Config,
     pub enabled_scanner_ids: Vec<String>,
@@ -94,9 +167,18 @@ pub struct ScanStartParams<'a> {
 }
 
 pub fn start_scan(params: &mut ScanStartParams) {
-    let enabled_ids = params.enabled_scanner_ids.clone();
+    let enabled_ids: Vec<String> = params
+        .enabled_scanner_ids
+        .iter()
+        .filter(|id| params.config.allows_scanner(id))
+        .cloned()
+        .collect();
 
+    // Nothing to run. Review explains that when it has no older results to show.
     if enabled_ids.is_empty() {
+        if params.report.is_none() {
+            *params.mode = AppMode::Review;
+        }
         return;
     }
 
@@ -104,16 +186,8 @@ pub fn start_scan(params: &mut ScanStartParams) {
 
     let progress_tx = tx.clone();
     let item_tx = tx.clone();
+    let phase_tx = tx.clone();
     let scan_config = ScanConfig {
-        min_size: params.config.scan.min_size_bytes,
-        max_depth: params.config.scan.max_depth,
-        excluded_paths: params
-            .config
-            .scan
-            .excluded_paths
-            .iter()
-            .map(|s| PathBuf::from(s))
-            .collect(),
         progress_callback: Some(std::sync::Arc::new(move |path: &str| {
             let _ = progress_tx.send(ScanMessage::ScanningPath {
                 path: path.to_string(),
@@ -123,6 +197,10 @@ pub fn start_scan(params: &mut ScanStartParams) {
             let scanner_id = item.metadata.get("scanner_id").cloned().unwrap_or_default();
             let _ = item_tx.send(ScanMessage::ItemFound { scanner_id, item });
         })),
+        phase_callback: Some(std::sync::Arc::new(move |phase| {
+            let _ = phase_tx.send(ScanMessage::PhaseProgress { phase });
+        })),
+        ..ScanConfig::from_config(params.config)
     };
 
     if let Some(ref mut report) = params.report {
@@ -150,20 +228,26 @@ pub fn start_scan(params: &mut ScanStartParams) {
             total_size: 0,
             total_items: 0,
             duration: Duration::from_secs(0),
+            timed_out: Vec::new(),
         });
     }
 
     *params.scan_progress = ScanProgress {
         current_scanner: "Initializing...".to_string(),
         current_path: None,
+        phase: None,
         scanners_done: 0,
         total_scanners: enabled_ids.len(),
         active_scanners: 0,
+        timed_out: Vec::new(),
+        unreadable: Vec::new(),
+        paused: false,
     };
+    scan_pause().set(false);
     *params.scan_receiver = Some(rx);
     *params.mode = AppMode::Review;
 
-    let all_scanners: Vec<(String, Box<dyn Scanner>, ScannerCategory)> = vec![
+    let mut all_scanners: Vec<(String, Box<dyn Scanner>, ScannerCategory)> = vec![
         (
             "system_caches".into(),
             Box::new(CacheScanner::new()) as Box<dyn Scanner>,
@@ -189,6 +273,16 @@ pub fn start_scan(params: &mut ScanStartParams) {
             Box::new(DevJunkScanner::new()) as Box<dyn Scanner>,
             ScannerCategory::Development,
         ),
+        (
+            "xcode_junk".into(),
+            Box::new(XcodeJunkScanner::new()) as Box<dyn Scanner>,
+            ScannerCategory::Development,
+        ),
+        (
+            "container_junk".into(),
+            Box::new(ContainerScanner::new()) as Box<dyn Scanner>,
+            ScannerCategory::Development,
+        ),
         (
             "large_old_files".into(),
             Box::new(LargeOldFilesScanner::new()) as Box<dyn Scanner>,
@@ -209,6 +303,16 @@ pub fn start_scan(params: &mut ScanStartParams) {
             Box::new(MusicJunkScanner::new()) as Box<dyn Scanner>,
             ScannerCategory::System,
         ),
+        (
+            "spotlight_metadata".into(),
+            Box::new(SpotlightMetadataScanner::new()) as Box<dyn Scanner>,
+            ScannerCategory::System,
+        ),
+        (
+            "ds_store".into(),
+            Box::new(DsStoreScanner::new()) as Box<dyn Scanner>,
+            ScannerCategory::System,
+        ),
         (
             "duplicates".into(),
             Box::new(DuplicatesScanner::new()) as Box<dyn Scanner>,
@@ -230,6 +334,16 @@ pub fn start_scan(params: &mut ScanStartParams) {
             ScannerCategory::System,
         ),
     ];
+    // The CLI reports a broken scanners.toml; here its scanners just don't show.
+    all_scanners.extend(
+        load_user_scanners()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|s| {
+                let (id, category) = (s.id().to_string(), s.category());
+                (id, Box::new(s) as Box<dyn Scanner>, category)
+            }),
+    );
 
     let scanners: Vec<_> = all_scanners
         .into_iter()
@@ -269,6 +383,16 @@ pub struct PollContext<'a> {
     pub list_state: &'a mut ListState,
 }
 
+fn clear_phase(progress: &mut ScanProgress, scanner_id: &str) {
+    if progress
+        .phase
+        .as_ref()
+        .is_some_and(|p| p.scanner_id == scanner_id)
+    {
+        progress.phase = None;
+    }
+}
+
 pub fn poll_scan_messages(ctx: &mut PollContext) {
     let rx_opt = ctx.scan_receiver.take();
     if let Some(ref rx) = rx_opt {
@@ -282,6 +406,9 @@ pub fn poll_scan_messages(ctx: &mut PollContext) {
                 ScanMessage::ScanningPath { path } => {
                     ctx.scan_progress.current_path = Some(path);
                 }
+                ScanMessage::PhaseProgress { phase } => {
+                    ctx.scan_progress.phase = Some(phase);
+                }
                 ScanMessage::ItemFound { scanner_id, item } => {
                     if let Some(ref mut report) = ctx.report {
                         report.total_size += item.size;
@@ -299,6 +426,9 @@ pub fn poll_scan_messages(ctx: &mut PollContext) {
                                 name: scanner_id.clone(),
                                 category: ScannerCategory::System,
                                 items: vec![item],
+                                scan_duration: Duration::ZERO,
+                                unreadable: 0,
+                                omitted: 0,
                             };
                             report.categories.push(new_cat);
                             if report.categories.len() == 1 {
@@ -311,7 +441,15 @@ pub fn poll_scan_messages(ctx: &mut PollContext) {
                     scanner_id,
                     name,
                     category,
+                    duration,
+                    unreadable,
+                    max_items,
                 } => {
+                    if unreadable > 0 {
+                        ctx.scan_progress
+                            .unreadable
+                            .push((name.clone(), unreadable));
+                    }
                     if let Some(ref mut report) = ctx.report {
                         if let Some(cat) = report
                             .categories
@@ -320,14 +458,47 @@ pub fn poll_scan_messages(ctx: &mut PollContext) {
                         {
                             cat.name = name;
                             cat.category = category;
+                            cat.scan_duration = duration;
+                            cat.unreadable = unreadable;
+                            // Items streamed in as they were found; trim once the
+                            // scanner is done, like CLI output does.
+                            let before = cat.total_size();
+                            cat.omitted = keep_largest(&mut cat.items, max_items);
+                            if cat.omitted > 0 {
+                                report.total_size -= before - cat.total_size();
+                                report.total_items -= cat.omitted;
+                            }
                         }
                     }
+                    clear_phase(ctx.scan_progress, &scanner_id);
                     ctx.scan_progress.scanners_done += 1;
                     ctx.scan_progress.active_scanners =
                         ctx.scan_progress.active_scanners.saturating_sub(1);
                     ctx.scan_progress.current_path = None;
                 }
-                ScanMessage::ScanComplete { .. } => {
+                ScanMessage::ScannerTimedOut { scanner_id, name } => {
+                    // Partial results from an abandoned scanner are dropped.
+                    if let Some(ref mut report) = ctx.report {
+                        if let Some(pos) = report
+                            .categories
+                            .iter()
+                            .position(|c| c.scanner_id == scanner_id)
+                        {
+                            let removed = report.categories.remove(pos);
+                            report.total_size =
+                                report.total_size.saturating_sub(removed.total_size());
+                            report.total_items =
+                                report.total_items.saturating_sub(removed.items.len());
+                        }
+                    }
+                    clear_phase(ctx.scan_progress, &scanner_id);
+                    ctx.scan_progress.timed_out.push(name);
+                    ctx.scan_progress.scanners_done += 1;
+                    ctx.scan_progress.active_scanners =
+                        ctx.scan_progress.active_scanners.saturating_sub(1);
+                    ctx.scan_progress.current_path = None;
+                }
+                ScanMessage::ScanComplete => {
                     complete = true;
                 }
             }
@@ -337,3 +508,37 @@ pub fn poll_scan_messages(ctx: &mut PollContext) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_enabled_set_shows_review_without_scanning() {
+        let config = Config {
+            allowed_scanners: Some(vec!["trash".to_string()]),
+            ..Default::default()
+        };
+        let mut report = None;
+        let mut scan_progress = ScanProgress::default();
+        let mut scan_receiver = None;
+        let mut mode = AppMode::CategorySelect;
+
+        // Nothing picked, and the only pick left is outside the policy.
+        for ids in [vec![], vec!["system_caches".to_string()]] {
+            let mut params = ScanStartParams {
+                config: &config,
+                enabled_scanner_ids: ids,
+                report: &mut report,
+                scan_progress: &mut scan_progress,
+                scan_receiver: &mut scan_receiver,
+                mode: &mut mode,
+            };
+            start_scan(&mut params);
+        }
+
+        assert!(scan_receiver.is_none());
+        assert!(report.is_none());
+        assert!(mode == AppMode::Review);
+    }
+}
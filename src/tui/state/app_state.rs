@@ -1,18 +1,34 @@
+use crate::cleaner::{EmptyTrashResult, TrashLocation};
 use crate::config::Config;
 use crate::plugin::registry::ScanReport;
+use crate::plugin::Scanner;
 use crate::tui::state::{
-    AppMode, AppsModeState, CleanResultDisplay, ScanMessage, ScanProgress, ScannerInfo, SortMode,
-    SpaceLensState,
+    AppMode, AppsModeState, CleanResultDisplay, DashboardState, ScanMessage, ScanProgress,
+    ScannerInfo, SizePrompt, SortMode, SpaceLensState,
 };
 use ratatui::widgets::ListState;
 use std::collections::HashSet;
 use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+pub const SELECTION_DELTA_TTL: Duration = Duration::from_millis(1500);
+pub const SELECTION_NOTE_TTL: Duration = Duration::from_secs(3);
 
 pub struct App {
     pub config: Config,
     pub report: Option<ScanReport>,
     pub selected_category: usize,
     pub selected_items: HashSet<String>,
+    // Review items currently showing their children.
+    pub expanded_items: HashSet<String>,
+    // Size change from the last selection key and when it happened; the footer
+    // shows it for SELECTION_DELTA_TTL.
+    pub selection_delta: Option<(i64, Instant)>,
+    // Open while typing a `>`/`<` threshold; the note reports what it did.
+    pub size_prompt: Option<SizePrompt>,
+    pub selection_note: Option<(String, Instant)>,
+    // Lines the detail pane is scrolled by; back to 0 when focus moves.
+    pub detail_scroll: u16,
     pub list_state: ListState,
     pub mode: AppMode,
     pub prev_mode: Option<AppMode>,
@@ -25,11 +41,16 @@ pub struct App {
     pub sort_mode: SortMode,
     pub space_lens: SpaceLensState,
     pub deleted_ids: HashSet<String>,
+    // What the Empty Trash modal shows, measured when it was opened.
+    pub trash_locations: Vec<TrashLocation>,
+    // Set while a worker empties the Trash, with how long it took once done.
+    pub trash_receiver: Option<Receiver<(EmptyTrashResult, Duration)>>,
+    pub dashboard: DashboardState,
 }
 
 impl App {
     pub fn new(config: Config) -> Self {
-        let available_scanners = vec![
+        let mut available_scanners = vec![
             ScannerInfo {
                 id: "system_caches".into(),
                 name: "System Caches".into(),
@@ -55,6 +76,16 @@ impl App {
                 name: "Development Junk".into(),
                 enabled: true,
             },
+            ScannerInfo {
+                id: "xcode_junk".into(),
+                name: "Xcode Junk".into(),
+                enabled: true,
+            },
+            ScannerInfo {
+                id: "container_junk".into(),
+                name: "Container Runtimes".into(),
+                enabled: true,
+            },
             ScannerInfo {
                 id: "large_old_files".into(),
                 name: "Large & Old Files".into(),
@@ -75,6 +106,16 @@ impl App {
                 name: "Music & Podcasts".into(),
                 enabled: true,
             },
+            ScannerInfo {
+                id: "spotlight_metadata".into(),
+                name: "Spotlight Metadata".into(),
+                enabled: true,
+            },
+            ScannerInfo {
+                id: "ds_store".into(),
+                name: ".DS_Store & Cruft".into(),
+                enabled: true,
+            },
             ScannerInfo {
                 id: "duplicates".into(),
                 name: "Duplicates".into(),
@@ -96,6 +137,18 @@ impl App {
                 enabled: false,
             },
         ];
+        available_scanners.extend(
+            crate::scanner::load_user_scanners()
+                .unwrap_or_default()
+                .iter()
+                .map(|s| ScannerInfo {
+                    id: s.id().to_string(),
+                    name: s.name().to_string(),
+                    enabled: true,
+                }),
+        );
+        // allowed_scanners는 정책 상한: 목록에서 아예 빠져서 켤 수 없다.
+        available_scanners.retain(|s| config.allows_scanner(&s.id));
 
         let mut list_state = ListState::default();
         list_state.select(Some(0));
@@ -105,6 +158,11 @@ impl App {
             report: None,
             selected_category: 0,
             selected_items: HashSet::new(),
+            expanded_items: HashSet::new(),
+            selection_delta: None,
+            size_prompt: None,
+            selection_note: None,
+            detail_scroll: 0,
             list_state,
             mode: AppMode::CategorySelect,
             prev_mode: None,
@@ -117,6 +175,9 @@ impl App {
             sort_mode: SortMode::default(),
             space_lens: SpaceLensState::default(),
             deleted_ids: HashSet::new(),
+            trash_locations: Vec::new(),
+            trash_receiver: None,
+            dashboard: DashboardState::default(),
         }
     }
 }
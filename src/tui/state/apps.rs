@@ -1,5 +1,7 @@
-use crate::uninstaller::{AppBundle, RelatedFile};
+use crate::uninstaller::{duplicate_installs, group_related, AppBundle, RelatedFile};
+use chrono::{Duration, Utc};
 use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Receiver;
 
 #[derive(Debug, Clone, Default)]
@@ -10,26 +12,157 @@ pub struct UninstallResultDisplay {
     pub errors: Vec<String>,
 }
 
+// What the confirm modal shows before an uninstall runs. The app bundle is
+// always removed; protected related files are skipped by the uninstaller.
+#[derive(Debug, Clone)]
+pub struct UninstallSummary {
+    pub app_name: String,
+    pub app_size: u64,
+    pub related_count: usize,
+    pub related_size: u64,
+    // (category, count, size) of the selected related files, in preview order.
+    pub groups: Vec<(&'static str, usize, u64)>,
+    pub protected_count: usize,
+    pub protected_size: u64,
+}
+
+impl UninstallSummary {
+    pub fn total_size(&self) -> u64 {
+        self.app_size + self.related_size
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AppListSort {
+    #[default]
+    Name,
+    // Newest first; apps with no known date go last.
+    Installed,
+}
+
+// What `f` steps through: everything, then installed in the last N days.
+const INSTALLED_FILTERS: [Option<i64>; 4] = [None, Some(7), Some(30), Some(90)];
+
+#[derive(Default)]
 pub struct AppsModeState {
     pub apps: Vec<AppBundle>,
     pub app_sizes: HashMap<usize, u64>,
     pub selected_app_idx: Option<usize>,
     pub selected_related: HashSet<usize>,
+    // Pre-select only the .app bundle: an older duplicate shares its related
+    // files with the copy the user keeps.
+    pub bundle_only: bool,
     pub uninstall_result: Option<UninstallResultDisplay>,
     pub cached_related_files: Vec<RelatedFile>,
     pub size_receiver: Option<Receiver<(usize, u64)>>,
+    pub duplicate_paths: HashSet<PathBuf>,
+    // Every copy in a duplicate group -> the group's older copy, if versions differ.
+    pub older_copies: HashMap<PathBuf, PathBuf>,
+    pub sort: AppListSort,
+    pub installed_within_days: Option<i64>,
+    // Indices into `apps` in display order, after sort and filter. `apps`
+    // itself never reorders, since sizes arrive keyed by index.
+    pub view: Vec<usize>,
 }
 
-impl Default for AppsModeState {
-    fn default() -> Self {
-        Self {
-            apps: Vec::new(),
-            app_sizes: HashMap::new(),
-            selected_app_idx: None,
-            selected_related: HashSet::new(),
-            uninstall_result: None,
-            cached_related_files: Vec::new(),
-            size_receiver: None,
+impl AppsModeState {
+    pub fn refresh_duplicates(&mut self) {
+        self.duplicate_paths.clear();
+        self.older_copies.clear();
+        for group in duplicate_installs(&self.apps) {
+            let older = group.older_copy().map(|copy| copy.app.path.clone());
+            for copy in group.copies {
+                if let Some(older) = &older {
+                    self.older_copies
+                        .insert(copy.app.path.clone(), older.clone());
+                }
+                self.duplicate_paths.insert(copy.app.path);
+            }
+        }
+    }
+
+    pub fn refresh_view(&mut self) {
+        let cutoff = self
+            .installed_within_days
+            .map(|days| Utc::now() - Duration::days(days));
+        let apps = &self.apps;
+        self.view = (0..apps.len())
+            .filter(|&i| match cutoff {
+                Some(cutoff) => apps[i].installed().is_some_and(|t| t >= cutoff),
+                None => true,
+            })
+            .collect();
+        if self.sort == AppListSort::Installed {
+            self.view.sort_by_key(|&i| {
+                let installed = apps[i].installed();
+                (installed.is_none(), std::cmp::Reverse(installed))
+            });
         }
     }
+
+    pub fn toggle_sort(&mut self) {
+        self.sort = match self.sort {
+            AppListSort::Name => AppListSort::Installed,
+            AppListSort::Installed => AppListSort::Name,
+        };
+        self.refresh_view();
+    }
+
+    pub fn cycle_installed_filter(&mut self) {
+        let current = INSTALLED_FILTERS
+            .iter()
+            .position(|f| *f == self.installed_within_days)
+            .unwrap_or(0);
+        self.installed_within_days = INSTALLED_FILTERS[(current + 1) % INSTALLED_FILTERS.len()];
+        self.refresh_view();
+    }
+
+    pub fn is_older_copy(&self, path: &Path) -> bool {
+        self.older_copies
+            .get(path)
+            .is_some_and(|older| older == path)
+    }
+
+    // Index of the older copy in the duplicate group the app at `idx` belongs to.
+    pub fn older_copy_index(&self, idx: usize) -> Option<usize> {
+        let older = self.older_copies.get(&self.apps.get(idx)?.path)?;
+        self.apps.iter().position(|app| &app.path == older)
+    }
+
+    pub fn uninstall_summary(&self) -> Option<UninstallSummary> {
+        let app = self.apps.get(self.selected_app_idx?)?;
+
+        let mut summary = UninstallSummary {
+            app_name: app.name().to_string(),
+            app_size: app.size(),
+            related_count: 0,
+            related_size: 0,
+            groups: Vec::new(),
+            protected_count: 0,
+            protected_size: 0,
+        };
+        let selected: Vec<RelatedFile> = self
+            .cached_related_files
+            .iter()
+            .enumerate()
+            .filter(|(i, file)| {
+                file.category.is_protected() || self.selected_related.contains(&(i + 1))
+            })
+            .map(|(_, file)| file.clone())
+            .collect();
+
+        let (groups, protected) = group_related(&selected);
+        for (category, files) in groups {
+            let size: u64 = files.iter().map(|f| f.size).sum();
+            summary.related_count += files.len();
+            summary.related_size += size;
+            summary
+                .groups
+                .push((category.display_name(), files.len(), size));
+        }
+        summary.protected_count = protected.len();
+        summary.protected_size = protected.iter().map(|f| f.size).sum();
+
+        Some(summary)
+    }
 }
@@ -0,0 +1,25 @@
+use crate::history::HistoryLogger;
+use crate::suggest::{disk_space_with_purgeable, DiskSpace};
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Default)]
+pub struct DashboardState {
+    pub last_clean: Option<DateTime<Utc>>,
+    pub disk: Option<DiskSpace>,
+}
+
+impl DashboardState {
+    // Read once when the dashboard opens rather than on every frame.
+    pub fn load() -> Self {
+        let last_clean = HistoryLogger::new()
+            .read_history(Some(1))
+            .ok()
+            .and_then(|entries| entries.first().map(|e| e.timestamp));
+        let home = crate::user::home_dir();
+
+        Self {
+            last_clean,
+            disk: disk_space_with_purgeable(&home).ok(),
+        }
+    }
+}
@@ -1,11 +1,15 @@
 pub mod app_state;
 pub mod apps;
+pub mod dashboard;
 pub mod modes;
+pub mod review;
 pub mod scan;
 pub mod space_lens;
 
 pub use app_state::App;
-pub use apps::{AppsModeState, UninstallResultDisplay};
+pub use apps::{AppListSort, AppsModeState, UninstallResultDisplay, UninstallSummary};
+pub use dashboard::DashboardState;
 pub use modes::{AppMode, SortMode};
+pub use review::SizePrompt;
 pub use scan::{CleanResultDisplay, ScanMessage, ScanProgress, ScannerInfo};
 pub use space_lens::{CachedScan, DeleteResult, FolderEntry, SpaceLensMode, SpaceLensState};
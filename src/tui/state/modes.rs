@@ -3,13 +3,17 @@ pub enum AppMode {
     CategorySelect,
     Review,
     ConfirmClean,
+    ConfirmQuit,
+    ConfirmEmptyTrash,
     ResultDisplay,
     Help,
     AppList,
     LoadingRelatedFiles,
     UninstallReview,
+    ConfirmUninstall,
     UninstallResult,
     SpaceLens,
+    Dashboard,
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -19,6 +23,8 @@ pub enum SortMode {
     SizeAsc,
     NameAsc,
     NameDesc,
+    AgeAsc,
+    CountDesc,
 }
 
 impl SortMode {
@@ -27,7 +33,9 @@ impl SortMode {
             SortMode::SizeDesc => SortMode::SizeAsc,
             SortMode::SizeAsc => SortMode::NameAsc,
             SortMode::NameAsc => SortMode::NameDesc,
-            SortMode::NameDesc => SortMode::SizeDesc,
+            SortMode::NameDesc => SortMode::AgeAsc,
+            SortMode::AgeAsc => SortMode::CountDesc,
+            SortMode::CountDesc => SortMode::SizeDesc,
         }
     }
 
@@ -37,6 +45,8 @@ impl SortMode {
             SortMode::SizeAsc => "Size ↑",
             SortMode::NameAsc => "Name A-Z",
             SortMode::NameDesc => "Name Z-A",
+            SortMode::AgeAsc => "Oldest",
+            SortMode::CountDesc => "Files ↓",
         }
     }
 }
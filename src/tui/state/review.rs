@@ -0,0 +1,24 @@
+// The one-line prompt `>`/`<` open in Review for a size threshold.
+#[derive(Debug, Clone, Default)]
+pub struct SizePrompt {
+    pub input: String,
+    // `>` selects items above the size, `<` deselects the ones below.
+    pub select_larger: bool,
+}
+
+impl SizePrompt {
+    pub fn new(select_larger: bool) -> Self {
+        Self {
+            input: String::new(),
+            select_larger,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        if self.select_larger {
+            "Select items over"
+        } else {
+            "Deselect items under"
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::plugin::{ScanResult, ScannerCategory};
+use crate::plugin::{CommandOutcome, ScanPhase, ScanResult, ScannerCategory};
 use std::time::Duration;
 
 pub enum ScanMessage {
@@ -8,6 +8,9 @@ pub enum ScanMessage {
     ScanningPath {
         path: String,
     },
+    PhaseProgress {
+        phase: ScanPhase,
+    },
     ItemFound {
         scanner_id: String,
         item: ScanResult,
@@ -16,6 +19,13 @@ pub enum ScanMessage {
         scanner_id: String,
         name: String,
         category: ScannerCategory,
+        duration: Duration,
+        unreadable: usize,
+        max_items: Option<usize>,
+    },
+    ScannerTimedOut {
+        scanner_id: String,
+        name: String,
     },
     ScanComplete,
 }
@@ -30,9 +40,15 @@ pub struct ScannerInfo {
 pub struct ScanProgress {
     pub current_scanner: String,
     pub current_path: Option<String>,
+    // A scanner's counted stage, e.g. "Hashing 230/4000", until it finishes.
+    pub phase: Option<ScanPhase>,
     pub scanners_done: usize,
     pub total_scanners: usize,
     pub active_scanners: usize,
+    pub timed_out: Vec<String>,
+    // Scanners that hit permission errors, with how many entries they couldn't read.
+    pub unreadable: Vec<(String, usize)>,
+    pub paused: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -40,5 +56,25 @@ pub struct CleanResultDisplay {
     pub success_count: usize,
     pub failed_count: usize,
     pub total_freed: u64,
+    // Still on disk until the Trash is emptied.
+    pub moved_to_trash: u64,
     pub duration: Duration,
+    // Maintenance tasks that ran, shown under the totals.
+    pub commands: Vec<CommandOutcome>,
+    // Set when nothing ran, e.g. another cleanmac held the clean lock.
+    pub error: Option<String>,
+}
+
+impl CleanResultDisplay {
+    pub fn refused(error: String) -> Self {
+        Self {
+            success_count: 0,
+            failed_count: 0,
+            total_freed: 0,
+            moved_to_trash: 0,
+            duration: Duration::ZERO,
+            commands: Vec::new(),
+            error: Some(error),
+        }
+    }
 }
@@ -1,7 +1,8 @@
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Receiver;
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 
 #[derive(Debug, Clone)]
 pub struct FolderEntry {
@@ -10,6 +11,7 @@ pub struct FolderEntry {
     pub size: u64,
     pub is_dir: bool,
     pub scanning: bool,
+    pub modified: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone)]
@@ -17,6 +19,37 @@ pub struct CachedScan {
     pub entries: Vec<FolderEntry>,
     pub total_size: u64,
     pub was_loading: bool,
+    pub cached_at: Instant,
+    pub dir_mtime: Option<SystemTime>,
+}
+
+impl CachedScan {
+    pub fn new(path: &Path, entries: Vec<FolderEntry>, total_size: u64, was_loading: bool) -> Self {
+        Self {
+            entries,
+            total_size,
+            was_loading,
+            cached_at: Instant::now(),
+            dir_mtime: dir_mtime(path),
+        }
+    }
+
+    // Expired by age, or the directory's own listing changed since it was cached.
+    pub fn is_stale(&self, path: &Path, ttl: Duration) -> bool {
+        self.cached_at.elapsed() > ttl || dir_mtime(path) != self.dir_mtime
+    }
+
+    pub fn is_reusable(&self, path: &Path, ttl: Duration) -> bool {
+        !self.was_loading && !self.is_stale(path, ttl)
+    }
+
+    pub fn latest_modified(&self) -> Option<DateTime<Utc>> {
+        self.entries.iter().filter_map(|e| e.modified).max()
+    }
+}
+
+fn dir_mtime(path: &Path) -> Option<SystemTime> {
+    path.metadata().and_then(|m| m.modified()).ok()
 }
 
 #[derive(Debug, Clone)]
@@ -25,6 +58,7 @@ pub struct DeleteResult {
     pub success: bool,
     pub size: u64,
     pub error: Option<String>,
+    pub trashed: bool,
 }
 
 pub enum SpaceLensMode {
@@ -45,6 +79,9 @@ pub struct SpaceLensState {
     pub delete_mode: SpaceLensMode,
     pub pending_delete: Option<FolderEntry>,
     pub delete_result: Option<DeleteResult>,
+    pub recent_days: i64,
+    pub cache_ttl: Duration,
+    pub serving_stale: bool,
 }
 
 impl Default for SpaceLensState {
@@ -61,6 +98,35 @@ impl Default for SpaceLensState {
             delete_mode: SpaceLensMode::Browse,
             pending_delete: None,
             delete_result: None,
+            recent_days: 7,
+            cache_ttl: Duration::from_secs(60),
+            serving_stale: false,
         }
     }
 }
+
+impl SpaceLensState {
+    // 루트가 아니면 목록 맨 위에 ".." 행을 둔다
+    pub fn has_parent_entry(&self) -> bool {
+        self.current_path
+            .parent()
+            .is_some_and(|parent| parent != self.current_path)
+    }
+
+    pub fn row_offset(&self) -> usize {
+        usize::from(self.has_parent_entry())
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.entries.len() + self.row_offset()
+    }
+
+    pub fn is_parent_row(&self, row: usize) -> bool {
+        self.has_parent_entry() && row == 0
+    }
+
+    pub fn entry_at_row(&self, row: usize) -> Option<&FolderEntry> {
+        row.checked_sub(self.row_offset())
+            .and_then(|idx| self.entries.get(idx))
+    }
+}
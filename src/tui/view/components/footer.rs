@@ -1,30 +1,93 @@
+use crate::tui::state::SizePrompt;
+use crate::tui::view::theme::Theme;
+use crate::utils::format_size_delta;
 use ratatui::layout::Rect;
-use ratatui::style::{Color, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
-pub fn render_review_footer(f: &mut Frame, area: Rect) {
-    let footer = Paragraph::new(Line::from(vec![
-        Span::styled("↑↓", Style::default().fg(Color::Cyan)),
+// `selection_delta` is the size change from the last toggle, shown briefly up
+// front; `note` says what a `>`/`<` threshold did.
+pub fn render_review_footer(
+    f: &mut Frame,
+    area: Rect,
+    selection_delta: Option<i64>,
+    note: Option<&str>,
+    theme: &Theme,
+) {
+    let mut spans = Vec::new();
+    if let Some(delta) = selection_delta {
+        let color = if delta > 0 { theme.safe } else { theme.warning };
+        spans.push(Span::styled(
+            format!("{}  ", format_size_delta(delta)),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(note) = note {
+        spans.push(Span::styled(
+            format!("{}  ", note),
+            Style::default().fg(theme.text),
+        ));
+    }
+    spans.extend([
+        Span::styled("↑↓", Style::default().fg(theme.accent)),
         Span::raw(" Nav  "),
-        Span::styled("←→", Style::default().fg(Color::Cyan)),
+        Span::styled("←→", Style::default().fg(theme.accent)),
         Span::raw(" Cat  "),
-        Span::styled("s", Style::default().fg(Color::Cyan)),
+        Span::styled("s", Style::default().fg(theme.accent)),
         Span::raw(" Sort  "),
-        Span::styled("v", Style::default().fg(Color::Cyan)),
+        Span::styled("v", Style::default().fg(theme.accent)),
         Span::raw(" Space  "),
-        Span::styled("Tab", Style::default().fg(Color::Cyan)),
+        Span::styled("Tab", Style::default().fg(theme.accent)),
         Span::raw(" Cats  "),
-        Span::styled("Space", Style::default().fg(Color::Cyan)),
+        Span::styled("Space", Style::default().fg(theme.accent)),
         Span::raw(" Select  "),
-        Span::styled("Enter", Style::default().fg(Color::Cyan)),
+        Span::styled("e", Style::default().fg(theme.accent)),
+        Span::raw(" Expand  "),
+        Span::styled("Enter", Style::default().fg(theme.accent)),
         Span::raw(" Clean  "),
-        Span::styled("?", Style::default().fg(Color::Cyan)),
+        Span::styled("?", Style::default().fg(theme.accent)),
         Span::raw(" Help"),
+    ]);
+    let footer = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::TOP));
+
+    f.render_widget(footer, area);
+}
+
+// Stands in for the review footer while a `>`/`<` threshold is typed.
+pub fn render_size_prompt(f: &mut Frame, area: Rect, prompt: &SizePrompt, theme: &Theme) {
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled(
+            format!("{}: ", prompt.label()),
+            Style::default().fg(theme.warning),
+        ),
+        Span::styled(
+            format!("{}_", prompt.input),
+            Style::default().add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("  e.g. 500MB, 1.5GB  ", Style::default().fg(theme.muted)),
+        Span::styled("Enter", Style::default().fg(theme.accent)),
+        Span::raw(" Apply  "),
+        Span::styled("Esc", Style::default().fg(theme.accent)),
+        Span::raw(" Cancel"),
     ]))
     .block(Block::default().borders(Borders::TOP));
+    f.render_widget(footer, area);
+}
 
+pub fn render_dashboard_footer(f: &mut Frame, area: Rect, theme: &Theme) {
+    let footer = Paragraph::new(Line::from(vec![
+        Span::styled("Enter", Style::default().fg(theme.accent)),
+        Span::raw(" Review  "),
+        Span::styled("Tab", Style::default().fg(theme.accent)),
+        Span::raw(" Cats  "),
+        Span::styled("?", Style::default().fg(theme.accent)),
+        Span::raw(" Help  "),
+        Span::styled("q", Style::default().fg(theme.accent)),
+        Span::raw(" Quit"),
+    ]))
+    .block(Block::default().borders(Borders::TOP));
     f.render_widget(footer, area);
 }
 
@@ -33,35 +96,36 @@ pub fn render_category_select_footer(
     area: Rect,
     has_cached: bool,
     cached_size: u64,
+    theme: &Theme,
 ) {
     use crate::utils::format_size;
 
     let mut footer_spans = vec![
-        Span::styled("↑↓", Style::default().fg(Color::Cyan)),
+        Span::styled("↑↓", Style::default().fg(theme.accent)),
         Span::raw(" Nav  "),
-        Span::styled("Space", Style::default().fg(Color::Cyan)),
+        Span::styled("Space", Style::default().fg(theme.accent)),
         Span::raw(" Toggle  "),
-        Span::styled("r", Style::default().fg(Color::Cyan)),
+        Span::styled("r", Style::default().fg(theme.accent)),
         Span::raw(" Scan  "),
-        Span::styled("a", Style::default().fg(Color::Cyan)),
+        Span::styled("a", Style::default().fg(theme.accent)),
         Span::raw(" All  "),
-        Span::styled("n", Style::default().fg(Color::Cyan)),
+        Span::styled("n", Style::default().fg(theme.accent)),
         Span::raw(" None  "),
     ];
 
     if has_cached {
-        footer_spans.push(Span::styled("Tab", Style::default().fg(Color::Cyan)));
+        footer_spans.push(Span::styled("Tab", Style::default().fg(theme.accent)));
         footer_spans.push(Span::raw(" View  "));
     }
 
-    footer_spans.push(Span::styled("q", Style::default().fg(Color::Cyan)));
+    footer_spans.push(Span::styled("q", Style::default().fg(theme.accent)));
     footer_spans.push(Span::raw(" Quit"));
 
     if has_cached {
         footer_spans.push(Span::raw("  "));
         footer_spans.push(Span::styled(
             format!("| {} cached", format_size(cached_size)),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.safe),
         ));
     }
 
@@ -70,64 +134,86 @@ pub fn render_category_select_footer(
     f.render_widget(footer, area);
 }
 
-pub fn render_app_list_footer(f: &mut Frame, area: Rect) {
+pub fn render_app_list_footer(f: &mut Frame, area: Rect, theme: &Theme) {
     let footer = Paragraph::new(Line::from(vec![
-        Span::styled("↑↓", Style::default().fg(Color::Cyan)),
+        Span::styled("↑↓", Style::default().fg(theme.accent)),
         Span::raw(" Navigate  "),
-        Span::styled("Enter", Style::default().fg(Color::Cyan)),
+        Span::styled("Enter", Style::default().fg(theme.accent)),
         Span::raw(" Select  "),
-        Span::styled("?", Style::default().fg(Color::Cyan)),
+        Span::styled("o", Style::default().fg(theme.accent)),
+        Span::raw(" Remove older copy  "),
+        Span::styled("d", Style::default().fg(theme.accent)),
+        Span::raw(" Sort by install  "),
+        Span::styled("f", Style::default().fg(theme.accent)),
+        Span::raw(" Recent only  "),
+        Span::styled("?", Style::default().fg(theme.accent)),
         Span::raw(" Help  "),
-        Span::styled("q", Style::default().fg(Color::Cyan)),
+        Span::styled("q", Style::default().fg(theme.accent)),
         Span::raw(" Quit"),
     ]))
     .block(Block::default().borders(Borders::TOP));
     f.render_widget(footer, area);
 }
 
-pub fn render_uninstall_review_footer(f: &mut Frame, area: Rect) {
+pub fn render_uninstall_review_footer(f: &mut Frame, area: Rect, theme: &Theme) {
     let footer = Paragraph::new(Line::from(vec![
-        Span::styled("↑↓", Style::default().fg(Color::Cyan)),
+        Span::styled("↑↓", Style::default().fg(theme.accent)),
         Span::raw(" Nav  "),
-        Span::styled("Space", Style::default().fg(Color::Cyan)),
+        Span::styled("Space", Style::default().fg(theme.accent)),
         Span::raw(" Toggle  "),
-        Span::styled("a", Style::default().fg(Color::Cyan)),
+        Span::styled("a", Style::default().fg(theme.accent)),
         Span::raw(" All  "),
-        Span::styled("n", Style::default().fg(Color::Cyan)),
+        Span::styled("n", Style::default().fg(theme.accent)),
         Span::raw(" None  "),
-        Span::styled("Enter", Style::default().fg(Color::Cyan)),
+        Span::styled("Enter", Style::default().fg(theme.accent)),
         Span::raw(" Delete  "),
-        Span::styled("Esc", Style::default().fg(Color::Cyan)),
+        Span::styled("Esc", Style::default().fg(theme.accent)),
         Span::raw(" Back"),
     ]))
     .block(Block::default().borders(Borders::TOP));
     f.render_widget(footer, area);
 }
 
-pub fn render_space_lens_footer(f: &mut Frame, area: Rect, parallel: bool) {
+pub fn render_space_lens_footer(f: &mut Frame, area: Rect, parallel: bool, theme: &Theme) {
     let mode_indicator = if parallel {
-        Span::styled(" [Parallel]", Style::default().fg(Color::Yellow))
+        Span::styled(" [Parallel]", Style::default().fg(theme.warning))
     } else {
-        Span::styled(" [Single]", Style::default().fg(Color::DarkGray))
+        Span::styled(" [Single]", Style::default().fg(theme.muted))
     };
 
     let footer = Paragraph::new(Line::from(vec![
-        Span::styled("↑↓", Style::default().fg(Color::Cyan)),
+        Span::styled("↑↓", Style::default().fg(theme.accent)),
         Span::raw(" Nav  "),
-        Span::styled("Enter", Style::default().fg(Color::Cyan)),
+        Span::styled("Enter", Style::default().fg(theme.accent)),
         Span::raw(" Open  "),
-        Span::styled("d", Style::default().fg(Color::Cyan)),
+        Span::styled("d", Style::default().fg(theme.accent)),
         Span::raw(" Delete  "),
-        Span::styled("Esc/⌫", Style::default().fg(Color::Cyan)),
+        Span::styled("Esc/⌫", Style::default().fg(theme.accent)),
         Span::raw(" Up/Back  "),
-        Span::styled("r", Style::default().fg(Color::Cyan)),
+        Span::styled("r", Style::default().fg(theme.accent)),
         Span::raw(" Refresh  "),
-        Span::styled("p", Style::default().fg(Color::Cyan)),
+        Span::styled("p", Style::default().fg(theme.accent)),
         Span::raw(" Parallel  "),
-        Span::styled("q", Style::default().fg(Color::Cyan)),
+        Span::styled("q", Style::default().fg(theme.accent)),
         Span::raw(" Exit"),
         mode_indicator,
     ]))
     .block(Block::default().borders(Borders::TOP));
     f.render_widget(footer, area);
 }
+
+// Drawn over the top-right corner of every screen while audit mode is on.
+pub fn render_audit_badge(f: &mut Frame, theme: &Theme) {
+    const BADGE: &str = " AUDIT MODE - deletion disabled ";
+    let area = f.area();
+    let width = (BADGE.len() as u16).min(area.width);
+    let badge_area = Rect::new(area.x + area.width - width, area.y, width, 1);
+    let badge = Paragraph::new(Span::styled(
+        BADGE,
+        Style::default()
+            .fg(theme.text)
+            .bg(theme.danger)
+            .add_modifier(Modifier::BOLD),
+    ));
+    f.render_widget(badge, badge_area);
+}
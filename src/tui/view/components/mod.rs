@@ -2,4 +2,4 @@ pub mod footer;
 pub mod modal;
 pub mod utils;
 
-pub use utils::centered_rect;
+pub use utils::{centered_rect, fit_width, size_style, truncate_path_middle};
@@ -1,57 +1,293 @@
+use crate::cleaner::TrashLocation;
+use crate::plugin::registry::ScanReport;
+use crate::plugin::{CommandOutcome, ScanResult};
+use crate::scanner::login_launch_warning;
+use crate::tui::logic::selected_in;
 use crate::tui::state::CleanResultDisplay;
-use crate::tui::view::components::centered_rect;
+use crate::tui::view::components::{centered_rect, truncate_path_middle};
+use crate::tui::view::theme::Theme;
 use crate::utils::format_size;
 use ratatui::layout::Alignment;
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
+use std::collections::HashSet;
+
+const TOP_ITEMS: usize = 5;
+const MAX_CATEGORIES: usize = 6;
 
 pub struct ConfirmModalData {
     pub selected_count: usize,
     pub total_size: u64,
+    pub warnings: Vec<String>,
+    pub top_items: Vec<(String, u64)>,
+    pub category_sizes: Vec<(String, usize, u64)>,
 }
 
-pub fn render_confirm_modal(f: &mut Frame, data: &ConfirmModalData) {
-    let area = centered_rect(60, 35, f.area());
+impl ConfirmModalData {
+    pub fn from_selection(report: Option<&ScanReport>, selected_items: &HashSet<String>) -> Self {
+        let mut selected: Vec<&ScanResult> = Vec::new();
+        let mut category_sizes = Vec::new();
 
-    let text = vec![
+        for category in report.iter().flat_map(|r| r.categories.iter()) {
+            let items = selected_in(&category.items, selected_items);
+            if items.is_empty() {
+                continue;
+            }
+            category_sizes.push((
+                category.name.clone(),
+                items.len(),
+                items.iter().map(|i| i.size).sum(),
+            ));
+            selected.extend(items);
+        }
+
+        category_sizes.sort_by_key(|(_, _, size)| std::cmp::Reverse(*size));
+        selected.sort_by_key(|item| std::cmp::Reverse(item.size));
+
+        Self {
+            selected_count: selected.len(),
+            total_size: selected.iter().map(|i| i.size).sum(),
+            warnings: selected
+                .iter()
+                .filter_map(|item| {
+                    login_launch_warning(item).or_else(|| {
+                        let warning = item.metadata.get("warning")?;
+                        Some(format!("{}: {}", item.name, warning))
+                    })
+                })
+                .collect(),
+            top_items: selected
+                .iter()
+                .take(TOP_ITEMS)
+                .map(|item| (item.path.display().to_string(), item.size))
+                .collect(),
+            category_sizes,
+        }
+    }
+}
+
+pub fn render_confirm_modal(f: &mut Frame, data: &ConfirmModalData, theme: &Theme) {
+    let area = centered_rect(70, 80, f.area());
+    let path_width = (area.width as usize).saturating_sub(20).max(20);
+
+    let mut text = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("Delete ", Style::default().fg(Color::White)),
+            Span::styled("Delete ", Style::default().fg(theme.text)),
             Span::styled(
                 format!("{} items", data.selected_count),
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(" (", Style::default().fg(Color::White)),
+            Span::styled(" (", Style::default().fg(theme.text)),
             Span::styled(
                 format_size(data.total_size),
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled(")?", Style::default().fg(Color::White)),
+            Span::styled(")?", Style::default().fg(theme.text)),
         ]),
         Line::from(""),
+    ];
+
+    if !data.category_sizes.is_empty() {
+        text.push(Line::from(Span::styled(
+            "By category",
+            Style::default()
+                .fg(theme.subtle)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (name, count, size) in data.category_sizes.iter().take(MAX_CATEGORIES) {
+            text.push(Line::from(vec![
+                Span::styled(format!("{:<24}", name), Style::default().fg(theme.text)),
+                Span::styled(
+                    format!("{:>5} items ", count),
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled(
+                    format!("{:>10}", format_size(*size)),
+                    Style::default().fg(theme.accent),
+                ),
+            ]));
+        }
+        if data.category_sizes.len() > MAX_CATEGORIES {
+            text.push(Line::from(Span::styled(
+                format!(
+                    "... and {} more",
+                    data.category_sizes.len() - MAX_CATEGORIES
+                ),
+                Style::default().fg(theme.muted),
+            )));
+        }
+        text.push(Line::from(""));
+    }
+
+    if !data.top_items.is_empty() {
+        text.push(Line::from(Span::styled(
+            "Largest items",
+            Style::default()
+                .fg(theme.subtle)
+                .add_modifier(Modifier::BOLD),
+        )));
+        for (path, size) in &data.top_items {
+            text.push(Line::from(vec![
+                Span::styled(
+                    truncate_path_middle(path, path_width),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled(
+                    format!("  {}", format_size(*size)),
+                    Style::default().fg(theme.accent),
+                ),
+            ]));
+        }
+        text.push(Line::from(""));
+    }
+
+    text.push(Line::from(Span::styled(
+        "Items go to the Trash; what is already in the Trash is deleted for good.",
+        Style::default().fg(theme.warning),
+    )));
+    text.push(Line::from(""));
+
+    // Enabled login agents need an explicit 'y'; Enter alone is not enough.
+    let confirm_key = if data.warnings.is_empty() {
+        "[y/Enter]"
+    } else {
+        for warning in &data.warnings {
+            text.push(Line::from(Span::styled(
+                format!("⚠ {}", warning),
+                Style::default().fg(theme.warning),
+            )));
+        }
+        text.push(Line::from(""));
+        "[y]"
+    };
+
+    text.push(Line::from(vec![
+        Span::styled(confirm_key, Style::default().fg(theme.safe)),
+        Span::raw(" Confirm     "),
+        Span::styled("[n/Esc]", Style::default().fg(theme.danger)),
+        Span::raw(" Cancel"),
+    ]));
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title(" Confirm Clean ")
+                .borders(Borders::ALL),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+pub fn render_quit_modal(f: &mut Frame, selected_count: usize, theme: &Theme) {
+    let area = centered_rect(50, 35, f.area());
+
+    let text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("You have ", Style::default().fg(theme.text)),
+            Span::styled(
+                format!("{} items", selected_count),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" selected", Style::default().fg(theme.text)),
+        ]),
         Line::from(Span::styled(
-            "This action cannot be undone.",
-            Style::default().fg(Color::Red),
+            "Discard the selection and quit?",
+            Style::default().fg(theme.text),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("[y/Enter]", Style::default().fg(Color::Green)),
-            Span::raw(" Confirm     "),
-            Span::styled("[n/Esc]", Style::default().fg(Color::Red)),
-            Span::raw(" Cancel"),
+            Span::styled("[y/q] ", Style::default().fg(theme.danger)),
+            Span::raw("Quit  "),
+            Span::styled("[n/Esc] ", Style::default().fg(theme.safe)),
+            Span::raw("Keep reviewing"),
         ]),
+        Line::from(Span::styled(
+            "Ctrl-C quits without asking",
+            Style::default().fg(theme.muted),
+        )),
     ];
 
+    let paragraph = Paragraph::new(text)
+        .block(Block::default().title(" Quit ").borders(Borders::ALL))
+        .alignment(Alignment::Center);
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+pub fn render_empty_trash_modal(
+    f: &mut Frame,
+    locations: &[TrashLocation],
+    emptying: bool,
+    theme: &Theme,
+) {
+    let area = centered_rect(60, 40, f.area());
+
+    let mut text = vec![Line::from("")];
+    if emptying {
+        text.push(Line::from(Span::styled(
+            "Emptying the Trash...",
+            Style::default().fg(theme.accent),
+        )));
+    } else if locations.is_empty() {
+        text.push(Line::from(Span::styled(
+            "Trash is already empty",
+            Style::default().fg(theme.safe),
+        )));
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::styled("[Esc] ", Style::default().fg(theme.accent)),
+            Span::raw("Back"),
+        ]));
+    } else {
+        let total: u64 = locations.iter().map(|l| l.size).sum();
+        let entries: usize = locations.iter().map(|l| l.entries).sum();
+        text.push(Line::from(vec![
+            Span::styled("Permanently delete ", Style::default().fg(theme.text)),
+            Span::styled(
+                format!("{} items ({})", entries, format_size(total)),
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(" from:", Style::default().fg(theme.text)),
+        ]));
+        let path_width = (area.width as usize).saturating_sub(16);
+        for location in locations {
+            text.push(Line::from(Span::styled(
+                format!(
+                    "{}  ({})",
+                    truncate_path_middle(&location.path.display().to_string(), path_width),
+                    format_size(location.size)
+                ),
+                Style::default().fg(theme.muted),
+            )));
+        }
+        text.push(Line::from(""));
+        text.push(Line::from(vec![
+            Span::styled("[y] ", Style::default().fg(theme.danger)),
+            Span::raw("Empty Trash  "),
+            Span::styled("[n/Esc] ", Style::default().fg(theme.safe)),
+            Span::raw("Cancel"),
+        ]));
+    }
+
     let paragraph = Paragraph::new(text)
         .block(
             Block::default()
-                .title(" Confirm Clean ")
+                .title(" Empty Trash ")
                 .borders(Borders::ALL),
         )
         .alignment(Alignment::Center);
@@ -60,59 +296,131 @@ pub fn render_confirm_modal(f: &mut Frame, data: &ConfirmModalData) {
     f.render_widget(paragraph, area);
 }
 
-pub fn render_result_modal(f: &mut Frame, result: Option<&CleanResultDisplay>) {
+fn command_outcome_line<'a>(command: &'a CommandOutcome, theme: &Theme) -> Line<'a> {
+    let status = match command.exit_code {
+        Some(0) => "done".to_string(),
+        Some(code) => format!("exit {}", code),
+        None => "killed".to_string(),
+    };
+    let mut spans = vec![
+        Span::styled(
+            format!("{}: ", command.task),
+            Style::default().fg(theme.subtle),
+        ),
+        Span::styled(
+            status,
+            Style::default().fg(if command.succeeded() {
+                theme.safe
+            } else {
+                theme.danger
+            }),
+        ),
+    ];
+    if let Some(freed) = command.freed {
+        spans.push(Span::styled(
+            format!(", freed {}", format_size(freed)),
+            Style::default().fg(theme.accent),
+        ));
+    }
+    Line::from(spans)
+}
+
+pub fn render_result_modal(f: &mut Frame, result: Option<&CleanResultDisplay>, theme: &Theme) {
     let area = centered_rect(60, 40, f.area());
 
-    let text = if let Some(r) = result {
+    let text = if let Some(error) = result.and_then(|r| r.error.as_ref()) {
         vec![
             Line::from(""),
             Line::from(vec![Span::styled(
-                "Clean Complete!",
+                "Nothing was deleted",
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(theme.danger)
                     .add_modifier(Modifier::BOLD),
             )]),
             Line::from(""),
+            Line::from(Span::styled(
+                error.clone(),
+                Style::default().fg(theme.subtle),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Press Enter to continue",
+                Style::default().fg(theme.muted),
+            )),
+        ]
+    } else if let Some(r) = result {
+        let mut lines = vec![
+            Line::from(""),
+            Line::from(vec![Span::styled(
+                "Clean Complete!",
+                Style::default().fg(theme.safe).add_modifier(Modifier::BOLD),
+            )]),
+            Line::from(""),
             Line::from(vec![
-                Span::styled("Cleaned: ", Style::default().fg(Color::Gray)),
+                Span::styled("Cleaned: ", Style::default().fg(theme.subtle)),
                 Span::styled(
                     format!("{} items", r.success_count),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.safe),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Failed: ", Style::default().fg(Color::Gray)),
+                Span::styled("Failed: ", Style::default().fg(theme.subtle)),
                 Span::styled(
                     format!("{} items", r.failed_count),
                     Style::default().fg(if r.failed_count > 0 {
-                        Color::Red
+                        theme.danger
                     } else {
-                        Color::Green
+                        theme.safe
                     }),
                 ),
             ]),
+        ];
+        if r.moved_to_trash > 0 {
+            lines.push(Line::from(vec![
+                Span::styled("Moved to Trash: ", Style::default().fg(theme.subtle)),
+                Span::styled(
+                    format_size(r.moved_to_trash),
+                    Style::default().fg(theme.accent),
+                ),
+            ]));
+        }
+        lines.extend([
             Line::from(vec![
-                Span::styled("Freed: ", Style::default().fg(Color::Gray)),
+                Span::styled("Freed: ", Style::default().fg(theme.subtle)),
                 Span::styled(
                     format_size(r.total_freed),
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Duration: ", Style::default().fg(Color::Gray)),
+                Span::styled("Duration: ", Style::default().fg(theme.subtle)),
                 Span::styled(
                     format!("{:.2}s", r.duration.as_secs_f64()),
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme.subtle),
                 ),
             ]),
-            Line::from(""),
-            Line::from(Span::styled(
-                "Press Enter to continue",
-                Style::default().fg(Color::DarkGray),
-            )),
-        ]
+        ]);
+        if !r.commands.is_empty() {
+            lines.push(Line::from(""));
+        }
+        for command in &r.commands {
+            lines.push(command_outcome_line(command, theme));
+            // The first thing it printed is usually the useful part.
+            if let Some(output) = command.stdout.lines().chain(command.stderr.lines()).next() {
+                lines.push(Line::from(Span::styled(
+                    output.to_string(),
+                    Style::default().fg(theme.muted),
+                )));
+            }
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            "Press Enter to continue",
+            Style::default().fg(theme.muted),
+        )));
+        lines
     } else {
         vec![Line::from("No result")]
     };
@@ -125,97 +433,137 @@ pub fn render_result_modal(f: &mut Frame, result: Option<&CleanResultDisplay>) {
     f.render_widget(paragraph, area);
 }
 
-pub fn render_help_modal(f: &mut Frame) {
+pub fn render_help_modal(f: &mut Frame, theme: &Theme) {
     let area = centered_rect(65, 65, f.area());
 
     let help_text = vec![
         Line::from(vec![Span::styled(
             "CleanX Help",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Navigation",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  ↑/↓    ", Style::default().fg(Color::Cyan)),
+            Span::styled("  ↑/↓    ", Style::default().fg(theme.accent)),
             Span::raw("Navigate items"),
         ]),
         Line::from(vec![
-            Span::styled("  ←/→    ", Style::default().fg(Color::Cyan)),
+            Span::styled("  ←/→    ", Style::default().fg(theme.accent)),
             Span::raw("Switch category"),
         ]),
+        Line::from(vec![
+            Span::styled("  1-9,0  ", Style::default().fg(theme.accent)),
+            Span::raw("Jump to category 1-10"),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Selection",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  Space  ", Style::default().fg(Color::Cyan)),
+            Span::styled("  Space  ", Style::default().fg(theme.accent)),
             Span::raw("Toggle selection"),
         ]),
         Line::from(vec![
-            Span::styled("  a      ", Style::default().fg(Color::Cyan)),
+            Span::styled("  a      ", Style::default().fg(theme.accent)),
             Span::raw("Select all in category"),
         ]),
         Line::from(vec![
-            Span::styled("  n      ", Style::default().fg(Color::Cyan)),
+            Span::styled("  Ctrl-A ", Style::default().fg(theme.accent)),
+            Span::raw("Select all in every category"),
+        ]),
+        Line::from(vec![
+            Span::styled("  ⇧↑/⇧↓  ", Style::default().fg(theme.accent)),
+            Span::raw("Extend selection up/down"),
+        ]),
+        Line::from(vec![
+            Span::styled("  > / <  ", Style::default().fg(theme.accent)),
+            Span::raw("Select over / deselect under a size"),
+        ]),
+        Line::from(vec![
+            Span::styled("  e      ", Style::default().fg(theme.accent)),
+            Span::raw("Expand/collapse a folder to pick its entries"),
+        ]),
+        Line::from(vec![
+            Span::styled("  k      ", Style::default().fg(theme.accent)),
+            Span::raw("Keep another copy of a duplicate group"),
+        ]),
+        Line::from(vec![
+            Span::styled("  [ ]    ", Style::default().fg(theme.accent)),
+            Span::raw("Scroll the details pane up/down"),
+        ]),
+        Line::from(vec![
+            Span::styled("  n      ", Style::default().fg(theme.accent)),
             Span::raw("Deselect all"),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Actions",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  Enter  ", Style::default().fg(Color::Cyan)),
+            Span::styled("  Enter  ", Style::default().fg(theme.accent)),
             Span::raw("Clean selected"),
         ]),
         Line::from(vec![
-            Span::styled("  r      ", Style::default().fg(Color::Cyan)),
+            Span::styled("  r      ", Style::default().fg(theme.accent)),
             Span::raw("Rescan"),
         ]),
+        Line::from(vec![
+            Span::styled("  p      ", Style::default().fg(theme.accent)),
+            Span::raw("Pause/resume a running scan"),
+        ]),
+        Line::from(vec![
+            Span::styled("  T      ", Style::default().fg(theme.accent)),
+            Span::raw("Empty the Trash (all volumes)"),
+        ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Safety Levels",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  ● Safe     ", Style::default().fg(Color::Green)),
+            Span::styled("  ● Safe     ", Style::default().fg(theme.safe)),
             Span::raw("Can be deleted"),
         ]),
         Line::from(vec![
-            Span::styled("  ● Caution  ", Style::default().fg(Color::Yellow)),
+            Span::styled("  ● Caution  ", Style::default().fg(theme.warning)),
             Span::raw("May affect apps"),
         ]),
         Line::from(vec![
-            Span::styled("  ● Protected", Style::default().fg(Color::Red)),
+            Span::styled("  ● Protected", Style::default().fg(theme.danger)),
             Span::raw("Cannot delete"),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  ?      ", Style::default().fg(Color::Cyan)),
+            Span::styled("  ?      ", Style::default().fg(theme.accent)),
             Span::raw("Show this help"),
         ]),
         Line::from(vec![
-            Span::styled("  q      ", Style::default().fg(Color::Cyan)),
-            Span::raw("Quit"),
+            Span::styled("  q      ", Style::default().fg(theme.accent)),
+            Span::raw("Quit (asks first while items are selected)"),
+        ]),
+        Line::from(vec![
+            Span::styled("  Ctrl-C ", Style::default().fg(theme.accent)),
+            Span::raw("Quit immediately"),
         ]),
         Line::from(""),
         Line::from(Span::styled(
             "Press q, Esc, or ? to close",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )),
     ];
 
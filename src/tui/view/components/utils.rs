@@ -1,4 +1,7 @@
+use crate::tui::view::theme::Theme;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Style;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::default()
@@ -19,3 +22,89 @@ pub fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+pub fn size_style(bytes: u64, theme: &Theme) -> Style {
+    if bytes >= theme.size_large_bytes {
+        Style::default().fg(theme.danger)
+    } else if bytes >= theme.size_medium_bytes {
+        Style::default().fg(theme.warning)
+    } else {
+        Style::default().fg(theme.muted)
+    }
+}
+
+pub fn size_bar(size: u64, max: u64, width: usize) -> String {
+    let filled = if max > 0 {
+        ((size as f64 / max as f64) * width as f64) as usize
+    } else {
+        0
+    }
+    .min(width);
+    "█".repeat(filled) + &"░".repeat(width - filled)
+}
+
+// Pads to exactly `width` columns, cutting long names with "…" so the size
+// column after them stays lined up.
+pub fn fit_width(name: &str, width: usize) -> String {
+    let mut fitted = String::new();
+    let mut used = 0;
+    if name.width() <= width {
+        fitted.push_str(name);
+        used = name.width();
+    } else {
+        for c in name.chars() {
+            let w = c.width().unwrap_or(0);
+            if used + w + 1 > width {
+                break;
+            }
+            fitted.push(c);
+            used += w;
+        }
+        if width > 0 {
+            fitted.push('…');
+            used += 1;
+        }
+    }
+    // A wide character that didn't fit can leave a column over.
+    fitted.extend(std::iter::repeat_n(' ', width.saturating_sub(used)));
+    fitted
+}
+
+pub fn truncate_path_middle(path: &str, max_len: usize) -> String {
+    if path.len() <= max_len {
+        return path.to_string();
+    }
+
+    let segments: Vec<&str> = path.split(std::path::MAIN_SEPARATOR).collect();
+
+    if segments.len() <= 4 {
+        return path.to_string();
+    }
+
+    let head_count = 2;
+    let tail_count = 2;
+
+    let head: String = segments[..head_count].join(std::path::MAIN_SEPARATOR_STR);
+    let tail: String = segments[segments.len() - tail_count..].join(std::path::MAIN_SEPARATOR_STR);
+
+    format!(
+        "{}{}...{}{}",
+        head,
+        std::path::MAIN_SEPARATOR,
+        std::path::MAIN_SEPARATOR,
+        tail
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_width_counts_terminal_columns() {
+        assert_eq!(fit_width("Safari", 8), "Safari  ");
+        assert_eq!(fit_width("카카오톡", 8), "카카오톡");
+        assert_eq!(fit_width("카카오톡 메신저", 8), "카카오… ");
+        assert_eq!(fit_width("Visual Studio Code", 8), "Visual …");
+    }
+}
@@ -1,7 +1,10 @@
 pub mod components;
 pub mod screens;
+pub mod theme;
 
 pub use screens::{
-    render_app_list, render_category_select, render_loading, render_review, render_space_lens,
-    render_uninstall_result, render_uninstall_review, CategorySelectData,
+    render_app_list, render_category_select, render_dashboard, render_loading, render_review,
+    render_space_lens, render_uninstall_confirm_modal, render_uninstall_result,
+    render_uninstall_review, CategorySelectData,
 };
+pub use theme::Theme;
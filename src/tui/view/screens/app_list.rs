@@ -1,14 +1,21 @@
-use crate::tui::state::AppsModeState;
+use crate::tui::state::{AppListSort, AppsModeState};
+use crate::tui::view::components::fit_width;
 use crate::tui::view::components::footer::render_app_list_footer;
+use crate::tui::view::theme::Theme;
 use crate::utils::format_size;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::ListState;
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 use ratatui::Frame;
 
-pub fn render_app_list(f: &mut Frame, list_state: &mut ListState, apps_mode: &AppsModeState) {
+pub fn render_app_list(
+    f: &mut Frame,
+    list_state: &mut ListState,
+    apps_mode: &AppsModeState,
+    theme: &Theme,
+) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -22,23 +29,39 @@ pub fn render_app_list(f: &mut Frame, list_state: &mut ListState, apps_mode: &Ap
         Span::styled(
             " CleanX ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("App Uninstaller"),
         Span::raw("   "),
         Span::styled(
-            format!("{} apps found", apps_mode.apps.len()),
-            Style::default().fg(Color::Green),
+            match apps_mode.installed_within_days {
+                Some(days) => format!(
+                    "{} of {} apps installed in the last {} days",
+                    apps_mode.view.len(),
+                    apps_mode.apps.len(),
+                    days
+                ),
+                None => format!("{} apps found", apps_mode.apps.len()),
+            },
+            Style::default().fg(theme.safe),
+        ),
+        Span::styled(
+            match apps_mode.sort {
+                AppListSort::Name => "   [Name]",
+                AppListSort::Installed => "   [Newest installs]",
+            },
+            Style::default().fg(theme.muted),
         ),
     ]))
     .block(Block::default().borders(Borders::BOTTOM));
     f.render_widget(title, chunks[0]);
 
     let mut items = Vec::new();
-    for (i, app) in apps_mode.apps.iter().enumerate() {
+    for &i in &apps_mode.view {
+        let app = &apps_mode.apps[i];
         let name = app.name();
-        let padded_name = format!("{:<30}", name);
+        let padded_name = fit_width(&name, 30);
 
         let size_str = if let Some(&size) = apps_mode.app_sizes.get(&i) {
             format_size(size)
@@ -46,9 +69,27 @@ pub fn render_app_list(f: &mut Frame, list_state: &mut ListState, apps_mode: &Ap
             "...".to_string()
         };
 
+        let duplicate_tag = if apps_mode.is_older_copy(&app.path) {
+            "  [older copy]"
+        } else if apps_mode.duplicate_paths.contains(&app.path) {
+            "  [duplicate]"
+        } else {
+            ""
+        };
+
+        let installed = app
+            .installed()
+            .map(|t| t.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "-".to_string());
+
         items.push(ListItem::new(Line::from(vec![
             Span::raw(padded_name),
-            Span::styled(size_str, Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                format!("{:<12}", installed),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(size_str, Style::default().fg(theme.muted)),
+            Span::styled(duplicate_tag, Style::default().fg(theme.warning)),
         ])));
     }
 
@@ -60,11 +101,11 @@ pub fn render_app_list(f: &mut Frame, list_state: &mut ListState, apps_mode: &Ap
         )
         .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
     f.render_stateful_widget(list, chunks[1], list_state);
 
-    render_app_list_footer(f, chunks[2]);
+    render_app_list_footer(f, chunks[2], theme);
 }
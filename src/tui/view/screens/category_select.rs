@@ -1,9 +1,10 @@
 use crate::plugin::registry::ScanReport;
 use crate::tui::state::ScannerInfo;
 use crate::tui::view::components::footer::render_category_select_footer;
+use crate::tui::view::theme::Theme;
 use crate::utils::format_size;
 use ratatui::layout::{Constraint, Direction, Layout};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::ListState;
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
@@ -16,7 +17,7 @@ pub struct CategorySelectData<'a> {
     pub report: Option<&'a ScanReport>,
 }
 
-pub fn render_category_select(f: &mut Frame, data: &mut CategorySelectData) {
+pub fn render_category_select(f: &mut Frame, data: &mut CategorySelectData, theme: &Theme) {
     let area = f.area();
 
     let chunks = Layout::default()
@@ -36,7 +37,7 @@ pub fn render_category_select(f: &mut Frame, data: &mut CategorySelectData) {
         Span::styled(
             " CleanX ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("Select Categories to Scan"),
@@ -47,7 +48,7 @@ pub fn render_category_select(f: &mut Frame, data: &mut CategorySelectData) {
                     format_size(cached_size),
                     cached_items
                 ),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )
         } else {
             Span::raw("")
@@ -73,28 +74,28 @@ pub fn render_category_select(f: &mut Frame, data: &mut CategorySelectData) {
                 .and_then(|r| r.categories.iter().find(|c| c.scanner_id == scanner.id));
 
             let style = if scanner.enabled {
-                Style::default().fg(Color::Green)
+                Style::default().fg(theme.safe)
             } else {
-                Style::default().fg(Color::DarkGray)
+                Style::default().fg(theme.muted)
             };
 
             let scan_indicator = if let Some(cat) = scanned_cat {
                 Span::styled(
                     format!(" ({})", format_size(cat.total_size())),
-                    Style::default().fg(Color::Yellow),
+                    Style::default().fg(theme.warning),
                 )
             } else {
                 Span::raw("")
             };
 
             let cached_mark = if is_scanned {
-                Span::styled(" ✓", Style::default().fg(Color::Cyan))
+                Span::styled(" ✓", Style::default().fg(theme.accent))
             } else {
                 Span::raw("")
             };
 
             ListItem::new(Line::from(vec![
-                Span::styled(check, Style::default().fg(Color::Cyan)),
+                Span::styled(check, Style::default().fg(theme.accent)),
                 Span::raw(" "),
                 Span::styled(&scanner.name, style),
                 scan_indicator,
@@ -115,7 +116,7 @@ pub fn render_category_select(f: &mut Frame, data: &mut CategorySelectData) {
         )
         .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
@@ -125,5 +126,5 @@ pub fn render_category_select(f: &mut Frame, data: &mut CategorySelectData) {
         .report
         .map(|r| !r.categories.is_empty())
         .unwrap_or(false);
-    render_category_select_footer(f, chunks[2], has_cached && has_viewable, cached_size);
+    render_category_select_footer(f, chunks[2], has_cached && has_viewable, cached_size, theme);
 }
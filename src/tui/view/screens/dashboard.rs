@@ -0,0 +1,172 @@
+use crate::plugin::registry::ScanReport;
+use crate::plugin::SafetyLevel;
+use crate::tui::state::DashboardState;
+use crate::tui::view::components::footer::render_dashboard_footer;
+use crate::tui::view::components::utils::{size_bar, size_style};
+use crate::tui::view::theme::Theme;
+use crate::utils::{format_relative_age, format_size};
+use chrono::Utc;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+const BAR_WIDTH: usize = 30;
+
+pub fn render_dashboard(
+    f: &mut Frame,
+    report: Option<&ScanReport>,
+    dashboard: &DashboardState,
+    theme: &Theme,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(4),
+            Constraint::Min(6),
+            Constraint::Length(6),
+            Constraint::Length(3),
+        ])
+        .split(f.area());
+
+    let title = Paragraph::new(Line::from(vec![
+        Span::styled(
+            " CleanX ",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw("Dashboard"),
+    ]))
+    .block(Block::default().borders(Borders::BOTTOM));
+    f.render_widget(title, chunks[0]);
+
+    let total_size = report.map(|r| r.total_size).unwrap_or(0);
+    let total_items = report.map(|r| r.total_items).unwrap_or(0);
+
+    let free_text = match dashboard.disk {
+        Some(disk) => format!(
+            "{} free of {}, {}",
+            format_size(disk.free_bytes),
+            format_size(disk.total_bytes),
+            disk.purgeable_bytes
+                .map(|p| format!("about {} purgeable", format_size(p)))
+                .unwrap_or_else(|| "purgeable unknown".to_string())
+        ),
+        None => "unknown".to_string(),
+    };
+    let last_clean_text = match dashboard.last_clean {
+        Some(ts) => format!(
+            "{} ({} ago)",
+            ts.format("%Y-%m-%d %H:%M"),
+            format_relative_age(ts, Utc::now())
+        ),
+        None => "never".to_string(),
+    };
+
+    let summary = Paragraph::new(vec![
+        Line::from(vec![
+            Span::raw(" Reclaimable: "),
+            Span::styled(
+                format_size(total_size),
+                size_style(total_size, theme).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  ({} items)", total_items),
+                Style::default().fg(theme.muted),
+            ),
+        ]),
+        Line::from(vec![
+            Span::raw(" Disk: "),
+            Span::styled(free_text, Style::default().fg(theme.safe)),
+        ]),
+        Line::from(vec![
+            Span::raw(" Last clean: "),
+            Span::styled(last_clean_text, Style::default().fg(theme.warning)),
+        ]),
+    ]);
+    f.render_widget(summary, chunks[1]);
+
+    let mut categories: Vec<(&str, u64)> = report
+        .map(|r| {
+            r.categories
+                .iter()
+                .map(|c| (c.name.as_str(), c.total_size()))
+                .filter(|(_, size)| *size > 0)
+                .collect()
+        })
+        .unwrap_or_default();
+    categories.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+    let max_size = categories.first().map(|(_, s)| *s).unwrap_or(0);
+
+    let category_lines: Vec<Line> = if categories.is_empty() {
+        vec![Line::from(Span::styled(
+            " Nothing to clean",
+            Style::default().fg(theme.muted),
+        ))]
+    } else {
+        categories
+            .iter()
+            .map(|(name, size)| {
+                Line::from(vec![
+                    Span::raw(format!(" {:<22} ", name)),
+                    Span::styled(
+                        size_bar(*size, max_size, BAR_WIDTH),
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::styled(
+                        format!(" {:>10}", format_size(*size)),
+                        size_style(*size, theme),
+                    ),
+                ])
+            })
+            .collect()
+    };
+    let category_block = Paragraph::new(category_lines).block(
+        Block::default()
+            .borders(Borders::TOP)
+            .title(" By category "),
+    );
+    f.render_widget(category_block, chunks[2]);
+
+    let mut by_safety = [0u64; 3];
+    for item in report
+        .iter()
+        .flat_map(|r| r.categories.iter())
+        .flat_map(|c| c.items.iter())
+    {
+        let idx = match item.safety_level {
+            SafetyLevel::Safe => 0,
+            SafetyLevel::Caution => 1,
+            SafetyLevel::Protected => 2,
+        };
+        by_safety[idx] += item.size;
+    }
+    let safety_max = by_safety.iter().copied().max().unwrap_or(0);
+
+    let safety_lines: Vec<Line> = [
+        ("Safe", theme.safe),
+        ("Caution", theme.warning),
+        ("Protected", theme.danger),
+    ]
+    .iter()
+    .zip(by_safety)
+    .map(|((label, color), size)| {
+        Line::from(vec![
+            Span::styled(format!(" {:<22} ", label), Style::default().fg(*color)),
+            Span::styled(
+                size_bar(size, safety_max, BAR_WIDTH),
+                Style::default().fg(*color),
+            ),
+            Span::raw(format!(" {:>10}", format_size(size))),
+        ])
+    })
+    .collect();
+    let safety_block = Paragraph::new(safety_lines)
+        .block(Block::default().borders(Borders::TOP).title(" By safety "));
+    f.render_widget(safety_block, chunks[3]);
+
+    render_dashboard_footer(f, chunks[4], theme);
+}
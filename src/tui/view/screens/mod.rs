@@ -1,5 +1,6 @@
 mod app_list;
 mod category_select;
+mod dashboard;
 mod loading;
 mod review;
 mod space_lens;
@@ -7,7 +8,10 @@ mod uninstall;
 
 pub use app_list::render_app_list;
 pub use category_select::{render_category_select, CategorySelectData};
+pub use dashboard::render_dashboard;
 pub use loading::render_loading;
 pub use review::render_review;
 pub use space_lens::render_space_lens;
-pub use uninstall::{render_uninstall_result, render_uninstall_review};
+pub use uninstall::{
+    render_uninstall_confirm_modal, render_uninstall_result, render_uninstall_review,
+};
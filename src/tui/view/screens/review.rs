@@ -1,27 +1,39 @@
 use crate::plugin::registry::{CategoryScanResult, ScanReport};
 use crate::plugin::{SafetyLevel, ScanResult};
-use crate::tui::state::{ScanProgress, SortMode};
-use crate::tui::view::components::footer::render_review_footer;
+use crate::scanner::login_launch_warning;
+use crate::tui::logic::{is_expandable, is_partially_selected, selected_in, visible_rows};
+use crate::tui::state::{ScanProgress, SizePrompt, SortMode};
+use crate::tui::view::components::footer::{render_review_footer, render_size_prompt};
+use crate::tui::view::components::{size_style, truncate_path_middle};
+use crate::tui::view::theme::Theme;
 use crate::utils::{format_number, format_size};
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::layout::{Alignment, Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::ListState;
-use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap};
 use ratatui::Frame;
 use std::collections::HashSet;
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_review(
     f: &mut Frame,
     list_state: &mut ListState,
     report: &mut Option<ScanReport>,
     selected_items: &HashSet<String>,
+    expanded_items: &HashSet<String>,
     selected_category: &mut usize,
+    detail_scroll: &mut u16,
+    selection_delta: Option<i64>,
+    size_prompt: Option<&SizePrompt>,
+    selection_note: Option<&str>,
     sort_mode: SortMode,
     scan_progress: &ScanProgress,
     is_scanning: bool,
+    no_scanners: bool,
+    theme: &Theme,
 ) {
-    let header_height = if is_scanning { 4 } else { 3 };
+    let header_height = if is_scanning { 5 } else { 3 };
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -40,18 +52,30 @@ pub fn render_review(
         scan_progress,
         is_scanning,
         sort_mode,
+        theme,
     );
-    render_main(
-        f,
-        chunks[1],
-        list_state,
-        report,
-        selected_items,
-        selected_category,
-    );
-    render_review_footer(f, chunks[2]);
+    if report.is_none() && !is_scanning && no_scanners {
+        render_no_scanners(f, chunks[1], theme);
+    } else {
+        render_main(
+            f,
+            chunks[1],
+            list_state,
+            report,
+            selected_items,
+            expanded_items,
+            selected_category,
+            detail_scroll,
+            theme,
+        );
+    }
+    match size_prompt {
+        Some(prompt) => render_size_prompt(f, chunks[2], prompt, theme),
+        None => render_review_footer(f, chunks[2], selection_delta, selection_note, theme),
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_header(
     f: &mut Frame,
     area: Rect,
@@ -60,14 +84,14 @@ pub fn render_header(
     scan_progress: &ScanProgress,
     is_scanning: bool,
     sort_mode: SortMode,
+    theme: &Theme,
 ) {
     let total_size: u64 = report.as_ref().map(|r| r.total_size).unwrap_or(0);
     let selected_size: u64 = report
         .as_ref()
         .iter()
         .flat_map(|r| r.categories.iter())
-        .flat_map(|c| c.items.iter())
-        .filter(|item| selected_items.contains(&item.id))
+        .flat_map(|c| selected_in(&c.items, selected_items))
         .map(|i| i.size)
         .sum();
 
@@ -77,22 +101,45 @@ pub fn render_header(
         let active = scan_progress.active_scanners;
         let current = &scan_progress.current_scanner;
 
-        format!(" [{}/{}c {}/4t|{}]", done, total, active, current)
+        if scan_progress.paused {
+            format!(" [{}/{}c PAUSED - p to resume]", done, total)
+        } else {
+            format!(" [{}/{}c {}/4t|{}]", done, total, active, current)
+        }
+    } else {
+        String::new()
+    };
+    let timeout_indicator = if scan_progress.timed_out.is_empty() {
+        String::new()
     } else {
+        format!(" timed out: {}", scan_progress.timed_out.join(", "))
+    };
+    let unreadable_indicator = if scan_progress.unreadable.is_empty() {
         String::new()
+    } else {
+        let names: Vec<String> = scan_progress
+            .unreadable
+            .iter()
+            .map(|(name, count)| format!("{} {}", name, format_number(*count as u64)))
+            .collect();
+        format!(" unreadable: {} (grant Full Disk Access)", names.join(", "))
     };
 
-    if is_scanning && area.height >= 4 {
+    if is_scanning && area.height >= 5 {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
-            .constraints([Constraint::Length(2), Constraint::Length(2)])
+            .constraints([
+                Constraint::Length(2),
+                Constraint::Length(1),
+                Constraint::Length(2),
+            ])
             .split(area);
 
         let header = Paragraph::new(Line::from(vec![
             Span::styled(
                 " CleanX ",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("System Cleaner"),
@@ -103,26 +150,51 @@ pub fn render_header(
                     format_size(total_size),
                     format_size(selected_size)
                 ),
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.safe),
             ),
-            Span::styled(scan_indicator, Style::default().fg(Color::Yellow)),
+            Span::styled(scan_indicator, Style::default().fg(theme.warning)),
+            Span::styled(timeout_indicator, Style::default().fg(theme.danger)),
+            Span::styled(unreadable_indicator, Style::default().fg(theme.warning)),
         ]));
         f.render_widget(header, chunks[0]);
 
+        render_scan_gauge(f, chunks[1], scan_progress, theme);
+
         let current_path = scan_progress.current_path.as_deref().unwrap_or("");
         let truncated = truncate_path_middle(current_path, 80);
+        let phase = scan_progress
+            .phase
+            .as_ref()
+            .map(|p| {
+                format!(
+                    "{} {}/{} {}  ",
+                    p.label,
+                    format_number(p.done as u64),
+                    format_number(p.total as u64),
+                    p.unit
+                )
+            })
+            .unwrap_or_default();
         let scan_line = Paragraph::new(Line::from(vec![
-            Span::styled(" Scanning: ", Style::default().fg(Color::DarkGray)),
-            Span::styled(truncated, Style::default().fg(Color::Gray)),
+            Span::styled(
+                if scan_progress.paused {
+                    " Paused at: "
+                } else {
+                    " Scanning: "
+                },
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(phase, Style::default().fg(theme.accent)),
+            Span::styled(truncated, Style::default().fg(theme.subtle)),
         ]))
         .block(Block::default().borders(Borders::BOTTOM));
-        f.render_widget(scan_line, chunks[1]);
+        f.render_widget(scan_line, chunks[2]);
     } else {
         let header = Paragraph::new(Line::from(vec![
             Span::styled(
                 " CleanX ",
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
             Span::raw("System Cleaner"),
@@ -133,27 +205,73 @@ pub fn render_header(
                     format_size(total_size),
                     format_size(selected_size)
                 ),
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.safe),
             ),
             Span::raw("   "),
             Span::styled(
                 format!("[{}]", sort_mode.label()),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             ),
-            Span::styled(scan_indicator, Style::default().fg(Color::Yellow)),
+            Span::styled(scan_indicator, Style::default().fg(theme.warning)),
+            Span::styled(timeout_indicator, Style::default().fg(theme.danger)),
+            Span::styled(unreadable_indicator, Style::default().fg(theme.warning)),
         ]))
         .block(Block::default().borders(Borders::BOTTOM));
         f.render_widget(header, area);
     }
 }
 
+// Nothing to scan and nothing left from before: say why instead of a blank pane.
+fn render_no_scanners(f: &mut Frame, area: Rect, theme: &Theme) {
+    let message = Paragraph::new(vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            "No categories selected",
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            "Press Tab to choose scanners, then r to scan",
+            Style::default().fg(theme.muted),
+        )),
+    ])
+    .alignment(Alignment::Center);
+    f.render_widget(message, area);
+}
+
+fn render_scan_gauge(f: &mut Frame, area: Rect, scan_progress: &ScanProgress, theme: &Theme) {
+    let done = scan_progress.scanners_done;
+    let total = scan_progress.total_scanners;
+    let ratio = if total > 0 {
+        (done as f64 / total as f64).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    let gauge = Gauge::default()
+        .gauge_style(Style::default().fg(theme.accent).bg(theme.muted))
+        .ratio(ratio)
+        .label(format!(
+            "{}/{} scanners ({:.0}%)",
+            done,
+            total,
+            ratio * 100.0
+        ));
+    f.render_widget(gauge, area);
+}
+
+#[allow(clippy::too_many_arguments)]
 fn render_main(
     f: &mut Frame,
     area: Rect,
     list_state: &mut ListState,
     report: &mut Option<ScanReport>,
     selected_items: &HashSet<String>,
+    expanded_items: &HashSet<String>,
     selected_category: &mut usize,
+    detail_scroll: &mut u16,
+    theme: &Theme,
 ) {
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
@@ -166,7 +284,9 @@ fn render_main(
         list_state,
         report,
         selected_items,
+        expanded_items,
         selected_category,
+        theme,
     );
     render_detail(
         f,
@@ -174,38 +294,45 @@ fn render_main(
         list_state,
         report,
         selected_items,
+        expanded_items,
         selected_category,
+        detail_scroll,
     );
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_sidebar(
     f: &mut Frame,
     area: Rect,
     list_state: &mut ListState,
     report: &Option<ScanReport>,
     selected_items: &HashSet<String>,
+    expanded_items: &HashSet<String>,
     selected_category: &usize,
+    theme: &Theme,
 ) {
     let mut items = Vec::new();
 
     if let Some(ref report) = report {
         for (i, category) in report.categories.iter().enumerate() {
             let is_selected = i == *selected_category;
-            let selected_count = category
-                .items
-                .iter()
-                .filter(|item| selected_items.contains(&item.id))
-                .count();
+            let selected_count = selected_in(&category.items, selected_items).len();
 
             let style = if is_selected {
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
 
             let prefix = if is_selected { "> " } else { "  " };
+            // The digit that jumps here; only the first ten categories get one.
+            let number = match i {
+                0..=8 => format!("{} ", i + 1),
+                9 => "0 ".to_string(),
+                _ => "  ".to_string(),
+            };
             let count_indicator = if selected_count > 0 {
                 format!(" [{}]", selected_count)
             } else {
@@ -214,52 +341,71 @@ fn render_sidebar(
 
             items.push(ListItem::new(Line::from(vec![
                 Span::styled(prefix, style),
+                Span::styled(number, Style::default().fg(theme.muted)),
                 Span::styled(&category.name, style),
                 Span::raw(count_indicator),
                 Span::raw(" "),
                 Span::styled(
                     format!("({})", format_size(category.total_size())),
-                    Style::default().fg(Color::DarkGray),
+                    size_style(category.total_size(), theme),
                 ),
             ])));
 
             if is_selected {
-                for (idx, item) in category.items.iter().enumerate() {
-                    let is_item_selected = selected_items.contains(&item.id);
+                let rows = visible_rows(&category.items, expanded_items);
+                for (idx, row) in rows.iter().enumerate() {
+                    let item = row.item;
+                    let is_item_selected = selected_items.contains(&item.id)
+                        || row.parent.is_some_and(|p| selected_items.contains(&p.id));
                     let is_focused = list_state.selected() == Some(idx);
-                    let check = if is_item_selected { "[x]" } else { "[ ]" };
+                    let check = if is_item_selected {
+                        "[x]"
+                    } else if is_partially_selected(item, selected_items) {
+                        "[~]"
+                    } else {
+                        "[ ]"
+                    };
+                    // 펼칠 수 있는 항목은 ▸/▾, 자식 행은 한 단계 더 들여쓴다.
+                    let marker = if row.parent.is_some() {
+                        "      "
+                    } else if !is_expandable(item) {
+                        "    "
+                    } else if expanded_items.contains(&item.id) {
+                        "  ▾ "
+                    } else {
+                        "  ▸ "
+                    };
 
                     let safety_color = match item.safety_level {
-                        SafetyLevel::Safe => Color::Green,
-                        SafetyLevel::Caution => Color::Yellow,
-                        SafetyLevel::Protected => Color::Red,
+                        SafetyLevel::Safe => theme.safe,
+                        SafetyLevel::Caution => theme.warning,
+                        SafetyLevel::Protected => theme.danger,
                     };
 
                     let name_style = if is_focused {
                         Style::default()
-                            .fg(Color::Yellow)
+                            .fg(theme.warning)
                             .add_modifier(Modifier::BOLD)
                     } else if is_item_selected {
-                        Style::default().fg(Color::White)
+                        Style::default().fg(theme.text)
                     } else {
                         Style::default()
                     };
 
                     items.push(ListItem::new(Line::from(vec![
-                        Span::raw("    "),
+                        Span::raw(marker),
                         Span::styled(check, Style::default().fg(safety_color)),
                         Span::raw(" "),
                         Span::styled(
                             item.path
                                 .file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("?"),
+                                .map_or("?".into(), |n| n.to_string_lossy()),
                             name_style,
                         ),
                         Span::raw(" "),
                         Span::styled(
                             format!("({})", format_size(item.size)),
-                            Style::default().fg(Color::DarkGray),
+                            size_style(item.size, theme),
                         ),
                     ])));
                 }
@@ -282,19 +428,23 @@ fn render_sidebar(
     f.render_stateful_widget(list, area, &mut temp_state);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_detail(
     f: &mut Frame,
     area: Rect,
     list_state: &mut ListState,
     report: &Option<ScanReport>,
     selected_items: &HashSet<String>,
+    expanded_items: &HashSet<String>,
     selected_category: &usize,
+    detail_scroll: &mut u16,
 ) {
     let detail_text = if let Some(ref report) = report {
         if let Some(category) = report.categories.get(*selected_category) {
             if let Some(idx) = list_state.selected() {
-                if let Some(item) = category.items.get(idx) {
-                    format_item_detail(item)
+                let rows = visible_rows(&category.items, expanded_items);
+                if let Some(row) = rows.get(idx) {
+                    format_item_detail(row.item)
                 } else {
                     format_category_detail(category, selected_items)
                 }
@@ -308,19 +458,45 @@ fn render_detail(
         "No data".to_string()
     };
 
-    let detail = Paragraph::new(detail_text)
-        .block(Block::default().borders(Borders::NONE).title(" Details "))
-        .wrap(Wrap { trim: true });
+    // Stop once the last wrapped row reaches the top, so `[` always scrolls
+    // back right away however often `]` was pressed.
+    let detail = Paragraph::new(detail_text).wrap(Wrap { trim: true });
+    let last_row = detail.line_count(area.width).saturating_sub(1);
+    *detail_scroll = (*detail_scroll).min(u16::try_from(last_row).unwrap_or(u16::MAX));
+    let title = if *detail_scroll > 0 {
+        format!(" Details (line {}, [ ] to scroll) ", *detail_scroll + 1)
+    } else {
+        " Details ".to_string()
+    };
+
+    let detail = detail
+        .block(Block::default().borders(Borders::NONE).title(title))
+        .scroll((*detail_scroll, 0));
 
     f.render_widget(detail, area);
 }
 
+// Metadata already rendered above (or only used internally) and left out of
+// the trailing "Metadata" list.
+const DETAIL_SHOWN_KEYS: [&str; 9] = [
+    "scanner_id",
+    "explanation",
+    "safety_reason",
+    "keep_path",
+    "member_size",
+    "member_hash",
+    "original_path",
+    "duplicate_paths",
+    "group_id",
+];
+
 fn format_item_detail(item: &ScanResult) -> String {
-    let (safety_str, safety_desc) = match item.safety_level {
-        SafetyLevel::Safe => ("Safe", "Can be safely executed"),
-        SafetyLevel::Caution => ("Caution", "May affect system behavior"),
-        SafetyLevel::Protected => ("Protected", "Cannot be executed"),
+    let safety_str = match item.safety_level {
+        SafetyLevel::Safe => "Safe",
+        SafetyLevel::Caution => "Caution",
+        SafetyLevel::Protected => "Protected",
     };
+    let safety_desc = item.safety_reason();
 
     if item.metadata.get("scanner_id").map(|s| s.as_str()) == Some("maintenance") {
         let description = item
@@ -335,7 +511,7 @@ fn format_item_detail(item: &ScanResult) -> String {
             .map(|s| s == "true")
             .unwrap_or(false);
 
-        return format!(
+        let mut detail = format!(
             "Task:\n  {}\n\nDescription:\n  {}\n\nCommand:\n  {}\n\nRequires Sudo:\n  {}\n\nSafety Level:\n  {}\n  ({})",
             item.name,
             description,
@@ -344,9 +520,16 @@ fn format_item_detail(item: &ScanResult) -> String {
             safety_str,
             safety_desc
         );
+        if let Some(size) = item.metadata.get("index_size") {
+            detail.push_str(&format!("\n\nCurrent Index:\n  {}", size));
+        }
+        if item.metadata.get("available").map(|s| s.as_str()) == Some("false") {
+            detail.push_str("\n\nUnavailable:\n  Command not found on this Mac; can't be selected");
+        }
+        return detail;
     }
 
-    format!(
+    let mut detail = format!(
         "Path:\n  {}\n\nSize:\n  {}\n\nFiles:\n  {}\n\nLast Accessed:\n  {}\n\nLast Modified:\n  {}\n\nSafety Level:\n  {}\n  ({})",
         item.path.display(),
         format_size(item.size),
@@ -359,59 +542,85 @@ fn format_item_detail(item: &ScanResult) -> String {
             .unwrap_or_else(|| "Unknown".to_string()),
         safety_str,
         safety_desc
-    )
+    );
+
+    if let Some(explanation) = item.explanation() {
+        detail.push_str(&format!("\n\nWhy:\n  {}", explanation));
+    }
+    if let Some(warning) =
+        login_launch_warning(item).or_else(|| item.metadata.get("warning").cloned())
+    {
+        detail.push_str(&format!("\n\nWarning:\n  {}", warning));
+    }
+    if !item.children.is_empty() {
+        detail.push_str(&format!(
+            "\n\nContents:\n  {} largest entries (e to expand and pick)",
+            item.children.len()
+        ));
+    } else if is_expandable(item) {
+        detail.push_str("\n\nContents:\n  e to list the largest entries and pick");
+    }
+    if item.metadata.contains_key("keep_path") {
+        let size = item
+            .metadata
+            .get("member_size")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
+        detail.push_str("\n\nCopies (oldest first, k to keep another):");
+        for member in &item.members {
+            let mark = if *member == item.path {
+                "keep  "
+            } else {
+                "remove"
+            };
+            detail.push_str(&format!(
+                "\n  {} {}  {}",
+                mark,
+                format_size(size),
+                member.display()
+            ));
+        }
+    }
+
+    let mut extra: Vec<_> = item
+        .metadata
+        .iter()
+        .filter(|(key, _)| !DETAIL_SHOWN_KEYS.contains(&key.as_str()))
+        .collect();
+    if !extra.is_empty() {
+        extra.sort();
+        detail.push_str("\n\nMetadata:");
+        for (key, value) in extra {
+            detail.push_str(&format!("\n  {}: {}", key, value));
+        }
+    }
+
+    detail
 }
 
 fn format_category_detail(
     category: &CategoryScanResult,
     selected_items: &HashSet<String>,
 ) -> String {
-    let selected_count = category
-        .items
-        .iter()
-        .filter(|item| selected_items.contains(&item.id))
-        .count();
-
-    let selected_size: u64 = category
-        .items
-        .iter()
-        .filter(|item| selected_items.contains(&item.id))
-        .map(|i| i.size)
-        .sum();
+    let selected = selected_in(&category.items, selected_items);
+    let selected_count = selected.len();
+    let selected_size: u64 = selected.iter().map(|i| i.size).sum();
+    let items = if category.omitted > 0 {
+        format!(
+            "top {} of {}",
+            category.items.len(),
+            category.items.len() + category.omitted
+        )
+    } else {
+        category.items.len().to_string()
+    };
 
     format!(
         "Category:\n  {}\n\nTotal Size:\n  {}\n\nItems:\n  {}\n\nSelected:\n  {} items ({})",
         category.name,
         format_size(category.total_size()),
-        category.items.len(),
+        items,
         selected_count,
         format_size(selected_size)
     )
 }
-
-fn truncate_path_middle(path: &str, max_len: usize) -> String {
-    if path.len() <= max_len {
-        return path.to_string();
-    }
-
-    let segments: Vec<&str> = path.split(std::path::MAIN_SEPARATOR).collect();
-
-    if segments.len() <= 4 {
-        return path.to_string();
-    }
-
-    let head_count = 2;
-    let tail_count = 2;
-
-    let head: String = segments[..head_count].join(&std::path::MAIN_SEPARATOR.to_string());
-    let tail: String =
-        segments[segments.len() - tail_count..].join(&std::path::MAIN_SEPARATOR.to_string());
-
-    format!(
-        "{}{}...{}{}",
-        head,
-        std::path::MAIN_SEPARATOR,
-        std::path::MAIN_SEPARATOR,
-        tail
-    )
-}
@@ -1,10 +1,12 @@
 use crate::tui::service::disk::get_active_threads;
 use crate::tui::state::{DeleteResult, SpaceLensMode, SpaceLensState};
 use crate::tui::view::components::footer::render_space_lens_footer;
-use crate::tui::view::components::utils::centered_rect;
-use crate::utils::format_size;
+use crate::tui::view::components::utils::{centered_rect, size_bar, size_style};
+use crate::tui::view::theme::Theme;
+use crate::utils::{format_relative_age, format_size};
+use chrono::Utc;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::ListState;
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
@@ -14,8 +16,9 @@ pub fn render_space_lens(
     f: &mut Frame,
     list_state: &mut ListState,
     space_lens: &mut SpaceLensState,
+    theme: &Theme,
 ) {
-    if !space_lens.entries.is_empty() && list_state.selected().is_none() {
+    if space_lens.row_count() > 0 && list_state.selected().is_none() {
         list_state.select(Some(0));
     }
 
@@ -46,13 +49,13 @@ pub fn render_space_lens(
         Span::styled(
             " CleanX ",
             Style::default()
-                .fg(Color::Cyan)
+                .fg(theme.accent)
                 .add_modifier(Modifier::BOLD),
         ),
         Span::raw("Space Lens"),
-        Span::styled(&thread_info, Style::default().fg(Color::Yellow)),
+        Span::styled(&thread_info, Style::default().fg(theme.warning)),
         Span::raw("  "),
-        Span::styled(&path_str, Style::default().fg(Color::Green)),
+        Span::styled(&path_str, Style::default().fg(theme.safe)),
     ]))
     .block(Block::default().borders(Borders::BOTTOM));
     f.render_widget(title, chunks[0]);
@@ -61,59 +64,78 @@ pub fn render_space_lens(
 
     let bar_width = 20u16;
     let selected_idx = list_state.selected();
+    let now = Utc::now();
+    let recent_cutoff =
+        (space_lens.recent_days > 0).then(|| now - chrono::Duration::days(space_lens.recent_days));
 
-    let items: Vec<ListItem> = space_lens
-        .entries
-        .iter()
-        .enumerate()
-        .map(|(idx, entry)| {
-            let is_focused = selected_idx == Some(idx);
+    let offset = space_lens.row_offset();
+    let mut items: Vec<ListItem> = Vec::with_capacity(space_lens.row_count());
+    if space_lens.has_parent_entry() {
+        items.push(parent_entry_item(selected_idx == Some(0), theme));
+    }
 
-            let filled = if max_size > 0 {
-                ((entry.size as f64 / max_size as f64) * bar_width as f64) as usize
-            } else {
-                0
-            };
-            let bar: String = "█".repeat(filled) + &"░".repeat(bar_width as usize - filled);
+    items.extend(space_lens.entries.iter().enumerate().map(|(idx, entry)| {
+        let is_focused = selected_idx == Some(idx + offset);
 
-            let name_style = if is_focused {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::White)
-            };
+        let bar = size_bar(entry.size, max_size, bar_width as usize);
 
-            let dir_indicator = if entry.is_dir { "/" } else { "" };
-            let size_percent = if space_lens.total_size > 0 && entry.size > 0 {
-                (entry.size as f64 / space_lens.total_size as f64 * 100.0) as u8
-            } else {
-                0
-            };
+        let is_recent = match (recent_cutoff, entry.modified) {
+            (Some(cutoff), Some(modified)) => modified >= cutoff,
+            _ => false,
+        };
 
-            let (size_text, size_style) = if entry.is_dir && entry.size == 0 {
-                ("...".to_string(), Style::default().fg(Color::DarkGray))
-            } else {
-                (format_size(entry.size), Style::default().fg(Color::Green))
-            };
+        let name_style = if is_focused {
+            Style::default()
+                .fg(theme.warning)
+                .add_modifier(Modifier::BOLD)
+        } else if is_recent {
+            Style::default().fg(theme.muted)
+        } else {
+            Style::default().fg(theme.text)
+        };
 
-            ListItem::new(Line::from(vec![
-                Span::styled(
-                    format!("{:<30}", format!("{}{}", entry.name, dir_indicator)),
-                    name_style,
-                ),
-                Span::styled(bar, Style::default().fg(Color::Cyan)),
-                Span::raw(" "),
-                Span::styled(format!("{:>12}", size_text), size_style),
-                Span::styled(
-                    format!(" {:>3}%", size_percent),
-                    Style::default().fg(Color::DarkGray),
-                ),
-            ]))
-        })
-        .collect();
+        let dir_indicator = if entry.is_dir { "/" } else { "" };
+        let size_percent = if space_lens.total_size > 0 && entry.size > 0 {
+            (entry.size as f64 / space_lens.total_size as f64 * 100.0) as u8
+        } else {
+            0
+        };
+
+        let (size_text, size_style) = if entry.is_dir && entry.size == 0 {
+            ("...".to_string(), Style::default().fg(theme.muted))
+        } else {
+            (format_size(entry.size), size_style(entry.size, theme))
+        };
+
+        let age_text = entry
+            .modified
+            .map(|m| format_relative_age(m, now))
+            .unwrap_or_else(|| "-".to_string());
+        let age_style = if is_recent {
+            Style::default().fg(theme.warning)
+        } else {
+            Style::default().fg(theme.muted)
+        };
+
+        ListItem::new(Line::from(vec![
+            Span::styled(
+                format!("{:<30}", format!("{}{}", entry.name, dir_indicator)),
+                name_style,
+            ),
+            Span::styled(bar, Style::default().fg(theme.accent)),
+            Span::raw(" "),
+            Span::styled(format!("{:>12}", size_text), size_style),
+            Span::styled(
+                format!(" {:>3}%", size_percent),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(format!(" {:>5}", age_text), age_style),
+        ]))
+    }));
 
-    let list_title = if space_lens.loading {
+    let list_title = if space_lens.loading && space_lens.serving_stale {
+        "Contents (cached, stale)"
+    } else if space_lens.loading {
         "Scanning..."
     } else if space_lens.cache.contains_key(&space_lens.current_path) {
         "Contents (cached)"
@@ -124,63 +146,82 @@ pub fn render_space_lens(
     let list = List::new(items)
         .block(Block::default().borders(Borders::NONE).title(Span::styled(
             format!("{} ({})", list_title, format_size(space_lens.total_size)),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.warning),
         )))
         .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
     f.render_stateful_widget(list, chunks[1], list_state);
 
-    render_space_lens_footer(f, chunks[2], space_lens.parallel_scan);
+    render_space_lens_footer(f, chunks[2], space_lens.parallel_scan, theme);
 
     match space_lens.delete_mode {
         SpaceLensMode::ConfirmDelete => {
             if let Some(ref entry) = space_lens.pending_delete {
-                render_delete_confirm_modal(f, entry);
+                render_delete_confirm_modal(f, entry, theme);
             }
         }
         SpaceLensMode::ShowResult => {
             if let Some(ref result) = space_lens.delete_result {
-                render_delete_result_modal(f, result);
+                render_delete_result_modal(f, result, theme);
             }
         }
         SpaceLensMode::Browse => {}
     }
 }
 
-fn render_delete_confirm_modal(f: &mut Frame, entry: &crate::tui::state::FolderEntry) {
+fn parent_entry_item(is_focused: bool, theme: &Theme) -> ListItem<'static> {
+    let name_style = if is_focused {
+        Style::default()
+            .fg(theme.warning)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(theme.accent)
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::styled(format!("{:<30}", "../"), name_style),
+        Span::styled("(parent directory)", Style::default().fg(theme.muted)),
+    ]))
+}
+
+fn render_delete_confirm_modal(
+    f: &mut Frame,
+    entry: &crate::tui::state::FolderEntry,
+    theme: &Theme,
+) {
     let area = centered_rect(60, 35, f.area());
 
     let dir_text = if entry.is_dir { "folder" } else { "file" };
     let text = vec![
         Line::from(""),
         Line::from(vec![
-            Span::styled("Delete ", Style::default().fg(Color::White)),
-            Span::styled(dir_text, Style::default().fg(Color::Yellow)),
-            Span::styled("?", Style::default().fg(Color::White)),
+            Span::styled("Delete ", Style::default().fg(theme.text)),
+            Span::styled(dir_text, Style::default().fg(theme.warning)),
+            Span::styled("?", Style::default().fg(theme.text)),
         ]),
         Line::from(""),
         Line::from(vec![Span::styled(
             &entry.name,
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme.accent),
         )]),
         Line::from(vec![Span::styled(
             format_size(entry.size),
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.safe),
         )]),
         Line::from(""),
         Line::from(Span::styled(
-            "This action cannot be undone.",
-            Style::default().fg(Color::Red),
+            "It goes to the Trash; anything already in a Trash is deleted for good.",
+            Style::default().fg(theme.warning),
         )),
         Line::from(""),
         Line::from(vec![
-            Span::styled("[y/Enter]", Style::default().fg(Color::Green)),
+            Span::styled("[y/Enter]", Style::default().fg(theme.safe)),
             Span::raw(" Confirm     "),
-            Span::styled("[n/Esc]", Style::default().fg(Color::Red)),
+            Span::styled("[n/Esc]", Style::default().fg(theme.danger)),
             Span::raw(" Cancel"),
         ]),
     ];
@@ -197,32 +238,42 @@ fn render_delete_confirm_modal(f: &mut Frame, entry: &crate::tui::state::FolderE
     f.render_widget(paragraph, area);
 }
 
-fn render_delete_result_modal(f: &mut Frame, result: &DeleteResult) {
+fn render_delete_result_modal(f: &mut Frame, result: &DeleteResult, theme: &Theme) {
     let area = centered_rect(60, 30, f.area());
 
     let text = if result.success {
         vec![
             Line::from(""),
             Line::from(vec![Span::styled(
-                "Deleted Successfully!",
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
+                if result.trashed {
+                    "Moved to Trash!"
+                } else {
+                    "Deleted Successfully!"
+                },
+                Style::default().fg(theme.safe).add_modifier(Modifier::BOLD),
             )]),
             Line::from(""),
+            Line::from(vec![Span::styled(
+                result
+                    .path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                Style::default().fg(theme.accent),
+            )]),
             Line::from(vec![
-                Span::styled("Freed: ", Style::default().fg(Color::Gray)),
+                Span::styled("Freed: ", Style::default().fg(theme.subtle)),
                 Span::styled(
                     format_size(result.size),
                     Style::default()
-                        .fg(Color::Cyan)
+                        .fg(theme.accent)
                         .add_modifier(Modifier::BOLD),
                 ),
             ]),
             Line::from(""),
             Line::from(Span::styled(
                 "Press Enter to continue",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )),
         ]
     } else {
@@ -230,20 +281,22 @@ fn render_delete_result_modal(f: &mut Frame, result: &DeleteResult) {
             Line::from(""),
             Line::from(vec![Span::styled(
                 "Delete Failed!",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(theme.danger)
+                    .add_modifier(Modifier::BOLD),
             )]),
             Line::from(""),
             Line::from(vec![
-                Span::styled("Error: ", Style::default().fg(Color::Gray)),
+                Span::styled("Error: ", Style::default().fg(theme.subtle)),
                 Span::styled(
                     result.error.as_deref().unwrap_or("Unknown error"),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(theme.danger),
                 ),
             ]),
             Line::from(""),
             Line::from(Span::styled(
                 "Press Enter to continue",
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             )),
         ]
     };
@@ -1,9 +1,10 @@
-use crate::tui::state::AppsModeState;
-use crate::tui::view::components::centered_rect;
+use crate::tui::state::{AppsModeState, UninstallSummary};
 use crate::tui::view::components::footer::render_uninstall_review_footer;
+use crate::tui::view::components::{centered_rect, fit_width};
+use crate::tui::view::theme::Theme;
 use crate::utils::format_size;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::ListState;
 use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph};
@@ -13,6 +14,7 @@ pub fn render_uninstall_review(
     f: &mut Frame,
     list_state: &mut ListState,
     apps_mode: &AppsModeState,
+    theme: &Theme,
 ) {
     let app_idx = apps_mode.selected_app_idx.unwrap_or(0);
     let app = match apps_mode.apps.get(app_idx) {
@@ -33,22 +35,22 @@ pub fn render_uninstall_review(
 
     let header_text = vec![
         Line::from(vec![
-            Span::styled("Uninstall: ", Style::default().fg(Color::Gray)),
+            Span::styled("Uninstall: ", Style::default().fg(theme.subtle)),
             Span::styled(
                 app.name(),
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(theme.warning)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
         Line::from(vec![
-            Span::styled("Size: ", Style::default().fg(Color::Gray)),
-            Span::styled(format_size(app.size()), Style::default().fg(Color::Cyan)),
+            Span::styled("Size: ", Style::default().fg(theme.subtle)),
+            Span::styled(format_size(app.size()), Style::default().fg(theme.accent)),
             Span::raw("   "),
-            Span::styled("Related: ", Style::default().fg(Color::Gray)),
+            Span::styled("Related: ", Style::default().fg(theme.subtle)),
             Span::styled(
                 format!("{} items", related_files.len()),
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.safe),
             ),
         ]),
     ];
@@ -62,23 +64,23 @@ pub fn render_uninstall_review(
 
     let app_selected = apps_mode.selected_related.contains(&0);
     let app_name = format!("{}.app", app.name());
-    let padded_app_name = format!("{:<35}", app_name);
+    let padded_app_name = fit_width(&app_name, 35);
     let app_size_str = format!("{:>10}", format_size(app.size()));
 
     items.push(ListItem::new(Line::from(vec![
         Span::styled(
             if app_selected { "[x] " } else { "[ ] " },
-            Style::default().fg(Color::Green),
+            Style::default().fg(theme.safe),
         ),
         Span::styled(
             padded_app_name,
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(app_size_str, Style::default().fg(Color::Cyan)),
+        Span::styled(app_size_str, Style::default().fg(theme.accent)),
         Span::raw("  "),
-        Span::styled("[App Bundle]", Style::default().fg(Color::DarkGray)),
+        Span::styled("[App Bundle]", Style::default().fg(theme.muted)),
     ])));
 
     for (i, file) in related_files.iter().enumerate() {
@@ -86,20 +88,19 @@ pub fn render_uninstall_review(
         let is_protected = file.category.is_protected();
 
         let check_color = if is_protected {
-            Color::Red
+            theme.danger
         } else if is_selected {
-            Color::Green
+            theme.safe
         } else {
-            Color::Gray
+            theme.subtle
         };
 
         let file_name = file
             .path
             .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("?");
+            .map_or("?".into(), |n| n.to_string_lossy());
 
-        let padded_name = format!("{:<35}", file_name);
+        let padded_name = fit_width(&file_name, 35);
         let size_str = format!("{:>10}", format_size(file.size));
         let protected_tag = if is_protected { " (Protected)" } else { "" };
 
@@ -109,13 +110,13 @@ pub fn render_uninstall_review(
                 Style::default().fg(check_color),
             ),
             Span::raw(padded_name),
-            Span::styled(size_str, Style::default().fg(Color::Cyan)),
+            Span::styled(size_str, Style::default().fg(theme.accent)),
             Span::raw("  "),
             Span::styled(
                 format!("[{}]", file.category.display_name()),
-                Style::default().fg(Color::DarkGray),
+                Style::default().fg(theme.muted),
             ),
-            Span::styled(protected_tag, Style::default().fg(Color::Red)),
+            Span::styled(protected_tag, Style::default().fg(theme.danger)),
         ])));
     }
 
@@ -133,21 +134,119 @@ pub fn render_uninstall_review(
     let list = List::new(items)
         .block(Block::default().borders(Borders::NONE).title(Span::styled(
             format!("Files to delete ({})", format_size(selected_size)),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.warning),
         )))
         .highlight_style(
             Style::default()
-                .fg(Color::Yellow)
+                .fg(theme.warning)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol("> ");
 
     f.render_stateful_widget(list, chunks[1], list_state);
 
-    render_uninstall_review_footer(f, chunks[2]);
+    render_uninstall_review_footer(f, chunks[2], theme);
 }
 
-pub fn render_uninstall_result(f: &mut Frame, apps_mode: &AppsModeState) {
+pub fn render_uninstall_confirm_modal(f: &mut Frame, summary: &UninstallSummary, theme: &Theme) {
+    let area = centered_rect(60, 60, f.area());
+
+    let mut text = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Uninstall ", Style::default().fg(theme.text)),
+            Span::styled(
+                &summary.app_name,
+                Style::default()
+                    .fg(theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("?", Style::default().fg(theme.text)),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                format!("{:<28}", "App bundle"),
+                Style::default().fg(theme.subtle),
+            ),
+            Span::styled(
+                format!("{:>10}", format_size(summary.app_size)),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("{:<28}", format!("Related ({})", summary.related_count)),
+                Style::default().fg(theme.subtle),
+            ),
+            Span::styled(
+                format!("{:>10}", format_size(summary.related_size)),
+                Style::default().fg(theme.accent),
+            ),
+        ]),
+    ];
+    for (category, count, size) in &summary.groups {
+        text.push(Line::from(vec![
+            Span::styled(
+                format!("{:<28}", format!("  {} ({})", category, count)),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(
+                format!("{:>10}", format_size(*size)),
+                Style::default().fg(theme.muted),
+            ),
+        ]));
+    }
+    text.extend([
+        Line::from(vec![
+            Span::styled(format!("{:<28}", "Total"), Style::default().fg(theme.text)),
+            Span::styled(
+                format!("{:>10}", format_size(summary.total_size())),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+    ]);
+
+    if summary.protected_count > 0 {
+        text.push(Line::from(Span::styled(
+            format!(
+                "{} protected items ({}) will be skipped",
+                summary.protected_count,
+                format_size(summary.protected_size)
+            ),
+            Style::default().fg(theme.warning),
+        )));
+        text.push(Line::from(""));
+    }
+
+    text.push(Line::from(Span::styled(
+        "This action cannot be undone.",
+        Style::default().fg(theme.danger),
+    )));
+    text.push(Line::from(""));
+    text.push(Line::from(vec![
+        Span::styled("[y]", Style::default().fg(theme.safe)),
+        Span::raw(" Confirm     "),
+        Span::styled("[n/Esc]", Style::default().fg(theme.danger)),
+        Span::raw(" Cancel"),
+    ]));
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .title(" Confirm Uninstall ")
+                .borders(Borders::ALL),
+        )
+        .alignment(Alignment::Center);
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+pub fn render_uninstall_result(f: &mut Frame, apps_mode: &AppsModeState, theme: &Theme) {
     let area = centered_rect(60, 40, f.area());
 
     let result = &apps_mode.uninstall_result;
@@ -161,34 +260,32 @@ pub fn render_uninstall_result(f: &mut Frame, apps_mode: &AppsModeState) {
                 } else {
                     "Uninstall Complete"
                 },
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD),
+                Style::default().fg(theme.safe).add_modifier(Modifier::BOLD),
             )]),
             Line::from(""),
         ];
 
         if r.app_deleted {
             lines.push(Line::from(vec![
-                Span::styled("App: ", Style::default().fg(Color::Gray)),
-                Span::styled("Deleted", Style::default().fg(Color::Green)),
+                Span::styled("App: ", Style::default().fg(theme.subtle)),
+                Span::styled("Deleted", Style::default().fg(theme.safe)),
             ]));
         }
 
         lines.push(Line::from(vec![
-            Span::styled("Related files: ", Style::default().fg(Color::Gray)),
+            Span::styled("Related files: ", Style::default().fg(theme.subtle)),
             Span::styled(
                 format!("{} deleted", r.related_deleted),
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.safe),
             ),
         ]));
 
         lines.push(Line::from(vec![
-            Span::styled("Freed: ", Style::default().fg(Color::Gray)),
+            Span::styled("Freed: ", Style::default().fg(theme.subtle)),
             Span::styled(
                 format_size(r.total_freed),
                 Style::default()
-                    .fg(Color::Cyan)
+                    .fg(theme.accent)
                     .add_modifier(Modifier::BOLD),
             ),
         ]));
@@ -197,14 +294,14 @@ pub fn render_uninstall_result(f: &mut Frame, apps_mode: &AppsModeState) {
             lines.push(Line::from(""));
             lines.push(Line::from(vec![Span::styled(
                 format!("Errors: {}", r.errors.len()),
-                Style::default().fg(Color::Red),
+                Style::default().fg(theme.danger),
             )]));
         }
 
         lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
             "Press Enter to continue",
-            Style::default().fg(Color::DarkGray),
+            Style::default().fg(theme.muted),
         )));
 
         lines
@@ -0,0 +1,96 @@
+use crate::config::{ThemeConfig, ThemeName};
+use ratatui::style::Color;
+
+// 화면 코드는 색상을 직접 쓰지 않고 역할(role)로만 참조한다
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub accent: Color,
+    pub warning: Color,
+    pub danger: Color,
+    pub safe: Color,
+    pub muted: Color,
+    pub text: Color,
+    pub subtle: Color,
+    pub size_large_bytes: u64,
+    pub size_medium_bytes: u64,
+}
+
+impl Theme {
+    pub fn from_config(config: &ThemeConfig) -> Self {
+        let mut theme = match config.name {
+            ThemeName::Dark => Self::dark(),
+            ThemeName::Light => Self::light(),
+            ThemeName::HighContrast => Self::high_contrast(),
+        };
+        theme.size_large_bytes = config.size_large_bytes;
+        theme.size_medium_bytes = config.size_medium_bytes;
+        theme
+    }
+
+    pub fn dark() -> Self {
+        let sizes = ThemeConfig::default();
+        Self {
+            accent: Color::Cyan,
+            warning: Color::Yellow,
+            danger: Color::Red,
+            safe: Color::Green,
+            muted: Color::DarkGray,
+            text: Color::White,
+            subtle: Color::Gray,
+            size_large_bytes: sizes.size_large_bytes,
+            size_medium_bytes: sizes.size_medium_bytes,
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            accent: Color::Blue,
+            warning: Color::Rgb(175, 95, 0),
+            danger: Color::Rgb(175, 0, 0),
+            safe: Color::Rgb(0, 125, 0),
+            muted: Color::Rgb(110, 110, 110),
+            text: Color::Black,
+            subtle: Color::DarkGray,
+            ..Self::dark()
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            accent: Color::LightCyan,
+            warning: Color::LightYellow,
+            danger: Color::LightRed,
+            safe: Color::LightGreen,
+            muted: Color::Gray,
+            text: Color::White,
+            subtle: Color::White,
+            ..Self::dark()
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::dark()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tui::view::components::size_style;
+
+    #[test]
+    fn light_theme_differs_from_dark() {
+        let dark = Theme::dark();
+        let light = Theme::from_config(&ThemeConfig {
+            name: ThemeName::Light,
+            ..ThemeConfig::default()
+        });
+
+        assert_ne!(dark, light);
+        assert_ne!(dark.text, light.text);
+        let large = dark.size_large_bytes;
+        assert_ne!(size_style(large, &dark), size_style(large, &light));
+    }
+}
@@ -1,10 +1,17 @@
 use anyhow::Result;
+use chrono::{DateTime, Utc};
 use plist::Value;
-use std::cell::{Cell, RefCell};
+use std::borrow::Cow;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Cursor;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::SystemTime;
 use walkdir::WalkDir;
 
 #[derive(Debug, Clone)]
@@ -17,6 +24,7 @@ pub struct AppBundle {
     pub path: PathBuf,
     info: RefCell<Option<PlistInfo>>,
     cached_size: Cell<Option<u64>>,
+    installed: OnceCell<Option<DateTime<Utc>>>,
 }
 
 impl Clone for AppBundle {
@@ -25,6 +33,7 @@ impl Clone for AppBundle {
             path: self.path.clone(),
             info: RefCell::new(self.info.borrow().clone()),
             cached_size: Cell::new(self.cached_size.get()),
+            installed: self.installed.clone(),
         }
     }
 }
@@ -44,9 +53,19 @@ impl AppBundle {
             path,
             info: RefCell::new(None),
             cached_size: Cell::new(None),
+            installed: OnceCell::new(),
         }
     }
 
+    // When the bundle landed in its folder, which is when it was installed for
+    // drag-in and most pkg installs.
+    pub fn installed(&self) -> Option<DateTime<Utc>> {
+        *self.installed.get_or_init(|| {
+            let meta = fs::metadata(&self.path).ok()?;
+            install_time(meta.created().ok(), meta.ctime())
+        })
+    }
+
     pub fn info(&self) -> Option<PlistInfo> {
         if self.info.borrow().is_none() {
             if let Ok(parsed) = Self::parse_plist(&self.path) {
@@ -85,14 +104,22 @@ impl AppBundle {
         size
     }
 
-    pub fn name(&self) -> &str {
+    // For display and matching only; anything that touches disk uses `path`.
+    pub fn name(&self) -> Cow<'_, str> {
         self.path
             .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("Unknown")
+            .map_or(Cow::Borrowed("Unknown"), |s| s.to_string_lossy())
     }
 }
 
+// The bundle directory's creation time, or its ctime where the filesystem
+// keeps no creation time.
+fn install_time(created: Option<SystemTime>, ctime_secs: i64) -> Option<DateTime<Utc>> {
+    created
+        .map(DateTime::<Utc>::from)
+        .or_else(|| (ctime_secs > 0).then(|| DateTime::from_timestamp(ctime_secs, 0))?)
+}
+
 fn calculate_dir_size(path: &Path) -> u64 {
     if !path.exists() {
         return 0;
@@ -107,7 +134,8 @@ fn calculate_dir_size(path: &Path) -> u64 {
         .sum()
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// Declaration order is the order previews list categories in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum RelatedCategory {
     AppSupport,
     Preferences,
@@ -158,17 +186,49 @@ pub struct RelatedFile {
     pub size: u64,
 }
 
+// An answer to "Delete <category>?": Enter means yes. Protected categories
+// are never deleted, whatever the answer.
+pub fn confirm_category(category: RelatedCategory, answer: &str) -> bool {
+    if category.is_protected() {
+        return false;
+    }
+    matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "" | "y" | "yes"
+    )
+}
+
+// Splits an uninstall into deletable files grouped by category and the protected
+// files that will be skipped, in the order previews show them.
+pub fn group_related(
+    files: &[RelatedFile],
+) -> (Vec<(RelatedCategory, Vec<&RelatedFile>)>, Vec<&RelatedFile>) {
+    let mut groups: BTreeMap<RelatedCategory, Vec<&RelatedFile>> = BTreeMap::new();
+    let mut protected = Vec::new();
+
+    for file in files {
+        if file.category.is_protected() {
+            protected.push(file);
+        } else {
+            groups.entry(file.category).or_default().push(file);
+        }
+    }
+
+    (groups.into_iter().collect(), protected)
+}
+
+const NESTED_APP_DEPTH: usize = 3;
+
 pub struct AppDetector {
     search_paths: Vec<PathBuf>,
 }
 
 impl AppDetector {
     pub fn new() -> Self {
-        let mut search_paths = vec![PathBuf::from("/Applications")];
-
-        if let Some(home) = dirs::home_dir() {
-            search_paths.push(home.join("Applications"));
-        }
+        let search_paths = vec![
+            PathBuf::from("/Applications"),
+            crate::user::home_dir().join("Applications"),
+        ];
 
         Self { search_paths }
     }
@@ -212,9 +272,129 @@ impl AppDetector {
             }
         }
 
-        apps.sort_by(|a, b| a.name().to_lowercase().cmp(&b.name().to_lowercase()));
+        apps.sort_by_key(|a| a.name().to_lowercase());
         apps
     }
+
+    // Like `list_all`, but also looks inside plain folders such as Utilities,
+    // Setapp or a vendor's folder, a few levels down.
+    pub fn list_nested(&self) -> Vec<AppBundle> {
+        let mut apps = Vec::new();
+
+        for path in &self.search_paths {
+            let mut walker = WalkDir::new(path)
+                .min_depth(1)
+                .max_depth(NESTED_APP_DEPTH)
+                .into_iter();
+            while let Some(entry) = walker.next() {
+                let Ok(entry) = entry else {
+                    continue;
+                };
+                if !entry.file_type().is_dir() {
+                    continue;
+                }
+                if entry.path().extension().is_some_and(|e| e == "app") {
+                    walker.skip_current_dir();
+                    apps.push(AppBundle::new(entry.into_path()));
+                }
+            }
+        }
+
+        apps.sort_by_key(|a| a.name().to_lowercase());
+        apps
+    }
+
+    #[cfg(test)]
+    fn with_search_paths(search_paths: Vec<PathBuf>) -> Self {
+        Self { search_paths }
+    }
+
+    pub fn find_duplicate_installs(&self) -> Vec<DuplicateInstall> {
+        duplicate_installs(&self.list_all())
+    }
+}
+
+// Groups apps that share a bundle id (e.g. a copy in both /Applications and
+// ~/Applications, or an old version left behind). Only groups with 2+ copies.
+pub fn find_duplicate_apps(apps: &[AppBundle]) -> Vec<(String, Vec<usize>)> {
+    let mut by_bundle_id: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+    for (idx, app) in apps.iter().enumerate() {
+        if let Some(info) = app.info() {
+            if !info.bundle_id.is_empty() {
+                by_bundle_id.entry(info.bundle_id).or_default().push(idx);
+            }
+        }
+    }
+
+    by_bundle_id
+        .into_iter()
+        .filter(|(_, indices)| indices.len() > 1)
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct InstalledCopy {
+    pub app: AppBundle,
+    pub version: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DuplicateInstall {
+    pub bundle_id: String,
+    // Newest version first.
+    pub copies: Vec<InstalledCopy>,
+}
+
+impl DuplicateInstall {
+    // The oldest copy, but only when it really is older than the newest one;
+    // identical versions leave nothing to pick between.
+    pub fn older_copy(&self) -> Option<&InstalledCopy> {
+        let newest = self.copies.first()?;
+        let oldest = self.copies.last()?;
+        (compare_versions(&newest.version, &oldest.version) == Ordering::Greater).then_some(oldest)
+    }
+}
+
+pub fn duplicate_installs(apps: &[AppBundle]) -> Vec<DuplicateInstall> {
+    find_duplicate_apps(apps)
+        .into_iter()
+        .map(|(bundle_id, indices)| {
+            let mut copies: Vec<InstalledCopy> = indices
+                .into_iter()
+                .map(|i| InstalledCopy {
+                    version: apps[i].info().map(|info| info.version).unwrap_or_default(),
+                    app: apps[i].clone(),
+                })
+                .collect();
+            copies.sort_by(|a, b| compare_versions(&b.version, &a.version));
+            DuplicateInstall { bundle_id, copies }
+        })
+        .collect()
+}
+
+// Compares CFBundleShortVersionString values part by part, numerically where
+// both parts are numbers, so "4.41" is newer than "4.9". Missing parts count as 0.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parts = |v: &str| -> Vec<String> {
+        v.split(['.', '-', ' '])
+            .filter(|p| !p.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let (a, b) = (parts(a), parts(b));
+
+    for i in 0..a.len().max(b.len()) {
+        let x = a.get(i).map(String::as_str).unwrap_or("0");
+        let y = b.get(i).map(String::as_str).unwrap_or("0");
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
 }
 
 impl Default for AppDetector {
@@ -230,7 +410,7 @@ pub struct RelatedFileDetector {
 impl RelatedFileDetector {
     pub fn new() -> Self {
         Self {
-            home: dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
+            home: crate::user::home_dir(),
         }
     }
 
@@ -252,7 +432,7 @@ impl RelatedFileDetector {
                     let name = entry.file_name().to_string_lossy().to_string();
                     let path = entry.path();
 
-                    if self.is_related(&name, app_name, &bundle_id) {
+                    if self.is_related(&name, &app_name, &bundle_id) {
                         files.push(RelatedFile {
                             path: path.clone(),
                             category,
@@ -358,13 +538,89 @@ const SYSTEM_APPS: &[&str] = &[
     "com.apple.Utilities",
 ];
 
+// What a helper binary printed; tests swap in a runner that fakes these.
+#[derive(Debug, Clone, Default)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+pub trait CommandRunner {
+    fn run(&self, program: &str, args: &[&str]) -> std::io::Result<CommandOutput>;
+}
+
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, program: &str, args: &[&str]) -> std::io::Result<CommandOutput> {
+        let output = Command::new(program).args(args).output()?;
+        Ok(CommandOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+const LIST_PROCESSES: &str = "tell application \"System Events\" to get name of every process";
+
+// errAEEventNotPermitted: the user (or MDM) has not allowed automating System Events.
+fn automation_denied(stderr: &str) -> bool {
+    stderr.contains("-1743") || stderr.contains("Not authorized to send Apple events")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutomationStatus {
+    Available,
+    Denied,
+    Missing,
+}
+
+impl AutomationStatus {
+    pub fn describe(self) -> &'static str {
+        match self {
+            AutomationStatus::Available => "ok",
+            AutomationStatus::Denied => {
+                "automation denied: running apps are checked with ps, admin-only files need manual removal"
+            }
+            AutomationStatus::Missing => {
+                "osascript unavailable: running apps are checked with ps, admin-only files need manual removal"
+            }
+        }
+    }
+}
+
+// Asks System Events for the process list once, the same call is_running makes.
+pub fn automation_status(runner: &dyn CommandRunner) -> AutomationStatus {
+    match runner.run("osascript", &["-e", LIST_PROCESSES]) {
+        Ok(output) if output.success => AutomationStatus::Available,
+        Ok(output) if automation_denied(&output.stderr) => AutomationStatus::Denied,
+        Ok(_) | Err(_) => AutomationStatus::Missing,
+    }
+}
+
 pub struct Uninstaller {
     dry_run: bool,
+    runner: Box<dyn CommandRunner>,
 }
 
 impl Uninstaller {
     pub fn new(dry_run: bool) -> Self {
-        Self { dry_run }
+        Self {
+            dry_run,
+            runner: Box::new(SystemRunner),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_runner(mut self, runner: Box<dyn CommandRunner>) -> Self {
+        self.runner = runner;
+        self
+    }
+
+    fn is_dry_run(&self) -> bool {
+        self.dry_run || crate::audit::is_active()
     }
 
     pub fn is_system_app(&self, app: &AppBundle) -> bool {
@@ -373,16 +629,27 @@ impl Uninstaller {
             .unwrap_or(false)
     }
 
+    // System Events when automation is allowed, otherwise the ps process table:
+    // a process counts if its executable lives in the bundle or shares its name.
     pub fn is_running(&self, app: &AppBundle) -> Result<bool> {
-        let output = Command::new("osascript")
-            .arg("-e")
-            .arg("tell application \"System Events\" to get name of every process")
-            .output()?;
-
-        let running = String::from_utf8_lossy(&output.stdout).to_lowercase();
         let app_name = app.name().to_lowercase();
+        if let Ok(output) = self.runner.run("osascript", &["-e", LIST_PROCESSES]) {
+            if output.success {
+                return Ok(output.stdout.to_lowercase().contains(&app_name));
+            }
+        }
 
-        Ok(running.contains(&app_name))
+        let output = self.runner.run("ps", &["-A", "-o", "comm="])?;
+        if !output.success {
+            anyhow::bail!("Could not list running processes: {}", output.stderr.trim());
+        }
+        let bundle = format!("{}/", app.path.display());
+        Ok(output.stdout.lines().map(str::trim).any(|command| {
+            command.starts_with(&bundle)
+                || Path::new(command)
+                    .file_name()
+                    .is_some_and(|n| n.to_string_lossy().to_lowercase() == app_name)
+        }))
     }
 
     pub fn uninstall(
@@ -406,14 +673,17 @@ impl Uninstaller {
             return Ok(result);
         }
 
+        // One path that can't be removed is reported, not fatal to the rest.
         let app_size = app.size();
-        if self.delete_path(&app.path)? {
-            result.deleted_app = true;
-            result.total_freed += app_size;
-        } else {
-            result
+        match self.delete_path(&app.path) {
+            Ok(true) => {
+                result.deleted_app = true;
+                result.total_freed += app_size;
+            }
+            Ok(false) => result
                 .errors
-                .push(format!("Failed to delete app: {}", app.path.display()));
+                .push(format!("Failed to delete app: {}", app.path.display())),
+            Err(e) => result.errors.push(e.to_string()),
         }
 
         for file in related_files {
@@ -422,17 +692,19 @@ impl Uninstaller {
                 continue;
             }
 
-            if self.delete_path(&file.path)? {
-                result.deleted_related.push(file.path.clone());
-                result.total_freed += file.size;
-            } else {
-                result
+            match self.delete_path(&file.path) {
+                Ok(true) => {
+                    result.deleted_related.push(file.path.clone());
+                    result.total_freed += file.size;
+                }
+                Ok(false) => result
                     .errors
-                    .push(format!("Failed to delete: {}", file.path.display()));
+                    .push(format!("Failed to delete: {}", file.path.display())),
+                Err(e) => result.errors.push(e.to_string()),
             }
         }
 
-        result.dry_run = self.dry_run;
+        result.dry_run = self.is_dry_run();
         Ok(result)
     }
 
@@ -441,7 +713,12 @@ impl Uninstaller {
             return Ok(false);
         }
 
-        if self.dry_run {
+        // Under `--user`, never touch files that belong to someone else.
+        if let Some(reason) = crate::user::ownership_refusal(path) {
+            anyhow::bail!("{}: {}", reason, path.display());
+        }
+
+        if self.is_dry_run() {
             println!("[DRY-RUN] Would delete: {}", path.display());
             return Ok(true);
         }
@@ -465,35 +742,48 @@ impl Uninstaller {
     }
 
     fn delete_with_admin_privileges(&self, path: &Path) -> Result<bool> {
-        let path_str = path.to_string_lossy();
-        let script = if path.is_dir() {
-            format!(
-                "do shell script \"rm -rf '{}'\" with administrator privileges",
-                path_str
-            )
-        } else {
-            format!(
-                "do shell script \"rm '{}'\" with administrator privileges",
-                path_str
-            )
-        };
-
-        let output = Command::new("osascript").arg("-e").arg(&script).output();
-
-        match output {
-            Ok(o) if o.status.success() => {
+        let command = rm_command(path, path.is_dir())
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        let script = format!(
+            "do shell script \"{}\" with administrator privileges",
+            command
+        );
+
+        let reason = match self.runner.run("osascript", &["-e", &script]) {
+            Ok(o) if o.success => {
                 println!("Deleted (with admin): {}", path.display());
-                Ok(true)
-            }
-            Ok(o) => {
-                let stderr = String::from_utf8_lossy(&o.stderr);
-                anyhow::bail!("Admin privileges denied or failed: {}", stderr);
+                return Ok(true);
             }
-            Err(e) => anyhow::bail!("Failed to request admin privileges: {}", e),
-        }
+            Ok(o) if automation_denied(&o.stderr) => "automation is not allowed".to_string(),
+            Ok(o) => o.stderr.trim().to_string(),
+            Err(e) => format!("osascript unavailable ({})", e),
+        };
+        anyhow::bail!(
+            "Requires manual removal with admin rights: {} ({})",
+            path.display(),
+            reason
+        )
     }
 }
 
+// A shell command removing exactly `path`. Every byte goes in as a printf octal
+// escape, so quotes and names that aren't UTF-8 reach rm unchanged instead of
+// as a lossy look-alike. The trailing x keeps a final newline in the name.
+fn rm_command(path: &Path, is_dir: bool) -> String {
+    let escaped: String = path
+        .as_os_str()
+        .as_bytes()
+        .iter()
+        .map(|b| format!("\\{:03o}", b))
+        .collect();
+    format!(
+        "p=$(printf '{}x'); rm {}-- \"${{p%x}}\"",
+        escaped,
+        if is_dir { "-rf " } else { "" }
+    )
+}
+
 #[derive(Debug, Default)]
 pub struct UninstallResult {
     pub dry_run: bool,
@@ -509,3 +799,284 @@ impl UninstallResult {
         Self::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_app(dir: &Path, name: &str, bundle_id: &str, version: &str) -> AppBundle {
+        let path = dir.join(format!("{}.app", name));
+        fs::create_dir_all(path.join("Contents")).unwrap();
+        fs::write(
+            path.join("Contents/Info.plist"),
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0"><dict>
+<key>CFBundleIdentifier</key><string>{}</string>
+<key>CFBundleShortVersionString</key><string>{}</string>
+</dict></plist>"#,
+                bundle_id, version
+            ),
+        )
+        .unwrap();
+        AppBundle::new(path)
+    }
+
+    #[test]
+    fn nested_listing_finds_apps_in_subfolders_but_not_inside_bundles() {
+        let dir = std::env::temp_dir().join(format!("cleanmac-nested-apps-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        make_app(&dir, "Top", "com.example.top", "1.0");
+        make_app(&dir.join("Utilities"), "Tool", "com.example.tool", "1.0");
+        make_app(&dir.join("Setapp"), "Paid", "com.example.paid", "1.0");
+        make_app(
+            &dir.join("Top.app/Contents/Helpers"),
+            "Helper",
+            "com.example.helper",
+            "1.0",
+        );
+
+        let detector = AppDetector::with_search_paths(vec![dir.clone()]);
+        let ids: Vec<String> = detector
+            .list_nested()
+            .iter()
+            .filter_map(|app| app.info())
+            .map(|info| info.bundle_id)
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["com.example.paid", "com.example.tool", "com.example.top"]
+        );
+        assert_eq!(detector.list_all().len(), 1);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn groups_copies_with_the_same_bundle_id() {
+        let dir = std::env::temp_dir().join(format!("cleanmac-dup-apps-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let system = dir.join("Applications");
+        let user = dir.join("home/Applications");
+
+        let apps = vec![
+            make_app(&system, "Slack", "com.tinyspeck.slackmacgap", "4.41"),
+            make_app(&system, "Notes", "com.example.notes", "1.0"),
+            make_app(&user, "Slack", "com.tinyspeck.slackmacgap", "4.29"),
+        ];
+        let groups = find_duplicate_apps(&apps);
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(
+            groups,
+            vec![("com.tinyspeck.slackmacgap".to_string(), vec![0, 2])]
+        );
+    }
+
+    #[test]
+    fn duplicate_installs_put_the_newest_version_first() {
+        let dir =
+            std::env::temp_dir().join(format!("cleanmac-dup-installs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let system = dir.join("Applications");
+        let user = dir.join("home/Applications");
+        make_app(&user, "Zoom", "us.zoom.xos", "5.9.1");
+        make_app(&system, "Zoom", "us.zoom.xos", "5.17.0");
+        make_app(&system, "Notes", "com.example.notes", "1.0");
+
+        let detector = AppDetector {
+            search_paths: vec![system.clone(), user.clone()],
+        };
+        let groups = detector.find_duplicate_installs();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].bundle_id, "us.zoom.xos");
+        let versions: Vec<&str> = groups[0]
+            .copies
+            .iter()
+            .map(|c| c.version.as_str())
+            .collect();
+        assert_eq!(versions, vec!["5.17.0", "5.9.1"]);
+        let older = groups[0].older_copy().unwrap();
+        assert_eq!(older.app.path, user.join("Zoom.app"));
+    }
+
+    #[test]
+    fn compares_versions_numerically() {
+        assert_eq!(compare_versions("4.41", "4.9"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0", "1"), Ordering::Equal);
+        assert_eq!(compare_versions("2.0.1", "2.0.10"), Ordering::Less);
+        assert_eq!(compare_versions("", ""), Ordering::Equal);
+    }
+
+    #[test]
+    fn groups_related_files_by_category_and_sets_protected_aside() {
+        let file = |path: &str, category, size| RelatedFile {
+            path: PathBuf::from(path),
+            category,
+            size,
+        };
+        let files = vec![
+            file("/c/app.cache", RelatedCategory::Caches, 30),
+            file("/d/com.app.helper.plist", RelatedCategory::LaunchDaemons, 1),
+            file("/s/App", RelatedCategory::AppSupport, 100),
+            file("/c/app.shipit", RelatedCategory::Caches, 5),
+        ];
+        let (groups, protected) = group_related(&files);
+
+        let summary: Vec<(RelatedCategory, usize, u64)> = groups
+            .iter()
+            .map(|(category, files)| (*category, files.len(), files.iter().map(|f| f.size).sum()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (RelatedCategory::AppSupport, 1, 100),
+                (RelatedCategory::Caches, 2, 35),
+            ]
+        );
+        assert_eq!(protected.len(), 1);
+        assert_eq!(protected[0].category, RelatedCategory::LaunchDaemons);
+    }
+
+    // Replies per program; None means the binary isn't there.
+    struct FakeRunner {
+        osascript: Option<CommandOutput>,
+        ps: Option<CommandOutput>,
+    }
+
+    impl CommandRunner for FakeRunner {
+        fn run(&self, program: &str, _args: &[&str]) -> std::io::Result<CommandOutput> {
+            let reply = match program {
+                "osascript" => &self.osascript,
+                _ => &self.ps,
+            };
+            reply
+                .clone()
+                .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::NotFound))
+        }
+    }
+
+    fn ok(stdout: &str) -> Option<CommandOutput> {
+        Some(CommandOutput {
+            success: true,
+            stdout: stdout.to_string(),
+            stderr: String::new(),
+        })
+    }
+
+    #[test]
+    fn is_running_falls_back_to_ps_without_automation() {
+        let app = AppBundle::new(PathBuf::from("/Applications/Slack.app"));
+        let denied = Some(CommandOutput {
+            success: false,
+            stdout: String::new(),
+            stderr:
+                "execution error: Not authorized to send Apple events to System Events. (-1743)"
+                    .to_string(),
+        });
+        let check = |osascript: Option<CommandOutput>, ps: Option<CommandOutput>| {
+            Uninstaller::new(true)
+                .with_runner(Box::new(FakeRunner { osascript, ps }))
+                .is_running(&app)
+        };
+
+        let in_bundle = ok("/sbin/launchd\n/Applications/Slack.app/Contents/MacOS/Slack\n");
+        assert!(check(denied.clone(), in_bundle.clone()).unwrap());
+        assert!(check(None, in_bundle).unwrap());
+        assert!(check(None, ok("/sbin/launchd\nslack\n")).unwrap());
+        assert!(!check(denied.clone(), ok("/sbin/launchd\n/usr/bin/slacker\n")).unwrap());
+        assert!(check(denied.clone(), None).is_err());
+        // System Events answers first when it's allowed to.
+        assert!(!check(ok("Finder, Dock"), ok("Slack")).unwrap());
+
+        let status = |osascript| {
+            automation_status(&FakeRunner {
+                osascript,
+                ps: None,
+            })
+        };
+        assert_eq!(status(denied), AutomationStatus::Denied);
+        assert_eq!(status(None), AutomationStatus::Missing);
+        assert_eq!(status(ok("Finder")), AutomationStatus::Available);
+    }
+
+    #[test]
+    fn confirm_each_never_deletes_protected_categories() {
+        let answers = [
+            (RelatedCategory::Caches, "\n", true),
+            (RelatedCategory::Logs, "y\n", true),
+            (RelatedCategory::Preferences, " YES ", true),
+            (RelatedCategory::AppSupport, "n\n", false),
+            (RelatedCategory::Cookies, "maybe", false),
+            (RelatedCategory::LaunchDaemons, "\n", false),
+            (RelatedCategory::Containers, "y", false),
+            (RelatedCategory::SystemAppSupport, "yes", false),
+        ];
+        for (category, answer, expected) in answers {
+            assert_eq!(
+                confirm_category(category, answer),
+                expected,
+                "{:?} answered {:?}",
+                category,
+                answer
+            );
+        }
+    }
+
+    #[test]
+    fn install_date_falls_back_to_ctime() {
+        let created = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let ctime = 1_600_000_000;
+
+        assert_eq!(
+            install_time(Some(created), ctime).map(|t| t.timestamp()),
+            Some(1_700_000_000)
+        );
+        assert_eq!(
+            install_time(None, ctime).map(|t| t.timestamp()),
+            Some(1_600_000_000)
+        );
+        assert_eq!(install_time(None, 0), None);
+
+        let dir = std::env::temp_dir().join(format!("cleanmac-installed-{}", std::process::id()));
+        let app = make_app(&dir, "Trial", "com.example.trial", "1.0");
+        let installed = app.installed();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(installed.is_some_and(|t| (Utc::now() - t).num_minutes() < 5));
+    }
+
+    #[test]
+    fn non_utf8_names_are_sized_and_removed_exactly() {
+        use std::ffi::OsStr;
+
+        let dir = std::env::temp_dir().join(format!("cleanmac-non-utf8-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        // "caf\xe9" is Latin-1 café; its lossy form is a different, valid name.
+        let target = dir.join(OsStr::from_bytes(b"it's caf\xe9"));
+        let look_alike = dir.join("it's caf\u{FFFD}");
+        fs::write(&target, vec![0u8; 100]).unwrap();
+        fs::write(&look_alike, vec![0u8; 10]).unwrap();
+
+        assert_eq!(calculate_dir_size(&dir), 110);
+        assert_eq!(
+            AppBundle::new(dir.join(OsStr::from_bytes(b"Caf\xe9.app"))).name(),
+            "Caf\u{FFFD}"
+        );
+
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(rm_command(&target, false))
+            .status()
+            .unwrap();
+        let target_left = target.exists();
+        let look_alike_left = look_alike.exists();
+        let _ = fs::remove_dir_all(&dir);
+
+        assert!(status.success());
+        assert!(!target_left);
+        assert!(look_alike_left);
+    }
+}
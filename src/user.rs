@@ -0,0 +1,146 @@
+use anyhow::{bail, Result};
+use std::ffi::{CStr, CString};
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use walkdir::WalkDir;
+
+// `--user`로 지정된 사용자. 설정되면 모든 홈 기준 경로가 이 사용자의 홈을 가리킨다.
+static TARGET_USER: OnceLock<TargetUser> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetUser {
+    pub name: String,
+    pub uid: u32,
+    pub home: PathBuf,
+}
+
+impl TargetUser {
+    pub fn lookup(name: &str) -> Result<Self> {
+        let c_name = CString::new(name)?;
+        let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+        let mut buf = vec![0 as libc::c_char; 4096];
+        let mut result: *mut libc::passwd = std::ptr::null_mut();
+
+        let rc = unsafe {
+            libc::getpwnam_r(
+                c_name.as_ptr(),
+                &mut pwd,
+                buf.as_mut_ptr(),
+                buf.len(),
+                &mut result,
+            )
+        };
+        if rc != 0 || result.is_null() {
+            bail!("Unknown user: {}", name);
+        }
+
+        let home = unsafe { CStr::from_ptr(pwd.pw_dir) };
+        Ok(Self {
+            name: name.to_string(),
+            uid: pwd.pw_uid,
+            home: PathBuf::from(std::ffi::OsStr::from_bytes(home.to_bytes())),
+        })
+    }
+
+    // The first entry at or under `path` that belongs to someone else.
+    // Symlinks are judged by their own owner and never followed.
+    pub fn foreign_entry(&self, path: &Path) -> Option<PathBuf> {
+        WalkDir::new(path)
+            .follow_links(false)
+            .follow_root_links(false)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .find(|e| e.metadata().is_ok_and(|m| m.uid() != self.uid))
+            .map(|e| e.into_path())
+    }
+}
+
+// Why `path` is off limits under `--user`: it, or something inside it,
+// belongs to another user. None without `--user`.
+pub fn ownership_refusal(path: &Path) -> Option<String> {
+    let user = target_user()?;
+    let foreign = user.foreign_entry(path)?;
+    Some(if foreign == path {
+        format!("Not owned by {}", user.name)
+    } else {
+        format!("Contains {} not owned by {}", foreign.display(), user.name)
+    })
+}
+
+// Acting on another user's files needs root; acting as yourself is always fine.
+pub fn set_target_user(name: &str) -> Result<()> {
+    let user = TargetUser::lookup(name)?;
+    let euid = unsafe { libc::geteuid() };
+    if euid != 0 && euid != user.uid {
+        bail!(
+            "--user {} requires root privileges (try: sudo cleanmac --user {} ...)",
+            name,
+            name
+        );
+    }
+    if !user.home.is_dir() {
+        bail!(
+            "Home directory of {} not found: {}",
+            name,
+            user.home.display()
+        );
+    }
+
+    let _ = TARGET_USER.set(user);
+    Ok(())
+}
+
+pub fn target_user() -> Option<&'static TargetUser> {
+    TARGET_USER.get()
+}
+
+pub fn home_dir() -> PathBuf {
+    match target_user() {
+        Some(user) => user.home.clone(),
+        None => dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_home_for_a_username() {
+        let root = TargetUser::lookup("root").unwrap();
+        assert_eq!(root.uid, 0);
+        assert!(root.home.is_absolute());
+        assert!(matches!(
+            root.home.to_str(),
+            Some("/root") | Some("/var/root")
+        ));
+
+        assert!(TargetUser::lookup("cleanmac-no-such-user").is_err());
+    }
+
+    #[test]
+    fn finds_entries_owned_by_someone_else_without_following_links() {
+        let dir = std::env::temp_dir().join(format!("cleanmac-owner-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("inner")).unwrap();
+        std::fs::write(dir.join("inner/file"), b"x").unwrap();
+        std::os::unix::fs::symlink("/nonexistent", dir.join("dangling")).unwrap();
+
+        let uid = std::fs::symlink_metadata(&dir).unwrap().uid();
+        let user = |uid| TargetUser {
+            name: "someone".to_string(),
+            uid,
+            home: PathBuf::from("/"),
+        };
+        assert_eq!(user(uid).foreign_entry(&dir), None);
+        assert_eq!(user(uid + 1).foreign_entry(&dir), Some(dir.clone()));
+        assert_eq!(
+            user(uid + 1).foreign_entry(&dir.join("dangling")),
+            Some(dir.join("dangling"))
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
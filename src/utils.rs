@@ -1,3 +1,6 @@
+use chrono::{DateTime, Utc};
+use std::path::PathBuf;
+
 pub fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
@@ -17,6 +20,31 @@ pub fn format_size(bytes: u64) -> String {
     }
 }
 
+// The reverse of format_size: "500MB", "1.5 GB", "10k", or plain bytes.
+pub fn parse_size(input: &str) -> Option<u64> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" => 1024,
+        "m" | "mb" => 1024 * 1024,
+        "g" | "gb" => 1024 * 1024 * 1024,
+        "t" | "tb" => 1024 * 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    Some((number * multiplier as f64) as u64)
+}
+
+// "+2.30 GB" / "-150.00 MB" for a change in size.
+pub fn format_size_delta(delta: i64) -> String {
+    let sign = if delta < 0 { '-' } else { '+' };
+    format!("{}{}", sign, format_size(delta.unsigned_abs()))
+}
+
 pub fn format_number(n: u64) -> String {
     let s = n.to_string();
     let mut result = String::new();
@@ -29,6 +57,87 @@ pub fn format_number(n: u64) -> String {
     result
 }
 
+pub fn format_relative_age(modified: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - modified).num_seconds().max(0);
+    let mins = secs / 60;
+    let hours = mins / 60;
+    let days = hours / 24;
+
+    if mins < 1 {
+        "now".to_string()
+    } else if hours < 1 {
+        format!("{}m", mins)
+    } else if days < 1 {
+        format!("{}h", hours)
+    } else if days < 30 {
+        format!("{}d", days)
+    } else if days < 365 {
+        format!("{}mo", days / 30)
+    } else {
+        format!("{}y", days / 365)
+    }
+}
+
+// `days` before `now`, or None when that is past the dates chrono can hold.
+pub fn days_before(now: DateTime<Utc>, days: u64) -> Option<DateTime<Utc>> {
+    i64::try_from(days)
+        .ok()
+        .and_then(chrono::Duration::try_days)
+        .and_then(|age| now.checked_sub_signed(age))
+}
+
+// One block per value, scaled to the largest. Zeros stay at the lowest block so
+// quiet days still show up as a gap in the line.
+pub fn sparkline(values: &[u64]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&v| {
+            if max == 0 {
+                BLOCKS[0]
+            } else {
+                BLOCKS[((v as u128 * 7) / max as u128) as usize]
+            }
+        })
+        .collect()
+}
+
+pub fn find_in_path(binary: &str) -> Option<PathBuf> {
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(binary))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+// "macOS 14.5" from sw_vers; elsewhere the kernel name and release.
+pub fn os_version() -> Option<String> {
+    if let Ok(output) = std::process::Command::new("sw_vers")
+        .arg("-productVersion")
+        .output()
+    {
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !version.is_empty() {
+            return Some(format!("macOS {}", version));
+        }
+    }
+
+    let mut uts: libc::utsname = unsafe { std::mem::zeroed() };
+    if unsafe { libc::uname(&mut uts) } != 0 {
+        return None;
+    }
+    let field = |f: &[libc::c_char]| {
+        let bytes: Vec<u8> = f
+            .iter()
+            .take_while(|&&c| c != 0)
+            .map(|&c| c as u8)
+            .collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+    Some(format!("{} {}", field(&uts.sysname), field(&uts.release)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -42,4 +151,60 @@ mod tests {
         assert_eq!(format_size(1048576), "1.00 MB");
         assert_eq!(format_size(1073741824), "1.00 GB");
     }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512"), Some(512));
+        assert_eq!(parse_size("10k"), Some(10 * 1024));
+        assert_eq!(parse_size("500MB"), Some(500 * 1048576));
+        assert_eq!(parse_size(" 1.5 gb "), Some(1610612736));
+        assert_eq!(parse_size(""), None);
+        assert_eq!(parse_size("MB"), None);
+        assert_eq!(parse_size("5 parsecs"), None);
+    }
+
+    #[test]
+    fn test_format_size_delta() {
+        assert_eq!(format_size_delta(1536), "+1.50 KB");
+        assert_eq!(format_size_delta(-1048576), "-1.00 MB");
+        assert_eq!(format_size_delta(0), "+0 B");
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[0, 0]), "▁▁");
+        assert_eq!(sparkline(&[0, 50, 100]), "▁▄█");
+        assert_eq!(sparkline(&[u64::MAX, 1]), "█▁");
+    }
+
+    #[test]
+    fn test_format_relative_age() {
+        let now = Utc::now();
+        assert_eq!(format_relative_age(now, now), "now");
+        assert_eq!(
+            format_relative_age(now - chrono::Duration::minutes(5), now),
+            "5m"
+        );
+        assert_eq!(
+            format_relative_age(now - chrono::Duration::hours(3), now),
+            "3h"
+        );
+        assert_eq!(
+            format_relative_age(now - chrono::Duration::days(12), now),
+            "12d"
+        );
+        assert_eq!(
+            format_relative_age(now - chrono::Duration::days(90), now),
+            "3mo"
+        );
+        assert_eq!(
+            format_relative_age(now - chrono::Duration::days(800), now),
+            "2y"
+        );
+        assert_eq!(
+            format_relative_age(now + chrono::Duration::hours(1), now),
+            "now"
+        );
+    }
 }
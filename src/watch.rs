@@ -0,0 +1,175 @@
+use crate::plugin::registry::ScanReport;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::Duration;
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_sigint(_: libc::c_int) {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+pub fn install_interrupt_handler() {
+    unsafe {
+        libc::signal(libc::SIGINT, on_sigint as *const () as libc::sighandler_t);
+    }
+}
+
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+// Sleeps in short slices so Ctrl-C is noticed without waiting out the interval.
+pub fn sleep_interruptible(total: Duration) {
+    let step = Duration::from_millis(100);
+    let mut slept = Duration::ZERO;
+    while slept < total && !interrupted() {
+        std::thread::sleep(step.min(total - slept));
+        slept += step;
+    }
+}
+
+// Runs `scan` on a worker and waits for it, giving up as soon as Ctrl-C is
+// pressed. None when interrupted; the abandoned scan ends with the process.
+pub fn run_interruptible<T: Send + 'static>(
+    scan: impl FnOnce() -> T + Send + 'static,
+) -> Option<T> {
+    let (tx, rx) = channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(scan());
+    });
+    loop {
+        match rx.recv_timeout(Duration::from_millis(100)) {
+            Ok(result) => return Some(result),
+            Err(RecvTimeoutError::Timeout) if !interrupted() => {}
+            Err(_) => return None,
+        }
+    }
+}
+
+pub fn parse_interval(s: &str) -> Result<Duration> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let Ok(value) = number.parse::<u64>() else {
+        bail!("Invalid interval '{}' (expected e.g. 500ms, 10s, 2m)", s);
+    };
+
+    let seconds = |per_unit: u64| {
+        value
+            .checked_mul(per_unit)
+            .map(Duration::from_secs)
+            .ok_or_else(|| anyhow::anyhow!("Interval '{}' is too long", s))
+    };
+    let duration = match unit {
+        "ms" => Duration::from_millis(value),
+        "" | "s" => Duration::from_secs(value),
+        "m" => seconds(60)?,
+        "h" => seconds(3600)?,
+        _ => bail!("Invalid interval unit '{}' (use ms, s, m or h)", unit),
+    };
+
+    if duration.is_zero() {
+        bail!("Interval must be greater than zero");
+    }
+    Ok(duration)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub sizes: BTreeMap<String, (String, u64)>,
+}
+
+impl Snapshot {
+    pub fn from_report(report: &ScanReport, category: &str) -> Self {
+        let sizes = report
+            .categories
+            .iter()
+            .filter(|c| category == "all" || c.scanner_id.contains(&category.to_lowercase()))
+            .map(|c| (c.scanner_id.clone(), (c.name.clone(), c.total_size())))
+            .collect();
+        Self { sizes }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CategoryChange {
+    pub id: String,
+    pub name: String,
+    pub size_bytes: u64,
+    pub delta_bytes: i64,
+    pub total_delta_bytes: i64,
+}
+
+pub fn diff(start: &Snapshot, previous: &Snapshot, current: &Snapshot) -> Vec<CategoryChange> {
+    let size_in = |snap: &Snapshot, id: &str| snap.sizes.get(id).map(|(_, s)| *s).unwrap_or(0);
+
+    current
+        .sizes
+        .iter()
+        .map(|(id, (name, size))| CategoryChange {
+            id: id.clone(),
+            name: name.clone(),
+            size_bytes: *size,
+            delta_bytes: *size as i64 - size_in(previous, id) as i64,
+            total_delta_bytes: *size as i64 - size_in(start, id) as i64,
+        })
+        .collect()
+}
+
+pub fn format_delta(delta: i64) -> String {
+    let sign = if delta < 0 { "-" } else { "+" };
+    format!(
+        "{}{}",
+        sign,
+        crate::utils::format_size(delta.unsigned_abs())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(entries: &[(&str, u64)]) -> Snapshot {
+        Snapshot {
+            sizes: entries
+                .iter()
+                .map(|(id, size)| (id.to_string(), (id.to_string(), *size)))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parses_intervals() {
+        assert_eq!(parse_interval("10s").unwrap(), Duration::from_secs(10));
+        assert_eq!(parse_interval("250ms").unwrap(), Duration::from_millis(250));
+        assert_eq!(parse_interval("2m").unwrap(), Duration::from_secs(120));
+        assert_eq!(parse_interval("5").unwrap(), Duration::from_secs(5));
+        assert!(parse_interval("0s").is_err());
+        assert!(parse_interval("10x").is_err());
+        assert!(parse_interval("fast").is_err());
+        assert!(parse_interval(&format!("{}h", u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn interruptible_runs_return_the_result() {
+        assert_eq!(run_interruptible(|| 7), Some(7));
+    }
+
+    #[test]
+    fn diff_tracks_tick_and_session_changes() {
+        let start = snapshot(&[("system_caches", 1000), ("system_logs", 500)]);
+        let previous = snapshot(&[("system_caches", 1200), ("system_logs", 500)]);
+        let current = snapshot(&[("system_caches", 1500), ("system_logs", 300)]);
+
+        let changes = diff(&start, &previous, &current);
+        assert_eq!(changes[0].id, "system_caches");
+        assert_eq!(changes[0].delta_bytes, 300);
+        assert_eq!(changes[0].total_delta_bytes, 500);
+        assert_eq!(changes[1].delta_bytes, -200);
+        assert_eq!(changes[1].total_delta_bytes, -200);
+    }
+}